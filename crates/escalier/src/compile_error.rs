@@ -1,5 +1,6 @@
 use std::fmt;
 
+use escalier_codegen::CodegenError;
 use escalier_hm::diagnostic::Diagnostic;
 use escalier_hm::type_error::TypeError;
 use escalier_parser::ParseError;
@@ -9,6 +10,7 @@ pub enum CompileError {
     TypeError(TypeError),
     Diagnostic(Vec<Diagnostic>),
     ParseError(ParseError),
+    CodegenError(CodegenError),
 }
 
 impl fmt::Display for CompileError {
@@ -23,6 +25,12 @@ impl From<ParseError> for CompileError {
     }
 }
 
+impl From<CodegenError> for CompileError {
+    fn from(error: CodegenError) -> Self {
+        CompileError::CodegenError(error)
+    }
+}
+
 // impl From<Vec<TypeError>> for CompileError {
 //     fn from(errors: Vec<TypeError>) -> Self {
 //         CompileError::TypeError(errors)
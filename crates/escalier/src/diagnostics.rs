@@ -62,6 +62,7 @@ pub fn get_diagnostics_from_compile_error(report: CompileError, src: &str) -> St
             .collect::<Vec<String>>()
             .join("\n"),
         CompileError::ParseError(error) => error.message.to_owned(),
+        CompileError::CodegenError(error) => error.message.to_owned(),
     };
 
     diagnostics
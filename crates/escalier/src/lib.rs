@@ -1,6 +1,11 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use wasm_bindgen::prelude::*;
 
+use escalier_ast::{script_json_schema, script_to_json};
+use escalier_hm::checker::Checker;
+use escalier_hm::context::Context;
+use escalier_hm::snapshot::load_snapshot;
 use escalier_interop::parse::parse_dts;
 
 pub mod compile_error;
@@ -18,6 +23,7 @@ pub struct CompileResult {
     error: String,
 }
 
+#[cfg(target_arch = "wasm32")]
 #[wasm_bindgen]
 extern "C" {
     // Use `js_namespace` here to bind `console.log(..)` instead of just
@@ -26,36 +32,51 @@ extern "C" {
     fn log(s: &str);
 }
 
-fn _compile(input: &str, lib: &str) -> Result<(String, String, String, String), CompileError> {
+// Imported functions panic outside of wasm so we don't log anything when
+// running natively, e.g. in tests.
+#[cfg(not(target_arch = "wasm32"))]
+fn log(_s: &str) {}
+
+fn _compile(
+    input: &str,
+    lib: (Checker, Context),
+) -> Result<(String, String, String, String), CompileError> {
     log(&format!("parsing input: {input}"));
     let mut program = escalier_parser::parse(input)?;
-    let ast = format!("{program:#?}");
 
-    let (js, srcmap) = escalier_codegen::js::codegen_js(input, &program);
+    let (js, srcmap) = escalier_codegen::js::codegen_js(input, &program)?;
 
-    // TODO: return errors as part of CompileResult
-    let (mut checker, mut ctx) = parse_dts(lib).unwrap();
+    let (mut checker, mut ctx) = lib;
 
-    // TODO: get rid of panics and return errors instead
-    match checker.infer_script(&mut program, &mut ctx) {
-        Ok(_) => {
-            if !checker.current_report.diagnostics.is_empty() {
-                panic!("was expecting infer_prog() to return no errors");
-            }
-        }
-        Err(error) => {
-            let message = error.to_string();
-            panic!("{message}");
-        }
+    checker.infer_script(&mut program, &mut ctx)?;
+    // Warnings, e.g. missing `throws` clauses, don't stop the script from
+    // compiling.
+    if !checker.current_report.diagnostics.is_empty() {
+        return Err(CompileError::Diagnostic(checker.current_report.diagnostics));
     }
 
     let dts = escalier_codegen::d_ts::codegen_d_ts(&program, &ctx, &checker)?;
+    let ast = script_to_json(&program, checker.get_type_names(&program));
 
     Ok((js, srcmap, dts, ast))
 }
 
 #[wasm_bindgen]
 pub fn compile(input: &str, lib: &str) -> Result<JsValue, JsValue> {
+    // TODO: return errors as part of CompileResult
+    let lib = parse_dts(lib).unwrap();
+    compile_with_lib(input, lib)
+}
+
+/// Like `compile` but uses a snapshot of the lib created by `escalier
+/// snapshot` so that the lib doesn't have to be parsed on every call.
+#[wasm_bindgen]
+pub fn compile_with_snapshot(input: &str, snapshot: &[u8]) -> Result<JsValue, JsValue> {
+    let lib = load_snapshot(snapshot).map_err(|err| JsValue::from_str(&err))?;
+    compile_with_lib(input, lib)
+}
+
+fn compile_with_lib(input: &str, lib: (Checker, Context)) -> Result<JsValue, JsValue> {
     match _compile(input, lib) {
         Ok((js, srcmap, dts, ast)) => {
             let result = CompileResult {
@@ -79,10 +100,55 @@ pub fn compile(input: &str, lib: &str) -> Result<JsValue, JsValue> {
 pub fn parse(input: &str) -> Result<JsValue, JsValue> {
     match escalier_parser::parse(input) {
         Ok(program) => {
-            // TODO: update the AST to implement Serialize/Deserialize
-            let ast = format!("{program:#?}");
+            let ast = script_to_json(&program, HashMap::new());
             Ok(serde_wasm_bindgen::to_value(&ast)?)
         }
         Err(e) => Err(serde_wasm_bindgen::to_value(&e.message)?),
     }
 }
+
+/// Returns the JSON schema for the ASTs returned by `parse` and `compile`.
+#[wasm_bindgen]
+pub fn ast_schema() -> String {
+    script_json_schema()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lib() -> (Checker, Context) {
+        (Checker::default(), Context::default())
+    }
+
+    #[test]
+    fn compiles_functions_missing_throws_clauses() {
+        let src = r#"
+        let div = fn (a: number, b: number) -> number {
+            if (b == 0) {
+                throw "division by zero"
+            }
+            return a / b
+        }
+        "#;
+
+        assert!(_compile(src, lib()).is_ok());
+    }
+
+    #[test]
+    fn non_exhaustive_matches_are_compile_errors() {
+        let src = r#"
+        declare let dir: "up" | "down" | "left" | "right"
+        let x = match (dir) {
+            "up" => 1,
+            "down" => 2
+        }
+        "#;
+
+        let error = _compile(src, lib()).unwrap_err();
+        assert_eq!(
+            get_diagnostics_from_compile_error(error, src),
+            "Match is not exhaustive, missing: \"left\", \"right\": "
+        );
+    }
+}
@@ -127,7 +127,7 @@ fn compile(input: &str, lib: &str) -> (String, String, String, String) {
         }
     };
 
-    let (js, srcmap) = escalier_codegen::js::codegen_js(input, &script);
+    let (js, srcmap) = escalier_codegen::js::codegen_js(input, &script).unwrap();
 
     // TODO: return errors as part of CompileResult
     let (mut checker, mut ctx) = parse_dts(lib).unwrap();
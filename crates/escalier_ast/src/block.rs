@@ -1,7 +1,10 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
 use crate::span::Span;
 use crate::stmt::Stmt;
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Block {
     pub span: Span,
     pub stmts: Vec<Stmt>,
@@ -1,4 +1,8 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
 use crate::block::Block;
+use crate::comment::Comments;
 use crate::expr::*;
 use crate::func_param::FuncParam;
 use crate::identifier::Ident;
@@ -6,7 +10,7 @@ use crate::span::Span;
 use crate::type_ann::TypeAnn;
 use crate::type_param::TypeParam;
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Class {
     pub span: Span,
     // pub name: Option<Ident>,
@@ -16,7 +20,7 @@ pub struct Class {
     pub body: Vec<ClassMember>,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Method {
     pub span: Span,
     pub name: PropName,
@@ -24,9 +28,10 @@ pub struct Method {
     pub is_mutating: bool,
     pub is_static: bool,
     pub function: Function,
+    pub comments: Comments,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Getter {
     pub span: Span,
     pub name: PropName,
@@ -34,9 +39,10 @@ pub struct Getter {
     pub type_ann: Option<TypeAnn>,
     pub params: Vec<FuncParam>, // should only contain `self` param
     pub body: Block,
+    pub comments: Comments,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Setter {
     pub span: Span,
     pub name: PropName,
@@ -44,17 +50,18 @@ pub struct Setter {
     pub type_ann: Option<TypeAnn>, // should always be `void`
     pub params: Vec<FuncParam>,    // should only contain `self`, `value` params
     pub body: Block,
+    pub comments: Comments,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub enum PropName {
     Ident(Ident),
     // Str(Str),
     // Num(Num),
-    Computed(Expr),
+    Computed(Box<Expr>),
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Constructor {
     pub span: Span,
     pub is_public: bool,
@@ -62,7 +69,7 @@ pub struct Constructor {
     pub body: Block,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Field {
     pub span: Span,
     pub name: Ident,
@@ -70,13 +77,29 @@ pub struct Field {
     pub is_static: bool,
     pub type_ann: Option<TypeAnn>,
     pub init: Option<Box<Expr>>,
+    pub comments: Comments,
     // TODO: add `is_static` and `is_optional` fields
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub enum ClassMember {
     Method(Method),
     Getter(Getter),
     Setter(Setter),
     Field(Field), // TODO: rename to property?
 }
+
+impl ClassMember {
+    pub fn comments(&self) -> &Comments {
+        match self {
+            ClassMember::Method(Method { comments, .. })
+            | ClassMember::Getter(Getter { comments, .. })
+            | ClassMember::Setter(Setter { comments, .. })
+            | ClassMember::Field(Field { comments, .. }) => comments,
+        }
+    }
+
+    pub fn doc(&self) -> Option<String> {
+        self.comments().doc()
+    }
+}
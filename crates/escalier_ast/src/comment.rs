@@ -0,0 +1,80 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::span::Span;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize, JsonSchema)]
+pub enum CommentKind {
+    Line,  // `// ...`
+    Block, // `/* ... */`
+}
+
+/// `text` doesn't include the `//`, `/*` or `*/` delimiters.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Comment {
+    pub kind: CommentKind,
+    pub text: String,
+    pub span: Span,
+}
+
+impl Comment {
+    /// Returns true for `/// ...` and `/** ... */` comments.
+    pub fn is_doc(&self) -> bool {
+        match self.kind {
+            CommentKind::Line => self.text.starts_with('/') && !self.text.starts_with("//"),
+            CommentKind::Block => self.text.starts_with('*') && !self.text.starts_with("**"),
+        }
+    }
+}
+
+/// The comments attached to an AST node.  Leading comments appear on the lines
+/// before the node and trailing comments appear after it on the same line.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Comments {
+    pub leading: Vec<Comment>,
+    pub trailing: Vec<Comment>,
+}
+
+impl Comments {
+    pub fn is_empty(&self) -> bool {
+        self.leading.is_empty() && self.trailing.is_empty()
+    }
+
+    /// Returns the contents of the doc comments immediately before the node
+    /// with the `///`, `/**`, `*/` and leading `*` markers removed.
+    pub fn doc(&self) -> Option<String> {
+        let count = self
+            .leading
+            .iter()
+            .rev()
+            .take_while(|comment| comment.is_doc())
+            .count();
+        let comments = &self.leading[self.leading.len() - count..];
+
+        let lines: Vec<&str> = match comments.last() {
+            None => return None,
+            // `/**` comments can't be combined so we only use the last one.
+            Some(comment) if comment.kind == CommentKind::Block => comment.text[1..]
+                .lines()
+                .map(|line| {
+                    let line = line.trim();
+                    let line = line.strip_prefix('*').unwrap_or(line);
+                    line.strip_prefix(' ').unwrap_or(line).trim_end()
+                })
+                .collect(),
+            Some(_) => comments
+                .iter()
+                .filter(|comment| comment.kind == CommentKind::Line)
+                .map(|comment| {
+                    let line = &comment.text[1..];
+                    line.strip_prefix(' ').unwrap_or(line).trim_end()
+                })
+                .collect(),
+        };
+
+        let start = lines.iter().position(|line| !line.is_empty())?;
+        let end = lines.iter().rposition(|line| !line.is_empty())?;
+
+        Some(lines[start..=end].join("\n"))
+    }
+}
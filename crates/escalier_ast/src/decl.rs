@@ -1,33 +1,112 @@
-use crate::expr::Expr;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::class::Method;
+use crate::comment::Comments;
+use crate::expr::{BlockOrExpr, Expr};
+use crate::func_param::FuncParam;
+use crate::identifier::Ident;
 use crate::pattern::Pattern;
 use crate::span::Span;
 use crate::type_ann::TypeAnn;
 use crate::type_param::TypeParam;
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct VarDecl {
     pub is_declare: bool,
     pub is_var: bool,
     pub pattern: Pattern,
-    pub expr: Option<Expr>,
+    pub expr: Option<Box<Expr>>,
     pub type_ann: Option<TypeAnn>,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct TypeDecl {
     pub name: String,
     pub type_ann: TypeAnn,
     pub type_params: Option<Vec<TypeParam>>,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct EnumVariant {
+    pub name: String,
+    pub fields: Vec<TypeAnn>,
+    pub span: Span,
+}
+
+/// A nominal union of variants, each of which can carry a payload, e.g.
+/// `enum IpAddr { V4(string), V6(string) }`.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct EnumDecl {
+    pub name: String,
+    pub type_params: Option<Vec<TypeParam>>,
+    pub variants: Vec<EnumVariant>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct StructField {
+    pub name: String,
+    pub type_ann: TypeAnn,
+    pub is_public: bool,
+    pub span: Span,
+}
+
+/// A nominal object type whose fields are private unless they're marked
+/// `pub`, e.g. `struct User { pub name: string, ssn: number }`.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct StructDecl {
+    pub name: String,
+    pub type_params: Option<Vec<TypeParam>>,
+    pub fields: Vec<StructField>,
+}
+
+/// A method signature in a trait.  Methods with a `body` provide a default
+/// implementation, all others must be implemented by each `impl` of the trait.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TraitMethod {
+    pub span: Span,
+    pub name: Ident,
+    pub is_mutating: bool,
+    pub type_params: Option<Vec<TypeParam>>,
+    pub params: Vec<FuncParam>,
+    pub type_ann: TypeAnn, // return type
+    pub body: Option<BlockOrExpr>,
+    pub comments: Comments,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TraitDecl {
+    pub name: String,
+    pub methods: Vec<TraitMethod>,
+}
+
+/// Implements a trait for a named type, e.g. `impl Shape for Circle { ... }`.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ImplDecl {
+    pub trait_name: Ident,
+    pub type_name: Ident,
+    pub methods: Vec<Method>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub enum DeclKind {
     TypeDecl(TypeDecl),
     VarDecl(VarDecl),
+    EnumDecl(EnumDecl),
+    StructDecl(StructDecl),
+    TraitDecl(TraitDecl),
+    ImplDecl(ImplDecl),
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Decl {
     pub kind: DeclKind,
     pub span: Span,
+    pub comments: Comments,
+}
+
+impl Decl {
+    pub fn doc(&self) -> Option<String> {
+        self.comments.doc()
+    }
 }
@@ -1,17 +1,21 @@
 use generational_arena::Index;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 
 use crate::block::Block;
 use crate::class::Class;
+use crate::comment::Comments;
 use crate::func_param::FuncParam;
 use crate::identifier::Ident;
 use crate::jsx::{JSXElement, JSXFragment};
 use crate::pattern::Pattern;
 use crate::span::*;
 use crate::type_ann::TypeAnn;
+use crate::type_names::serialize_index;
 use crate::type_param::TypeParam;
 
 // TODO: track source location
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub enum ObjectKey {
     Ident(Ident),
     String(String),
@@ -20,90 +24,121 @@ pub enum ObjectKey {
 }
 
 // TODO: track source location
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub enum Prop {
-    Shorthand(Ident),
-    Property { key: ObjectKey, value: Expr },
+    Shorthand {
+        ident: Ident,
+        comments: Comments,
+    },
+    Property {
+        key: ObjectKey,
+        value: Box<Expr>,
+        comments: Comments,
+    },
+}
+
+impl Prop {
+    pub fn comments(&self) -> &Comments {
+        match self {
+            Prop::Shorthand { comments, .. } | Prop::Property { comments, .. } => comments,
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub enum PropOrSpread {
     Prop(Prop),
-    Spread(Expr),
+    Spread(Box<Expr>),
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub enum ExprOrSpread {
     Expr(Expr),
     Spread(Expr),
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Num {
     pub value: String,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Str {
     pub span: Span,
     pub value: String,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+/// `/pattern/flags`
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Regex {
+    pub pattern: String,
+    pub flags: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Bool {
     pub value: bool,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Null {}
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Undefined {}
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct TemplateLiteral {
     pub parts: Vec<Str>,
     pub exprs: Vec<Expr>,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct TaggedTemplateLiteral {
     pub tag: Box<Expr>,
     pub template: TemplateLiteral,
+    #[serde(serialize_with = "serialize_index", skip_deserializing)]
+    #[schemars(with = "Option<String>")]
     pub throws: Option<Index>,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Object {
     pub properties: Vec<PropOrSpread>,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+/// Constructs an instance of a struct, e.g. `User {name: "John", ssn: 123}`.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Struct {
+    pub name: Ident,
+    pub properties: Vec<PropOrSpread>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Tuple {
     pub elements: Vec<ExprOrSpread>,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Assign {
     pub left: Box<Expr>,
     pub op: AssignOp,
     pub right: Box<Expr>,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Binary {
     pub left: Box<Expr>,
     pub op: BinaryOp,
     pub right: Box<Expr>,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Unary {
     pub op: UnaryOp,
     pub right: Box<Expr>,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Function {
     pub type_params: Option<Vec<TypeParam>>,
     pub params: Vec<FuncParam>,
@@ -114,61 +149,89 @@ pub struct Function {
     pub is_gen: bool,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Call {
     pub callee: Box<Expr>,
     pub type_args: Option<Vec<TypeAnn>>,
     pub args: Vec<Expr>,
     pub opt_chain: bool,
+    #[serde(serialize_with = "serialize_index", skip_deserializing)]
+    #[schemars(with = "Option<String>")]
     pub throws: Option<Index>,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct New {
     pub callee: Box<Expr>,
     pub type_args: Option<Vec<TypeAnn>>,
     pub args: Vec<Expr>,
+    #[serde(serialize_with = "serialize_index", skip_deserializing)]
+    #[schemars(with = "Option<String>")]
     pub throws: Option<Index>,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Member {
     pub object: Box<Expr>,
     pub property: MemberProp,
     pub opt_chain: bool,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+/// `start..end`, the `end` is exclusive.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Range {
+    pub start: Box<Expr>,
+    pub end: Box<Expr>,
+}
+
+/// `left |> right`, `_` within `right` refers to the value of `left`.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Pipeline {
+    pub left: Box<Expr>,
+    pub right: Box<Expr>,
+}
+
+/// `object[start:end]`, either bound can be omitted.  This is sugar for
+/// `object.slice(start, end)`.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Slice {
+    pub object: Box<Expr>,
+    pub start: Option<Box<Expr>>,
+    pub end: Option<Box<Expr>>,
+    pub opt_chain: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub enum MemberProp {
     Ident(Ident),
     Computed(ComputedPropName),
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct ComputedPropName {
     pub span: Span, // includes enclosing []
     pub expr: Box<Expr>,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct OptionalChain {
     pub base: Box<Expr>,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct IfElse {
     pub cond: Box<Expr>,
     pub consequent: Block,
     pub alternate: Option<BlockOrExpr>,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Match {
     pub expr: Box<Expr>,
     pub arms: Vec<MatchArm>,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Try {
     pub body: Block,
     // At least `catch` or `finally` must be present
@@ -176,30 +239,41 @@ pub struct Try {
     pub finally: Option<Block>,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Do {
     pub body: Block,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+/// `loop { ... }`, evaluates to the value passed to the `break` that exits it.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Loop {
+    pub label: Option<String>,
+    pub body: Block,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Await {
     pub arg: Box<Expr>,
     // Awaiting a rejected promise turns it into a throw.
+    #[serde(serialize_with = "serialize_index", skip_deserializing)]
+    #[schemars(with = "Option<String>")]
     pub throws: Option<Index>, // the type of the thrown value
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Yield {
     pub arg: Box<Expr>,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Throw {
     pub arg: Box<Expr>,
+    #[serde(serialize_with = "serialize_index", skip_deserializing)]
+    #[schemars(with = "Option<String>")]
     pub throws: Option<Index>, // the type of the thrown value
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub enum ExprKind {
     Ident(Ident),
     Num(Num),
@@ -209,9 +283,9 @@ pub enum ExprKind {
     Undefined(Undefined),
     TemplateLiteral(TemplateLiteral),
     TaggedTemplateLiteral(TaggedTemplateLiteral),
-    // TODO: Add regex support
-    // Regex(Regex),
+    Regex(Regex),
     Object(Object),
+    Struct(Struct),
     Tuple(Tuple),
     Assign(Assign),
     Binary(Binary),
@@ -221,10 +295,14 @@ pub enum ExprKind {
     Call(Call),
     New(New),
     Member(Member),
+    Slice(Slice),
+    Range(Range),
+    Pipeline(Pipeline),
     IfElse(IfElse),
     Match(Match),
     Try(Try),
     Do(Do),
+    Loop(Loop),
     Await(Await),
     Yield(Yield),
     Throw(Throw),
@@ -232,20 +310,22 @@ pub enum ExprKind {
     JSXFragment(JSXFragment),
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Expr {
     pub kind: ExprKind,
     pub span: Span,
+    #[serde(serialize_with = "serialize_index", skip_deserializing)]
+    #[schemars(with = "Option<String>")]
     pub inferred_type: Option<Index>,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CatchClause {
     pub param: Option<Pattern>,
     pub body: Block,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct MatchArm {
     // pub loc: SourceLocation,
     pub span: Span,
@@ -254,13 +334,13 @@ pub struct MatchArm {
     pub body: BlockOrExpr,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub enum BlockOrExpr {
     Block(Block),
     Expr(Box<Expr>),
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub enum AssignOp {
     Assign,
     AddAssign,
@@ -270,7 +350,7 @@ pub enum AssignOp {
     ModAssign,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub enum BinaryOp {
     Plus,
     Minus,
@@ -287,7 +367,7 @@ pub enum BinaryOp {
     And,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub enum UnaryOp {
     Plus,
     Minus,
@@ -1,16 +1,22 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use swc_atoms::JsWord;
 use swc_common::{self, BytePos, SyntaxContext};
 use swc_ecma_ast;
 
 use crate::span::*;
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(
+    Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, JsonSchema,
+)]
 pub struct Ident {
     pub name: String,
     pub span: Span,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(
+    Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, JsonSchema,
+)]
 pub struct BindingIdent {
     pub name: String,
     pub span: Span,
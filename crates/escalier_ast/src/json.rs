@@ -0,0 +1,17 @@
+use generational_arena::Index;
+use std::collections::HashMap;
+
+use crate::script::Script;
+use crate::type_names::with_type_names;
+
+/// Serializes `script` as pretty-printed JSON.  Each `inferred_type` and
+/// `throws` field is the printed type from `type_names` or `null` if there
+/// isn't one, e.g. when the script hasn't been type checked.
+pub fn script_to_json(script: &Script, type_names: HashMap<Index, String>) -> String {
+    with_type_names(type_names, || serde_json::to_string_pretty(script).unwrap())
+}
+
+/// Returns the JSON schema for the output of `script_to_json`.
+pub fn script_json_schema() -> String {
+    serde_json::to_string_pretty(&schemars::schema_for!(Script)).unwrap()
+}
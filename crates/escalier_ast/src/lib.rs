@@ -1,9 +1,11 @@
 pub mod block;
 pub mod class;
+pub mod comment;
 pub mod decl;
 pub mod expr;
 pub mod func_param;
 pub mod identifier;
+pub mod json;
 pub mod jsx;
 pub mod literal;
 pub mod module;
@@ -12,15 +14,18 @@ pub mod script;
 pub mod span;
 pub mod stmt;
 pub mod type_ann;
+pub mod type_names;
 pub mod type_param;
 pub mod visitor;
 
 pub use block::*;
 pub use class::*;
+pub use comment::*;
 pub use decl::*;
 pub use expr::*;
 pub use func_param::*;
 pub use identifier::*;
+pub use json::*;
 pub use jsx::*;
 pub use literal::*;
 pub use module::*;
@@ -29,5 +34,6 @@ pub use script::*;
 pub use span::*;
 pub use stmt::*;
 pub use type_ann::*;
+pub use type_names::*;
 pub use type_param::*;
 pub use visitor::*;
@@ -1,37 +1,40 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
 use crate::decl::Decl;
 use crate::span::Span;
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ImportSpecifier {
     pub local: String,            // the local name of the imported symbol
     pub imported: Option<String>, // the symbol being imported
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Import {
     pub specifiers: Vec<ImportSpecifier>,
     pub source: String,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Export {
     pub decl: Decl,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub enum ModuleItemKind {
     Import(Import),
     Export(Export),
     Decl(Decl),
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ModuleItem {
     pub kind: ModuleItemKind,
     pub span: Span,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Module {
     pub items: Vec<ModuleItem>,
 }
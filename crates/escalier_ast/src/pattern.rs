@@ -1,11 +1,14 @@
 use generational_arena::Index;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 
 use crate::expr::Expr;
 use crate::identifier::{BindingIdent, Ident};
 use crate::literal::Literal;
 use crate::span::*;
+use crate::type_names::serialize_index;
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub enum PatternKind {
     Ident(BindingIdent),
     Rest(RestPat),
@@ -13,15 +16,19 @@ pub enum PatternKind {
     Tuple(TuplePat),
     Lit(LitPat),
     Is(IsPat),
+    Enum(EnumPat),
+    Struct(StructPat),
     Wildcard,
     // This can't be used at the top level similar to rest
     // Assign(AssignPat),
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct Pattern {
     pub kind: PatternKind,
     pub span: Span,
+    #[serde(serialize_with = "serialize_index", skip_deserializing)]
+    #[schemars(with = "Option<String>")]
     pub inferred_type: Option<Index>,
 }
 
@@ -34,50 +41,67 @@ impl Pattern {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct LitPat {
     pub lit: Literal,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct IsPat {
     pub ident: BindingIdent,
     pub is_id: Ident,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+/// Matches a variant of an enum, e.g. `IpAddr.V4(addr)`, and destructures
+/// its fields.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct EnumPat {
+    pub enum_name: Ident,
+    pub variant: Ident,
+    pub args: Vec<Pattern>,
+}
+
+/// Matches an instance of a struct, e.g. `User {name}`, and destructures its
+/// fields.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct StructPat {
+    pub name: Ident,
+    pub props: Vec<ObjectPatProp>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct RestPat {
     pub arg: Box<Pattern>,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct TuplePat {
     // The elements are optional to support sparse arrays.
     pub elems: Vec<Option<TuplePatElem>>,
     pub optional: bool,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct TuplePatElem {
     // TODO: add .span property
     pub pattern: Pattern,
     pub init: Option<Box<Expr>>,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct ObjectPat {
     pub props: Vec<ObjectPatProp>,
     pub optional: bool,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub enum ObjectPatProp {
     KeyValue(KeyValuePatProp),
     Shorthand(ShorthandPatProp),
     Rest(RestPat), // TODO: create a new RestPatProp that includes a span
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct KeyValuePatProp {
     // pub loc: SourceLocation,
     pub span: Span,
@@ -86,7 +110,7 @@ pub struct KeyValuePatProp {
     pub init: Option<Box<Expr>>,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct ShorthandPatProp {
     // pub loc: SourceLocation,
     pub span: Span,
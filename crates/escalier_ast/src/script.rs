@@ -1,6 +1,9 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
 use crate::stmt::Stmt;
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Script {
     pub stmts: Vec<Stmt>,
 }
@@ -1,7 +1,9 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use std::cmp::{max, min};
 use std::fmt;
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, JsonSchema)]
 pub struct Span {
     pub start: usize,
     pub end: usize,
@@ -1,32 +1,59 @@
 use generational_arena::Index;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 
 use crate::block::Block;
 use crate::decl::*;
 use crate::expr::Expr;
 use crate::pattern::Pattern;
 use crate::span::Span;
+use crate::type_names::serialize_index;
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ExprStmt {
     pub expr: Expr,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ForStmt {
+    pub label: Option<String>,
     pub left: Box<Pattern>,
     pub right: Box<Expr>,
     pub body: Block,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct WhileStmt {
+    pub label: Option<String>,
+    pub test: Box<Expr>,
+    pub body: Block,
+}
+
+/// `break`, `break label`, `break value`, or `break label value`.  Only breaks
+/// out of a `loop` can have a value.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct BreakStmt {
+    pub label: Option<String>,
+    pub arg: Option<Expr>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ContinueStmt {
+    pub label: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ReturnStmt {
     pub arg: Option<Expr>,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub enum StmtKind {
     Expr(ExprStmt),
     For(ForStmt),
+    While(WhileStmt),
+    Break(BreakStmt),
+    Continue(ContinueStmt),
     Return(ReturnStmt),
     Decl(Decl),
     // VarDecl(VarDecl),
@@ -37,9 +64,11 @@ pub enum StmtKind {
     // - class decls: `class Foo {}` desugars to `let Foo = class {}`
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Stmt {
     pub kind: StmtKind,
     pub span: Span,
+    #[serde(serialize_with = "serialize_index", skip_deserializing)]
+    #[schemars(with = "Option<String>")]
     pub inferred_type: Option<Index>,
 }
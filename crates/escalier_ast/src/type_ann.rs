@@ -1,20 +1,24 @@
 use generational_arena::Index;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 
-use crate::expr::BinaryOp;
 // use crate::func_param::FuncParam;
+use crate::comment::Comments;
+use crate::expr::BinaryOp;
 use crate::identifier::Ident;
 use crate::pattern::Pattern;
 use crate::span::*;
+use crate::type_names::serialize_index;
 use crate::type_param::TypeParam;
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct TypeAnnFuncParam {
     pub pattern: Pattern,
     pub type_ann: TypeAnn,
     pub optional: bool,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub enum ObjectProp {
     Call(FunctionType),
     Constructor(FunctionType),
@@ -25,7 +29,21 @@ pub enum ObjectProp {
     Prop(Prop),
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+impl ObjectProp {
+    /// Call and constructor signatures don't have comments attached to them.
+    pub fn comments(&self) -> Option<&Comments> {
+        match self {
+            ObjectProp::Call(_) | ObjectProp::Constructor(_) => None,
+            ObjectProp::Method(MethodType { comments, .. })
+            | ObjectProp::Getter(GetterType { comments, .. })
+            | ObjectProp::Setter(SetterType { comments, .. })
+            | ObjectProp::Mapped(Mapped { comments, .. })
+            | ObjectProp::Prop(Prop { comments, .. }) => Some(comments),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct MethodType {
     pub span: Span,
     pub name: String,
@@ -34,30 +52,33 @@ pub struct MethodType {
     pub ret: Box<TypeAnn>,
     pub throws: Option<Box<TypeAnn>>,
     pub mutates: bool,
+    pub comments: Comments,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct GetterType {
     pub span: Span,
     pub name: String,
     pub ret: Box<TypeAnn>,
+    pub comments: Comments,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SetterType {
     pub span: Span,
     pub name: String,
     pub param: Box<TypeAnnFuncParam>,
+    pub comments: Comments,
 }
 
 // TODO: dedupe with TPropModifier
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub enum PropModifier {
     Getter,
     Setter,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Prop {
     pub span: Span,
     pub name: String,
@@ -65,15 +86,16 @@ pub struct Prop {
     pub optional: bool,
     pub readonly: bool,
     pub type_ann: Box<TypeAnn>,
+    pub comments: Comments,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub enum MappedModifier {
     Add,
     Remove,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Mapped {
     pub key: Box<TypeAnn>,
     pub value: Box<TypeAnn>,
@@ -85,9 +107,11 @@ pub struct Mapped {
     // First half of a Conditional
     pub check: Option<Box<TypeAnn>>,
     pub extends: Option<Box<TypeAnn>>,
+
+    pub comments: Comments,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct FunctionType {
     pub span: Span,
     pub type_params: Option<Vec<TypeParam>>,
@@ -96,7 +120,7 @@ pub struct FunctionType {
     pub throws: Option<Box<TypeAnn>>,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ConditionType {
     pub check: Box<TypeAnn>,
     pub extends: Box<TypeAnn>,
@@ -104,26 +128,26 @@ pub struct ConditionType {
     pub false_type: Box<TypeAnn>,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct MatchType {
     pub matchable: Box<TypeAnn>,
     pub cases: Vec<MatchTypeCase>,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct MatchTypeCase {
     pub extends: Box<TypeAnn>,
     pub true_type: Box<TypeAnn>,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct BinaryTypeAnn {
     pub left: Box<TypeAnn>,
     pub op: BinaryOp,
     pub right: Box<TypeAnn>,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub enum TypeAnnKind {
     BoolLit(bool),
     Boolean,
@@ -154,9 +178,11 @@ pub enum TypeAnnKind {
     Binary(BinaryTypeAnn),
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct TypeAnn {
     pub kind: TypeAnnKind,
     pub span: Span,
+    #[serde(serialize_with = "serialize_index", skip_deserializing)]
+    #[schemars(with = "Option<String>")]
     pub inferred_type: Option<Index>,
 }
@@ -0,0 +1,32 @@
+use generational_arena::Index;
+use serde::{Serialize, Serializer};
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+thread_local! {
+    static TYPE_NAMES: RefCell<Option<HashMap<Index, String>>> = const { RefCell::new(None) };
+}
+
+/// Types live in the checker's arena so the AST only stores their `Index`es.
+/// While `f` is running, any `Index` in the AST that appears in `names` is
+/// serialized as the corresponding printed type.  All other `Index`es are
+/// serialized as `null`.
+pub fn with_type_names<R>(names: HashMap<Index, String>, f: impl FnOnce() -> R) -> R {
+    let prev = TYPE_NAMES.with(|cell| cell.replace(Some(names)));
+    let result = f();
+    TYPE_NAMES.with(|cell| cell.replace(prev));
+    result
+}
+
+pub(crate) fn serialize_index<S: Serializer>(
+    index: &Option<Index>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    TYPE_NAMES.with(|cell| {
+        let name = match (index, cell.borrow().as_ref()) {
+            (Some(index), Some(names)) => names.get(index).cloned(),
+            _ => None,
+        };
+        name.serialize(serializer)
+    })
+}
@@ -34,6 +34,10 @@ pub trait Visitor: Sized {
     fn visit_type_ann(&mut self, type_ann: &TypeAnn) {
         walk_type_ann(self, type_ann)
     }
+
+    fn visit_block(&mut self, block: &Block) {
+        walk_block(self, block)
+    }
 }
 
 pub fn walk_program<V: Visitor>(visitor: &mut V, program: &Script) {
@@ -47,6 +51,7 @@ pub fn walk_expr<V: Visitor>(visitor: &mut V, expr: &Expr) {
         crate::ExprKind::Ident(_) => {}
         crate::ExprKind::Num(_) => {}
         crate::ExprKind::Str(_) => {}
+        crate::ExprKind::Regex(_) => {}
         crate::ExprKind::Bool(_) => {}
         crate::ExprKind::Null(_) => {}
         crate::ExprKind::Undefined(_) => {}
@@ -66,12 +71,13 @@ pub fn walk_expr<V: Visitor>(visitor: &mut V, expr: &Expr) {
                 visitor.visit_expr(expr);
             }
         }
-        crate::ExprKind::Object(Object { properties }) => {
+        crate::ExprKind::Object(Object { properties })
+        | crate::ExprKind::Struct(Struct { properties, .. }) => {
             for prop in properties {
                 match prop {
                     crate::PropOrSpread::Prop(prop) => match prop {
-                        Prop::Shorthand(_) => {}
-                        Prop::Property { key: _, value } => {
+                        Prop::Shorthand { .. } => {}
+                        Prop::Property { value, .. } => {
                             visitor.visit_expr(value);
                         }
                     },
@@ -215,13 +221,35 @@ pub fn walk_expr<V: Visitor>(visitor: &mut V, expr: &Expr) {
                 }
             };
         }
+        crate::ExprKind::Slice(Slice {
+            object,
+            start,
+            end,
+            opt_chain: _,
+        }) => {
+            visitor.visit_expr(object);
+            if let Some(start) = start {
+                visitor.visit_expr(start);
+            }
+            if let Some(end) = end {
+                visitor.visit_expr(end);
+            }
+        }
+        crate::ExprKind::Range(Range { start, end }) => {
+            visitor.visit_expr(start);
+            visitor.visit_expr(end);
+        }
+        crate::ExprKind::Pipeline(Pipeline { left, right }) => {
+            visitor.visit_expr(left);
+            visitor.visit_expr(right);
+        }
         crate::ExprKind::IfElse(IfElse {
             cond,
             consequent,
             alternate,
         }) => {
             visitor.visit_expr(cond);
-            walk_block(visitor, consequent);
+            visitor.visit_block(consequent);
             if let Some(alternate) = alternate {
                 walk_block_or_expr(visitor, alternate);
             }
@@ -247,18 +275,19 @@ pub fn walk_expr<V: Visitor>(visitor: &mut V, expr: &Expr) {
             catch,
             finally,
         }) => {
-            walk_block(visitor, body);
+            visitor.visit_block(body);
             if let Some(catch) = catch {
                 if let Some(param) = &catch.param {
                     visitor.visit_pattern(param);
                 }
-                walk_block(visitor, &catch.body);
+                visitor.visit_block(&catch.body);
             }
             if let Some(finally) = finally {
-                walk_block(visitor, finally);
+                visitor.visit_block(finally);
             }
         }
-        crate::ExprKind::Do(Do { body }) => walk_block(visitor, body),
+        crate::ExprKind::Do(Do { body }) => visitor.visit_block(body),
+        crate::ExprKind::Loop(Loop { label: _, body }) => visitor.visit_block(body),
         crate::ExprKind::Await(Await { arg, throws: _ }) => visitor.visit_expr(arg),
         crate::ExprKind::Yield(Yield { arg }) => visitor.visit_expr(arg),
         crate::ExprKind::Throw(Throw { arg, throws: _ }) => visitor.visit_expr(arg),
@@ -271,7 +300,8 @@ pub fn walk_pattern<V: Visitor>(visitor: &mut V, pattern: &Pattern) {
     match &pattern.kind {
         crate::PatternKind::Ident(_) => {}
         crate::PatternKind::Rest(RestPat { arg }) => visitor.visit_pattern(arg),
-        crate::PatternKind::Object(ObjectPat { props, optional: _ }) => {
+        crate::PatternKind::Object(ObjectPat { props, optional: _ })
+        | crate::PatternKind::Struct(StructPat { props, .. }) => {
             for prop in props {
                 match prop {
                     ObjectPatProp::KeyValue(KeyValuePatProp {
@@ -302,6 +332,11 @@ pub fn walk_pattern<V: Visitor>(visitor: &mut V, pattern: &Pattern) {
         }
         crate::PatternKind::Lit(_) => {}
         crate::PatternKind::Is(_) => {}
+        crate::PatternKind::Enum(EnumPat { args, .. }) => {
+            for arg in args {
+                visitor.visit_pattern(arg);
+            }
+        }
         crate::PatternKind::Wildcard => {}
     }
 }
@@ -340,17 +375,118 @@ pub fn walk_decl<V: Visitor>(visitor: &mut V, decl: &Decl) {
             }
             visitor.visit_type_ann(type_ann);
         }
+        DeclKind::EnumDecl(EnumDecl {
+            name: _,
+            type_params,
+            variants,
+        }) => {
+            if let Some(type_params) = type_params {
+                for type_param in type_params {
+                    if let Some(bound) = &type_param.bound {
+                        visitor.visit_type_ann(bound);
+                    }
+                    if let Some(default) = &type_param.default {
+                        visitor.visit_type_ann(default);
+                    }
+                }
+            }
+            for variant in variants {
+                for field in &variant.fields {
+                    visitor.visit_type_ann(field);
+                }
+            }
+        }
+        DeclKind::StructDecl(StructDecl {
+            name: _,
+            type_params,
+            fields,
+        }) => {
+            if let Some(type_params) = type_params {
+                for type_param in type_params {
+                    if let Some(bound) = &type_param.bound {
+                        visitor.visit_type_ann(bound);
+                    }
+                    if let Some(default) = &type_param.default {
+                        visitor.visit_type_ann(default);
+                    }
+                }
+            }
+            for field in fields {
+                visitor.visit_type_ann(&field.type_ann);
+            }
+        }
+        DeclKind::TraitDecl(TraitDecl { name: _, methods }) => {
+            for method in methods {
+                if let Some(type_params) = &method.type_params {
+                    for type_param in type_params {
+                        if let Some(bound) = &type_param.bound {
+                            visitor.visit_type_ann(bound);
+                        }
+                        if let Some(default) = &type_param.default {
+                            visitor.visit_type_ann(default);
+                        }
+                    }
+                }
+                for param in &method.params {
+                    visitor.visit_pattern(&param.pattern);
+                    if let Some(type_ann) = &param.type_ann {
+                        visitor.visit_type_ann(type_ann);
+                    }
+                }
+                visitor.visit_type_ann(&method.type_ann);
+                if let Some(body) = &method.body {
+                    walk_block_or_expr(visitor, body);
+                }
+            }
+        }
+        DeclKind::ImplDecl(ImplDecl {
+            trait_name: _,
+            type_name: _,
+            methods,
+        }) => {
+            for Method { function, .. } in methods {
+                for param in &function.params {
+                    visitor.visit_pattern(&param.pattern);
+                    if let Some(type_ann) = &param.type_ann {
+                        visitor.visit_type_ann(type_ann);
+                    }
+                }
+                walk_block_or_expr(visitor, &function.body);
+                if let Some(type_ann) = &function.type_ann {
+                    visitor.visit_type_ann(type_ann);
+                }
+            }
+        }
     }
 }
 
 pub fn walk_stmt<V: Visitor>(visitor: &mut V, stmt: &Stmt) {
     match &stmt.kind {
         StmtKind::Expr(ExprStmt { expr }) => visitor.visit_expr(expr),
-        StmtKind::For(ForStmt { left, right, body }) => {
+        StmtKind::For(ForStmt {
+            label: _,
+            left,
+            right,
+            body,
+        }) => {
             visitor.visit_pattern(left);
             visitor.visit_expr(right);
-            walk_block(visitor, body);
+            visitor.visit_block(body);
+        }
+        StmtKind::While(WhileStmt {
+            label: _,
+            test,
+            body,
+        }) => {
+            visitor.visit_expr(test);
+            visitor.visit_block(body);
+        }
+        StmtKind::Break(BreakStmt { label: _, arg }) => {
+            if let Some(arg) = arg {
+                visitor.visit_expr(arg);
+            }
         }
+        StmtKind::Continue(_) => {}
         StmtKind::Return(ReturnStmt { arg }) => {
             if let Some(arg) = arg {
                 visitor.visit_expr(arg);
@@ -401,7 +537,7 @@ pub fn walk_block<V: Visitor>(visitor: &mut V, block: &Block) {
 
 pub fn walk_block_or_expr<V: Visitor>(visitor: &mut V, block_or_expr: &BlockOrExpr) {
     match block_or_expr {
-        BlockOrExpr::Block(block) => walk_block(visitor, block),
+        BlockOrExpr::Block(block) => visitor.visit_block(block),
         BlockOrExpr::Expr(expr) => visitor.visit_expr(expr),
     }
 }
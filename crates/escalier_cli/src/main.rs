@@ -1,3 +1,373 @@
-fn main() {
-    eprintln!("Hello, world!");
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use escalier_ast::{script_json_schema, script_to_json};
+use escalier_fmt::format_script;
+use escalier_hm::checker::Checker;
+use escalier_hm::context::Context;
+use escalier_hm::snapshot::{load_snapshot, save_snapshot};
+use escalier_interop::convert::convert_dts;
+use escalier_interop::parse::parse_dts_files;
+use escalier_interop::resolver::Resolver;
+
+const USAGE: &str = "usage: escalier fmt [--check] <paths>...
+       escalier ast [--types [--lib <name|path>]... [--snapshot <path>]] <file>
+       escalier ast --schema
+       escalier snapshot [--lib <name|path>]... <output>
+       escalier convert [-o <output>] <file.d.ts>
+
+convert turns each class into a type for its instances and a `declare let`
+for its constructor.  Namespaces and `declare module` blocks are skipped.";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    match args.first().map(|arg| arg.as_str()) {
+        Some("fmt") => fmt(&args[1..]),
+        Some("ast") => ast(&args[1..]),
+        Some("snapshot") => snapshot(&args[1..]),
+        Some("convert") => convert(&args[1..]),
+        _ => {
+            eprintln!("{USAGE}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Formats the given files in place.  Directories are searched recursively
+/// for .esc files.  With `--check` files aren't written, instead we exit with
+/// a non-zero status if any of them would change.
+fn fmt(args: &[String]) -> ExitCode {
+    let mut check = false;
+    let mut paths = vec![];
+    for arg in args {
+        match arg.as_str() {
+            "--check" => check = true,
+            arg if arg.starts_with('-') => {
+                eprintln!("unknown option: {arg}\n{USAGE}");
+                return ExitCode::FAILURE;
+            }
+            arg => paths.push(PathBuf::from(arg)),
+        }
+    }
+
+    if paths.is_empty() {
+        eprintln!("{USAGE}");
+        return ExitCode::FAILURE;
+    }
+
+    let mut files = vec![];
+    for path in &paths {
+        if let Err(err) = find_source_files(path, &mut files) {
+            eprintln!("{}: {err}", path.display());
+            return ExitCode::FAILURE;
+        }
+    }
+
+    let mut failed = false;
+    for file in &files {
+        let src = match fs::read_to_string(file) {
+            Ok(src) => src,
+            Err(err) => {
+                eprintln!("{}: {err}", file.display());
+                failed = true;
+                continue;
+            }
+        };
+
+        let output = match format_script(&src) {
+            Ok(output) => output,
+            Err(err) => {
+                eprintln!("{}: {}", file.display(), err.message);
+                failed = true;
+                continue;
+            }
+        };
+
+        if output == src {
+            continue;
+        }
+
+        if check {
+            println!("{}", file.display());
+            failed = true;
+        } else if let Err(err) = fs::write(file, output) {
+            eprintln!("{}: {err}", file.display());
+            failed = true;
+        }
+    }
+
+    match failed {
+        true => ExitCode::FAILURE,
+        false => ExitCode::SUCCESS,
+    }
+}
+
+/// Prints the AST of the given file as JSON.  With `--types` the script is
+/// type checked first, against the libs passed to `--lib` if there are any,
+/// and each node includes its inferred type.  Each lib is either a path to a
+/// .d.ts file or the name of a TypeScript lib, e.g. "es2015" or "dom".  A
+/// snapshot created by `escalier snapshot` can be passed to `--snapshot`
+/// instead.  With `--schema` the JSON schema for the AST is printed instead.
+fn ast(args: &[String]) -> ExitCode {
+    let mut types = false;
+    let mut libs: Vec<String> = vec![];
+    let mut snapshot = None;
+    let mut file = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--schema" => {
+                println!("{}", script_json_schema());
+                return ExitCode::SUCCESS;
+            }
+            "--types" => types = true,
+            "--lib" => match args.next() {
+                Some(lib) => libs.push(lib.to_owned()),
+                None => {
+                    eprintln!("missing name or path for --lib\n{USAGE}");
+                    return ExitCode::FAILURE;
+                }
+            },
+            "--snapshot" => match args.next() {
+                Some(path) => snapshot = Some(PathBuf::from(path)),
+                None => {
+                    eprintln!("missing path for --snapshot\n{USAGE}");
+                    return ExitCode::FAILURE;
+                }
+            },
+            arg if arg.starts_with('-') => {
+                eprintln!("unknown option: {arg}\n{USAGE}");
+                return ExitCode::FAILURE;
+            }
+            arg if file.is_none() => file = Some(PathBuf::from(arg)),
+            _ => {
+                eprintln!("{USAGE}");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    let Some(file) = file else {
+        eprintln!("{USAGE}");
+        return ExitCode::FAILURE;
+    };
+
+    if snapshot.is_some() && !libs.is_empty() {
+        eprintln!("--lib and --snapshot can't be used together\n{USAGE}");
+        return ExitCode::FAILURE;
+    }
+
+    let src = match fs::read_to_string(&file) {
+        Ok(src) => src,
+        Err(err) => {
+            eprintln!("{}: {err}", file.display());
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut script = match escalier_parser::parse(&src) {
+        Ok(script) => script,
+        Err(err) => {
+            eprintln!("{}: {}", file.display(), err.message);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let type_names = match types {
+        true => {
+            // Names of TypeScript libs are resolved from the `node_modules`
+            // directories above `file`.
+            let root = fs::canonicalize(&file)
+                .ok()
+                .and_then(|path| path.parent().map(Path::to_path_buf))
+                .unwrap_or_default();
+
+            let result = match &snapshot {
+                Some(path) => fs::read(path)
+                    .map_err(|err| err.to_string())
+                    .and_then(|bytes| load_snapshot(&bytes))
+                    .map_err(|message| format!("{}: {message}", path.display())),
+                None if libs.is_empty() => Ok((Checker::default(), Context::default())),
+                None => load_libs(&root, &libs),
+            };
+            let (mut checker, mut ctx) = match result {
+                Ok(value) => value,
+                Err(message) => {
+                    eprintln!("{message}");
+                    return ExitCode::FAILURE;
+                }
+            };
+
+            if let Err(err) = checker.infer_script(&mut script, &mut ctx) {
+                eprintln!("{}: {err}", file.display());
+                return ExitCode::FAILURE;
+            }
+
+            checker.get_type_names(&script)
+        }
+        false => HashMap::new(),
+    };
+
+    println!("{}", script_to_json(&script, type_names));
+    ExitCode::SUCCESS
+}
+
+/// Writes a snapshot of the given libs, or lib.es5.d.ts if there aren't any,
+/// to `output` so that they can be loaded without being parsed again.
+fn snapshot(args: &[String]) -> ExitCode {
+    let mut libs: Vec<String> = vec![];
+    let mut output = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--lib" => match args.next() {
+                Some(lib) => libs.push(lib.to_owned()),
+                None => {
+                    eprintln!("missing name or path for --lib\n{USAGE}");
+                    return ExitCode::FAILURE;
+                }
+            },
+            arg if arg.starts_with('-') => {
+                eprintln!("unknown option: {arg}\n{USAGE}");
+                return ExitCode::FAILURE;
+            }
+            arg if output.is_none() => output = Some(PathBuf::from(arg)),
+            _ => {
+                eprintln!("{USAGE}");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    let Some(output) = output else {
+        eprintln!("{USAGE}");
+        return ExitCode::FAILURE;
+    };
+
+    if libs.is_empty() {
+        libs.push("es5".to_string());
+    }
+
+    let root = env::current_dir().unwrap_or_default();
+    let result = load_libs(&root, &libs)
+        .and_then(|(checker, ctx)| save_snapshot(&checker, &ctx))
+        .and_then(|bytes| {
+            fs::write(&output, bytes).map_err(|err| format!("{}: {err}", output.display()))
+        });
+
+    match result {
+        Ok(_) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("{message}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Converts the declarations in a .d.ts file into Escalier declarations so that
+/// they can be vendored and edited by hand.  The output is printed unless a
+/// path is passed to `-o`.
+fn convert(args: &[String]) -> ExitCode {
+    let mut output = None;
+    let mut file = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-o" => match args.next() {
+                Some(path) => output = Some(PathBuf::from(path)),
+                None => {
+                    eprintln!("missing path for -o\n{USAGE}");
+                    return ExitCode::FAILURE;
+                }
+            },
+            arg if arg.starts_with('-') => {
+                eprintln!("unknown option: {arg}\n{USAGE}");
+                return ExitCode::FAILURE;
+            }
+            arg if file.is_none() => file = Some(PathBuf::from(arg)),
+            _ => {
+                eprintln!("{USAGE}");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    let Some(file) = file else {
+        eprintln!("{USAGE}");
+        return ExitCode::FAILURE;
+    };
+
+    let result = fs::read_to_string(&file)
+        .map_err(|err| err.to_string())
+        .and_then(|src| convert_dts(&src).map_err(|err| err.kind().msg().to_string()))
+        .map_err(|message| format!("{}: {message}", file.display()));
+    let src = match result {
+        Ok(src) => src,
+        Err(message) => {
+            eprintln!("{message}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match output {
+        Some(output) => match fs::write(&output, src) {
+            Ok(_) => ExitCode::SUCCESS,
+            Err(err) => {
+                eprintln!("{}: {err}", output.display());
+                ExitCode::FAILURE
+            }
+        },
+        None => {
+            print!("{src}");
+            ExitCode::SUCCESS
+        }
+    }
+}
+
+/// Loads the given libs into a single context.  Names of TypeScript libs are
+/// resolved from the `node_modules` directories above `root`.
+fn load_libs(root: &Path, libs: &[String]) -> Result<(Checker, Context), String> {
+    let resolver = Resolver::new(root);
+
+    let (paths, names): (Vec<_>, Vec<_>) = libs.iter().partition(|lib| lib.ends_with(".d.ts"));
+    let names = names.into_iter().cloned().collect::<Vec<_>>();
+
+    let mut paths = paths.into_iter().map(PathBuf::from).collect::<Vec<_>>();
+    paths.extend(resolver.lib_files(&names)?);
+
+    let mut files = vec![];
+    for path in paths {
+        match fs::read_to_string(&path) {
+            Ok(src) => files.push((path, src)),
+            Err(err) => return Err(format!("{}: {err}", path.display())),
+        }
+    }
+
+    parse_dts_files(&files).map_err(|err| err.kind().msg().to_string())
+}
+
+fn find_source_files(path: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    if !path.is_dir() {
+        files.push(path.to_owned());
+        return Ok(());
+    }
+
+    let mut entries = fs::read_dir(path)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    entries.sort();
+
+    for entry in entries {
+        if entry.is_dir() {
+            find_source_files(&entry, files)?;
+        } else if entry.extension().map_or(false, |ext| ext == "esc") {
+            files.push(entry);
+        }
+    }
+
+    Ok(())
 }
@@ -3,7 +3,7 @@ use escalier_parser::parse;
 
 fn compile(input: &str) -> String {
     let script = parse(input).unwrap();
-    let (js, _) = codegen_js(input, &script);
+    let (js, _) = codegen_js(input, &script).unwrap();
     js
 }
 
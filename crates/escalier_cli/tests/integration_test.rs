@@ -454,7 +454,7 @@ fn infer_async_math() -> Result<(), TypeError> {
 fn codegen_let_rec() -> Result<(), TypeError> {
     let src = "let f = fn () => f()";
     let (script, (ctx, checker)) = infer_script(src);
-    let (js, _) = codegen_js(src, &script);
+    let (js, _) = codegen_js(src, &script).unwrap();
 
     insta::assert_snapshot!(js, @"export const f = ()=>f();
 ");
@@ -474,7 +474,7 @@ fn codegen_if_else() -> Result<(), TypeError> {
     "#;
     let (script, (ctx, checker)) = infer_script(src);
 
-    let (js, _) = codegen_js(src, &script);
+    let (js, _) = codegen_js(src, &script).unwrap();
     insta::assert_snapshot!(js, @r###"
     export const cond = true;
     let $temp_0;
@@ -501,7 +501,7 @@ fn codegen_if_else() -> Result<(), TypeError> {
 fn codegen_object() -> Result<(), TypeError> {
     let src = "let point = {x: 5, y: 10}";
     let (script, (ctx, checker)) = infer_script(src);
-    let (js, _) = codegen_js(src, &script);
+    let (js, _) = codegen_js(src, &script).unwrap();
 
     insta::assert_snapshot!(js, @r###"
     export const point = {
@@ -528,7 +528,7 @@ fn codegen_async_math() -> Result<(), TypeError> {
     let src = "let add = async fn (a, b) => await a() + await b()";
     let (script, (ctx, checker)) = infer_script(src);
 
-    let (js, _) = codegen_js(src, &script);
+    let (js, _) = codegen_js(src, &script).unwrap();
 
     insta::assert_snapshot!(js, @"export const add = async (a, b)=>await a() + await b();
 ");
@@ -803,7 +803,7 @@ fn codegen_code_with_type_delcarations() -> Result<(), TypeError> {
     let point: Point = {x: 5, y: 10}
     "#;
     let (script, (ctx, checker)) = infer_script(src);
-    let (js, _) = codegen_js(src, &script);
+    let (js, _) = codegen_js(src, &script).unwrap();
 
     insta::assert_snapshot!(js, @r###"
     ;
@@ -908,7 +908,7 @@ fn codegen_object_type_with_optional_property() -> Result<(), TypeError> {
     let point: Point = {y: 10}
     "#;
     let (script, (ctx, checker)) = infer_script(src);
-    let (js, _) = codegen_js(src, &script);
+    let (js, _) = codegen_js(src, &script).unwrap();
 
     insta::assert_snapshot!(js, @r###"
     ;
@@ -982,7 +982,7 @@ fn codegen_block_with_multiple_non_let_lines() -> Result<(), TypeError> {
         x
     }"#;
     let (script, (ctx, checker)) = infer_script(src);
-    let (js, _) = codegen_js(src, &script);
+    let (js, _) = codegen_js(src, &script).unwrap();
 
     insta::assert_snapshot!(js, @r###"
     let $temp_0;
@@ -1455,7 +1455,7 @@ fn codegen_if_let() -> Result<(), TypeError> {
 
     let (script, (ctx, checker)) = infer_script(src);
 
-    let (js, _) = codegen_js(src, &script);
+    let (js, _) = codegen_js(src, &script).unwrap();
     insta::assert_snapshot!(js, @r###"
     export const p = {
         x: 5,
@@ -1494,7 +1494,7 @@ fn codegen_if_let_with_rename() -> Result<(), TypeError> {
 
     let (script, (ctx, checker)) = infer_script(src);
 
-    let (js, _) = codegen_js(src, &script);
+    let (js, _) = codegen_js(src, &script).unwrap();
     insta::assert_snapshot!(js, @r###"
     export const p = {
         x: 5,
@@ -1560,7 +1560,7 @@ fn infer_if_let_refutable_pattern_obj() -> Result<(), TypeError> {
         "{x: 5, y: 10}"
     );
 
-    let (js, _) = codegen_js(src, &script);
+    let (js, _) = codegen_js(src, &script).unwrap();
     insta::assert_snapshot!(js, @r###"
     export const p = {
         x: 5,
@@ -1600,7 +1600,7 @@ fn infer_if_let_refutable_pattern_nested_obj() -> Result<(), TypeError> {
 
     let (script, (ctx, checker)) = infer_script(src);
 
-    let (js, _) = codegen_js(src, &script);
+    let (js, _) = codegen_js(src, &script).unwrap();
     insta::assert_snapshot!(js, @r###"
     export const action = {
         type: "moveto",
@@ -1648,7 +1648,7 @@ fn infer_if_let_refutable_pattern_with_disjoint_union() -> Result<(), TypeError>
 
     let (script, (ctx, checker)) = infer_script(src);
 
-    let (js, _) = codegen_js(src, &script);
+    let (js, _) = codegen_js(src, &script).unwrap();
     insta::assert_snapshot!(js, @r###"
     ;
     ;
@@ -1700,7 +1700,7 @@ fn infer_if_let_refutable_pattern_array() -> Result<(), TypeError> {
         "[5, 10]"
     );
 
-    let (js, _) = codegen_js(src, &script);
+    let (js, _) = codegen_js(src, &script).unwrap();
     insta::assert_snapshot!(js, @r###"
     export const p = [
         5,
@@ -1736,7 +1736,7 @@ fn infer_if_let_refutable_pattern_nested_array() -> Result<(), TypeError> {
 
     let (script, (ctx, checker)) = infer_script(src);
 
-    let (js, _) = codegen_js(src, &script);
+    let (js, _) = codegen_js(src, &script).unwrap();
     insta::assert_snapshot!(js, @r###"
     export const action = [
         "moveto",
@@ -1775,7 +1775,7 @@ fn codegen_if_let_with_is_prim() -> Result<(), TypeError> {
 
     let (script, (ctx, checker)) = infer_script(src);
 
-    let (js, _) = codegen_js(src, &script);
+    let (js, _) = codegen_js(src, &script).unwrap();
     insta::assert_snapshot!(js, @r###"
     ;
     let $temp_0;
@@ -1831,7 +1831,7 @@ fn codegen_if_let_with_is_class() {
         }
     };
 
-    let (js, _) = codegen_js(src, &script);
+    let (js, _) = codegen_js(src, &script).unwrap();
     insta::assert_snapshot!(js, @r###"
     ;
     ;
@@ -1862,7 +1862,7 @@ fn codegen_array() -> Result<(), TypeError> {
 
     let (script, (ctx, checker)) = infer_script(src);
 
-    let (js, _) = codegen_js(src, &script);
+    let (js, _) = codegen_js(src, &script).unwrap();
     insta::assert_snapshot!(js, @r###"
     export const arr = [
         "hello",
@@ -0,0 +1,4 @@
+#[derive(Debug, PartialEq, Eq)]
+pub struct CodegenError {
+    pub message: String,
+}
@@ -1,8 +1,9 @@
 use generational_arena::Index;
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::rc::Rc;
 use swc_atoms::*;
-use swc_common::{SourceMap, DUMMY_SP};
+use swc_common::comments::{Comment, CommentKind, Comments, SingleThreadedComments};
+use swc_common::{BytePos, SourceMap, Span, DUMMY_SP};
 use swc_ecma_ast::*;
 use swc_ecma_codegen::*;
 
@@ -18,10 +19,12 @@ pub fn codegen_d_ts(
     ctx: &Context,
     checker: &Checker,
 ) -> core::result::Result<String, TypeError> {
-    Ok(print_d_ts(&build_d_ts(program, ctx, checker)?))
+    let comments = SingleThreadedComments::default();
+    let program = build_d_ts(program, ctx, checker, &comments)?;
+    Ok(print_d_ts(&program, &comments))
 }
 
-fn print_d_ts(program: &Program) -> String {
+fn print_d_ts(program: &Program, comments: &SingleThreadedComments) -> String {
     let mut buf = vec![];
     let cm = Rc::new(SourceMap::default());
 
@@ -30,19 +33,22 @@ fn print_d_ts(program: &Program) -> String {
             ..Default::default()
         },
         cm: cm.clone(),
-        comments: None,
+        comments: Some(comments),
         wr: text_writer::JsWriter::new(cm, "\n", &mut buf, None),
     };
 
     emitter.emit_program(program).unwrap();
 
-    String::from_utf8_lossy(&buf).to_string()
+    // swc follows block comments with a space instead of a newline so we move
+    // decls with a JSDoc comment onto their own line.
+    String::from_utf8_lossy(&buf).replace("\n */ ", "\n */\n")
 }
 
 fn build_type_params_from_type_params(
     type_params: Option<&Vec<types::TypeParam>>,
     ctx: &Context,
     checker: &Checker,
+    comments: &SingleThreadedComments,
 ) -> Option<Box<TsTypeParamDecl>> {
     type_params.as_ref().map(|type_params| {
         Box::from(TsTypeParamDecl {
@@ -50,10 +56,9 @@ fn build_type_params_from_type_params(
             params: type_params
                 .iter()
                 .map(|type_param| {
-                    let constraint = type_param
-                        .constraint
-                        .as_ref()
-                        .map(|constraint| Box::from(build_type(constraint, ctx, checker)));
+                    let constraint = type_param.constraint.as_ref().map(|constraint| {
+                        Box::from(build_type(constraint, ctx, checker, comments))
+                    });
                     TsTypeParam {
                         span: DUMMY_SP,
                         name: build_ident(&type_param.name),
@@ -69,14 +74,50 @@ fn build_type_params_from_type_params(
     })
 }
 
+// Comments can't be attached to nodes with a `DUMMY_SP` so we give each decl
+// with a doc comment its own span.
+fn build_doc_span(
+    docs: &HashMap<String, String>,
+    name: &str,
+    comments: &SingleThreadedComments,
+) -> Span {
+    match docs.get(name) {
+        Some(doc) => {
+            let pos = BytePos(comments.borrow_all().0.len() as u32 + 1);
+            let mut text = String::from("*\n");
+            for line in doc.lines() {
+                text.push_str(format!(" * {line}").trim_end());
+                text.push('\n');
+            }
+            text.push(' ');
+            comments.add_leading(
+                pos,
+                Comment {
+                    kind: CommentKind::Block,
+                    span: DUMMY_SP,
+                    text: text.into(),
+                },
+            );
+            Span::new(pos, pos, Default::default())
+        }
+        None => DUMMY_SP,
+    }
+}
+
 fn build_d_ts(
     program: &values::Script,
     ctx: &Context,
     checker: &Checker,
+    comments: &SingleThreadedComments,
 ) -> core::result::Result<Program, TypeError> {
     // TODO: Create a common `Export` type
     let mut type_exports: BTreeSet<String> = BTreeSet::new();
     let mut value_exports: BTreeSet<String> = BTreeSet::new();
+    let mut docs: HashMap<String, String> = HashMap::new();
+    let mut enums: HashMap<String, &values::EnumDecl> = HashMap::new();
+    let mut structs: BTreeMap<String, &values::StructDecl> = BTreeMap::new();
+    let mut traits: BTreeSet<String> = BTreeSet::new();
+    let mut impls: BTreeMap<String, Vec<String>> = BTreeMap::new();
 
     for stmt in &program.stmts {
         match &stmt.kind {
@@ -91,17 +132,51 @@ fn build_d_ts(
             // }
             values::StmtKind::Decl(decl) => match &decl.kind {
                 values::DeclKind::TypeDecl(values::TypeDecl { name, .. }) => {
+                    if let Some(doc) = decl.doc() {
+                        docs.insert(name.to_owned(), doc);
+                    }
                     type_exports.insert(name.to_owned());
                 }
+                values::DeclKind::EnumDecl(enum_decl) => {
+                    if let Some(doc) = decl.doc() {
+                        docs.insert(enum_decl.name.to_owned(), doc);
+                    }
+                    type_exports.insert(enum_decl.name.to_owned());
+                    enums.insert(enum_decl.name.to_owned(), enum_decl);
+                }
+                values::DeclKind::StructDecl(struct_decl) => {
+                    if let Some(doc) = decl.doc() {
+                        docs.insert(struct_decl.name.to_owned(), doc);
+                    }
+                    structs.insert(struct_decl.name.to_owned(), struct_decl);
+                }
+                values::DeclKind::TraitDecl(trait_decl) => {
+                    if let Some(doc) = decl.doc() {
+                        docs.insert(trait_decl.name.to_owned(), doc);
+                    }
+                    traits.insert(trait_decl.name.to_owned());
+                }
+                values::DeclKind::ImplDecl(impl_decl) => {
+                    impls
+                        .entry(impl_decl.type_name.name.to_owned())
+                        .or_default()
+                        .push(impl_decl.trait_name.name.to_owned());
+                }
                 values::DeclKind::VarDecl(values::VarDecl { pattern, .. }) => {
                     let bindings = get_bindings(pattern);
                     for name in bindings {
+                        if let Some(doc) = decl.doc() {
+                            docs.insert(name.to_owned(), doc);
+                        }
                         value_exports.insert(name);
                     }
                 }
             },
-            values::StmtKind::Expr(_) => (),   // nothing is exported
-            values::StmtKind::For(_) => (),    // nothing is exported
+            values::StmtKind::Expr(_) => (),  // nothing is exported
+            values::StmtKind::For(_) => (),   // nothing is exported
+            values::StmtKind::While(_) => (), // nothing is exported
+            values::StmtKind::Break(_) => (), // nothing is exported
+            values::StmtKind::Continue(_) => (), // nothing is exported
             values::StmtKind::Return(_) => (), // nothing is exported
         }
     }
@@ -112,16 +187,16 @@ fn build_d_ts(
         let scheme = ctx.get_scheme(&name)?;
 
         let type_params =
-            build_type_params_from_type_params(scheme.type_params.as_ref(), ctx, checker);
+            build_type_params_from_type_params(scheme.type_params.as_ref(), ctx, checker, comments);
 
         if let types::TypeKind::Object(obj) = &checker.arena[scheme.t].kind {
             let mutable_decl =
                 ModuleItem::Stmt(Stmt::Decl(Decl::TsTypeAlias(Box::from(TsTypeAliasDecl {
-                    span: DUMMY_SP,
+                    span: build_doc_span(&docs, &name, comments),
                     declare: true,
                     id: build_ident(&name),
                     type_params: type_params.clone(),
-                    type_ann: Box::from(build_obj_type(obj, ctx, checker)),
+                    type_ann: Box::from(build_obj_type(obj, ctx, checker, comments)),
                 }))));
             body.push(mutable_decl);
 
@@ -133,7 +208,7 @@ fn build_d_ts(
                             declare: true,
                             id: build_ident(format!("Readonly{name}").as_str()),
                             type_params,
-                            type_ann: Box::from(build_obj_type(&obj, ctx, checker)),
+                            type_ann: Box::from(build_obj_type(&obj, ctx, checker, comments)),
                         }),
                     )));
 
@@ -143,15 +218,66 @@ fn build_d_ts(
         } else {
             let decl =
                 ModuleItem::Stmt(Stmt::Decl(Decl::TsTypeAlias(Box::from(TsTypeAliasDecl {
-                    span: DUMMY_SP,
+                    span: build_doc_span(&docs, &name, comments),
                     declare: true,
                     id: build_ident(&name),
                     type_params,
-                    type_ann: Box::from(build_type(&scheme.t, ctx, checker)),
+                    type_ann: Box::from(build_type(&scheme.t, ctx, checker, comments)),
                 }))));
 
             body.push(decl);
         }
+
+        if let Some(enum_decl) = enums.get(&name) {
+            body.push(build_enum_namespace(enum_decl, ctx, checker, comments)?);
+        }
+    }
+
+    for name in traits {
+        let span = build_doc_span(&docs, &name, comments);
+        body.push(build_trait_interface(&name, span, ctx, checker, comments)?);
+    }
+
+    for (name, struct_decl) in structs {
+        let span = build_doc_span(&docs, &name, comments);
+        body.push(build_struct_class(
+            struct_decl,
+            span,
+            ctx,
+            checker,
+            comments,
+        )?);
+    }
+
+    // The methods from each trait are merged into the struct's class by
+    // declaring an interface with the same name, e.g.
+    // `export interface Circle extends Shape {}`.
+    for (name, trait_names) in impls {
+        // The interface's type params must match those of the class.
+        let scheme = ctx.get_scheme(&name)?;
+        let type_params =
+            build_type_params_from_type_params(scheme.type_params.as_ref(), ctx, checker, comments);
+        body.push(ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+            span: DUMMY_SP,
+            decl: Decl::TsInterface(Box::from(TsInterfaceDecl {
+                span: DUMMY_SP,
+                id: build_ident(&name),
+                declare: false,
+                type_params,
+                extends: trait_names
+                    .iter()
+                    .map(|trait_name| TsExprWithTypeArgs {
+                        span: DUMMY_SP,
+                        expr: Box::from(Expr::from(build_ident(trait_name))),
+                        type_args: None,
+                    })
+                    .collect(),
+                body: TsInterfaceBody {
+                    span: DUMMY_SP,
+                    body: vec![],
+                },
+            })),
+        })));
     }
 
     for name in value_exports {
@@ -161,12 +287,12 @@ fn build_d_ts(
             id: build_ident(&name),
             type_ann: Some(Box::from(TsTypeAnn {
                 span: DUMMY_SP,
-                type_ann: Box::from(build_type(&binding.index, ctx, checker)),
+                type_ann: Box::from(build_type(&binding.index, ctx, checker, comments)),
             })),
         });
 
         let decl = ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
-            span: DUMMY_SP,
+            span: build_doc_span(&docs, &name, comments),
             decl: Decl::Var(Box::from(VarDecl {
                 span: DUMMY_SP,
                 kind: VarDeclKind::Const,
@@ -190,6 +316,403 @@ fn build_d_ts(
     }))
 }
 
+// enum IpAddr { V4(string), V6(string) } ->
+// export declare namespace IpAddr {
+//     class V4 {
+//         readonly payload: string;
+//         constructor(payload: string);
+//     }
+//     ...
+// }
+// The type alias `IpAddr = IpAddr.V4 | IpAddr.V6` is generated from the
+// enum's scheme.
+fn build_enum_namespace(
+    decl: &values::EnumDecl,
+    ctx: &Context,
+    checker: &Checker,
+    comments: &SingleThreadedComments,
+) -> core::result::Result<ModuleItem, TypeError> {
+    let mut classes: Vec<ModuleItem> = vec![];
+
+    for variant in &decl.variants {
+        let scheme = ctx.get_scheme(&format!("{}.{}", decl.name, variant.name))?;
+        let type_params =
+            build_type_params_from_type_params(scheme.type_params.as_ref(), ctx, checker, comments);
+
+        let payload = match &checker.arena[scheme.t].kind {
+            types::TypeKind::Object(obj) => obj.elems.iter().find_map(|elem| match elem {
+                types::TObjElem::Prop(prop) => Some(prop.t),
+                _ => None,
+            }),
+            _ => None,
+        };
+
+        let mut members: Vec<ClassMember> = vec![];
+        if let Some(payload) = payload {
+            let type_ann = Box::from(TsTypeAnn {
+                span: DUMMY_SP,
+                type_ann: Box::from(build_type(&payload, ctx, checker, comments)),
+            });
+            members.push(ClassMember::ClassProp(ClassProp {
+                span: DUMMY_SP,
+                key: PropName::Ident(build_ident("payload")),
+                value: None,
+                type_ann: Some(type_ann.clone()),
+                is_static: false,
+                decorators: vec![],
+                accessibility: None,
+                is_abstract: false,
+                is_optional: false,
+                is_override: false,
+                readonly: true,
+                declare: false,
+                definite: false,
+            }));
+
+            let pat = match variant.fields.len() {
+                1 => Pat::Ident(BindingIdent {
+                    id: build_ident("payload"),
+                    type_ann: Some(type_ann),
+                }),
+                _ => Pat::Rest(RestPat {
+                    span: DUMMY_SP,
+                    dot3_token: DUMMY_SP,
+                    arg: Box::from(Pat::Ident(BindingIdent::from(build_ident("payload")))),
+                    type_ann: Some(type_ann),
+                }),
+            };
+            members.push(ClassMember::Constructor(Constructor {
+                span: DUMMY_SP,
+                key: PropName::Ident(build_ident("constructor")),
+                params: vec![ParamOrTsParamProp::Param(Param {
+                    span: DUMMY_SP,
+                    decorators: vec![],
+                    pat,
+                })],
+                body: None,
+                accessibility: None,
+                is_optional: false,
+            }));
+        }
+
+        classes.push(ModuleItem::Stmt(Stmt::Decl(Decl::Class(ClassDecl {
+            ident: build_ident(&variant.name),
+            declare: false,
+            class: Box::from(Class {
+                span: DUMMY_SP,
+                decorators: vec![],
+                body: members,
+                super_class: None,
+                is_abstract: false,
+                type_params,
+                super_type_params: None,
+                implements: vec![],
+            }),
+        }))));
+    }
+
+    Ok(ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+        span: DUMMY_SP,
+        decl: Decl::TsModule(Box::from(TsModuleDecl {
+            span: DUMMY_SP,
+            declare: true,
+            global: false,
+            id: TsModuleName::Ident(build_ident(&decl.name)),
+            body: Some(TsNamespaceBody::TsModuleBlock(TsModuleBlock {
+                span: DUMMY_SP,
+                body: classes,
+            })),
+        })),
+    })))
+}
+
+// trait Shape { fn area(self) -> number } ->
+// export interface Shape { area(): number; }
+fn build_trait_interface(
+    name: &str,
+    span: Span,
+    ctx: &Context,
+    checker: &Checker,
+    comments: &SingleThreadedComments,
+) -> core::result::Result<ModuleItem, TypeError> {
+    let scheme = ctx.get_scheme(name)?;
+
+    let obj = match &checker.arena[scheme.t].kind {
+        types::TypeKind::Object(obj) => obj,
+        _ => panic!("{name} is not a trait"),
+    };
+
+    let body: Vec<TsTypeElement> = obj
+        .elems
+        .iter()
+        .filter_map(|elem| match elem {
+            types::TObjElem::Method(method) => {
+                Some(build_method_signature(method, ctx, checker, comments))
+            }
+            _ => None,
+        })
+        .collect();
+
+    Ok(ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+        span,
+        decl: Decl::TsInterface(Box::from(TsInterfaceDecl {
+            span: DUMMY_SP,
+            id: build_ident(name),
+            declare: false,
+            type_params: None,
+            extends: vec![],
+            body: TsInterfaceBody {
+                span: DUMMY_SP,
+                body,
+            },
+        })),
+    })))
+}
+
+fn build_method_signature(
+    method: &types::TMethod,
+    ctx: &Context,
+    checker: &Checker,
+    comments: &SingleThreadedComments,
+) -> TsTypeElement {
+    let types::Function {
+        params,
+        ret,
+        type_params,
+        throws,
+    } = &method.function;
+
+    TsTypeElement::TsMethodSignature(TsMethodSignature {
+        span: build_throws_span(throws.as_ref(), ctx, checker, comments),
+        readonly: false,
+        key: build_prop_key(&method.name),
+        computed: false,
+        optional: false,
+        params: build_fn_params(params, ctx, checker, comments),
+        type_ann: Some(Box::from(build_type_ann(ret, ctx, checker, comments))),
+        type_params: build_type_params_from_type_params(
+            type_params.as_ref(),
+            ctx,
+            checker,
+            comments,
+        ),
+    })
+}
+
+fn build_prop_key(name: &types::TPropKey) -> Box<Expr> {
+    let key = match name {
+        types::TPropKey::StringKey(key) => key,
+        types::TPropKey::NumberKey(key) => key,
+    };
+    Box::from(Expr::from(build_ident(key)))
+}
+
+fn build_accessor_key(
+    name: &types::TPropKey,
+    throws: Option<&Index>,
+    ctx: &Context,
+    checker: &Checker,
+    comments: &SingleThreadedComments,
+) -> Box<Expr> {
+    let key = match name {
+        types::TPropKey::StringKey(key) => key,
+        types::TPropKey::NumberKey(key) => key,
+    };
+    let mut ident = build_ident(key);
+    ident.span = build_throws_span(throws, ctx, checker, comments);
+    Box::from(Expr::from(ident))
+}
+
+fn build_fn_params(
+    params: &[types::FuncParam],
+    ctx: &Context,
+    checker: &Checker,
+    comments: &SingleThreadedComments,
+) -> Vec<TsFnParam> {
+    params
+        .iter()
+        .map(|param| build_fn_param(param, ctx, checker, comments))
+        .collect()
+}
+
+fn build_fn_param(
+    param: &types::FuncParam,
+    ctx: &Context,
+    checker: &Checker,
+    comments: &SingleThreadedComments,
+) -> TsFnParam {
+    let type_ann = Some(Box::from(build_type_ann(&param.t, ctx, checker, comments)));
+    let pat = tpat_to_pat(&param.pattern, type_ann);
+    pat_to_fn_param(param, pat)
+}
+
+// TypeScript doesn't track what a function throws so we record it with a JSDoc
+// `@throws` tag on the signature instead, e.g.
+// `/** @throws {RangeError} */ get(index: number): T;`.
+fn build_throws_span(
+    throws: Option<&Index>,
+    ctx: &Context,
+    checker: &Checker,
+    comments: &SingleThreadedComments,
+) -> Span {
+    let Some(throws) = throws else {
+        return DUMMY_SP;
+    };
+    if let types::TypeKind::Keyword(types::Keyword::Never) = &checker.arena[*throws].kind {
+        return DUMMY_SP;
+    }
+
+    let t = print_ts_type(&build_type(throws, ctx, checker, comments));
+    let pos = BytePos(comments.borrow_all().0.len() as u32 + 1);
+    comments.add_leading(
+        pos,
+        Comment {
+            kind: CommentKind::Block,
+            span: DUMMY_SP,
+            text: format!("* @throws {{{t}}} ").into(),
+        },
+    );
+    Span::new(pos, pos, Default::default())
+}
+
+fn print_ts_type(t: &TsType) -> String {
+    let mut buf = vec![];
+    let cm = Rc::new(SourceMap::default());
+
+    let mut emitter = Emitter {
+        cfg: swc_ecma_codegen::Config {
+            ..Default::default()
+        },
+        cm: cm.clone(),
+        comments: None,
+        wr: text_writer::JsWriter::new(cm, "\n", &mut buf, None),
+    };
+
+    t.emit_with(&mut emitter).unwrap();
+
+    String::from_utf8_lossy(&buf).to_string()
+}
+
+fn build_mapped_modifier(modifier: &Option<types::MappedModifier>) -> Option<TruePlusMinus> {
+    modifier.as_ref().map(|modifier| match modifier {
+        types::MappedModifier::Add => TruePlusMinus::True,
+        types::MappedModifier::Remove => TruePlusMinus::Minus,
+    })
+}
+
+// struct User { pub name: string, ssn: number } ->
+// export declare class User {
+//     name: string;
+//     #private;
+//     constructor(values: {name: string; ssn: number});
+// }
+fn build_struct_class(
+    decl: &values::StructDecl,
+    span: Span,
+    ctx: &Context,
+    checker: &Checker,
+    comments: &SingleThreadedComments,
+) -> core::result::Result<ModuleItem, TypeError> {
+    let scheme = ctx.get_scheme(&decl.name)?;
+    let type_params =
+        build_type_params_from_type_params(scheme.type_params.as_ref(), ctx, checker, comments);
+
+    let obj = match &checker.arena[scheme.t].kind {
+        types::TypeKind::Object(obj) => obj,
+        _ => panic!("{} is not a struct", decl.name),
+    };
+
+    let mut members: Vec<ClassMember> = vec![];
+    for elem in &obj.elems {
+        if let types::TObjElem::Prop(prop) = elem {
+            let name = prop.name.to_string();
+            let is_public = decl
+                .fields
+                .iter()
+                .any(|field| field.name == name && field.is_public);
+            if !is_public {
+                continue;
+            }
+            members.push(ClassMember::ClassProp(ClassProp {
+                span: DUMMY_SP,
+                key: PropName::Ident(build_ident(&name)),
+                value: None,
+                type_ann: Some(Box::from(TsTypeAnn {
+                    span: DUMMY_SP,
+                    type_ann: Box::from(build_type(&prop.t, ctx, checker, comments)),
+                })),
+                is_static: false,
+                decorators: vec![],
+                accessibility: None,
+                is_abstract: false,
+                is_optional: false,
+                is_override: false,
+                readonly: false,
+                declare: false,
+                definite: false,
+            }));
+        }
+    }
+
+    // TypeScript only records that a class has private fields, not what they
+    // are, e.g. `#private;`.
+    if decl.fields.iter().any(|field| !field.is_public) {
+        members.push(ClassMember::PrivateProp(PrivateProp {
+            span: DUMMY_SP,
+            key: PrivateName {
+                span: DUMMY_SP,
+                id: build_ident("private"),
+            },
+            value: None,
+            type_ann: None,
+            is_static: false,
+            decorators: vec![],
+            accessibility: None,
+            is_optional: false,
+            is_override: false,
+            readonly: false,
+            definite: false,
+        }));
+    }
+
+    members.push(ClassMember::Constructor(Constructor {
+        span: DUMMY_SP,
+        key: PropName::Ident(build_ident("constructor")),
+        params: vec![ParamOrTsParamProp::Param(Param {
+            span: DUMMY_SP,
+            decorators: vec![],
+            pat: Pat::Ident(BindingIdent {
+                id: build_ident("values"),
+                type_ann: Some(Box::from(TsTypeAnn {
+                    span: DUMMY_SP,
+                    type_ann: Box::from(build_obj_type(obj, ctx, checker, comments)),
+                })),
+            }),
+        })],
+        body: None,
+        accessibility: None,
+        is_optional: false,
+    }));
+
+    Ok(ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+        span,
+        decl: Decl::Class(ClassDecl {
+            ident: build_ident(&decl.name),
+            declare: true,
+            class: Box::from(Class {
+                span: DUMMY_SP,
+                decorators: vec![],
+                body: members,
+                super_class: None,
+                is_abstract: false,
+                type_params,
+                super_type_params: None,
+                implements: vec![],
+            }),
+        }),
+    })))
+}
+
 // TODO: create a trait for this and then provide multiple implementations
 pub fn build_ident(name: &str) -> Ident {
     Ident {
@@ -269,6 +792,7 @@ fn tpat_to_pat(pat: &types::TPat, type_ann: Option<Box<TsTypeAnn>>) -> Pat {
         }
         types::TPat::Lit(_) => todo!(),
         types::TPat::Is(_) => todo!(),
+        types::TPat::Enum(_) => todo!(),
         types::TPat::Wildcard => todo!(),
     }
 }
@@ -297,21 +821,13 @@ pub fn build_ts_fn_type_with_params(
     type_params: Option<Box<TsTypeParamDecl>>,
     ctx: &Context,
     checker: &Checker,
+    comments: &SingleThreadedComments,
 ) -> TsType {
-    let params: Vec<TsFnParam> = params
-        .iter()
-        .map(|param| {
-            let type_ann = Some(Box::from(build_type_ann(&param.t, ctx, checker)));
-            let pat = tpat_to_pat(&param.pattern, type_ann);
-            pat_to_fn_param(param, pat)
-        })
-        .collect();
-
     TsType::TsFnOrConstructorType(TsFnOrConstructorType::TsFnType(TsFnType {
         span: DUMMY_SP,
-        params,
+        params: build_fn_params(params, ctx, checker, comments),
         type_params,
-        type_ann: Box::from(build_type_ann(ret, ctx, checker)),
+        type_ann: Box::from(build_type_ann(ret, ctx, checker, comments)),
     }))
 }
 
@@ -324,6 +840,7 @@ pub fn build_type(
     // type_params: Option<&TsTypeParamDecl>,
     ctx: &Context,
     checker: &Checker,
+    comments: &SingleThreadedComments,
 ) -> TsType {
     let t = &checker.arena[*t];
     let mutable = false;
@@ -335,7 +852,7 @@ pub fn build_type(
             instance,
         }) => {
             if let Some(instance) = instance {
-                return build_type(instance, ctx, checker);
+                return build_type(instance, ctx, checker, comments);
             }
 
             // TODO: handle constraints on type variables
@@ -423,18 +940,25 @@ pub fn build_type(
             params,
             ret,
             type_params,
-            throws: _,
+            throws,
         }) => {
             let type_params =
-                build_type_params_from_type_params(type_params.as_ref(), ctx, checker);
-            build_ts_fn_type_with_params(params, ret, type_params, ctx, checker)
+                build_type_params_from_type_params(type_params.as_ref(), ctx, checker, comments);
+            let mut fn_type =
+                build_ts_fn_type_with_params(params, ret, type_params, ctx, checker, comments);
+            if let TsType::TsFnOrConstructorType(TsFnOrConstructorType::TsFnType(fn_type)) =
+                &mut fn_type
+            {
+                fn_type.span = build_throws_span(throws.as_ref(), ctx, checker, comments);
+            }
+            fn_type
         }
         types::TypeKind::Union(types::Union { types }) => {
             TsType::TsUnionOrIntersectionType(TsUnionOrIntersectionType::TsUnionType(TsUnionType {
                 span: DUMMY_SP,
                 types: sort_types(types)
                     .iter()
-                    .map(|t| Box::from(build_type(t, ctx, checker)))
+                    .map(|t| Box::from(build_type(t, ctx, checker, comments)))
                     .collect(),
             }))
         }
@@ -444,19 +968,45 @@ pub fn build_type(
                     span: DUMMY_SP,
                     types: sort_types(types)
                         .iter()
-                        .map(|t| Box::from(build_type(t, ctx, checker)))
+                        .map(|t| Box::from(build_type(t, ctx, checker, comments)))
                         .collect(),
                 },
             ))
         }
-        types::TypeKind::Object(obj) => build_obj_type(obj, ctx, checker),
+        types::TypeKind::Object(obj) => build_obj_type(obj, ctx, checker, comments),
         types::TypeKind::TypeRef(types::TypeRef {
             name, type_args, ..
         }) => {
+            // Ranges are emitted as generators which is all that TypeScript
+            // needs to know about them.
+            if name == "Range" && ctx.get_scheme(name).is_err() {
+                return TsType::TsTypeRef(TsTypeRef {
+                    span: DUMMY_SP,
+                    type_name: TsEntityName::from(Ident {
+                        span: DUMMY_SP,
+                        sym: JsWord::from("Iterable"),
+                        optional: false,
+                    }),
+                    type_params: Some(Box::from(TsTypeParamInstantiation {
+                        span: DUMMY_SP,
+                        params: vec![Box::from(TsType::TsKeywordType(TsKeywordType {
+                            span: DUMMY_SP,
+                            kind: TsKeywordTypeKind::TsNumberKeyword,
+                        }))],
+                    })),
+                });
+            }
+
             let mut sym = JsWord::from(name.to_owned());
             let use_readonly_utility = false;
 
-            if !mutable && !name.ends_with("Constructor") {
+            // Structs are emitted as classes and traits as interfaces so they
+            // don't have Readonly variants.
+            if !mutable
+                && !name.ends_with("Constructor")
+                && !ctx.structs.contains_key(name)
+                && !ctx.traits.contains_key(name)
+            {
                 if let Ok(scheme) = ctx.get_scheme(name) {
                     if let types::TypeKind::Object(obj) = &checker.arena[scheme.t].kind {
                         if immutable_obj_type(obj).is_some() {
@@ -486,7 +1036,7 @@ pub fn build_type(
                         span: DUMMY_SP,
                         params: type_args
                             .iter()
-                            .map(|t| Box::from(build_type(t, ctx, checker)))
+                            .map(|t| Box::from(build_type(t, ctx, checker, comments)))
                             .collect(),
                     }))
                 };
@@ -519,15 +1069,26 @@ pub fn build_type(
                 t
             }
         }
-        types::TypeKind::Tuple(types::Tuple { types }) => {
+        types::TypeKind::Tuple(tuple) => {
             let type_ann = TsType::TsTupleType(TsTupleType {
                 span: DUMMY_SP,
-                elem_types: types
+                elem_types: tuple
+                    .types
                     .iter()
-                    .map(|t| TsTupleElement {
-                        span: DUMMY_SP,
-                        label: None,
-                        ty: Box::from(build_type(t, ctx, checker)),
+                    .enumerate()
+                    .map(|(i, t)| {
+                        let mut ty = build_type(t, ctx, checker, comments);
+                        if tuple.is_optional(i) {
+                            ty = TsType::TsOptionalType(TsOptionalType {
+                                span: DUMMY_SP,
+                                type_ann: Box::from(ty),
+                            });
+                        }
+                        TsTupleElement {
+                            span: DUMMY_SP,
+                            label: None,
+                            ty: Box::from(ty),
+                        }
                     })
                     .collect(),
             });
@@ -545,7 +1106,7 @@ pub fn build_type(
         types::TypeKind::Array(types::Array { t }) => {
             let type_ann = TsType::TsArrayType(TsArrayType {
                 span: DUMMY_SP,
-                elem_type: Box::from(build_type(t, ctx, checker)),
+                elem_type: Box::from(build_type(t, ctx, checker, comments)),
             });
 
             if mutable {
@@ -563,14 +1124,14 @@ pub fn build_type(
         types::TypeKind::KeyOf(types::KeyOf { t }) => TsType::TsTypeOperator(TsTypeOperator {
             span: DUMMY_SP,
             op: TsTypeOperatorOp::KeyOf,
-            type_ann: Box::from(build_type(t, ctx, checker)),
+            type_ann: Box::from(build_type(t, ctx, checker, comments)),
         }),
         types::TypeKind::IndexedAccess(types::IndexedAccess { obj: object, index }) => {
             TsType::TsIndexedAccessType(TsIndexedAccessType {
                 span: DUMMY_SP,
                 readonly: false,
-                obj_type: Box::from(build_type(object, ctx, checker)),
-                index_type: Box::from(build_type(index, ctx, checker)),
+                obj_type: Box::from(build_type(object, ctx, checker, comments)),
+                index_type: Box::from(build_type(index, ctx, checker, comments)),
             })
         }
         types::TypeKind::Conditional(types::Conditional {
@@ -580,10 +1141,10 @@ pub fn build_type(
             false_type,
         }) => TsType::TsConditionalType(TsConditionalType {
             span: DUMMY_SP,
-            check_type: Box::from(build_type(check_type, ctx, checker)),
-            extends_type: Box::from(build_type(extends_type, ctx, checker)),
-            true_type: Box::from(build_type(true_type, ctx, checker)),
-            false_type: Box::from(build_type(false_type, ctx, checker)),
+            check_type: Box::from(build_type(check_type, ctx, checker, comments)),
+            extends_type: Box::from(build_type(extends_type, ctx, checker, comments)),
+            true_type: Box::from(build_type(true_type, ctx, checker, comments)),
+            false_type: Box::from(build_type(false_type, ctx, checker, comments)),
         }),
         types::TypeKind::Infer(types::Infer { name }) => TsType::TsInferType(TsInferType {
             span: DUMMY_SP,
@@ -610,61 +1171,119 @@ pub fn build_type(
             // a `number` or `boolean` type.
             todo!()
         }
+        types::TypeKind::TypePredicate(types::TypePredicate { param, t, asserts }) => {
+            let param_name = match param.as_str() {
+                "self" => TsThisTypeOrIdent::TsThisType(TsThisType { span: DUMMY_SP }),
+                _ => TsThisTypeOrIdent::Ident(build_ident(param)),
+            };
+            TsType::TsTypePredicate(TsTypePredicate {
+                span: DUMMY_SP,
+                asserts: *asserts,
+                param_name,
+                type_ann: t.map(|t| {
+                    Box::from(TsTypeAnn {
+                        span: DUMMY_SP,
+                        type_ann: Box::from(build_type(&t, ctx, checker, comments)),
+                    })
+                }),
+            })
+        }
     }
 }
 
 // TODO: generate separate types for immutable and mutable object types
-fn build_obj_type(obj: &types::Object, ctx: &Context, checker: &Checker) -> TsType {
+fn build_obj_type(
+    obj: &types::Object,
+    ctx: &Context,
+    checker: &Checker,
+    comments: &SingleThreadedComments,
+) -> TsType {
     let mut members: Vec<TsTypeElement> = vec![];
     let mut mapped_types: Vec<TsType> = vec![];
 
     for elem in &obj.elems {
         match elem {
-            types::TObjElem::Call(_) => todo!(),
-            types::TObjElem::Constructor(types::Function {
+            types::TObjElem::Call(types::Function {
                 params,
                 ret,
                 type_params,
-                throws: _, // TODO
+                throws,
             }) => {
-                let type_params =
-                    build_type_params_from_type_params(type_params.as_ref(), ctx, checker);
-                let params: Vec<TsFnParam> = params
-                    .iter()
-                    .map(|param| {
-                        let type_ann = Some(Box::from(build_type_ann(&param.t, ctx, checker)));
-                        let pat = tpat_to_pat(&param.pattern, type_ann);
-                        pat_to_fn_param(param, pat)
-                    })
-                    .collect();
+                let type_elem = TsTypeElement::TsCallSignatureDecl(TsCallSignatureDecl {
+                    span: build_throws_span(throws.as_ref(), ctx, checker, comments),
+                    params: build_fn_params(params, ctx, checker, comments),
+                    type_ann: Some(Box::from(build_type_ann(ret, ctx, checker, comments))),
+                    type_params: build_type_params_from_type_params(
+                        type_params.as_ref(),
+                        ctx,
+                        checker,
+                        comments,
+                    ),
+                });
 
+                members.push(type_elem);
+            }
+            types::TObjElem::Constructor(types::Function {
+                params,
+                ret,
+                type_params,
+                throws,
+            }) => {
                 let type_elem = TsTypeElement::TsConstructSignatureDecl(TsConstructSignatureDecl {
-                    span: DUMMY_SP,
-                    params,
-                    type_ann: Some(Box::from(build_type_ann(ret, ctx, checker))),
-                    type_params,
+                    span: build_throws_span(throws.as_ref(), ctx, checker, comments),
+                    params: build_fn_params(params, ctx, checker, comments),
+                    type_ann: Some(Box::from(build_type_ann(ret, ctx, checker, comments))),
+                    type_params: build_type_params_from_type_params(
+                        type_params.as_ref(),
+                        ctx,
+                        checker,
+                        comments,
+                    ),
                 });
 
                 members.push(type_elem);
             }
-            types::TObjElem::Method(_) => todo!(), // TODO
-            types::TObjElem::Getter(_) => todo!(), // TODO
-            types::TObjElem::Setter(_) => todo!(), // TODO
+            types::TObjElem::Method(method) => {
+                members.push(build_method_signature(method, ctx, checker, comments));
+            }
+            // swc doesn't print comments on accessor signatures so the
+            // `@throws` tag goes on the key instead.
+            types::TObjElem::Getter(types::TGetter { name, ret, throws }) => {
+                let type_elem = TsTypeElement::TsGetterSignature(TsGetterSignature {
+                    span: DUMMY_SP,
+                    readonly: false,
+                    key: build_accessor_key(name, throws.as_ref(), ctx, checker, comments),
+                    computed: false,
+                    optional: false,
+                    type_ann: Some(Box::from(build_type_ann(ret, ctx, checker, comments))),
+                });
+                members.push(type_elem);
+            }
+            types::TObjElem::Setter(types::TSetter {
+                name,
+                param,
+                throws,
+            }) => {
+                let type_elem = TsTypeElement::TsSetterSignature(TsSetterSignature {
+                    span: DUMMY_SP,
+                    readonly: false,
+                    key: build_accessor_key(name, throws.as_ref(), ctx, checker, comments),
+                    computed: false,
+                    optional: false,
+                    param: build_fn_param(param, ctx, checker, comments),
+                });
+                members.push(type_elem);
+            }
             types::TObjElem::Prop(prop) => {
-                let key = match &prop.name {
-                    types::TPropKey::StringKey(key) => key.to_owned(),
-                    types::TPropKey::NumberKey(key) => key.to_owned(),
-                };
-
                 let type_elem = TsTypeElement::TsPropertySignature(TsPropertySignature {
                     span: DUMMY_SP,
                     readonly: prop.readonly,
-                    key: Box::from(Expr::from(build_ident(&key))),
+                    key: build_prop_key(&prop.name),
                     computed: false,
                     optional: prop.optional,
                     init: None,
                     params: vec![],
-                    type_ann: Some(Box::from(build_type_ann(&prop.t, ctx, checker))),
+                    type_ann: Some(Box::from(build_type_ann(&prop.t, ctx, checker, comments))),
                     type_params: None,
                 });
                 members.push(type_elem);
@@ -674,17 +1293,18 @@ fn build_obj_type(obj: &types::Object, ctx: &Context, checker: &Checker) -> TsTy
                 value,
                 target, // TODO: make this an Ident
                 source,
-                optional: _, // TODO
+                optional,
+                readonly,
                 // TODO:
                 check: _,
                 extends: _,
             }) => {
                 let mapped = TsType::TsMappedType(TsMappedType {
                     span: DUMMY_SP,
-                    readonly: None, // TODO
-                    optional: None, // TODO
-                    name_type: Some(Box::new(build_type(key, ctx, checker))),
-                    type_ann: Some(Box::new(build_type(value, ctx, checker))),
+                    readonly: build_mapped_modifier(readonly),
+                    optional: build_mapped_modifier(optional),
+                    name_type: Some(Box::new(build_type(key, ctx, checker, comments))),
+                    type_ann: Some(Box::new(build_type(value, ctx, checker, comments))),
                     type_param: TsTypeParam {
                         span: DUMMY_SP,
                         name: Ident {
@@ -695,7 +1315,7 @@ fn build_obj_type(obj: &types::Object, ctx: &Context, checker: &Checker) -> TsTy
                         is_in: true,
                         is_out: false,
                         is_const: false,
-                        constraint: Some(Box::new(build_type(source, ctx, checker))),
+                        constraint: Some(Box::new(build_type(source, ctx, checker, comments))),
                         default: None, // TODO
                     },
                 });
@@ -741,10 +1361,15 @@ fn build_obj_type(obj: &types::Object, ctx: &Context, checker: &Checker) -> TsTy
     }
 }
 
-fn build_type_ann(t: &Index, ctx: &Context, checker: &Checker) -> TsTypeAnn {
+fn build_type_ann(
+    t: &Index,
+    ctx: &Context,
+    checker: &Checker,
+    comments: &SingleThreadedComments,
+) -> TsTypeAnn {
     TsTypeAnn {
         span: DUMMY_SP,
-        type_ann: Box::from(build_type(t, ctx, checker)),
+        type_ann: Box::from(build_type(t, ctx, checker, comments)),
     }
 }
 
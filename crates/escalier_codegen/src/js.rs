@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::rc::Rc;
 
 use swc_atoms::*;
@@ -14,11 +15,40 @@ use swc_ecma_visit::*;
 
 use escalier_ast::{self as values};
 
+use crate::codegen_error::CodegenError;
+
 pub struct Context {
     pub temp_id: u32,
+    // Maps each enum to the number of fields in each of its variants.
+    pub enums: HashMap<String, HashMap<String, usize>>,
+    // Maps each trait to the names of its methods with default implementations.
+    pub traits: HashMap<String, Vec<String>>,
+    // The temp that `_` refers to within the right side of a pipeline.
+    pub topic: Option<Ident>,
+    // The labels of the `loop`s we're inside of along with the temps that
+    // `break value` assigns to.
+    pub loops: Vec<(Option<String>, Option<Ident>)>,
 }
 
 impl Context {
+    // Returns the number of fields in the variant if `expr` references an
+    // enum variant, e.g. `IpAddr.V4`.
+    fn get_variant_arity(&self, expr: &values::Expr) -> Option<usize> {
+        match &expr.kind {
+            values::ExprKind::Member(values::Member {
+                object,
+                property: values::MemberProp::Ident(variant),
+                ..
+            }) => match &object.kind {
+                values::ExprKind::Ident(ident) => {
+                    self.enums.get(&ident.name)?.get(&variant.name).copied()
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
     pub fn new_ident(&mut self) -> Ident {
         let ident = Ident {
             span: DUMMY_SP,
@@ -30,9 +60,18 @@ impl Context {
     }
 }
 
-pub fn codegen_js(src: &str, program: &values::Script) -> (String, String) {
-    let mut ctx = Context { temp_id: 0 };
-    let program = build_js(program, &mut ctx);
+pub fn codegen_js(
+    src: &str,
+    program: &values::Script,
+) -> core::result::Result<(String, String), CodegenError> {
+    let mut ctx = Context {
+        temp_id: 0,
+        enums: HashMap::new(),
+        traits: HashMap::new(),
+        topic: None,
+        loops: vec![],
+    };
+    let program = build_js(program, &mut ctx)?;
 
     let cm = Rc::new(source_map::SourceMap::default());
     let comments: Option<SingleThreadedComments> = None;
@@ -48,7 +87,7 @@ pub fn codegen_js(src: &str, program: &values::Script) -> (String, String) {
         let unresolved_mark = Mark::new();
         let mut v = react(cm, comments, options, top_level_mark, unresolved_mark);
         let program = program.fold_with(&mut v);
-        print_js(src, &program)
+        Ok(print_js(src, &program))
     })
 }
 
@@ -81,121 +120,613 @@ fn print_js(src: &str, program: &Program) -> (String, String) {
     (output_code, String::from_utf8(source_map_buf).unwrap())
 }
 
-fn build_js(program: &values::Script, ctx: &mut Context) -> Program {
-    let body: Vec<ModuleItem> = program
-        .stmts
-        .iter()
-        .flat_map(|child| {
-            let mut stmts: Vec<Stmt> = vec![];
-            let result = match &child.kind {
-                values::StmtKind::Decl(decl) => match &decl.kind {
-                    values::DeclKind::TypeDecl(_) => {
-                        ModuleItem::Stmt(Stmt::Empty(EmptyStmt { span: DUMMY_SP }))
-                    }
-                    values::DeclKind::VarDecl(values::VarDecl {
-                        pattern,
-                        expr: init,
-                        is_declare: declare,
-                        ..
-                    }) => match declare {
-                        true => ModuleItem::Stmt(Stmt::Empty(EmptyStmt { span: DUMMY_SP })),
-                        false => {
-                            // It should be okay to unwrap this here since any decl that isn't
-                            // using `declare` should have an initial value.
-                            let init = init.as_ref().unwrap();
-
-                            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
-                                span: DUMMY_SP,
-                                decl: Decl::Var(Box::from(build_var_decl(
-                                    pattern,
-                                    Some(init),
-                                    &mut stmts,
-                                    ctx,
-                                ))),
-                            }))
-                        }
-                    },
-                },
-                values::StmtKind::Expr(values::ExprStmt { expr }) => {
-                    ModuleItem::Stmt(Stmt::Expr(ExprStmt {
+fn build_js(
+    program: &values::Script,
+    ctx: &mut Context,
+) -> core::result::Result<Program, CodegenError> {
+    for stmt in &program.stmts {
+        if let values::StmtKind::Decl(decl) = &stmt.kind {
+            match &decl.kind {
+                values::DeclKind::EnumDecl(decl) => {
+                    let variants = decl
+                        .variants
+                        .iter()
+                        .map(|variant| (variant.name.to_owned(), variant.fields.len()))
+                        .collect();
+                    ctx.enums.insert(decl.name.to_owned(), variants);
+                }
+                values::DeclKind::TraitDecl(decl) => {
+                    let default_methods = decl
+                        .methods
+                        .iter()
+                        .filter(|method| method.body.is_some())
+                        .map(|method| method.name.name.to_owned())
+                        .collect();
+                    ctx.traits.insert(decl.name.to_owned(), default_methods);
+                }
+                _ => (),
+            }
+        }
+    }
+
+    let mut body: Vec<ModuleItem> = vec![];
+    for child in &program.stmts {
+        let mut stmts: Vec<Stmt> = vec![];
+        let result = match &child.kind {
+            values::StmtKind::Decl(decl) => match &decl.kind {
+                values::DeclKind::TypeDecl(_) => {
+                    ModuleItem::Stmt(Stmt::Empty(EmptyStmt { span: DUMMY_SP }))
+                }
+                values::DeclKind::EnumDecl(decl) => {
+                    ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
                         span: DUMMY_SP,
-                        expr: Box::from(build_expr(expr, &mut stmts, ctx)),
+                        decl: Decl::Var(Box::from(build_enum_decl(decl))),
                     }))
                 }
-                values::StmtKind::For(values::ForStmt { left, right, body }) => {
-                    let stmt = Stmt::ForOf(ForOfStmt {
+                values::DeclKind::StructDecl(decl) => {
+                    ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
                         span: DUMMY_SP,
-                        is_await: false,
-                        left: ForHead::VarDecl(Box::from(build_var_decl(
-                            left, None, &mut stmts, ctx,
-                        ))),
-                        right: Box::from(build_expr(right, &mut stmts, ctx)),
-                        body: Box::from(Stmt::Block(build_body_block_stmt(
-                            body,
-                            &BlockFinalizer::ExprStmt,
-                            ctx,
-                        ))),
-                    });
-                    ModuleItem::Stmt(stmt)
+                        decl: Decl::Class(build_struct_decl(decl)),
+                    }))
                 }
-                // values::StmtKind::ClassDecl(values::ClassDecl { class, ident, .. }) => {
-                //     let ident = Ident::from(ident);
-                //     let class = build_class(class, &mut stmts, ctx);
-
-                //     ModuleItem::Stmt(Stmt::Decl(Decl::Class(ClassDecl {
-                //         ident,
-                //         class: Box::from(class),
-                //         declare: false,
-                //     })))
-                // }
+                values::DeclKind::TraitDecl(decl) => {
+                    ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                        span: DUMMY_SP,
+                        decl: Decl::Var(Box::from(build_trait_decl(decl, &mut stmts, ctx))),
+                    }))
+                }
+                values::DeclKind::ImplDecl(decl) => {
+                    build_impl_decl(decl, &mut stmts, ctx);
+                    ModuleItem::Stmt(Stmt::Empty(EmptyStmt { span: DUMMY_SP }))
+                }
+                values::DeclKind::VarDecl(values::VarDecl {
+                    pattern,
+                    expr: init,
+                    is_declare: declare,
+                    is_var,
+                    ..
+                }) => match declare {
+                    true => ModuleItem::Stmt(Stmt::Empty(EmptyStmt { span: DUMMY_SP })),
+                    false => {
+                        // It should be okay to unwrap this here since any decl that isn't
+                        // using `declare` should have an initial value.
+                        let init = init.as_ref().unwrap();
+
+                        ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                            span: DUMMY_SP,
+                            decl: Decl::Var(Box::from(build_var_decl(
+                                *is_var,
+                                pattern,
+                                Some(init),
+                                &mut stmts,
+                                ctx,
+                            ))),
+                        }))
+                    }
+                },
+            },
+            values::StmtKind::Expr(values::ExprStmt {
+                expr:
+                    values::Expr {
+                        kind: values::ExprKind::Loop(loop_expr),
+                        ..
+                    },
+            }) => ModuleItem::Stmt(build_loop(loop_expr, None, ctx)),
+            values::StmtKind::Expr(values::ExprStmt {
+                expr:
+                    values::Expr {
+                        kind: values::ExprKind::Throw(throw),
+                        ..
+                    },
+            }) => ModuleItem::Stmt(build_throw_stmt(throw, &mut stmts, ctx)),
+            values::StmtKind::Expr(values::ExprStmt {
+                expr:
+                    values::Expr {
+                        kind: values::ExprKind::IfElse(if_else),
+                        span,
+                        ..
+                    },
+            }) => ModuleItem::Stmt(build_if_else_stmt(
+                if_else,
+                build_span(span),
+                &BlockFinalizer::ExprStmt,
+                &mut stmts,
+                ctx,
+            )),
+            values::StmtKind::Expr(values::ExprStmt { expr }) => {
+                ModuleItem::Stmt(Stmt::Expr(ExprStmt {
+                    span: DUMMY_SP,
+                    expr: Box::from(build_expr(expr, &mut stmts, ctx)),
+                }))
+            }
+            values::StmtKind::For(for_stmt) => {
+                ModuleItem::Stmt(build_for_stmt(for_stmt, &mut stmts, ctx))
+            }
+            values::StmtKind::While(while_stmt) => {
+                ModuleItem::Stmt(build_while_stmt(while_stmt, &mut stmts, ctx))
+            }
+            values::StmtKind::Break(_) | values::StmtKind::Continue(_) => {
+                return Err(CodegenError {
+                    message: "break and continue statements aren't allowed at the top level"
+                        .to_string(),
+                });
+            }
+            // values::StmtKind::ClassDecl(values::ClassDecl { class, ident, .. }) => {
+            //     let ident = Ident::from(ident);
+            //     let class = build_class(class, &mut stmts, ctx);
+
+            //     ModuleItem::Stmt(Stmt::Decl(Decl::Class(ClassDecl {
+            //         ident,
+            //         class: Box::from(class),
+            //         declare: false,
+            //     })))
+            // }
+
+            // values::StmtKind::ForStmt(for_stmt) => ModuleItem::Stmt(Stmt::ForOf(ForOfStmt {
+            //     span: DUMMY_SP,
+            //     is_await: false,
+            //     left: ForHead::VarDecl(Box::from(build_var_decl(
+            //         &for_stmt.pattern,
+            //         None,
+            //         &mut stmts,
+            //         ctx,
+            //     ))),
+            //     right: Box::from(build_expr(&for_stmt.expr, &mut stmts, ctx)),
+            //     body: Box::from(Stmt::Block(build_body_block_stmt(
+            //         &for_stmt.body,
+            //         &BlockFinalizer::ExprStmt,
+            //         ctx,
+            //     ))),
+            // })),
+            values::StmtKind::Return { .. } => {
+                return Err(CodegenError {
+                    message: "return statements aren't allowed at the top level".to_string(),
+                });
+            }
+        };
+
+        body.extend(stmts.into_iter().map(ModuleItem::Stmt));
+        body.push(result);
+    }
+
+    Ok(Program::Module(Module {
+        span: DUMMY_SP,
+        body,
+        shebang: None,
+    }))
+}
+
+// enum IpAddr { V4(string), V6(string) } ->
+// const IpAddr = {
+//     V4: class V4 { constructor(payload) { this.payload = payload; } },
+//     V6: class V6 { constructor(payload) { this.payload = payload; } },
+// };
+// Variants with multiple fields store them in an array.
+fn build_enum_decl(decl: &values::EnumDecl) -> VarDecl {
+    let props: Vec<PropOrSpread> = decl
+        .variants
+        .iter()
+        .map(|variant| {
+            let ident = Ident {
+                span: DUMMY_SP,
+                sym: JsWord::from(variant.name.to_owned()),
+                optional: false,
+            };
+            let payload = Ident {
+                span: DUMMY_SP,
+                sym: JsWord::from("payload"),
+                optional: false,
+            };
+
+            let body: Vec<ClassMember> = match variant.fields.len() {
+                0 => vec![],
+                len => {
+                    let pat = Pat::Ident(BindingIdent::from(payload.to_owned()));
+                    let pat = match len {
+                        1 => pat,
+                        _ => Pat::Rest(RestPat {
+                            span: DUMMY_SP,
+                            dot3_token: DUMMY_SP,
+                            arg: Box::from(pat),
+                            type_ann: None,
+                        }),
+                    };
+
+                    // this.payload = payload;
+                    let assign = Stmt::Expr(ExprStmt {
+                        span: DUMMY_SP,
+                        expr: Box::from(Expr::Assign(AssignExpr {
+                            span: DUMMY_SP,
+                            op: AssignOp::Assign,
+                            left: PatOrExpr::Expr(Box::from(Expr::Member(MemberExpr {
+                                span: DUMMY_SP,
+                                obj: Box::from(Expr::This(ThisExpr { span: DUMMY_SP })),
+                                prop: MemberProp::Ident(payload.to_owned()),
+                            }))),
+                            right: Box::from(Expr::Ident(payload)),
+                        })),
+                    });
 
-                // values::StmtKind::ForStmt(for_stmt) => ModuleItem::Stmt(Stmt::ForOf(ForOfStmt {
-                //     span: DUMMY_SP,
-                //     is_await: false,
-                //     left: ForHead::VarDecl(Box::from(build_var_decl(
-                //         &for_stmt.pattern,
-                //         None,
-                //         &mut stmts,
-                //         ctx,
-                //     ))),
-                //     right: Box::from(build_expr(&for_stmt.expr, &mut stmts, ctx)),
-                //     body: Box::from(Stmt::Block(build_body_block_stmt(
-                //         &for_stmt.body,
-                //         &BlockFinalizer::ExprStmt,
-                //         ctx,
-                //     ))),
-                // })),
-                values::StmtKind::Return { .. } => {
-                    panic!("return statements aren't allowed at the top level")
+                    vec![ClassMember::Constructor(Constructor {
+                        span: DUMMY_SP,
+                        key: PropName::Ident(Ident {
+                            span: DUMMY_SP,
+                            sym: JsWord::from("constructor"),
+                            optional: false,
+                        }),
+                        params: vec![ParamOrTsParamProp::Param(Param {
+                            span: DUMMY_SP,
+                            decorators: vec![],
+                            pat,
+                        })],
+                        body: Some(BlockStmt {
+                            span: DUMMY_SP,
+                            stmts: vec![assign],
+                        }),
+                        accessibility: None,
+                        is_optional: false,
+                    })]
                 }
             };
 
-            let mut items: Vec<ModuleItem> = stmts
-                .iter()
-                .map(|stmt| ModuleItem::Stmt(stmt.to_owned()))
-                .collect();
-            items.push(result);
+            let class = Expr::Class(ClassExpr {
+                ident: Some(ident.to_owned()),
+                class: Box::from(Class {
+                    span: DUMMY_SP,
+                    decorators: vec![],
+                    body,
+                    super_class: None,
+                    is_abstract: false,
+                    type_params: None,
+                    super_type_params: None,
+                    implements: vec![],
+                }),
+            });
 
-            items
+            PropOrSpread::Prop(Box::from(Prop::KeyValue(KeyValueProp {
+                key: PropName::Ident(ident),
+                value: Box::from(class),
+            })))
         })
         .collect();
 
-    Program::Module(Module {
+    VarDecl {
         span: DUMMY_SP,
-        body,
-        shebang: None,
-    })
+        kind: VarDeclKind::Const,
+        declare: false,
+        decls: vec![VarDeclarator {
+            span: DUMMY_SP,
+            name: Pat::Ident(BindingIdent::from(Ident {
+                span: DUMMY_SP,
+                sym: JsWord::from(decl.name.to_owned()),
+                optional: false,
+            })),
+            init: Some(Box::from(Expr::Object(ObjectLit {
+                span: DUMMY_SP,
+                props,
+            }))),
+            definite: false,
+        }],
+    }
 }
 
+// Structs are emitted as classes whose constructor copies each field from a
+// single object argument.  Non-`pub` fields become `#private` fields.
+fn build_struct_decl(decl: &values::StructDecl) -> ClassDecl {
+    let values = Ident {
+        span: DUMMY_SP,
+        sym: JsWord::from("values"),
+        optional: false,
+    };
+
+    let mut body: Vec<ClassMember> = vec![];
+    let mut assigns: Vec<Stmt> = vec![];
+
+    for field in &decl.fields {
+        let ident = Ident {
+            span: DUMMY_SP,
+            sym: JsWord::from(field.name.to_owned()),
+            optional: false,
+        };
+
+        let (member, prop) = match field.is_public {
+            true => (
+                ClassMember::ClassProp(ClassProp {
+                    span: DUMMY_SP,
+                    key: PropName::Ident(ident.to_owned()),
+                    value: None,
+                    type_ann: None,
+                    is_static: false,
+                    decorators: vec![],
+                    accessibility: None,
+                    is_abstract: false,
+                    is_optional: false,
+                    is_override: false,
+                    readonly: false,
+                    declare: false,
+                    definite: false,
+                }),
+                MemberProp::Ident(ident.to_owned()),
+            ),
+            false => {
+                let name = PrivateName {
+                    span: DUMMY_SP,
+                    id: ident.to_owned(),
+                };
+                (
+                    ClassMember::PrivateProp(PrivateProp {
+                        span: DUMMY_SP,
+                        key: name.to_owned(),
+                        value: None,
+                        type_ann: None,
+                        is_static: false,
+                        decorators: vec![],
+                        accessibility: None,
+                        is_optional: false,
+                        is_override: false,
+                        readonly: false,
+                        definite: false,
+                    }),
+                    MemberProp::PrivateName(name),
+                )
+            }
+        };
+        body.push(member);
+
+        // this.field = values.field;
+        assigns.push(Stmt::Expr(ExprStmt {
+            span: DUMMY_SP,
+            expr: Box::from(Expr::Assign(AssignExpr {
+                span: DUMMY_SP,
+                op: AssignOp::Assign,
+                left: PatOrExpr::Expr(Box::from(Expr::Member(MemberExpr {
+                    span: DUMMY_SP,
+                    obj: Box::from(Expr::This(ThisExpr { span: DUMMY_SP })),
+                    prop,
+                }))),
+                right: Box::from(Expr::Member(MemberExpr {
+                    span: DUMMY_SP,
+                    obj: Box::from(Expr::Ident(values.to_owned())),
+                    prop: MemberProp::Ident(ident),
+                })),
+            })),
+        }));
+    }
+
+    body.push(ClassMember::Constructor(Constructor {
+        span: DUMMY_SP,
+        key: PropName::Ident(Ident {
+            span: DUMMY_SP,
+            sym: JsWord::from("constructor"),
+            optional: false,
+        }),
+        params: vec![ParamOrTsParamProp::Param(Param {
+            span: DUMMY_SP,
+            decorators: vec![],
+            pat: Pat::Ident(BindingIdent::from(values)),
+        })],
+        body: Some(BlockStmt {
+            span: DUMMY_SP,
+            stmts: assigns,
+        }),
+        accessibility: None,
+        is_optional: false,
+    }));
+
+    ClassDecl {
+        ident: Ident {
+            span: DUMMY_SP,
+            sym: JsWord::from(decl.name.to_owned()),
+            optional: false,
+        },
+        declare: false,
+        class: Box::from(Class {
+            span: DUMMY_SP,
+            decorators: vec![],
+            body,
+            super_class: None,
+            is_abstract: false,
+            type_params: None,
+            super_type_params: None,
+            implements: vec![],
+        }),
+    }
+}
+
+// trait Shape { fn area(self) -> number; fn describe(self) -> string { ... } } ->
+// const Shape = { describe() { const self = this; ... } };
+// Only default methods are emitted, they're copied onto the prototype of each
+// type that implements the trait.
+fn build_trait_decl(decl: &values::TraitDecl, stmts: &mut Vec<Stmt>, ctx: &mut Context) -> VarDecl {
+    let props: Vec<PropOrSpread> = decl
+        .methods
+        .iter()
+        .filter_map(|method| {
+            let body = method.body.as_ref()?;
+            let function = build_method_function(&method.params, body, false, stmts, ctx);
+            Some(PropOrSpread::Prop(Box::from(Prop::Method(MethodProp {
+                key: PropName::Ident(Ident::from(&method.name)),
+                function: Box::from(function),
+            }))))
+        })
+        .collect();
+
+    let ident = Ident {
+        span: DUMMY_SP,
+        sym: JsWord::from(decl.name.to_owned()),
+        optional: false,
+    };
+
+    VarDecl {
+        span: DUMMY_SP,
+        kind: VarDeclKind::Const,
+        declare: false,
+        decls: vec![VarDeclarator {
+            span: DUMMY_SP,
+            name: Pat::Ident(BindingIdent::from(ident)),
+            init: Some(Box::from(Expr::Object(ObjectLit {
+                span: DUMMY_SP,
+                props,
+            }))),
+            definite: false,
+        }],
+    }
+}
+
+// impl Shape for Circle { fn area(self) -> number { ... } } ->
+// Circle.prototype.area = function () { const self = this; ... };
+// Circle.prototype.describe = Shape.describe;
+fn build_impl_decl(decl: &values::ImplDecl, stmts: &mut Vec<Stmt>, ctx: &mut Context) {
+    let type_ident = Ident::from(&decl.type_name);
+    let trait_ident = Ident::from(&decl.trait_name);
+
+    let prototype_member = |name: &str| {
+        PatOrExpr::Expr(Box::from(Expr::Member(MemberExpr {
+            span: DUMMY_SP,
+            obj: Box::from(Expr::Member(MemberExpr {
+                span: DUMMY_SP,
+                obj: Box::from(Expr::Ident(type_ident.to_owned())),
+                prop: MemberProp::Ident(Ident {
+                    span: DUMMY_SP,
+                    sym: JsWord::from("prototype"),
+                    optional: false,
+                }),
+            })),
+            prop: MemberProp::Ident(Ident {
+                span: DUMMY_SP,
+                sym: JsWord::from(name),
+                optional: false,
+            }),
+        })))
+    };
+
+    let mut implemented: Vec<String> = vec![];
+    for method in &decl.methods {
+        let name = match &method.name {
+            values::PropName::Ident(ident) => ident.name.to_owned(),
+            values::PropName::Computed(_) => todo!(),
+        };
+        let function = build_method_function(
+            &method.function.params,
+            &method.function.body,
+            method.function.is_async,
+            stmts,
+            ctx,
+        );
+        stmts.push(Stmt::Expr(ExprStmt {
+            span: DUMMY_SP,
+            expr: Box::from(Expr::Assign(AssignExpr {
+                span: DUMMY_SP,
+                op: AssignOp::Assign,
+                left: prototype_member(&name),
+                right: Box::from(Expr::Fn(FnExpr {
+                    ident: None,
+                    function: Box::from(function),
+                })),
+            })),
+        }));
+        implemented.push(name);
+    }
+
+    let default_methods = ctx.traits.get(&decl.trait_name.name).cloned();
+    for name in default_methods.iter().flatten() {
+        if implemented.contains(name) {
+            continue;
+        }
+        stmts.push(Stmt::Expr(ExprStmt {
+            span: DUMMY_SP,
+            expr: Box::from(Expr::Assign(AssignExpr {
+                span: DUMMY_SP,
+                op: AssignOp::Assign,
+                left: prototype_member(name),
+                right: Box::from(Expr::Member(MemberExpr {
+                    span: DUMMY_SP,
+                    obj: Box::from(Expr::Ident(trait_ident.to_owned())),
+                    prop: MemberProp::Ident(Ident {
+                        span: DUMMY_SP,
+                        sym: JsWord::from(name.to_owned()),
+                        optional: false,
+                    }),
+                })),
+            })),
+        }));
+    }
+}
+
+// Trait and impl methods aren't defined inside of a class body so `self` is
+// bound to `this` at the start of the method's body.
+fn build_method_function(
+    params: &[values::FuncParam],
+    body: &values::BlockOrExpr,
+    is_async: bool,
+    stmts: &mut Vec<Stmt>,
+    ctx: &mut Context,
+) -> Function {
+    let params: Vec<Param> = params
+        .iter()
+        .map(|param| Param {
+            span: DUMMY_SP,
+            decorators: vec![],
+            pat: build_pattern(&param.pattern, stmts, ctx).unwrap(),
+        })
+        .collect();
+
+    let mut body = match body {
+        values::BlockOrExpr::Block(block) => {
+            build_body_block_stmt(block, &BlockFinalizer::ExprStmt, ctx)
+        }
+        values::BlockOrExpr::Expr(expr) => {
+            let mut body_stmts: Vec<Stmt> = vec![];
+            let expr = build_expr(expr, &mut body_stmts, ctx);
+            body_stmts.push(Stmt::Return(ReturnStmt {
+                span: DUMMY_SP,
+                arg: Some(Box::from(expr)),
+            }));
+            BlockStmt {
+                span: DUMMY_SP,
+                stmts: body_stmts,
+            }
+        }
+    };
+    let self_ident = Ident {
+        span: DUMMY_SP,
+        sym: JsWord::from("self"),
+        optional: false,
+    };
+    body.stmts.insert(
+        0,
+        build_const_decl_stmt(&self_ident, Expr::This(ThisExpr { span: DUMMY_SP })),
+    );
+
+    Function {
+        params,
+        decorators: vec![],
+        span: DUMMY_SP,
+        body: Some(body),
+        is_generator: false,
+        is_async,
+        type_params: None,
+        return_type: None,
+    }
+}
+
+// Bindings that can be reassigned, i.e. `var` decls and patterns with `mut`
+// identifiers, are declared with `let`, everything else uses `const`.
 fn build_var_decl(
+    is_var: bool,
     pattern: &values::Pattern,
     init: Option<&values::Expr>,
     stmts: &mut Vec<Stmt>,
     ctx: &mut Context,
 ) -> VarDecl {
+    let kind = match is_var || is_pattern_mutable(pattern) {
+        true => VarDeclKind::Let,
+        false => VarDeclKind::Const,
+    };
+
     VarDecl {
         span: DUMMY_SP,
-        kind: VarDeclKind::Const,
+        kind,
         declare: false,
         decls: vec![VarDeclarator {
             span: DUMMY_SP,
@@ -206,8 +737,88 @@ fn build_var_decl(
     }
 }
 
+fn is_pattern_mutable(pattern: &values::Pattern) -> bool {
+    match &pattern.kind {
+        values::PatternKind::Ident(values::BindingIdent { mutable, .. }) => *mutable,
+        values::PatternKind::Is(values::IsPat { ident, .. }) => ident.mutable,
+        values::PatternKind::Rest(values::RestPat { arg }) => is_pattern_mutable(arg),
+        values::PatternKind::Object(values::ObjectPat { props, .. })
+        | values::PatternKind::Struct(values::StructPat { props, .. }) => {
+            props.iter().any(|prop| match prop {
+                values::ObjectPatProp::KeyValue(kv) => is_pattern_mutable(&kv.value),
+                values::ObjectPatProp::Shorthand(shorthand) => shorthand.ident.mutable,
+                values::ObjectPatProp::Rest(values::RestPat { arg }) => is_pattern_mutable(arg),
+            })
+        }
+        values::PatternKind::Tuple(values::TuplePat { elems, .. }) => elems
+            .iter()
+            .flatten()
+            .any(|elem| is_pattern_mutable(&elem.pattern)),
+        values::PatternKind::Enum(values::EnumPat { args, .. }) => {
+            args.iter().any(is_pattern_mutable)
+        }
+        values::PatternKind::Lit(_) | values::PatternKind::Wildcard => false,
+    }
+}
+
 // TODO: See if we can avoid returning an Option<> here so that we don't have
 // to unwrap() in when calling it from build_expr().
+fn build_object_pat(
+    pattern: &values::Pattern,
+    props: &[values::ObjectPatProp],
+    optional: bool,
+    span: swc_common::Span,
+    stmts: &mut Vec<Stmt>,
+    ctx: &mut Context,
+) -> Option<Pat> {
+    let props: Vec<ObjectPatProp> = props
+        .iter()
+        .filter_map(|p| match p {
+            values::ObjectPatProp::KeyValue(kvp) => build_pattern(kvp.value.as_ref(), stmts, ctx)
+                .map(|value| {
+                    ObjectPatProp::KeyValue(KeyValuePatProp {
+                        key: PropName::Ident(Ident::from(&kvp.key)),
+                        value: Box::from(value),
+                    })
+                }),
+            values::ObjectPatProp::Shorthand(values::ShorthandPatProp { ident, init, .. }) => {
+                Some(ObjectPatProp::Assign(AssignPatProp {
+                    span: DUMMY_SP,
+                    key: Ident::from(ident),
+                    value: init
+                        .clone()
+                        .map(|value| Box::from(build_expr(&value, stmts, ctx))),
+                }))
+            }
+            values::ObjectPatProp::Rest(values::RestPat { arg }) => {
+                let dot3_token = swc_common::Span {
+                    lo: BytePos(pattern.span.start as u32 + 1),
+                    hi: BytePos(pattern.span.start as u32 + 4),
+                    ctxt: SyntaxContext::empty(),
+                };
+                let span = swc_common::Span {
+                    lo: BytePos(pattern.span.start as u32 + 1),
+                    hi: BytePos(pattern.span.end as u32 + 1),
+                    ctxt: SyntaxContext::empty(),
+                };
+                Some(ObjectPatProp::Rest(RestPat {
+                    span,
+                    dot3_token,
+                    arg: Box::from(build_pattern(arg, stmts, ctx)?),
+                    type_ann: None,
+                }))
+            }
+        })
+        .collect();
+
+    Some(Pat::Object(ObjectPat {
+        span,
+        optional,
+        type_ann: None, // because we're generating .js
+        props,
+    }))
+}
+
 fn build_pattern(
     pattern: &values::Pattern,
     stmts: &mut Vec<Stmt>,
@@ -237,70 +848,25 @@ fn build_pattern(
             type_ann: None,
         })),
         values::PatternKind::Rest(values::RestPat { arg }) => {
-            let dot3_token = swc_common::Span {
-                lo: BytePos(pattern.span.start as u32 + 1),
-                hi: BytePos(pattern.span.start as u32 + 4),
-                ctxt: SyntaxContext::empty(),
-            };
-            let arg = build_pattern(arg, stmts, ctx).unwrap();
-            Some(Pat::Rest(RestPat {
-                span,
-                dot3_token,
-                type_ann: None,
-                arg: Box::from(arg),
-            }))
-        }
-        values::PatternKind::Object(values::ObjectPat { props, optional }) => {
-            let props: Vec<ObjectPatProp> = props
-                .iter()
-                .filter_map(|p| match p {
-                    values::ObjectPatProp::KeyValue(kvp) => {
-                        build_pattern(kvp.value.as_ref(), stmts, ctx).map(|value| {
-                            ObjectPatProp::KeyValue(KeyValuePatProp {
-                                key: PropName::Ident(Ident::from(&kvp.key)),
-                                value: Box::from(value),
-                            })
-                        })
-                    }
-                    values::ObjectPatProp::Shorthand(values::ShorthandPatProp {
-                        ident,
-                        init,
-                        ..
-                    }) => Some(ObjectPatProp::Assign(AssignPatProp {
-                        span: DUMMY_SP,
-                        key: Ident::from(ident),
-                        value: init
-                            .clone()
-                            .map(|value| Box::from(build_expr(&value, stmts, ctx))),
-                    })),
-                    values::ObjectPatProp::Rest(values::RestPat { arg }) => {
-                        let dot3_token = swc_common::Span {
-                            lo: BytePos(pattern.span.start as u32 + 1),
-                            hi: BytePos(pattern.span.start as u32 + 4),
-                            ctxt: SyntaxContext::empty(),
-                        };
-                        let span = swc_common::Span {
-                            lo: BytePos(pattern.span.start as u32 + 1),
-                            hi: BytePos(pattern.span.end as u32 + 1),
-                            ctxt: SyntaxContext::empty(),
-                        };
-                        Some(ObjectPatProp::Rest(RestPat {
-                            span,
-                            dot3_token,
-                            arg: Box::from(build_pattern(arg, stmts, ctx)?),
-                            type_ann: None,
-                        }))
-                    }
-                })
-                .collect();
-
-            Some(Pat::Object(ObjectPat {
+            let dot3_token = swc_common::Span {
+                lo: BytePos(pattern.span.start as u32 + 1),
+                hi: BytePos(pattern.span.start as u32 + 4),
+                ctxt: SyntaxContext::empty(),
+            };
+            let arg = build_pattern(arg, stmts, ctx).unwrap();
+            Some(Pat::Rest(RestPat {
                 span,
-                optional: optional.to_owned(),
-                type_ann: None, // because we're generating .js
-                props,
+                dot3_token,
+                type_ann: None,
+                arg: Box::from(arg),
             }))
         }
+        values::PatternKind::Struct(values::StructPat { props, .. }) => {
+            build_object_pat(pattern, props, false, span, stmts, ctx)
+        }
+        values::PatternKind::Object(values::ObjectPat { props, optional }) => {
+            build_object_pat(pattern, props, *optional, span, stmts, ctx)
+        }
         values::PatternKind::Tuple(values::TuplePat { elems, optional }) => {
             let elems: Vec<Option<Pat>> = elems
                 .iter()
@@ -322,21 +888,56 @@ fn build_pattern(
             id: Ident::from(ident),
             type_ann: None,
         })),
+        // IpAddr.V4(addr) -> {payload: addr}
+        // Shape.Rect(w, h) -> {payload: [w, h]}
+        values::PatternKind::Enum(values::EnumPat { args, .. }) => {
+            let value = match args.as_slice() {
+                [] => return None,
+                [arg] => build_pattern(arg, stmts, ctx)?,
+                _ => Pat::Array(ArrayPat {
+                    span,
+                    elems: args
+                        .iter()
+                        .map(|arg| build_pattern(arg, stmts, ctx))
+                        .collect(),
+                    optional: false,
+                    type_ann: None, // because we're generating .js.
+                }),
+            };
+
+            Some(Pat::Object(ObjectPat {
+                span,
+                optional: false,
+                type_ann: None, // because we're generating .js
+                props: vec![ObjectPatProp::KeyValue(KeyValuePatProp {
+                    key: PropName::Ident(Ident {
+                        span: DUMMY_SP,
+                        sym: JsWord::from("payload"),
+                        optional: false,
+                    }),
+                    value: Box::from(value),
+                })],
+            }))
+        }
     }
 }
 
-fn build_expr(expr: &values::Expr, stmts: &mut Vec<Stmt>, ctx: &mut Context) -> Expr {
-    let span = swc_common::Span {
-        lo: BytePos(expr.span.start as u32 + 1),
-        hi: BytePos(expr.span.end as u32 + 1),
+fn build_span(span: &values::Span) -> swc_common::Span {
+    swc_common::Span {
+        lo: BytePos(span.start as u32 + 1),
+        hi: BytePos(span.end as u32 + 1),
         ctxt: SyntaxContext::empty(),
-    };
+    }
+}
+
+fn build_expr(expr: &values::Expr, stmts: &mut Vec<Stmt>, ctx: &mut Context) -> Expr {
+    let span = build_span(&expr.span);
 
     match &expr.kind {
         values::ExprKind::Call(values::Call {
             callee: lam, args, ..
         }) => {
-            let callee = Callee::Expr(Box::from(build_expr(lam.as_ref(), stmts, ctx)));
+            let callee = Box::from(build_expr(lam.as_ref(), stmts, ctx));
 
             let args: Vec<ExprOrSpread> = args
                 .iter()
@@ -352,14 +953,169 @@ fn build_expr(expr: &values::Expr, stmts: &mut Vec<Stmt>, ctx: &mut Context) ->
                 })
                 .collect();
 
+            match ctx.get_variant_arity(lam) {
+                // IpAddr.V4("127.0.0.1") -> new IpAddr.V4("127.0.0.1")
+                Some(_) => Expr::New(NewExpr {
+                    span,
+                    callee,
+                    args: Some(args),
+                    type_args: None,
+                }),
+                None => Expr::Call(CallExpr {
+                    span,
+                    callee: Callee::Expr(callee),
+                    args,
+                    type_args: None,
+                }),
+            }
+        }
+        values::ExprKind::New(_) => todo!(),
+        // x |> f(_) -> ($temp_n = x, f($temp_n))
+        values::ExprKind::Pipeline(values::Pipeline { left, right }) => {
+            // let $temp_n;
+            let temp_id = ctx.new_ident();
+            let temp_decl = build_let_decl_stmt(&temp_id);
+            stmts.push(temp_decl);
+
+            let left = build_expr(left, stmts, ctx);
+            let outer_topic = ctx.topic.replace(temp_id.to_owned());
+            let right = build_expr(right, stmts, ctx);
+            ctx.topic = outer_topic;
+
+            let assign = Expr::Assign(AssignExpr {
+                span: DUMMY_SP,
+                op: AssignOp::Assign,
+                left: PatOrExpr::Pat(Box::from(Pat::Ident(BindingIdent::from(temp_id)))),
+                right: Box::from(left),
+            });
+
+            Expr::Paren(ParenExpr {
+                span,
+                expr: Box::from(Expr::Seq(SeqExpr {
+                    span: DUMMY_SP,
+                    exprs: vec![Box::from(assign), Box::from(right)],
+                })),
+            })
+        }
+        // xs[1:3] -> xs.slice(1, 3)
+        values::ExprKind::Slice(values::Slice {
+            object,
+            start,
+            end,
+            opt_chain,
+        }) => {
+            let start = match (start, end) {
+                (Some(start), _) => Some(build_expr(start, stmts, ctx)),
+                // xs[:3] -> xs.slice(0, 3)
+                (None, Some(_)) => Some(Expr::Lit(Lit::Num(Number {
+                    span: DUMMY_SP,
+                    value: 0.0,
+                    raw: None,
+                }))),
+                (None, None) => None,
+            };
+            let end = end.as_ref().map(|end| build_expr(end, stmts, ctx));
+            let args = start
+                .into_iter()
+                .chain(end)
+                .map(|expr| ExprOrSpread {
+                    spread: None,
+                    expr: Box::from(expr),
+                })
+                .collect();
+
+            let member = MemberExpr {
+                span: DUMMY_SP,
+                obj: Box::from(build_expr(object, stmts, ctx)),
+                prop: MemberProp::Ident(Ident {
+                    span: DUMMY_SP,
+                    sym: JsWord::from("slice"),
+                    optional: false,
+                }),
+            };
+            let callee = match opt_chain {
+                true => Expr::OptChain(OptChainExpr {
+                    span: DUMMY_SP,
+                    optional: true,
+                    base: Box::from(OptChainBase::Member(member)),
+                }),
+                false => Expr::Member(member),
+            };
+
             Expr::Call(CallExpr {
                 span,
-                callee,
+                callee: Callee::Expr(Box::from(callee)),
                 args,
                 type_args: None,
             })
         }
-        values::ExprKind::New(_) => todo!(),
+        // Ranges outside of `for` loops are emitted as generators, e.g.
+        // 0..n -> (function* (start, end) { for (...) yield i; })(0, n)
+        values::ExprKind::Range(values::Range { start, end }) => {
+            let [start_id, end_id, i] = ["start", "end", "i"].map(|name| Ident {
+                span: DUMMY_SP,
+                sym: JsWord::from(name),
+                optional: false,
+            });
+            let body = Stmt::Expr(ExprStmt {
+                span: DUMMY_SP,
+                expr: Box::from(Expr::Yield(YieldExpr {
+                    span: DUMMY_SP,
+                    arg: Some(Box::from(Expr::Ident(i.to_owned()))),
+                    delegate: false,
+                })),
+            });
+            let for_stmt = build_range_loop(
+                &i,
+                Expr::Ident(start_id.to_owned()),
+                Expr::Ident(end_id.to_owned()),
+                body,
+                ctx,
+            );
+
+            let generator = Expr::Fn(FnExpr {
+                ident: None,
+                function: Box::from(Function {
+                    params: [start_id, end_id]
+                        .into_iter()
+                        .map(|id| Param {
+                            span: DUMMY_SP,
+                            decorators: vec![],
+                            pat: Pat::Ident(BindingIdent::from(id)),
+                        })
+                        .collect(),
+                    decorators: vec![],
+                    span: DUMMY_SP,
+                    body: Some(BlockStmt {
+                        span: DUMMY_SP,
+                        stmts: vec![for_stmt],
+                    }),
+                    is_generator: true,
+                    is_async: false,
+                    type_params: None,
+                    return_type: None,
+                }),
+            });
+
+            Expr::Call(CallExpr {
+                span,
+                callee: Callee::Expr(Box::from(Expr::Paren(ParenExpr {
+                    span: DUMMY_SP,
+                    expr: Box::from(generator),
+                }))),
+                args: vec![
+                    ExprOrSpread {
+                        spread: None,
+                        expr: Box::from(build_expr(start, stmts, ctx)),
+                    },
+                    ExprOrSpread {
+                        spread: None,
+                        expr: Box::from(build_expr(end, stmts, ctx)),
+                    },
+                ],
+                type_args: None,
+            })
+        }
         // TODO: Support `Point::new(5, 10)` -> `new Point(5, 10)`.
         // values::ExprKind::New(values::New { expr, args, .. }) => {
         //     let callee = Box::from(build_expr(expr.as_ref(), stmts, ctx));
@@ -383,7 +1139,10 @@ fn build_expr(expr: &values::Expr, stmts: &mut Vec<Stmt>, ctx: &mut Context) ->
         //         type_args: None,
         //     })
         // }
-        values::ExprKind::Ident(ident) => Expr::from(Ident::from(ident)),
+        values::ExprKind::Ident(ident) => match &ctx.topic {
+            Some(topic) if ident.name == "_" => Expr::Ident(topic.to_owned()),
+            _ => Expr::from(Ident::from(ident)),
+        },
         values::ExprKind::Function(values::Function {
             params: args,
             body,
@@ -532,12 +1291,7 @@ fn build_expr(expr: &values::Expr, stmts: &mut Vec<Stmt>, ctx: &mut Context) ->
                 arg: Box::from(build_expr(arg, stmts, ctx)),
             })
         }
-        values::ExprKind::IfElse(values::IfElse {
-            cond,
-            consequent,
-            alternate,
-            ..
-        }) => {
+        values::ExprKind::IfElse(if_else) => {
             // let $temp_n;
             let temp_id = ctx.new_ident();
             let temp_decl = build_let_decl_stmt(&temp_id);
@@ -546,32 +1300,24 @@ fn build_expr(expr: &values::Expr, stmts: &mut Vec<Stmt>, ctx: &mut Context) ->
             let finalizer = BlockFinalizer::Assign(temp_id.clone());
 
             // if (cond) { ...; $temp_n = <cons_res> } else { ...; $temp_n = <alt_res> }
-            let test = Box::from(build_expr(cond.as_ref(), stmts, ctx));
-            let cons = Box::from(Stmt::Block(build_body_block_stmt(
-                consequent, &finalizer, ctx,
-            )));
-            let alt = alternate
-                .as_ref()
-                .map(|alt| Box::from(build_alt(alt, &finalizer, stmts, ctx)));
-            stmts.push(Stmt::If(IfStmt {
-                span,
-                test,
-                cons,
-                alt,
-            }));
+            let stmt = build_if_else_stmt(if_else, span, &finalizer, stmts, ctx);
+            stmts.push(stmt);
 
             // $temp_n
             Expr::Ident(temp_id)
         }
-        values::ExprKind::Object(values::Object { properties: props }) => {
+        values::ExprKind::Object(values::Object { properties: props })
+        | values::ExprKind::Struct(values::Struct {
+            properties: props, ..
+        }) => {
             let props: Vec<PropOrSpread> = props
                 .iter()
                 .map(|prop| match prop {
                     values::PropOrSpread::Prop(prop) => match prop {
-                        values::expr::Prop::Shorthand(ident) => {
+                        values::expr::Prop::Shorthand { ident, .. } => {
                             PropOrSpread::Prop(Box::from(Prop::Shorthand(Ident::from(ident))))
                         }
-                        values::expr::Prop::Property { key, value } => {
+                        values::expr::Prop::Property { key, value, .. } => {
                             PropOrSpread::Prop(Box::from(Prop::KeyValue(KeyValueProp {
                                 key: prop_name_from_object_key(key, ctx),
                                 value: Box::from(build_expr(value, stmts, ctx)),
@@ -582,7 +1328,20 @@ fn build_expr(expr: &values::Expr, stmts: &mut Vec<Stmt>, ctx: &mut Context) ->
                 })
                 .collect();
 
-            Expr::Object(ObjectLit { span, props })
+            let obj = Expr::Object(ObjectLit { span, props });
+            match &expr.kind {
+                // User {name: "John"} -> new User({name: "John"})
+                values::ExprKind::Struct(values::Struct { name, .. }) => Expr::New(NewExpr {
+                    span,
+                    callee: Box::from(Expr::Ident(Ident::from(name))),
+                    args: Some(vec![ExprOrSpread {
+                        spread: None,
+                        expr: Box::from(obj),
+                    }]),
+                    type_args: None,
+                }),
+                _ => obj,
+            }
         }
         values::ExprKind::Await(values::Await { arg: expr, .. }) => Expr::Await(AwaitExpr {
             span,
@@ -622,11 +1381,22 @@ fn build_expr(expr: &values::Expr, stmts: &mut Vec<Stmt>, ctx: &mut Context) ->
                     })
                 }
             };
-            Expr::Member(MemberExpr {
+            let member = Expr::Member(MemberExpr {
                 span,
                 obj: Box::from(build_expr(obj, stmts, ctx)),
                 prop,
-            })
+            });
+
+            match ctx.get_variant_arity(expr) {
+                // Option.None -> new Option.None()
+                Some(0) => Expr::New(NewExpr {
+                    span,
+                    callee: Box::from(member),
+                    args: Some(vec![]),
+                    type_args: None,
+                }),
+                _ => member,
+            }
         }
         // values::ExprKind::Empty => Expr::from(Ident {
         //     span,
@@ -723,14 +1493,14 @@ fn build_expr(expr: &values::Expr, stmts: &mut Vec<Stmt>, ctx: &mut Context) ->
                 class: Box::from(class),
             })
         }
-        // values::ExprKind::Regex(regex) => Expr::Lit(Lit::Regex(Regex {
-        //     span,
-        //     exp: Atom::new(regex.pattern.as_ref()),
-        //     flags: match &regex.flags {
-        //         Some(flags) => Atom::new(flags.as_ref()),
-        //         None => Atom::new(""),
-        //     },
-        // })),
+        values::ExprKind::Regex(regex) => Expr::Lit(Lit::Regex(Regex {
+            span,
+            exp: Atom::new(regex.pattern.as_ref()),
+            flags: match &regex.flags {
+                Some(flags) => Atom::new(flags.as_ref()),
+                None => Atom::new(""),
+            },
+        })),
         values::ExprKind::Do(do_expr) => {
             let temp_id = ctx.new_ident();
             let temp_decl = build_let_decl_stmt(&temp_id);
@@ -743,12 +1513,61 @@ fn build_expr(expr: &values::Expr, stmts: &mut Vec<Stmt>, ctx: &mut Context) ->
 
             Expr::Ident(temp_id)
         }
+        values::ExprKind::Loop(loop_expr) => {
+            // let $temp_n;
+            let temp_id = ctx.new_ident();
+            let temp_decl = build_let_decl_stmt(&temp_id);
+            stmts.push(temp_decl);
+
+            // while (true) { ...; $temp_n = <break_value>; break; }
+            stmts.push(build_loop(loop_expr, Some(temp_id.clone()), ctx));
+
+            // $temp_n
+            Expr::Ident(temp_id)
+        }
         values::ExprKind::Try(_) => todo!(),
         values::ExprKind::Yield(_) => todo!(),
         values::ExprKind::Throw(_) => todo!(),
     }
 }
 
+fn build_throw_stmt(throw: &values::Throw, stmts: &mut Vec<Stmt>, ctx: &mut Context) -> Stmt {
+    Stmt::Throw(ThrowStmt {
+        span: DUMMY_SP,
+        arg: Box::from(build_expr(&throw.arg, stmts, ctx)),
+    })
+}
+
+// The final expression in each branch is passed to `finalizer`.
+fn build_if_else_stmt(
+    if_else: &values::IfElse,
+    span: swc_common::Span,
+    finalizer: &BlockFinalizer,
+    stmts: &mut Vec<Stmt>,
+    ctx: &mut Context,
+) -> Stmt {
+    let values::IfElse {
+        cond,
+        consequent,
+        alternate,
+    } = if_else;
+
+    let test = Box::from(build_expr(cond.as_ref(), stmts, ctx));
+    let cons = Box::from(Stmt::Block(build_body_block_stmt(
+        consequent, finalizer, ctx,
+    )));
+    let alt = alternate
+        .as_ref()
+        .map(|alt| Box::from(build_alt(alt, finalizer, stmts, ctx)));
+
+    Stmt::If(IfStmt {
+        span,
+        test,
+        cons,
+        alt,
+    })
+}
+
 fn build_alt(
     block_or_expr: &values::BlockOrExpr,
     finalizer: &BlockFinalizer,
@@ -868,16 +1687,50 @@ fn build_body_block_stmt(
                         type_ann: _,
                         expr: Some(init),
                         is_declare: _,
-                        ..
+                        is_var,
                     }),
                 ..
             }) => {
-                let stmt = match build_pattern(pattern, &mut new_stmts, ctx) {
-                    Some(name) => {
-                        build_const_decl_stmt_with_pat(name, build_expr(init, &mut new_stmts, ctx))
-                    }
-                    None => todo!(),
-                };
+                let decl = build_var_decl(*is_var, pattern, Some(init), &mut new_stmts, ctx);
+                new_stmts.push(Stmt::Decl(Decl::Var(Box::from(decl))));
+            }
+            values::StmtKind::Expr(values::ExprStmt {
+                expr:
+                    values::Expr {
+                        kind: values::ExprKind::Loop(loop_expr),
+                        ..
+                    },
+            }) if i != len - 1 || matches!(finalizer, BlockFinalizer::ExprStmt) => {
+                // The loop's value is unused so there's no need for a temp.
+                new_stmts.push(build_loop(loop_expr, None, ctx));
+            }
+            values::StmtKind::Expr(values::ExprStmt {
+                expr:
+                    values::Expr {
+                        kind: values::ExprKind::IfElse(if_else),
+                        span,
+                        ..
+                    },
+            }) if i != len - 1 || matches!(finalizer, BlockFinalizer::ExprStmt) => {
+                // The if-else's value is unused so there's no need for a temp.
+                let stmt = build_if_else_stmt(
+                    if_else,
+                    build_span(span),
+                    &BlockFinalizer::ExprStmt,
+                    &mut new_stmts,
+                    ctx,
+                );
+                new_stmts.push(stmt);
+            }
+            values::StmtKind::Expr(values::ExprStmt {
+                expr:
+                    values::Expr {
+                        kind: values::ExprKind::Throw(throw),
+                        ..
+                    },
+            }) => {
+                // Nothing runs after a `throw` so it doesn't need a finalizer.
+                let stmt = build_throw_stmt(throw, &mut new_stmts, ctx);
                 new_stmts.push(stmt);
             }
             values::StmtKind::Expr(values::ExprStmt { expr }) => {
@@ -892,25 +1745,24 @@ fn build_body_block_stmt(
                 };
                 new_stmts.push(stmt);
             }
-            values::StmtKind::For(values::ForStmt { left, right, body }) => {
-                let stmt = Stmt::ForOf(ForOfStmt {
-                    span: DUMMY_SP,
-                    is_await: false,
-                    left: ForHead::VarDecl(Box::from(build_var_decl(
-                        left,
-                        None,
-                        &mut new_stmts,
-                        ctx,
-                    ))),
-                    right: Box::from(build_expr(right, &mut new_stmts, ctx)),
-                    body: Box::from(Stmt::Block(build_body_block_stmt(
-                        body,
-                        &BlockFinalizer::ExprStmt,
-                        ctx,
-                    ))),
-                });
+            values::StmtKind::For(for_stmt) => {
+                let stmt = build_for_stmt(for_stmt, &mut new_stmts, ctx);
                 new_stmts.push(stmt);
             }
+            values::StmtKind::While(while_stmt) => {
+                let stmt = build_while_stmt(while_stmt, &mut new_stmts, ctx);
+                new_stmts.push(stmt);
+            }
+            values::StmtKind::Break(break_stmt) => {
+                let stmt = build_break_stmt(break_stmt, &mut new_stmts, ctx);
+                new_stmts.push(stmt);
+            }
+            values::StmtKind::Continue(values::ContinueStmt { label }) => {
+                new_stmts.push(Stmt::Continue(ContinueStmt {
+                    span: DUMMY_SP,
+                    label: label.as_ref().map(build_label),
+                }));
+            }
             // values::StmtKind::Class { class, ident, .. } => {
             //     let ident = Ident::from(ident);
             //     let class = build_class(class, &mut new_stmts, ctx);
@@ -1300,7 +2152,7 @@ fn build_cond_for_pat(pat: &values::Pattern, id: &Ident) -> Option<Expr> {
         Some(iter.fold(first, |prev, next| {
             Expr::Bin(BinExpr {
                 span: DUMMY_SP,
-                op: BinaryOp::LogicalOr,
+                op: BinaryOp::LogicalAnd,
                 left: Box::from(prev),
                 right: Box::from(cond_to_expr(next, id)),
             })
@@ -1320,6 +2172,8 @@ fn is_refutable(pat: &values::Pattern) -> bool {
         // refutable
         values::PatternKind::Lit(_) => true,
         values::PatternKind::Is(_) => true,
+        values::PatternKind::Enum(_) => true,
+        values::PatternKind::Struct(_) => true,
 
         // refutable if at least one sub-pattern is refutable
         values::PatternKind::Object(values::ObjectPat { props, .. }) => {
@@ -1390,7 +2244,8 @@ enum Check {
     EqualLit(values::Literal),
     Typeof(String), // limit this to primitives: "number", "string", "boolean"
     Instanceof(values::Ident),
-    // TODO: array length
+    Variant(values::Ident, values::Ident), // checks the value is an instance of enum variant
+                                           // TODO: array length
 }
 
 type Path = Vec<PathElem>;
@@ -1409,20 +2264,15 @@ fn get_conds_for_pat(pat: &values::Pattern, conds: &mut Vec<Condition>, path: &m
         values::PatternKind::Wildcard => (),
 
         // refutable and possibly refutable
+        values::PatternKind::Struct(values::StructPat { name, props }) => {
+            conds.push(Condition {
+                path: path.to_owned(),
+                check: Check::Instanceof(name.to_owned()),
+            });
+            get_conds_for_props(props, conds, path);
+        }
         values::PatternKind::Object(values::ObjectPat { props, .. }) => {
-            for prop in props {
-                match prop {
-                    values::ObjectPatProp::KeyValue(values::KeyValuePatProp {
-                        value, key, ..
-                    }) => {
-                        path.push(PathElem::ObjProp(key.name.clone()));
-                        get_conds_for_pat(value, conds, path);
-                        path.pop();
-                    }
-                    values::ObjectPatProp::Shorthand(_) => (),
-                    values::ObjectPatProp::Rest(_) => (),
-                }
-            }
+            get_conds_for_props(props, conds, path);
         }
         values::PatternKind::Tuple(values::TuplePat { elems, .. }) => {
             for (index, elem) in elems.iter().enumerate() {
@@ -1454,6 +2304,46 @@ fn get_conds_for_pat(pat: &values::Pattern, conds: &mut Vec<Condition>, path: &m
                 });
             }
         },
+        values::PatternKind::Enum(values::EnumPat {
+            enum_name,
+            variant,
+            args,
+        }) => {
+            conds.push(Condition {
+                path: path.to_owned(),
+                check: Check::Variant(enum_name.to_owned(), variant.to_owned()),
+            });
+            path.push(PathElem::ObjProp("payload".to_string()));
+            match args.as_slice() {
+                [arg] => get_conds_for_pat(arg, conds, path),
+                _ => {
+                    for (index, arg) in args.iter().enumerate() {
+                        path.push(PathElem::ArrayIndex(index as u32));
+                        get_conds_for_pat(arg, conds, path);
+                        path.pop();
+                    }
+                }
+            }
+            path.pop();
+        }
+    }
+}
+
+fn get_conds_for_props(
+    props: &[values::ObjectPatProp],
+    conds: &mut Vec<Condition>,
+    path: &mut Path,
+) {
+    for prop in props {
+        match prop {
+            values::ObjectPatProp::KeyValue(values::KeyValuePatProp { value, key, .. }) => {
+                path.push(PathElem::ObjProp(key.name.clone()));
+                get_conds_for_pat(value, conds, path);
+                path.pop();
+            }
+            values::ObjectPatProp::Shorthand(_) => (),
+            values::ObjectPatProp::Rest(_) => (),
+        }
     }
 }
 
@@ -1513,9 +2403,191 @@ fn cond_to_expr(cond: &Condition, id: &Ident) -> Expr {
             left: Box::from(left),
             right: Box::from(Expr::Ident(Ident::from(id))),
         }),
+        Check::Variant(enum_name, variant) => Expr::Bin(BinExpr {
+            span: DUMMY_SP,
+            op: BinaryOp::InstanceOf,
+            left: Box::from(left),
+            right: Box::from(Expr::Member(MemberExpr {
+                span: DUMMY_SP,
+                obj: Box::from(Expr::Ident(Ident::from(enum_name))),
+                prop: MemberProp::Ident(Ident::from(variant)),
+            })),
+        }),
+    }
+}
+
+// Looping over a range doesn't require creating an iterator, e.g.
+// for (i in 0..n) { ... } -> for(let i = 0; i < n; i++) { ... }
+fn build_for_stmt(for_stmt: &values::ForStmt, stmts: &mut Vec<Stmt>, ctx: &mut Context) -> Stmt {
+    let values::ForStmt {
+        label,
+        left,
+        right,
+        body,
+    } = for_stmt;
+
+    if let (
+        values::PatternKind::Ident(binding),
+        values::ExprKind::Range(values::Range { start, end }),
+    ) = (&left.kind, &right.kind)
+    {
+        let start = build_expr(start, stmts, ctx);
+        let end = build_expr(end, stmts, ctx);
+        let body = Stmt::Block(build_body_block_stmt(body, &BlockFinalizer::ExprStmt, ctx));
+        let stmt = build_range_loop(&Ident::from(binding), start, end, body, ctx);
+        return build_labeled_stmt(label, stmt);
+    }
+
+    let stmt = Stmt::ForOf(ForOfStmt {
+        span: DUMMY_SP,
+        is_await: false,
+        left: ForHead::VarDecl(Box::from(build_var_decl(false, left, None, stmts, ctx))),
+        right: Box::from(build_expr(right, stmts, ctx)),
+        body: Box::from(Stmt::Block(build_body_block_stmt(
+            body,
+            &BlockFinalizer::ExprStmt,
+            ctx,
+        ))),
+    });
+    build_labeled_stmt(label, stmt)
+}
+
+fn build_while_stmt(
+    while_stmt: &values::WhileStmt,
+    stmts: &mut Vec<Stmt>,
+    ctx: &mut Context,
+) -> Stmt {
+    let values::WhileStmt { label, test, body } = while_stmt;
+
+    let stmt = Stmt::While(WhileStmt {
+        span: DUMMY_SP,
+        test: Box::from(build_expr(test, stmts, ctx)),
+        body: Box::from(Stmt::Block(build_body_block_stmt(
+            body,
+            &BlockFinalizer::ExprStmt,
+            ctx,
+        ))),
+    });
+    build_labeled_stmt(label, stmt)
+}
+
+// `loop { ... }` becomes `while (true) { ... }`.  If `temp_id` is provided,
+// `break value` assigns `value` to it before exiting the loop.
+fn build_loop(loop_expr: &values::Loop, temp_id: Option<Ident>, ctx: &mut Context) -> Stmt {
+    let values::Loop { label, body } = loop_expr;
+
+    ctx.loops.push((label.to_owned(), temp_id));
+    let body = build_body_block_stmt(body, &BlockFinalizer::ExprStmt, ctx);
+    ctx.loops.pop();
+
+    let stmt = Stmt::While(WhileStmt {
+        span: DUMMY_SP,
+        test: Box::from(Expr::Lit(Lit::Bool(Bool {
+            span: DUMMY_SP,
+            value: true,
+        }))),
+        body: Box::from(Stmt::Block(body)),
+    });
+    build_labeled_stmt(label, stmt)
+}
+
+fn build_break_stmt(
+    break_stmt: &values::BreakStmt,
+    stmts: &mut Vec<Stmt>,
+    ctx: &mut Context,
+) -> Stmt {
+    let values::BreakStmt { label, arg } = break_stmt;
+
+    if let Some(arg) = arg {
+        let arg = build_expr(arg, stmts, ctx);
+        let scope = match label {
+            Some(label) => ctx
+                .loops
+                .iter()
+                .rev()
+                .find(|(loop_label, _)| loop_label.as_ref() == Some(label)),
+            None => ctx.loops.last(),
+        };
+        // The value is still evaluated if the loop's value is unused.
+        let finalizer = match scope {
+            Some((_, Some(temp_id))) => BlockFinalizer::Assign(temp_id.to_owned()),
+            _ => BlockFinalizer::ExprStmt,
+        };
+        stmts.push(build_finalizer(&arg, &finalizer));
+    }
+
+    Stmt::Break(BreakStmt {
+        span: DUMMY_SP,
+        label: label.as_ref().map(build_label),
+    })
+}
+
+fn build_label(label: &String) -> Ident {
+    Ident {
+        span: DUMMY_SP,
+        sym: JsWord::from(label.to_owned()),
+        optional: false,
+    }
+}
+
+fn build_labeled_stmt(label: &Option<String>, stmt: Stmt) -> Stmt {
+    match label {
+        Some(label) => Stmt::Labeled(LabeledStmt {
+            span: DUMMY_SP,
+            label: build_label(label),
+            body: Box::from(stmt),
+        }),
+        None => stmt,
     }
 }
 
+fn build_range_loop(id: &Ident, start: Expr, end: Expr, body: Stmt, ctx: &mut Context) -> Stmt {
+    let mut decls = vec![VarDeclarator {
+        span: DUMMY_SP,
+        name: Pat::Ident(BindingIdent::from(id.to_owned())),
+        init: Some(Box::from(start)),
+        definite: false,
+    }];
+
+    // The end of the range is only evaluated once.
+    let end = match end {
+        Expr::Lit(_) | Expr::Ident(_) => end,
+        _ => {
+            let temp_id = ctx.new_ident();
+            decls.push(VarDeclarator {
+                span: DUMMY_SP,
+                name: Pat::Ident(BindingIdent::from(temp_id.to_owned())),
+                init: Some(Box::from(end)),
+                definite: false,
+            });
+            Expr::Ident(temp_id)
+        }
+    };
+
+    Stmt::For(ForStmt {
+        span: DUMMY_SP,
+        init: Some(VarDeclOrExpr::VarDecl(Box::from(VarDecl {
+            span: DUMMY_SP,
+            kind: VarDeclKind::Let,
+            declare: false,
+            decls,
+        }))),
+        test: Some(Box::from(Expr::Bin(BinExpr {
+            span: DUMMY_SP,
+            op: BinaryOp::Lt,
+            left: Box::from(Expr::Ident(id.to_owned())),
+            right: Box::from(end),
+        }))),
+        update: Some(Box::from(Expr::Update(UpdateExpr {
+            span: DUMMY_SP,
+            op: UpdateOp::PlusPlus,
+            prefix: false,
+            arg: Box::from(Expr::Ident(id.to_owned())),
+        }))),
+        body: Box::from(body),
+    })
+}
+
 fn build_const_decl_stmt(id: &Ident, expr: Expr) -> Stmt {
     build_const_decl_stmt_with_pat(Pat::Ident(BindingIdent::from(id.to_owned())), expr)
 }
@@ -1,5 +1,7 @@
+pub mod codegen_error;
 pub mod d_ts;
 pub mod js;
 
+pub use codegen_error::CodegenError;
 pub use d_ts::codegen_d_ts;
 pub use js::codegen_js;
@@ -1,13 +1,15 @@
 use escalier_codegen::d_ts::codegen_d_ts;
 use escalier_codegen::js::codegen_js;
+use escalier_codegen::CodegenError;
 use escalier_hm::checker::Checker;
 use escalier_hm::context::Context;
 use escalier_hm::type_error::TypeError;
+use escalier_hm::types::{Object, TObjElem, TypeKind};
 use escalier_parser::parse;
 
 fn compile(input: &str) -> (String, String) {
     let program = parse(input).unwrap();
-    codegen_js(input, &program)
+    codegen_js(input, &program).unwrap()
 }
 
 #[test]
@@ -724,7 +726,7 @@ fn mutable_array() -> Result<(), TypeError> {
     let (js, _) = compile(src);
 
     insta::assert_snapshot!(js, @r###"
-    export const arr = [
+    export let arr = [
         1,
         2,
         3
@@ -770,6 +772,155 @@ fn mutable_obj() -> Result<(), TypeError> {
     Ok(())
 }
 
+#[test]
+fn obj_type_with_callables_and_accessors() -> Result<(), TypeError> {
+    let src = r#"
+    type Collection = {
+        fn (index: number) -> string,
+        new fn (items: string[]) -> Collection,
+        fn at<T>(self, index: number) -> T throws RangeError,
+        get size(self) -> number,
+        set size(mut self, value: number) -> undefined,
+        name: string,
+    }
+    type RangeError = {message: string}
+    "#;
+
+    let mut program = parse(src).unwrap();
+    let mut checker = Checker::default();
+    let mut ctx = Context::default();
+    checker.infer_script(&mut program, &mut ctx)?;
+    let result = codegen_d_ts(&program, &ctx, &checker)?;
+
+    insta::assert_snapshot!(result, @r###"
+    declare type Collection = {
+        (index: number) : string;
+        new(items: readonly string[]): ReadonlyCollection;
+        /** @throws {ReadonlyRangeError} */ at<T>(index: number): T;
+        get size(): number;
+        set size(value: number);
+        name: string;
+    };
+    declare type ReadonlyCollection = {
+        (index: number) : string;
+        new(items: readonly string[]): ReadonlyCollection;
+        /** @throws {ReadonlyRangeError} */ at<T>(index: number): T;
+        get size(): number;
+        readonly name: string;
+    };
+    declare type RangeError = {
+        message: string;
+    };
+    declare type ReadonlyRangeError = {
+        readonly message: string;
+    };
+    "###);
+
+    Ok(())
+}
+
+#[test]
+fn fn_types_with_throws() -> Result<(), TypeError> {
+    let src = r#"
+    type Parser = {
+        parse: fn (input: string) -> number throws RangeError,
+    }
+    declare let parse: fn (input: string) -> number throws RangeError
+    type RangeError = {message: string}
+    "#;
+
+    let mut program = parse(src).unwrap();
+    let mut checker = Checker::default();
+    let mut ctx = Context::default();
+    checker.infer_script(&mut program, &mut ctx)?;
+    let result = codegen_d_ts(&program, &ctx, &checker)?;
+
+    insta::assert_snapshot!(result, @r###"
+    declare type Parser = {
+        parse: /** @throws {ReadonlyRangeError} */ (input: string) => number;
+    };
+    declare type ReadonlyParser = {
+        readonly parse: /** @throws {ReadonlyRangeError} */ (input: string) => number;
+    };
+    declare type RangeError = {
+        message: string;
+    };
+    declare type ReadonlyRangeError = {
+        readonly message: string;
+    };
+    export declare const parse: /** @throws {ReadonlyRangeError} */ (input: string) => number;
+    "###);
+
+    Ok(())
+}
+
+#[test]
+fn accessors_with_throws() -> Result<(), TypeError> {
+    let src = r#"
+    type Collection = {
+        get size(self) -> number,
+        set size(mut self, value: number) -> undefined,
+    }
+    type RangeError = {message: string}
+    "#;
+
+    let mut program = parse(src).unwrap();
+    let mut checker = Checker::default();
+    let mut ctx = Context::default();
+    checker.infer_script(&mut program, &mut ctx)?;
+
+    // Accessor types can't be annotated with `throws` yet so we add it here.
+    let throws = checker.new_type_ref("RangeError", None, &[]);
+    let collection = ctx.schemes.get("Collection").unwrap().t;
+    if let TypeKind::Object(Object { elems }) = &mut checker.arena[collection].kind {
+        for elem in elems.iter_mut() {
+            match elem {
+                TObjElem::Getter(getter) => getter.throws = Some(throws),
+                TObjElem::Setter(setter) => setter.throws = Some(throws),
+                _ => (),
+            }
+        }
+    }
+
+    let result = codegen_d_ts(&program, &ctx, &checker)?;
+
+    insta::assert_snapshot!(result, @r###"
+    declare type Collection = {
+        get /** @throws {ReadonlyRangeError} */ size(): number;
+        set /** @throws {ReadonlyRangeError} */ size(value: number);
+    };
+    declare type RangeError = {
+        message: string;
+    };
+    declare type ReadonlyRangeError = {
+        readonly message: string;
+    };
+    "###);
+
+    Ok(())
+}
+
+#[test]
+fn required_type() -> Result<(), TypeError> {
+    let src = r#"
+    type Required<T> = {[P]-?: T[P] for P in keyof T}
+    "#;
+
+    let mut program = parse(src).unwrap();
+    let mut checker = Checker::default();
+    let mut ctx = Context::default();
+    checker.infer_script(&mut program, &mut ctx)?;
+    let result = codegen_d_ts(&program, &ctx, &checker)?;
+
+    insta::assert_snapshot!(result, @r###"
+    declare type Required<T> = {
+        [P in keyof T]-?: T[P];
+    };
+    "###);
+
+    Ok(())
+}
+
 // TODO: finish porting codgen_d_ts()
 #[test]
 #[ignore]
@@ -843,7 +994,7 @@ fn for_loop() -> Result<(), TypeError> {
 
     let (js, _) = compile(src);
     insta::assert_snapshot!(js, @r###"
-    export const sum = 0;
+    export let sum = 0;
     for (const num of [
         1,
         2,
@@ -880,7 +1031,7 @@ fn for_loop_inside_fn() -> Result<(), TypeError> {
     let (js, _) = compile(src);
     insta::assert_snapshot!(js, @r###"
     export const sum = (arr)=>{
-        const result = 0;
+        let result = 0;
         for (const num of arr){
             result = result + num;
         }
@@ -1015,22 +1166,60 @@ fn class_inside_function() -> Result<(), TypeError> {
 }
 
 #[test]
-#[should_panic = "return statements aren't allowed at the top level"]
-fn top_level_return() {
+fn js_throw() {
     let src = r#"
-    return 5
+    let div = fn (a: number, b: number) -> number throws string {
+        if (b == 0) {
+            throw "division by zero"
+        }
+        return a / b
+    }
     "#;
+
     let (js, _) = compile(src);
 
     insta::assert_snapshot!(js, @r###"
-    export const add = (a, b)=>a + b;
-    export const sum = add(...[
-        5,
-        10
-    ]);
+    export const div = (a, b)=>{
+        if (b === 0) {
+            throw "division by zero";
+        }
+        return a / b;
+    };
     "###);
 }
 
+#[test]
+fn top_level_return() {
+    let src = r#"
+    return 5
+    "#;
+    let program = parse(src).unwrap();
+    let result = codegen_js(src, &program);
+
+    assert_eq!(
+        result,
+        Err(CodegenError {
+            message: "return statements aren't allowed at the top level".to_string()
+        })
+    );
+}
+
+#[test]
+fn top_level_break() {
+    let src = r#"
+    break
+    "#;
+    let program = parse(src).unwrap();
+    let result = codegen_js(src, &program);
+
+    assert_eq!(
+        result,
+        Err(CodegenError {
+            message: "break and continue statements aren't allowed at the top level".to_string()
+        })
+    );
+}
+
 #[test]
 fn multiple_returns_stress_test() -> Result<(), TypeError> {
     let src = r#"
@@ -1051,17 +1240,13 @@ fn multiple_returns_stress_test() -> Result<(), TypeError> {
     insta::assert_snapshot!(js, @r###"
     export const foo = (cond)=>{
         const bar = ()=>{
-            let $temp_0;
             if (cond) {
                 return 5;
             }
-            $temp_0;
         };
-        let $temp_1;
         if (cond) {
             return bar();
         }
-        $temp_1;
         return 10;
     };
     "###);
@@ -1072,8 +1257,7 @@ fn multiple_returns_stress_test() -> Result<(), TypeError> {
     checker.infer_script(&mut program, &mut ctx)?;
     let result = codegen_d_ts(&program, &ctx, &checker)?;
 
-    // TODO: the return value should be `5 | 10 | undefined`
-    insta::assert_snapshot!(result, @"export declare const foo: (cond: boolean) => undefined | 10;
+    insta::assert_snapshot!(result, @"export declare const foo: (cond: boolean) => 5 | undefined | 10;
 ");
 
     Ok(())
@@ -1111,7 +1295,7 @@ fn partial_type() -> Result<(), TypeError> {
         readonly b: number;
     };
     declare type Partial<T> = {
-        [P in keyof T]: T[P];
+        [P in keyof T]?: T[P];
     };
     declare type PartialObj = Partial<ReadonlyObj>;
     "###);
@@ -1231,3 +1415,579 @@ fn compile_jsx() -> Result<(), TypeError> {
 
     Ok(())
 }
+
+#[test]
+fn doc_comments_in_d_ts() -> Result<(), TypeError> {
+    let src = r#"
+    /// A point in 2D space.
+    type Point = {x: number, y: number}
+    /**
+     * Adds two numbers.
+     *
+     * Returns their sum.
+     */
+    let add = fn (a: number, b: number) => a + b
+    // not a doc comment
+    let x = 5
+    "#;
+
+    let mut program = parse(src).unwrap();
+    let mut checker = Checker::default();
+    let mut ctx = Context::default();
+    checker.infer_script(&mut program, &mut ctx)?;
+    let result = codegen_d_ts(&program, &ctx, &checker)?;
+
+    insta::assert_snapshot!(result, @r###"
+    /**
+     * A point in 2D space.
+     */
+    declare type Point = {
+        x: number;
+        y: number;
+    };
+    declare type ReadonlyPoint = {
+        readonly x: number;
+        readonly y: number;
+    };
+    /**
+     * Adds two numbers.
+     *
+     * Returns their sum.
+     */
+    export declare const add: (a: number, b: number) => number;
+    export declare const x: 5;
+    "###);
+
+    Ok(())
+}
+
+#[test]
+fn enum_with_payloads() {
+    let src = r#"
+    enum IpAddr { V4(string), V6(string) }
+    let home = IpAddr.V4("127.0.0.1")
+    declare let addr: IpAddr
+    let addr_str = match (addr) {
+        IpAddr.V4(address) => address,
+        IpAddr.V6(address) => address
+    }
+    "#;
+    let (js, _) = compile(src);
+
+    insta::assert_snapshot!(js, @r###"
+    export const IpAddr = {
+        V4: class V4 {
+            constructor(payload){
+                this.payload = payload;
+            }
+        },
+        V6: class V6 {
+            constructor(payload){
+                this.payload = payload;
+            }
+        }
+    };
+    export const home = new IpAddr.V4("127.0.0.1");
+    ;
+    let $temp_0;
+    const $temp_1 = addr;
+    if ($temp_1 instanceof IpAddr.V4) {
+        const { payload: address } = $temp_1;
+        $temp_0 = address;
+    } else if ($temp_1 instanceof IpAddr.V6) {
+        const { payload: address } = $temp_1;
+        $temp_0 = address;
+    }
+    export const addr_str = $temp_0;
+    "###);
+}
+
+#[test]
+fn enum_with_multiple_fields_and_unit_variants() {
+    let src = r#"
+    enum Shape { Rect(number, number), Empty }
+    let empty = Shape.Empty
+    declare let shape: Shape
+    let area = match (shape) {
+        Shape.Rect(w, 0) => 0,
+        Shape.Rect(w, h) => w * h,
+        Shape.Empty => 0
+    }
+    "#;
+    let (js, _) = compile(src);
+
+    insta::assert_snapshot!(js, @r###"
+    export const Shape = {
+        Rect: class Rect {
+            constructor(...payload){
+                this.payload = payload;
+            }
+        },
+        Empty: class Empty {
+        }
+    };
+    export const empty = new Shape.Empty();
+    ;
+    let $temp_0;
+    const $temp_1 = shape;
+    if ($temp_1 instanceof Shape.Rect && $temp_1.payload[1] === 0) {
+        const { payload: [w, ] } = $temp_1;
+        $temp_0 = 0;
+    } else if ($temp_1 instanceof Shape.Rect) {
+        const { payload: [w, h] } = $temp_1;
+        $temp_0 = w * h;
+    } else if ($temp_1 instanceof Shape.Empty) {
+        $temp_0 = 0;
+    }
+    export const area = $temp_0;
+    "###);
+}
+
+#[test]
+fn enum_in_d_ts() -> Result<(), TypeError> {
+    let src = r#"
+    /// An optional value.
+    enum Option<T> { Some(T), None }
+    enum Shape { Circle(number), Rect(number, number) }
+    let some = Option.Some(5)
+    "#;
+
+    let mut program = parse(src).unwrap();
+    let mut checker = Checker::default();
+    let mut ctx = Context::default();
+    checker.infer_script(&mut program, &mut ctx)?;
+    let result = codegen_d_ts(&program, &ctx, &checker)?;
+
+    insta::assert_snapshot!(result, @r###"
+    /**
+     * An optional value.
+     */
+    declare type Option<T> = Option.Some<T> | Option.None<T>;
+    export declare module Option {
+        class Some<T> {
+            readonly payload: T;
+            constructor(payload: T);
+        }
+        class None<T> {
+        }
+    }
+    declare type Shape = Shape.Circle | Shape.Rect;
+    export declare module Shape {
+        class Circle {
+            readonly payload: number;
+            constructor(payload: number);
+        }
+        class Rect {
+            readonly payload: readonly [number, number];
+            constructor(...payload: readonly [number, number]);
+        }
+    }
+    export declare const some: Option.Some<5>;
+    "###);
+
+    Ok(())
+}
+
+#[test]
+fn struct_with_private_fields() {
+    let src = r#"
+    struct User { pub name: string, pub age: number, ssn: number }
+    let user = User { name: "John Doe", age: 43, ssn: 123456789 }
+    "#;
+    let (js, _) = compile(src);
+
+    insta::assert_snapshot!(js, @r###"
+    export class User {
+        name;
+        age;
+        #ssn;
+        constructor(values){
+            this.name = values.name;
+            this.age = values.age;
+            this.#ssn = values.ssn;
+        }
+    }
+    export const user = new User({
+        name: "John Doe",
+        age: 43,
+        ssn: 123456789
+    });
+    "###);
+}
+
+#[test]
+fn struct_patterns() {
+    let src = r#"
+    struct Circle { pub radius: number }
+    struct Rect { pub width: number, pub height: number }
+    declare let shape: Circle | Rect
+    let size = match (shape) {
+        Circle {radius} => radius,
+        Rect {width: w, height: 0} => w
+    }
+    "#;
+    let (js, _) = compile(src);
+
+    insta::assert_snapshot!(js, @r###"
+    export class Circle {
+        radius;
+        constructor(values){
+            this.radius = values.radius;
+        }
+    }
+    export class Rect {
+        width;
+        height;
+        constructor(values){
+            this.width = values.width;
+            this.height = values.height;
+        }
+    }
+    ;
+    let $temp_0;
+    const $temp_1 = shape;
+    if ($temp_1 instanceof Circle) {
+        const { radius } = $temp_1;
+        $temp_0 = radius;
+    } else if ($temp_1 instanceof Rect && $temp_1.height === 0) {
+        const { width: w } = $temp_1;
+        $temp_0 = w;
+    }
+    export const size = $temp_0;
+    "###);
+}
+
+#[test]
+fn struct_in_d_ts() -> Result<(), TypeError> {
+    let src = r#"
+    /// A user of the system.
+    struct User { pub name: string, ssn: number }
+    struct Box<T> { pub value: T }
+    let user = User { name: "John Doe", ssn: 123456789 }
+    "#;
+
+    let mut program = parse(src).unwrap();
+    let mut checker = Checker::default();
+    let mut ctx = Context::default();
+    checker.infer_script(&mut program, &mut ctx)?;
+    let result = codegen_d_ts(&program, &ctx, &checker)?;
+
+    insta::assert_snapshot!(result, @r###"
+    export declare class Box<T> {
+        value: T;
+        constructor(values: {
+            value: T;
+        });
+    }
+    /**
+     * A user of the system.
+     */
+    export declare class User {
+        name: string;
+        #private;
+        constructor(values: {
+            name: string;
+            ssn: number;
+        });
+    }
+    export declare const user: User;
+    "###);
+
+    Ok(())
+}
+
+#[test]
+fn traits_and_impls() {
+    let src = r#"
+    trait Shape {
+        fn area(self) -> number
+        fn describe(self) -> string {
+            return `area = ${self.area()}`
+        }
+    }
+    struct Circle { pub radius: number }
+    impl Shape for Circle {
+        fn area(self) -> number {
+            return 3.14 * self.radius * self.radius
+        }
+    }
+    let desc = Circle { radius: 5 }.describe()
+    "#;
+    let (js, _) = compile(src);
+
+    insta::assert_snapshot!(js, @r###"
+    export const Shape = {
+        describe () {
+            const self = this;
+            return `area = ${self.area()}`;
+        }
+    };
+    export class Circle {
+        radius;
+        constructor(values){
+            this.radius = values.radius;
+        }
+    }
+    Circle.prototype.area = function() {
+        const self = this;
+        return 3.14 * self.radius * self.radius;
+    };
+    Circle.prototype.describe = Shape.describe;
+    ;
+    export const desc = new Circle({
+        radius: 5
+    }).describe();
+    "###);
+}
+
+#[test]
+fn traits_in_d_ts() -> Result<(), TypeError> {
+    let src = r#"
+    /// Something with an area.
+    trait Shape {
+        fn area(self) -> number
+        fn scale(mut self, factor: number) -> undefined
+    }
+    struct Square { pub size: number }
+    impl Shape for Square {
+        fn area(self) -> number {
+            return self.size * self.size
+        }
+        fn scale(mut self, factor: number) -> undefined {
+            self.size = self.size * factor
+        }
+    }
+    declare let shape: Shape
+    "#;
+
+    let mut program = parse(src).unwrap();
+    let mut checker = Checker::default();
+    let mut ctx = Context::default();
+    checker.infer_script(&mut program, &mut ctx)?;
+    let result = codegen_d_ts(&program, &ctx, &checker)?;
+
+    insta::assert_snapshot!(result, @r###"
+    /**
+     * Something with an area.
+     */
+    export interface Shape {
+        area(): number;
+        scale(factor: number): undefined;
+    }
+    export declare class Square {
+        size: number;
+        constructor(values: {
+            size: number;
+        });
+    }
+    export interface Square extends Shape {
+    }
+    export declare const shape: Shape;
+    "###);
+
+    Ok(())
+}
+
+#[test]
+fn ranges_in_for_loops() {
+    let src = r#"
+    let xs = [1, 2, 3]
+    for (i in 0..10) {
+        let x = i * 2
+    }
+    for (i in 1..xs.length + 1) {
+        let y = i * 2
+    }
+    "#;
+    let (js, _) = compile(src);
+
+    insta::assert_snapshot!(js, @r###"
+    export const xs = [
+        1,
+        2,
+        3
+    ];
+    for(let i = 0; i < 10; i++){
+        const x = i * 2;
+    }
+    for(let i = 1, $temp_0 = xs.length + 1; i < $temp_0; i++){
+        const y = i * 2;
+    }
+    "###);
+}
+
+#[test]
+fn range_values() -> Result<(), TypeError> {
+    let src = r#"
+    let range = 0..10
+    "#;
+    let (js, _) = compile(src);
+
+    insta::assert_snapshot!(js, @r###"
+    export const range = (function*(start, end) {
+        for(let i = start; i < end; i++)yield i;
+    })(0, 10);
+    "###);
+
+    let mut program = parse(src).unwrap();
+    let mut checker = Checker::default();
+    let mut ctx = Context::default();
+    checker.infer_script(&mut program, &mut ctx)?;
+    let result = codegen_d_ts(&program, &ctx, &checker)?;
+
+    insta::assert_snapshot!(result, @r###"
+    export declare const range: Iterable<number>;
+    "###);
+
+    Ok(())
+}
+
+#[test]
+fn slices() {
+    let src = r#"
+    let xs = [1, 2, 3, 4]
+    let a = xs[1:3]
+    let b = xs[:2]
+    let c = xs[2:]
+    let d = xs[:]
+    declare let ys: number[] | undefined
+    let e = ys?.[1:]
+    "#;
+    let (js, _) = compile(src);
+
+    insta::assert_snapshot!(js, @r###"
+    export const xs = [
+        1,
+        2,
+        3,
+        4
+    ];
+    export const a = xs.slice(1, 3);
+    export const b = xs.slice(0, 2);
+    export const c = xs.slice(2);
+    export const d = xs.slice();
+    ;
+    export const e = ys?.slice(1);
+    "###);
+}
+
+#[test]
+fn pipelines() {
+    let src = r#"
+    let result = 5 |> double(_) |> show(_, 16)
+    let nested = xs |> _.map(fn (x) => x |> inc(_))
+    "#;
+    let (js, _) = compile(src);
+
+    insta::assert_snapshot!(js, @r###"
+    let $temp_0;
+    let $temp_1;
+    export const result = ($temp_0 = 5, ($temp_1 = double($temp_0), show($temp_1, 16)));
+    let $temp_2;
+    let $temp_3;
+    export const nested = ($temp_2 = xs, $temp_2.map((x)=>($temp_3 = x, inc($temp_3))));
+    "###);
+}
+
+#[test]
+fn regex_literals() {
+    let src = r#"
+    let digits = /\d+/g
+    let color = /rgb\((?<r>\d+),(?<g>\d+),(?<b>\d+)\)/
+    let ratio = a / b / /[/\]]+\//i
+    "#;
+    let (js, _) = compile(src);
+
+    insta::assert_snapshot!(js, @r###"
+    export const digits = /\d+/g;
+    export const color = /rgb\((?<r>\d+),(?<g>\d+),(?<b>\d+)\)/;
+    export const ratio = a / b / /[/\]]+\//i;
+    "###);
+}
+
+#[test]
+fn while_loop_with_counter() {
+    let src = r#"
+    let count = fn () {
+        var mut i: number = 0
+        while (i < 10) {
+            i = i + 1
+        }
+        return i
+    }
+    var total = 0
+    "#;
+    let (js, _) = compile(src);
+
+    insta::assert_snapshot!(js, @r###"
+    export const count = ()=>{
+        let i = 0;
+        while(i < 10){
+            i = i + 1;
+        }
+        return i;
+    };
+    export let total = 0;
+    "###);
+}
+
+#[test]
+fn loops() {
+    let src = r#"
+    let result = loop {
+        let n = next()
+        if (n > 10) {
+            break n
+        }
+    }
+    outer: for (row in rows) {
+        for (cell in row) {
+            if (cell < 0) {
+                continue outer
+            }
+            if (cell == 0) {
+                break outer
+            }
+        }
+    }
+    let run = fn () {
+        while (running()) {
+            tick()
+        }
+        loop {
+            tick()
+        }
+    }
+    "#;
+    let (js, _) = compile(src);
+
+    insta::assert_snapshot!(js, @r###"
+    let $temp_0;
+    while(true){
+        const n = next();
+        if (n > 10) {
+            $temp_0 = n;
+            break;
+        }
+    }
+    export const result = $temp_0;
+    outer: for (const row of rows){
+        for (const cell of row){
+            if (cell < 0) {
+                continue outer;
+            }
+            if (cell === 0) {
+                break outer;
+            }
+        }
+    }
+    export const run = ()=>{
+        while(running()){
+            tick();
+        }
+        while(true){
+            tick();
+        }
+    };
+    "###);
+}
@@ -0,0 +1,174 @@
+pub const MAX_WIDTH: usize = 80;
+pub const INDENT_WIDTH: usize = 4;
+
+/// A document describing the possible layouts of a piece of source code.
+/// `Line` and `SoftLine` are printed as line breaks if the enclosing `Group`
+/// doesn't fit on the current line.
+#[derive(Debug, Clone)]
+pub enum Doc {
+    Text(String),
+    Line,     // " " when flat
+    SoftLine, // "" when flat
+    HardLine, // always breaks, forces enclosing groups to break
+    Concat(Vec<Doc>),
+    Indent(Vec<Doc>),
+    Group(Vec<Doc>, bool), // `bool` is true if the group contains a hard line
+    IfBreak(String),       // only printed when the enclosing group breaks
+    LineSuffix(String),    // printed at the end of the line, e.g. trailing comments
+}
+
+pub fn text(s: impl Into<String>) -> Doc {
+    Doc::Text(s.into())
+}
+
+pub fn concat(docs: Vec<Doc>) -> Doc {
+    Doc::Concat(docs)
+}
+
+pub fn indent(docs: Vec<Doc>) -> Doc {
+    Doc::Indent(docs)
+}
+
+pub fn group(docs: Vec<Doc>) -> Doc {
+    let has_hard_line = docs.iter().any(has_hard_line);
+    Doc::Group(docs, has_hard_line)
+}
+
+pub fn join(docs: Vec<Doc>, separator: Vec<Doc>) -> Vec<Doc> {
+    let mut result = vec![];
+    for (i, doc) in docs.into_iter().enumerate() {
+        if i > 0 {
+            result.extend(separator.iter().cloned());
+        }
+        result.push(doc);
+    }
+    result
+}
+
+/// `open` and `close` surrounding a comma separated list of `items`.  If the
+/// list doesn't fit on a single line each item is placed on its own line with
+/// a trailing comma.
+pub fn comma_list(open: &str, items: Vec<Doc>, close: &str) -> Doc {
+    comma_list_with_tail(open, items, vec![], close)
+}
+
+/// Same as `comma_list`, but `tail` is placed on separate lines after the
+/// last item.  This is used for comments that appear before `close`.
+pub fn comma_list_with_tail(open: &str, items: Vec<Doc>, tail: Vec<Doc>, close: &str) -> Doc {
+    if items.is_empty() && tail.is_empty() {
+        return text(format!("{open}{close}"));
+    }
+
+    let mut docs = vec![Doc::SoftLine];
+    if !items.is_empty() {
+        docs.push(concat(join(items, vec![text(","), Doc::Line])));
+        docs.push(Doc::IfBreak(String::from(",")));
+        docs.push(concat(
+            tail.into_iter()
+                .flat_map(|doc| vec![Doc::HardLine, doc])
+                .collect(),
+        ));
+    } else {
+        docs.extend(join(tail, vec![Doc::HardLine]));
+    }
+
+    group(vec![text(open), indent(docs), Doc::SoftLine, text(close)])
+}
+
+pub fn has_hard_line(doc: &Doc) -> bool {
+    match doc {
+        Doc::Text(s) => s.contains('\n'),
+        // Nothing can follow a trailing comment on the same line.
+        Doc::HardLine | Doc::LineSuffix(_) => true,
+        Doc::Concat(docs) | Doc::Indent(docs) => docs.iter().any(has_hard_line),
+        Doc::Group(_, has_hard_line) => *has_hard_line,
+        Doc::Line | Doc::SoftLine | Doc::IfBreak(_) => false,
+    }
+}
+
+pub fn render(doc: &Doc) -> String {
+    let mut out = String::new();
+    let mut column = 0;
+    let mut line_suffix = String::new();
+    let mut stack: Vec<(usize, bool, &Doc)> = vec![(0, false, doc)];
+
+    while let Some((level, flat, doc)) = stack.pop() {
+        match doc {
+            Doc::Text(s) => {
+                out.push_str(s);
+                column = match s.rfind('\n') {
+                    Some(index) => s[index + 1..].chars().count(),
+                    None => column + s.chars().count(),
+                };
+            }
+            Doc::Line | Doc::SoftLine if flat => {
+                if let Doc::Line = doc {
+                    out.push(' ');
+                    column += 1;
+                }
+            }
+            Doc::Line | Doc::SoftLine | Doc::HardLine => {
+                out.push_str(&line_suffix);
+                line_suffix.clear();
+                // Avoids trailing whitespace, e.g. on blank lines.
+                out.truncate(out.trim_end_matches(' ').len());
+                out.push('\n');
+                out.push_str(&" ".repeat(level * INDENT_WIDTH));
+                column = level * INDENT_WIDTH;
+            }
+            Doc::Concat(docs) => {
+                for doc in docs.iter().rev() {
+                    stack.push((level, flat, doc));
+                }
+            }
+            Doc::Indent(docs) => {
+                for doc in docs.iter().rev() {
+                    stack.push((level + 1, flat, doc));
+                }
+            }
+            Doc::Group(docs, has_hard_line) => {
+                let flat = flat
+                    || (!has_hard_line && fits(docs, MAX_WIDTH.saturating_sub(column) as isize));
+                for doc in docs.iter().rev() {
+                    stack.push((level, flat, doc));
+                }
+            }
+            Doc::IfBreak(s) => {
+                if !flat {
+                    out.push_str(s);
+                    column += s.chars().count();
+                }
+            }
+            Doc::LineSuffix(s) => line_suffix.push_str(s),
+        }
+    }
+
+    out.push_str(&line_suffix);
+    out
+}
+
+// Returns true if `docs` can be printed flat within `width` columns.
+fn fits(docs: &[Doc], mut width: isize) -> bool {
+    let mut stack: Vec<&Doc> = docs.iter().rev().collect();
+
+    while let Some(doc) = stack.pop() {
+        match doc {
+            Doc::Text(s) => match s.find('\n') {
+                Some(index) => return width - s[..index].chars().count() as isize >= 0,
+                None => width -= s.chars().count() as isize,
+            },
+            Doc::Line => width -= 1,
+            Doc::SoftLine | Doc::IfBreak(_) | Doc::LineSuffix(_) => (),
+            Doc::HardLine => return false,
+            Doc::Concat(docs) | Doc::Indent(docs) | Doc::Group(docs, _) => {
+                stack.extend(docs.iter().rev());
+            }
+        }
+
+        if width < 0 {
+            return false;
+        }
+    }
+
+    true
+}
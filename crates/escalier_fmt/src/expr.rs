@@ -0,0 +1,448 @@
+use escalier_ast::*;
+
+use crate::doc::*;
+use crate::printer::{print_label, quote, Printer};
+
+// Precedences match those used by the parser, see escalier_parser's
+// precedence.rs.
+const ATOM: u32 = 20;
+const POSTFIX: u32 = 17;
+const NEW: u32 = 16;
+const PREFIX: u32 = 14;
+const RANGE: u32 = 10;
+const PIPELINE: u32 = 3;
+const ASSIGN: u32 = 2;
+
+fn get_precedence(expr: &Expr) -> u32 {
+    match &expr.kind {
+        ExprKind::Member(_)
+        | ExprKind::Slice(_)
+        | ExprKind::Call(_)
+        | ExprKind::TaggedTemplateLiteral(_) => POSTFIX,
+        ExprKind::Range(_) => RANGE,
+        ExprKind::Pipeline(_) => PIPELINE,
+        ExprKind::New(_) => NEW,
+        ExprKind::Unary(_) | ExprKind::Await(_) | ExprKind::Throw(_) => PREFIX,
+        ExprKind::Binary(Binary { op, .. }) => get_binary_precedence(op),
+        ExprKind::Assign(_) | ExprKind::Yield(_) => ASSIGN,
+        // These either end with an expression or a block, in both cases we
+        // want to parenthesize them when they appear as operands.
+        ExprKind::Function(_)
+        | ExprKind::Class(_)
+        | ExprKind::IfElse(_)
+        | ExprKind::Match(_)
+        | ExprKind::Try(_)
+        | ExprKind::Do(_)
+        | ExprKind::Loop(_) => ASSIGN,
+        _ => ATOM,
+    }
+}
+
+fn get_binary_precedence(op: &BinaryOp) -> u32 {
+    match op {
+        BinaryOp::Times | BinaryOp::Divide | BinaryOp::Modulo => 12,
+        BinaryOp::Plus | BinaryOp::Minus => 11,
+        BinaryOp::LessThan
+        | BinaryOp::LessThanOrEqual
+        | BinaryOp::GreaterThan
+        | BinaryOp::GreaterThanOrEqual => 9,
+        BinaryOp::Equals | BinaryOp::NotEquals => 8,
+        BinaryOp::And => 4,
+        BinaryOp::Or => 3,
+    }
+}
+
+pub fn print_binary_op(op: &BinaryOp) -> &'static str {
+    match op {
+        BinaryOp::Plus => "+",
+        BinaryOp::Minus => "-",
+        BinaryOp::Times => "*",
+        BinaryOp::Divide => "/",
+        BinaryOp::Modulo => "%",
+        BinaryOp::Equals => "==",
+        BinaryOp::NotEquals => "!=",
+        BinaryOp::LessThan => "<",
+        BinaryOp::LessThanOrEqual => "<=",
+        BinaryOp::GreaterThan => ">",
+        BinaryOp::GreaterThanOrEqual => ">=",
+        BinaryOp::Or => "||",
+        BinaryOp::And => "&&",
+    }
+}
+
+fn print_assign_op(op: &AssignOp) -> &'static str {
+    match op {
+        AssignOp::Assign => "=",
+        AssignOp::AddAssign => "+=",
+        AssignOp::SubAssign => "-=",
+        AssignOp::MulAssign => "*=",
+        AssignOp::DivAssign => "/=",
+        AssignOp::ModAssign => "%=",
+    }
+}
+
+impl<'a> Printer<'a> {
+    /// Prints `expr`, wrapping it in parens if its precedence is lower than
+    /// `min_prec`.
+    pub fn print_expr(&mut self, expr: &Expr, min_prec: u32) -> Doc {
+        let doc = self.print_expr_kind(expr);
+        match get_precedence(expr) < min_prec {
+            true => concat(vec![text("("), doc, text(")")]),
+            false => doc,
+        }
+    }
+
+    fn print_expr_kind(&mut self, expr: &Expr) -> Doc {
+        match &expr.kind {
+            ExprKind::Ident(Ident { name, .. }) => text(name),
+            ExprKind::Num(Num { value }) => text(value),
+            ExprKind::Str(Str { value, .. }) => text(quote(value)),
+            ExprKind::Regex(Regex { pattern, flags }) => text(format!(
+                "/{pattern}/{}",
+                flags.as_deref().unwrap_or_default()
+            )),
+            ExprKind::Bool(Bool { value }) => text(value.to_string()),
+            ExprKind::Null(_) => text("null"),
+            ExprKind::Undefined(_) => text("undefined"),
+            ExprKind::TemplateLiteral(template) => self.print_template(template),
+            ExprKind::TaggedTemplateLiteral(TaggedTemplateLiteral { tag, template, .. }) => {
+                concat(vec![
+                    self.print_expr(tag, POSTFIX),
+                    self.print_template(template),
+                ])
+            }
+            ExprKind::Object(Object { properties })
+            | ExprKind::Struct(Struct { properties, .. }) => {
+                let items = properties
+                    .iter()
+                    .map(|prop| match prop {
+                        PropOrSpread::Prop(prop) => {
+                            let doc = match prop {
+                                expr::Prop::Shorthand { ident, .. } => text(&ident.name),
+                                expr::Prop::Property { key, value, .. } => {
+                                    let key = match key {
+                                        ObjectKey::Ident(Ident { name, .. }) => text(name),
+                                        ObjectKey::String(value) => text(quote(value)),
+                                        ObjectKey::Number(value) => text(value),
+                                        ObjectKey::Computed(expr) => concat(vec![
+                                            text("["),
+                                            self.print_expr(expr, 0),
+                                            text("]"),
+                                        ]),
+                                    };
+                                    concat(vec![key, text(": "), self.print_expr(value, 0)])
+                                }
+                            };
+                            self.print_with_comments(prop.comments(), doc)
+                        }
+                        // Comments aren't attached to spreads.
+                        PropOrSpread::Spread(expr) => {
+                            let comments = self.take_comments(expr.span.start);
+                            let mut docs = self.print_leading_comments(&comments);
+                            docs.push(text("..."));
+                            docs.push(self.print_expr(expr, 0));
+                            concat(docs)
+                        }
+                    })
+                    .collect();
+                let tail = self.print_tail_comments(expr.span.end);
+                let obj = comma_list_with_tail("{", items, tail, "}");
+                match &expr.kind {
+                    ExprKind::Struct(Struct { name, .. }) => {
+                        concat(vec![text(format!("{} ", name.name)), obj])
+                    }
+                    _ => obj,
+                }
+            }
+            ExprKind::Tuple(Tuple { elements }) => {
+                let items = elements
+                    .iter()
+                    .map(|elem| match elem {
+                        ExprOrSpread::Expr(expr) => self.print_expr(expr, 0),
+                        ExprOrSpread::Spread(expr) => {
+                            concat(vec![text("..."), self.print_expr(expr, 0)])
+                        }
+                    })
+                    .collect();
+                comma_list("[", items, "]")
+            }
+            ExprKind::Assign(Assign { left, op, right }) => concat(vec![
+                self.print_expr(left, POSTFIX),
+                text(format!(" {} ", print_assign_op(op))),
+                self.print_expr(right, ASSIGN),
+            ]),
+            ExprKind::Binary(Binary { left, op, right }) => {
+                let prec = get_binary_precedence(op);
+                concat(vec![
+                    self.print_expr(left, prec),
+                    text(format!(" {} ", print_binary_op(op))),
+                    self.print_expr(right, prec + 1),
+                ])
+            }
+            ExprKind::Unary(Unary { op, right }) => {
+                let op = match op {
+                    UnaryOp::Plus => "+",
+                    UnaryOp::Minus => "-",
+                    UnaryOp::Not => "!",
+                };
+                // Nested unary operators are parenthesized to avoid printing
+                // things like `--x`.
+                let right = match &right.kind {
+                    ExprKind::Unary(_) => self.print_expr(right, ATOM),
+                    _ => self.print_expr(right, PREFIX),
+                };
+                concat(vec![text(op), right])
+            }
+            ExprKind::Function(function) => self.print_function(function, None, None),
+            ExprKind::Class(class) => self.print_class(class),
+            ExprKind::Call(Call {
+                callee,
+                type_args,
+                args,
+                opt_chain,
+                ..
+            }) => {
+                let mut docs = vec![self.print_expr(callee, POSTFIX)];
+                if *opt_chain {
+                    docs.push(text("?."));
+                }
+                docs.push(self.print_type_args(type_args));
+                docs.push(self.print_args(args));
+                concat(docs)
+            }
+            ExprKind::New(New {
+                callee,
+                type_args,
+                args,
+                ..
+            }) => concat(vec![
+                text("new "),
+                self.print_expr(callee, POSTFIX),
+                self.print_type_args(type_args),
+                self.print_args(args),
+            ]),
+            ExprKind::Member(Member {
+                object,
+                property,
+                opt_chain,
+            }) => {
+                // `1.foo` would be parsed as a number.
+                let object = match &object.kind {
+                    ExprKind::Num(_) => self.print_expr(object, ATOM + 1),
+                    _ => self.print_expr(object, POSTFIX),
+                };
+                let property = match (property, opt_chain) {
+                    (MemberProp::Ident(Ident { name, .. }), false) => text(format!(".{name}")),
+                    (MemberProp::Ident(Ident { name, .. }), true) => text(format!("?.{name}")),
+                    (MemberProp::Computed(ComputedPropName { expr, .. }), opt_chain) => {
+                        concat(vec![
+                            text(if *opt_chain { "?.[" } else { "[" }),
+                            self.print_expr(expr, 0),
+                            text("]"),
+                        ])
+                    }
+                };
+                concat(vec![object, property])
+            }
+            ExprKind::Slice(Slice {
+                object,
+                start,
+                end,
+                opt_chain,
+            }) => {
+                let object = match &object.kind {
+                    ExprKind::Num(_) => self.print_expr(object, ATOM + 1),
+                    _ => self.print_expr(object, POSTFIX),
+                };
+                let mut parts = vec![object, text(if *opt_chain { "?.[" } else { "[" })];
+                if let Some(start) = start {
+                    parts.push(self.print_expr(start, 0));
+                }
+                parts.push(text(":"));
+                if let Some(end) = end {
+                    parts.push(self.print_expr(end, 0));
+                }
+                parts.push(text("]"));
+                concat(parts)
+            }
+            ExprKind::Range(Range { start, end }) => concat(vec![
+                self.print_expr(start, RANGE),
+                text(".."),
+                self.print_expr(end, RANGE + 1),
+            ]),
+            // Pipelines are right associative.
+            ExprKind::Pipeline(Pipeline { left, right }) => concat(vec![
+                self.print_expr(left, PIPELINE + 1),
+                text(" |> "),
+                self.print_expr(right, PIPELINE),
+            ]),
+            ExprKind::IfElse(if_else) => self.print_if_else(if_else),
+            ExprKind::Match(Match { expr, arms }) => {
+                let arms = arms
+                    .iter()
+                    .map(|arm| {
+                        let mut docs = vec![self.print_pattern(&arm.pattern)];
+                        if let Some(guard) = &arm.guard {
+                            docs.push(text(" if ("));
+                            docs.push(self.print_expr(guard, 0));
+                            docs.push(text(")"));
+                        }
+                        docs.push(text(" => "));
+                        match &arm.body {
+                            BlockOrExpr::Block(block) => docs.push(self.print_block(block)),
+                            // Object literals would be parsed as blocks.
+                            BlockOrExpr::Expr(expr) => match &expr.kind {
+                                ExprKind::Object(_) => docs.push(self.print_expr(expr, ATOM + 1)),
+                                _ => docs.push(self.print_expr(expr, 0)),
+                            },
+                        }
+                        docs.push(text(","));
+                        concat(docs)
+                    })
+                    .collect();
+
+                concat(vec![
+                    text("match ("),
+                    self.print_expr(expr, 0),
+                    text(") {"),
+                    indent(vec![Doc::HardLine, concat(join(arms, vec![Doc::HardLine]))]),
+                    Doc::HardLine,
+                    text("}"),
+                ])
+            }
+            ExprKind::Try(Try {
+                body,
+                catch,
+                finally,
+            }) => {
+                let mut docs = vec![text("try "), self.print_block(body)];
+                if let Some(CatchClause { param, body }) = catch {
+                    docs.push(text(" catch "));
+                    if let Some(param) = param {
+                        docs.push(text("("));
+                        docs.push(self.print_pattern(param));
+                        docs.push(text(") "));
+                    }
+                    docs.push(self.print_block(body));
+                }
+                if let Some(finally) = finally {
+                    docs.push(text(" finally "));
+                    docs.push(self.print_block(finally));
+                }
+                concat(docs)
+            }
+            ExprKind::Do(Do { body }) => concat(vec![text("do "), self.print_block(body)]),
+            ExprKind::Loop(Loop { label, body }) => concat(vec![
+                print_label(label),
+                text("loop "),
+                self.print_block(body),
+            ]),
+            ExprKind::Await(Await { arg, .. }) => {
+                concat(vec![text("await "), self.print_expr(arg, PREFIX)])
+            }
+            ExprKind::Yield(Yield { arg }) => {
+                concat(vec![text("yield "), self.print_expr(arg, ASSIGN)])
+            }
+            ExprKind::Throw(Throw { arg, .. }) => {
+                concat(vec![text("throw "), self.print_expr(arg, PREFIX)])
+            }
+            ExprKind::JSXElement(elem) => self.print_jsx_element(elem),
+            ExprKind::JSXFragment(frag) => self.print_jsx_fragment(frag),
+        }
+    }
+
+    fn print_if_else(&mut self, if_else: &IfElse) -> Doc {
+        let IfElse {
+            cond,
+            consequent,
+            alternate,
+        } = if_else;
+
+        let mut docs = vec![
+            text("if ("),
+            self.print_expr(cond, 0),
+            text(") "),
+            self.print_block(consequent),
+        ];
+        match alternate {
+            Some(BlockOrExpr::Block(block)) => {
+                docs.push(text(" else "));
+                docs.push(self.print_block(block));
+            }
+            Some(BlockOrExpr::Expr(expr)) => {
+                docs.push(text(" else "));
+                docs.push(self.print_expr(expr, 0));
+            }
+            None => (),
+        }
+        concat(docs)
+    }
+
+    fn print_args(&mut self, args: &[Expr]) -> Doc {
+        let items: Vec<Doc> = args.iter().map(|arg| self.print_expr(arg, 0)).collect();
+
+        // A trailing function or object "hugs" the parens instead of putting
+        // every argument on its own line, e.g.
+        // foo(a, fn () {
+        //     ...
+        // })
+        let hug = matches!(
+            args.last().map(|arg| &arg.kind),
+            Some(ExprKind::Function(Function {
+                body: BlockOrExpr::Block(_),
+                ..
+            })) | Some(ExprKind::Object(_))
+        );
+
+        let (last, rest) = match items.split_last() {
+            Some((last, rest)) if hug && !rest.iter().any(has_hard_line) => (last, rest),
+            _ => return comma_list("(", items, ")"),
+        };
+
+        let mut docs = vec![text("(")];
+        for item in rest {
+            docs.push(item.to_owned());
+            docs.push(text(", "));
+        }
+        docs.push(last.to_owned());
+        docs.push(text(")"));
+        concat(docs)
+    }
+
+    fn print_template(&mut self, template: &TemplateLiteral) -> Doc {
+        let mut docs = vec![text("`")];
+        for (i, part) in template.parts.iter().enumerate() {
+            let next = template
+                .parts
+                .get(i + 1)
+                .and_then(|_| template.exprs.get(i));
+            docs.push(text(escape_template_part(&part.value)));
+            if let Some(expr) = next {
+                docs.push(text("${"));
+                docs.push(self.print_expr(expr, 0));
+                docs.push(text("}"));
+            }
+        }
+        docs.push(text("`"));
+        concat(docs)
+    }
+}
+
+// Template literals don't support `\\` or `\$` escapes so we use unicode
+// escapes for those instead.
+fn escape_template_part(value: &str) -> String {
+    let mut result = String::new();
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '`' => result.push_str("\\`"),
+            '\\' => result.push_str("\\u005C"),
+            '$' if chars.peek() == Some(&'{') => result.push_str("\\u0024"),
+            '\u{0008}' => result.push_str("\\b"),
+            '\u{000c}' => result.push_str("\\f"),
+            '\r' => result.push_str("\\r"),
+            c => result.push(c),
+        }
+    }
+    result
+}
@@ -0,0 +1,76 @@
+use escalier_ast::*;
+
+use crate::doc::*;
+use crate::printer::Printer;
+
+impl<'a> Printer<'a> {
+    pub fn print_jsx_element(&mut self, elem: &JSXElement) -> Doc {
+        let name = print_jsx_element_name(&elem.opening.name);
+
+        let mut docs = vec![text(format!("<{name}"))];
+        for attr in &elem.opening.attrs {
+            docs.push(text(format!(" {}", attr.name)));
+            match &attr.value {
+                Some(JSXAttrValue::Str(value)) => docs.push(text(format!("=\"{value}\""))),
+                Some(JSXAttrValue::ExprContainer(JSXExprContainer { expr })) => {
+                    docs.push(text("={"));
+                    docs.push(self.print_expr(expr, 0));
+                    docs.push(text("}"));
+                }
+                None => (),
+            }
+        }
+
+        if elem.opening.self_closing {
+            docs.push(text(" />"));
+            return concat(docs);
+        }
+
+        docs.push(text(">"));
+        docs.push(self.print_jsx_children(&elem.children));
+        docs.push(text(format!("</{name}>")));
+        concat(docs)
+    }
+
+    pub fn print_jsx_fragment(&mut self, frag: &JSXFragment) -> Doc {
+        concat(vec![
+            text("<>"),
+            self.print_jsx_children(&frag.children),
+            text("</>"),
+        ])
+    }
+
+    // Whitespace in JSX text is significant so we print children as is.
+    fn print_jsx_children(&mut self, children: &[JSXElementChild]) -> Doc {
+        let docs = children
+            .iter()
+            .map(|child| match child {
+                JSXElementChild::Text(JSXText { value, .. }) => text(value),
+                JSXElementChild::ExprContainer(JSXExprContainer { expr }) => {
+                    concat(vec![text("{"), self.print_expr(expr, 0), text("}")])
+                }
+                JSXElementChild::SpreadChild(JSXSpreadChild { expr }) => {
+                    concat(vec![text("{..."), self.print_expr(expr, 0), text("}")])
+                }
+                JSXElementChild::Element(elem) => self.print_jsx_element(elem),
+                JSXElementChild::Fragment(frag) => self.print_jsx_fragment(frag),
+            })
+            .collect();
+        concat(docs)
+    }
+}
+
+fn print_jsx_element_name(name: &JSXElementName) -> String {
+    match name {
+        JSXElementName::Ident(Ident { name, .. }) => name.to_owned(),
+        JSXElementName::JSXMemberExpr(member) => print_jsx_member_expr(member),
+    }
+}
+
+fn print_jsx_member_expr(member: &JSXMemberExpr) -> String {
+    let obj = match &member.obj {
+        JSXObject::JSXMemberExpr(member) => print_jsx_member_expr(member),
+        JSXObject::Ident(Ident { name, .. }) => name.to_owned(),
+    };
+    format!("{obj}.{}", member.prop.name)
+}
@@ -0,0 +1,55 @@
+mod doc;
+mod expr;
+mod jsx;
+mod pattern;
+mod printer;
+mod type_ann;
+
+use escalier_ast::Script;
+use escalier_parser::{ParseError, Parser};
+
+use crate::doc::render;
+use crate::printer::Printer;
+
+/// Formats an Escalier script.  Comments are preserved and formatting the
+/// output again doesn't change it.
+pub fn format_script(src: &str) -> Result<String, ParseError> {
+    let mut parser = Parser::new(src);
+    let script = parser.parse_script()?;
+
+    let mut printer = Printer::new(src, parser.comments);
+    let doc = printer.print_script(&script);
+
+    Ok(finish(render(&doc)))
+}
+
+/// Formats an Escalier module, see `format_script`.
+pub fn format_module(src: &str) -> Result<String, ParseError> {
+    let mut parser = Parser::new(src);
+    let module = parser.parse_module()?;
+
+    let mut printer = Printer::new(src, parser.comments);
+    let doc = printer.print_module(&module);
+
+    Ok(finish(render(&doc)))
+}
+
+/// Prints a script that wasn't parsed from source, e.g. declarations that were
+/// generated from a .d.ts file.  Statements are separated by blank lines.
+pub fn print_script(script: &Script) -> String {
+    let mut printer = Printer::new("", vec![]);
+    let stmts = script
+        .stmts
+        .iter()
+        .map(|stmt| render(&printer.print_stmt(stmt)))
+        .collect::<Vec<_>>();
+
+    finish(stmts.join("\n\n"))
+}
+
+fn finish(output: String) -> String {
+    match output.is_empty() {
+        true => output,
+        false => format!("{output}\n"),
+    }
+}
@@ -0,0 +1,91 @@
+use escalier_ast::*;
+
+use crate::doc::*;
+use crate::printer::{quote, Printer};
+
+impl<'a> Printer<'a> {
+    pub fn print_pattern(&mut self, pattern: &Pattern) -> Doc {
+        match &pattern.kind {
+            PatternKind::Ident(binding) => print_binding(binding),
+            PatternKind::Rest(RestPat { arg }) => {
+                concat(vec![text("..."), self.print_pattern(arg)])
+            }
+            PatternKind::Object(ObjectPat { props, .. })
+            | PatternKind::Struct(StructPat { props, .. }) => {
+                let items = props
+                    .iter()
+                    .map(|prop| match prop {
+                        ObjectPatProp::KeyValue(KeyValuePatProp {
+                            key, value, init, ..
+                        }) => {
+                            let mut docs = vec![text(format!("{}: ", key.name))];
+                            docs.push(self.print_pattern(value));
+                            docs.push(self.print_init(init));
+                            concat(docs)
+                        }
+                        ObjectPatProp::Shorthand(ShorthandPatProp { ident, init, .. }) => {
+                            concat(vec![print_binding(ident), self.print_init(init)])
+                        }
+                        ObjectPatProp::Rest(RestPat { arg }) => {
+                            concat(vec![text("..."), self.print_pattern(arg)])
+                        }
+                    })
+                    .collect();
+                let obj = comma_list("{", items, "}");
+                match &pattern.kind {
+                    PatternKind::Struct(StructPat { name, .. }) => {
+                        concat(vec![text(format!("{} ", name.name)), obj])
+                    }
+                    _ => obj,
+                }
+            }
+            PatternKind::Tuple(TuplePat { elems, .. }) => {
+                let items = elems
+                    .iter()
+                    .map(|elem| match elem {
+                        Some(TuplePatElem { pattern, init }) => {
+                            concat(vec![self.print_pattern(pattern), self.print_init(init)])
+                        }
+                        None => concat(vec![]),
+                    })
+                    .collect();
+                comma_list("[", items, "]")
+            }
+            PatternKind::Lit(LitPat { lit }) => match lit {
+                Literal::String(value) => text(quote(value)),
+                lit => text(lit.to_string()),
+            },
+            PatternKind::Is(IsPat { ident, is_id }) => concat(vec![
+                print_binding(ident),
+                text(format!(" is {}", is_id.name)),
+            ]),
+            PatternKind::Enum(EnumPat {
+                enum_name,
+                variant,
+                args,
+            }) => {
+                let mut docs = vec![text(format!("{}.{}", enum_name.name, variant.name))];
+                if !args.is_empty() {
+                    let items = args.iter().map(|arg| self.print_pattern(arg)).collect();
+                    docs.push(comma_list("(", items, ")"));
+                }
+                concat(docs)
+            }
+            PatternKind::Wildcard => text("_"),
+        }
+    }
+
+    fn print_init(&mut self, init: &Option<Box<Expr>>) -> Doc {
+        match init {
+            Some(init) => concat(vec![text(" = "), self.print_expr(init, 0)]),
+            None => concat(vec![]),
+        }
+    }
+}
+
+fn print_binding(binding: &BindingIdent) -> Doc {
+    match binding.mutable {
+        true => text(format!("mut {}", binding.name)),
+        false => text(&binding.name),
+    }
+}
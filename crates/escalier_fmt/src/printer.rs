@@ -0,0 +1,720 @@
+use escalier_ast::*;
+
+use crate::doc::*;
+use crate::type_ann::PREFIX;
+
+pub struct Printer<'a> {
+    src: &'a str,
+    // Comments that aren't attached to AST nodes, sorted by position.
+    comments: Vec<Comment>,
+    next_comment: usize,
+}
+
+impl<'a> Printer<'a> {
+    pub fn new(src: &'a str, mut comments: Vec<Comment>) -> Self {
+        comments.sort_by_key(|comment| comment.span.start);
+        Self {
+            src,
+            comments,
+            next_comment: 0,
+        }
+    }
+
+    pub fn print_script(&mut self, script: &Script) -> Doc {
+        let docs = self.print_list(
+            &script.stmts,
+            self.src.len(),
+            get_stmt_start,
+            Self::print_stmt,
+        );
+        concat(docs)
+    }
+
+    pub fn print_module(&mut self, module: &Module) -> Doc {
+        let docs = self.print_list(
+            &module.items,
+            self.src.len(),
+            |item| match &item.kind {
+                ModuleItemKind::Export(Export { decl }) | ModuleItemKind::Decl(decl) => {
+                    get_start(&decl.comments, item.span.start)
+                }
+                ModuleItemKind::Import(_) => item.span.start,
+            },
+            Self::print_module_item,
+        );
+        concat(docs)
+    }
+
+    // Prints `items` on separate lines along with any unattached comments that
+    // appear before `end`.  Comments are only allowed between statements and
+    // members so we can output them in source order as we go.
+    fn print_list<T>(
+        &mut self,
+        items: &[T],
+        end: usize,
+        get_start: fn(&T) -> usize,
+        print: fn(&mut Self, &T) -> Doc,
+    ) -> Vec<Doc> {
+        let mut docs = vec![];
+
+        for item in items {
+            let start = get_start(item);
+            self.print_comments(start, &mut docs);
+            if !docs.is_empty() {
+                self.print_line_break(start, &mut docs);
+            }
+            docs.push(print(self, item));
+        }
+        self.print_comments(end, &mut docs);
+
+        docs
+    }
+
+    fn print_comments(&mut self, end: usize, docs: &mut Vec<Doc>) {
+        while let Some(comment) = self.comments.get(self.next_comment) {
+            let start = comment.span.start;
+            if start >= end {
+                break;
+            }
+            let comment_text = print_comment(comment);
+
+            // Comments that follow code on the same line stay there.
+            if docs.is_empty() {
+                docs.push(comment_text);
+            } else if self.get_line_prefix(start).trim().is_empty() {
+                self.print_line_break(start, docs);
+                docs.push(comment_text);
+            } else {
+                docs.push(text(" "));
+                docs.push(comment_text);
+            }
+
+            self.next_comment += 1;
+        }
+    }
+
+    // Takes the unattached comments that appear before `end`.
+    pub fn take_comments(&mut self, end: usize) -> Vec<Comment> {
+        let mut comments = vec![];
+        while let Some(comment) = self.comments.get(self.next_comment) {
+            if comment.span.start >= end {
+                break;
+            }
+            comments.push(comment.to_owned());
+            self.next_comment += 1;
+        }
+        comments
+    }
+
+    /// Prints the unattached comments before `end` that appear after the
+    /// last item in a list.
+    pub fn print_tail_comments(&mut self, end: usize) -> Vec<Doc> {
+        self.take_comments(end).iter().map(print_comment).collect()
+    }
+
+    /// Prints `doc` with its leading comments on the lines before it and its
+    /// trailing comments after it.
+    pub fn print_with_comments(&self, comments: &Comments, doc: Doc) -> Doc {
+        let mut docs = self.print_leading_comments(&comments.leading);
+        docs.push(doc);
+        for comment in &comments.trailing {
+            docs.push(Doc::LineSuffix(match comment.kind {
+                CommentKind::Line => format!(" //{}", comment.text.trim_end()),
+                CommentKind::Block => format!(" /*{}*/", comment.text),
+            }));
+        }
+        concat(docs)
+    }
+
+    pub fn print_leading_comments(&self, comments: &[Comment]) -> Vec<Doc> {
+        let mut docs = vec![];
+        for comment in comments {
+            docs.push(print_comment(comment));
+            docs.push(Doc::HardLine);
+
+            // A blank line after the comment is preserved.
+            let mut lines = self.src[comment.span.end..].split('\n').skip(1);
+            if lines.next().map_or(false, |line| line.trim().is_empty()) {
+                docs.push(Doc::HardLine);
+            }
+        }
+        docs
+    }
+
+    // Blank lines between statements are preserved, but collapsed into one.
+    // We only look at the lines before `start` because the parser doesn't
+    // always compute the end of spans correctly.
+    fn print_line_break(&self, start: usize, docs: &mut Vec<Doc>) {
+        docs.push(Doc::HardLine);
+
+        let line_start = start - self.get_line_prefix(start).len();
+        if line_start > 0 && self.get_line_prefix(line_start - 1).trim().is_empty() {
+            docs.push(Doc::HardLine);
+        }
+    }
+
+    // Returns the text between the start of the line and `offset`.
+    fn get_line_prefix(&self, offset: usize) -> &str {
+        let line_start = self.src[..offset].rfind('\n').map_or(0, |index| index + 1);
+        &self.src[line_start..offset]
+    }
+
+    pub fn print_block(&mut self, block: &Block) -> Doc {
+        let docs = self.print_list(
+            &block.stmts,
+            block.span.end,
+            get_stmt_start,
+            Self::print_stmt,
+        );
+        if docs.is_empty() {
+            return text("{}");
+        }
+        concat(vec![
+            text("{"),
+            indent(vec![Doc::HardLine, concat(docs)]),
+            Doc::HardLine,
+            text("}"),
+        ])
+    }
+
+    fn print_module_item(&mut self, item: &ModuleItem) -> Doc {
+        match &item.kind {
+            ModuleItemKind::Import(Import { specifiers, source }) => {
+                let specifiers = specifiers
+                    .iter()
+                    .map(|specifier| match &specifier.imported {
+                        Some(imported) => text(format!("{imported} as {}", specifier.local)),
+                        None => text(&specifier.local),
+                    })
+                    .collect();
+                concat(vec![
+                    text("import "),
+                    comma_list("{", specifiers, "}"),
+                    text(format!(" from {}", quote(source))),
+                ])
+            }
+            ModuleItemKind::Export(Export { decl }) => {
+                let doc = concat(vec![text("export "), self.print_decl(decl)]);
+                self.print_with_comments(&decl.comments, doc)
+            }
+            ModuleItemKind::Decl(decl) => {
+                let doc = self.print_decl(decl);
+                self.print_with_comments(&decl.comments, doc)
+            }
+        }
+    }
+
+    pub fn print_stmt(&mut self, stmt: &Stmt) -> Doc {
+        match &stmt.kind {
+            StmtKind::Expr(ExprStmt { expr }) => self.print_expr(expr, 0),
+            StmtKind::For(ForStmt {
+                label,
+                left,
+                right,
+                body,
+            }) => concat(vec![
+                print_label(label),
+                text("for ("),
+                self.print_pattern(left),
+                text(" in "),
+                self.print_expr(right, 0),
+                text(") "),
+                self.print_block(body),
+            ]),
+            StmtKind::While(WhileStmt { label, test, body }) => concat(vec![
+                print_label(label),
+                text("while ("),
+                self.print_expr(test, 0),
+                text(") "),
+                self.print_block(body),
+            ]),
+            StmtKind::Break(BreakStmt { label, arg }) => {
+                let mut docs = vec![text("break")];
+                if let Some(label) = label {
+                    docs.push(text(format!(" {label}")));
+                }
+                if let Some(arg) = arg {
+                    docs.push(text(" "));
+                    docs.push(self.print_expr(arg, 0));
+                }
+                concat(docs)
+            }
+            StmtKind::Continue(ContinueStmt { label }) => match label {
+                Some(label) => text(format!("continue {label}")),
+                None => text("continue"),
+            },
+            StmtKind::Return(ReturnStmt { arg }) => match arg {
+                Some(arg) => concat(vec![text("return "), self.print_expr(arg, 0)]),
+                None => text("return"),
+            },
+            StmtKind::Decl(decl) => {
+                let doc = self.print_decl(decl);
+                self.print_with_comments(&decl.comments, doc)
+            }
+        }
+    }
+
+    fn print_decl(&mut self, decl: &Decl) -> Doc {
+        match &decl.kind {
+            DeclKind::VarDecl(VarDecl {
+                is_declare,
+                is_var,
+                pattern,
+                expr,
+                type_ann,
+            }) => {
+                let mut docs = vec![];
+                if *is_declare {
+                    docs.push(text("declare "));
+                }
+                docs.push(text(if *is_var { "var " } else { "let " }));
+                docs.push(self.print_pattern(pattern));
+                if let Some(type_ann) = type_ann {
+                    docs.push(text(": "));
+                    docs.push(self.print_type_ann(type_ann, 0));
+                }
+                if let Some(expr) = expr {
+                    docs.push(text(" = "));
+                    docs.push(self.print_expr(expr, 0));
+                }
+                concat(docs)
+            }
+            DeclKind::TypeDecl(TypeDecl {
+                name,
+                type_ann,
+                type_params,
+            }) => concat(vec![
+                text(format!("type {name}")),
+                self.print_type_params(type_params),
+                text(" = "),
+                self.print_type_ann(type_ann, 0),
+            ]),
+            DeclKind::EnumDecl(EnumDecl {
+                name,
+                type_params,
+                variants,
+            }) => {
+                let items = variants
+                    .iter()
+                    .map(|variant| {
+                        let mut docs = vec![text(&variant.name)];
+                        if !variant.fields.is_empty() {
+                            let fields = variant
+                                .fields
+                                .iter()
+                                .map(|field| self.print_type_ann(field, 0))
+                                .collect();
+                            docs.push(comma_list("(", fields, ")"));
+                        }
+                        concat(docs)
+                    })
+                    .collect();
+                concat(vec![
+                    text(format!("enum {name}")),
+                    self.print_type_params(type_params),
+                    text(" "),
+                    comma_list("{", items, "}"),
+                ])
+            }
+            DeclKind::StructDecl(StructDecl {
+                name,
+                type_params,
+                fields,
+            }) => {
+                let items = fields
+                    .iter()
+                    .map(|field| {
+                        let mut docs = vec![];
+                        if field.is_public {
+                            docs.push(text("pub "));
+                        }
+                        docs.push(text(format!("{}: ", field.name)));
+                        docs.push(self.print_type_ann(&field.type_ann, 0));
+                        concat(docs)
+                    })
+                    .collect();
+                concat(vec![
+                    text(format!("struct {name}")),
+                    self.print_type_params(type_params),
+                    text(" "),
+                    comma_list("{", items, "}"),
+                ])
+            }
+            DeclKind::TraitDecl(TraitDecl { name, methods }) => {
+                let members = methods
+                    .iter()
+                    .map(|method| {
+                        let doc = self.print_trait_method(method);
+                        self.print_with_comments(&method.comments, doc)
+                    })
+                    .collect();
+                concat(vec![text(format!("trait {name} ")), print_members(members)])
+            }
+            DeclKind::ImplDecl(ImplDecl {
+                trait_name,
+                type_name,
+                methods,
+            }) => {
+                let members = methods
+                    .iter()
+                    .map(|method| {
+                        let name = self.print_prop_name(&method.name);
+                        let self_param = match method.is_mutating {
+                            true => "mut self",
+                            false => "self",
+                        };
+                        let doc =
+                            self.print_function(&method.function, Some(name), Some(self_param));
+                        self.print_with_comments(&method.comments, doc)
+                    })
+                    .collect();
+                concat(vec![
+                    text(format!("impl {} for {} ", trait_name.name, type_name.name)),
+                    print_members(members),
+                ])
+            }
+        }
+    }
+
+    /// Prints `fn name<T>(self, params) -> R` followed by the method's default
+    /// implementation if it has one.
+    fn print_trait_method(&mut self, method: &TraitMethod) -> Doc {
+        let self_param = match method.is_mutating {
+            true => "mut self",
+            false => "self",
+        };
+        let mut docs = vec![
+            text(format!("fn {}", method.name.name)),
+            self.print_type_params(&method.type_params),
+            self.print_params(&method.params, Some(self_param)),
+            text(" -> "),
+            self.print_type_ann(&method.type_ann, 0),
+        ];
+        match &method.body {
+            Some(BlockOrExpr::Block(block)) => {
+                docs.push(text(" "));
+                docs.push(self.print_block(block));
+            }
+            Some(BlockOrExpr::Expr(expr)) => {
+                docs.push(text(" => "));
+                docs.push(self.print_expr(expr, 0));
+            }
+            None => (),
+        }
+        concat(docs)
+    }
+
+    pub fn print_type_params(&mut self, type_params: &Option<Vec<TypeParam>>) -> Doc {
+        let type_params = match type_params {
+            Some(type_params) => type_params,
+            None => return concat(vec![]),
+        };
+
+        let items = type_params
+            .iter()
+            .map(|type_param| {
+                let mut docs = vec![text(&type_param.name)];
+                if let Some(bound) = &type_param.bound {
+                    docs.push(text(": "));
+                    docs.push(self.print_type_ann(bound, 0));
+                }
+                if let Some(default) = &type_param.default {
+                    docs.push(text(" = "));
+                    docs.push(self.print_type_ann(default, 0));
+                }
+                concat(docs)
+            })
+            .collect();
+
+        comma_list("<", items, ">")
+    }
+
+    pub fn print_type_args(&mut self, type_args: &Option<Vec<TypeAnn>>) -> Doc {
+        match type_args {
+            Some(type_args) => {
+                let items = type_args
+                    .iter()
+                    .map(|type_arg| self.print_type_ann(type_arg, 0))
+                    .collect();
+                comma_list("<", items, ">")
+            }
+            None => concat(vec![]),
+        }
+    }
+
+    pub fn print_class(&mut self, class: &Class) -> Doc {
+        let mut docs = vec![text("class")];
+        docs.push(self.print_type_params(&class.type_params));
+        if let Some(super_class) = &class.super_class {
+            docs.push(text(format!(" extends {}", super_class.name)));
+            docs.push(self.print_type_args(&class.super_type_args));
+        }
+
+        let mut members: Vec<Doc> = class
+            .body
+            .iter()
+            .map(|member| {
+                let doc = self.print_class_member(member);
+                self.print_with_comments(member.comments(), doc)
+            })
+            .collect();
+        members.extend(self.print_tail_comments(class.span.end));
+
+        if members.is_empty() {
+            docs.push(text(" {}"));
+            return concat(docs);
+        }
+
+        docs.push(text(" {"));
+        docs.push(indent(vec![
+            Doc::HardLine,
+            concat(join(members, vec![Doc::HardLine])),
+        ]));
+        docs.push(Doc::HardLine);
+        docs.push(text("}"));
+        concat(docs)
+    }
+
+    fn print_class_member(&mut self, member: &ClassMember) -> Doc {
+        let is_public = match member {
+            ClassMember::Method(Method { is_public, .. })
+            | ClassMember::Getter(Getter { is_public, .. })
+            | ClassMember::Setter(Setter { is_public, .. })
+            | ClassMember::Field(Field { is_public, .. }) => *is_public,
+        };
+        let is_static = match member {
+            ClassMember::Method(Method { is_static, .. })
+            | ClassMember::Field(Field { is_static, .. }) => *is_static,
+            ClassMember::Getter(_) | ClassMember::Setter(_) => false,
+        };
+
+        let mut docs = vec![];
+        if is_public {
+            docs.push(text("pub "));
+        }
+        if is_static {
+            docs.push(text("static "));
+        }
+
+        match member {
+            ClassMember::Method(Method {
+                name,
+                is_mutating,
+                is_static,
+                function,
+                ..
+            }) => {
+                let name = self.print_prop_name(name);
+                let self_param = match (is_static, is_mutating) {
+                    (true, _) => None,
+                    (false, true) => Some("mut self"),
+                    (false, false) => Some("self"),
+                };
+                docs.push(self.print_function(function, Some(name), self_param));
+            }
+            ClassMember::Getter(Getter {
+                name, params, body, ..
+            }) => {
+                docs.push(text("get "));
+                docs.push(self.print_prop_name(name));
+                docs.push(self.print_params(params, None));
+                docs.push(text(" "));
+                docs.push(self.print_block(body));
+            }
+            ClassMember::Setter(Setter {
+                name, params, body, ..
+            }) => {
+                docs.push(text("set "));
+                docs.push(self.print_prop_name(name));
+                docs.push(self.print_params(params, None));
+                docs.push(text(" "));
+                docs.push(self.print_block(body));
+            }
+            ClassMember::Field(Field {
+                name,
+                type_ann,
+                init,
+                ..
+            }) => {
+                docs.push(text(&name.name));
+                if let Some(type_ann) = type_ann {
+                    docs.push(text(": "));
+                    docs.push(self.print_type_ann(type_ann, 0));
+                }
+                if let Some(init) = init {
+                    docs.push(text(" = "));
+                    docs.push(self.print_expr(init, 0));
+                }
+            }
+        }
+
+        concat(docs)
+    }
+
+    fn print_prop_name(&mut self, name: &PropName) -> Doc {
+        match name {
+            PropName::Ident(ident) => text(&ident.name),
+            PropName::Computed(expr) => {
+                concat(vec![text("["), self.print_expr(expr, 0), text("]")])
+            }
+        }
+    }
+
+    /// Prints `[async ][gen ]fn[ name]<T>(params) -> R throws E` followed by
+    /// the function's body.
+    pub fn print_function(
+        &mut self,
+        function: &Function,
+        name: Option<Doc>,
+        self_param: Option<&str>,
+    ) -> Doc {
+        let mut docs = vec![];
+        if function.is_async {
+            docs.push(text("async "));
+        }
+        if function.is_gen {
+            docs.push(text("gen "));
+        }
+        match name {
+            Some(name) => {
+                docs.push(text("fn "));
+                docs.push(name);
+            }
+            None => docs.push(text("fn ")),
+        }
+        docs.push(self.print_type_params(&function.type_params));
+        docs.push(self.print_params(&function.params, self_param));
+        if let Some(type_ann) = &function.type_ann {
+            // A function type in the return position would take the `throws`.
+            let min_prec = if function.throws.is_some() {
+                PREFIX + 1
+            } else {
+                0
+            };
+            docs.push(text(" -> "));
+            docs.push(self.print_type_ann(type_ann, min_prec));
+        }
+        if let Some(throws) = &function.throws {
+            docs.push(text(" throws "));
+            docs.push(self.print_type_ann(throws, 0));
+        }
+        match &function.body {
+            BlockOrExpr::Block(block) => {
+                docs.push(text(" "));
+                docs.push(self.print_block(block));
+            }
+            BlockOrExpr::Expr(expr) => {
+                docs.push(text(" => "));
+                docs.push(self.print_expr(expr, 0));
+            }
+        }
+        concat(docs)
+    }
+
+    fn print_params(&mut self, params: &[FuncParam], self_param: Option<&str>) -> Doc {
+        let mut items = vec![];
+        if let Some(self_param) = self_param {
+            items.push(text(self_param));
+        }
+        for param in params {
+            let mut docs = vec![self.print_pattern(&param.pattern)];
+            if let Some(type_ann) = &param.type_ann {
+                if param.optional {
+                    docs.push(text("?"));
+                }
+                docs.push(text(": "));
+                docs.push(self.print_type_ann(type_ann, 0));
+            }
+            items.push(concat(docs));
+        }
+        comma_list("(", items, ")")
+    }
+}
+
+// Prints the members of a trait or impl on separate lines.
+fn print_members(members: Vec<Doc>) -> Doc {
+    if members.is_empty() {
+        return text("{}");
+    }
+    concat(vec![
+        text("{"),
+        indent(vec![
+            Doc::HardLine,
+            concat(join(members, vec![Doc::HardLine])),
+        ]),
+        Doc::HardLine,
+        text("}"),
+    ])
+}
+
+fn get_start(comments: &Comments, start: usize) -> usize {
+    comments
+        .leading
+        .first()
+        .map_or(start, |comment| comment.span.start)
+}
+
+pub fn print_label(label: &Option<String>) -> Doc {
+    match label {
+        Some(label) => text(format!("{label}: ")),
+        None => text(""),
+    }
+}
+
+fn get_stmt_start(stmt: &Stmt) -> usize {
+    match &stmt.kind {
+        StmtKind::Decl(decl) => get_start(&decl.comments, stmt.span.start),
+        _ => stmt.span.start,
+    }
+}
+
+pub fn print_comment(comment: &Comment) -> Doc {
+    let Comment {
+        kind, text: value, ..
+    } = comment;
+    if *kind == CommentKind::Line {
+        return text(format!("//{}", value.trim_end()));
+    }
+
+    // JSDoc style comments are re-indented, other block comments are printed
+    // as is.
+    let lines: Vec<&str> = value.split('\n').collect();
+    match lines.split_first() {
+        Some((first, rest)) if !rest.is_empty() => match rest.split_last() {
+            Some((last, middle))
+                if last.trim().is_empty()
+                    && middle.iter().all(|line| line.trim_start().starts_with('*')) =>
+            {
+                let mut docs = vec![text(format!("/*{}", first.trim_end()))];
+                for line in middle {
+                    docs.push(Doc::HardLine);
+                    docs.push(text(format!(" {}", line.trim())));
+                }
+                docs.push(Doc::HardLine);
+                docs.push(text(" */"));
+                concat(docs)
+            }
+            _ => text(format!("/*{value}*/")),
+        },
+        _ => text(format!("/*{value}*/")),
+    }
+}
+
+/// Quotes and escapes `value` so that it can be parsed as a string literal.
+pub fn quote(value: &str) -> String {
+    let mut result = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\u{0008}' => result.push_str("\\b"),
+            '\u{000c}' => result.push_str("\\f"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
@@ -0,0 +1,307 @@
+use escalier_ast::*;
+
+use crate::doc::*;
+use crate::expr::print_binary_op;
+use crate::printer::{quote, Printer};
+
+const ATOM: u32 = 20;
+const POSTFIX: u32 = 17;
+const INTERSECTION: u32 = 4;
+const UNION: u32 = 3;
+// `fn` and `keyof` types extend as far to the right as possible.
+pub const PREFIX: u32 = 1;
+
+fn get_precedence(type_ann: &TypeAnn) -> u32 {
+    match &type_ann.kind {
+        TypeAnnKind::Array(_) | TypeAnnKind::IndexedAccess(_, _) => POSTFIX,
+        TypeAnnKind::Binary(BinaryTypeAnn { op, .. }) => match op {
+            BinaryOp::Times | BinaryOp::Divide | BinaryOp::Modulo => 12,
+            _ => 11,
+        },
+        TypeAnnKind::Intersection(_) => INTERSECTION,
+        TypeAnnKind::Union(_) => UNION,
+        TypeAnnKind::Function(_) | TypeAnnKind::KeyOf(_) => PREFIX,
+        _ => ATOM,
+    }
+}
+
+impl<'a> Printer<'a> {
+    /// Prints `type_ann`, wrapping it in parens if its precedence is lower
+    /// than `min_prec`.
+    pub fn print_type_ann(&mut self, type_ann: &TypeAnn, min_prec: u32) -> Doc {
+        let doc = self.print_type_ann_kind(type_ann);
+        match get_precedence(type_ann) < min_prec {
+            true => concat(vec![text("("), doc, text(")")]),
+            false => doc,
+        }
+    }
+
+    fn print_type_ann_kind(&mut self, type_ann: &TypeAnn) -> Doc {
+        match &type_ann.kind {
+            TypeAnnKind::BoolLit(value) => text(value.to_string()),
+            TypeAnnKind::Boolean => text("boolean"),
+            TypeAnnKind::NumLit(value) => text(value),
+            TypeAnnKind::Number => text("number"),
+            TypeAnnKind::StrLit(value) => text(quote(value)),
+            TypeAnnKind::String => text("string"),
+            TypeAnnKind::Symbol => text("symbol"),
+            TypeAnnKind::Null => text("null"),
+            TypeAnnKind::Undefined => text("undefined"),
+            TypeAnnKind::Unknown => text("unknown"),
+            TypeAnnKind::Never => text("never"),
+            TypeAnnKind::Object(props) => {
+                let items = props
+                    .iter()
+                    .map(|prop| {
+                        let doc = self.print_object_prop(prop);
+                        match prop.comments() {
+                            Some(comments) => self.print_with_comments(comments, doc),
+                            None => doc,
+                        }
+                    })
+                    .collect();
+                let tail = self.print_tail_comments(type_ann.span.end);
+                comma_list_with_tail("{", items, tail, "}")
+            }
+            TypeAnnKind::Tuple(elems) => {
+                let items = elems
+                    .iter()
+                    .map(|elem| self.print_type_ann(elem, 0))
+                    .collect();
+                comma_list("[", items, "]")
+            }
+            TypeAnnKind::Array(elem) => {
+                concat(vec![self.print_type_ann(elem, POSTFIX), text("[]")])
+            }
+            TypeAnnKind::TypeRef(name, type_args) => {
+                concat(vec![text(name), self.print_type_args(type_args)])
+            }
+            TypeAnnKind::Function(function) => {
+                concat(vec![text("fn "), self.print_function_type(function)])
+            }
+            TypeAnnKind::Union(types) => {
+                let types = types
+                    .iter()
+                    .map(|t| self.print_type_ann(t, UNION + 1))
+                    .collect();
+                concat(join(types, vec![text(" | ")]))
+            }
+            TypeAnnKind::Intersection(types) => {
+                let types = types
+                    .iter()
+                    .map(|t| self.print_type_ann(t, INTERSECTION + 1))
+                    .collect();
+                concat(join(types, vec![text(" & ")]))
+            }
+            TypeAnnKind::IndexedAccess(obj, index) => concat(vec![
+                self.print_type_ann(obj, POSTFIX),
+                text("["),
+                self.print_type_ann(index, 0),
+                text("]"),
+            ]),
+            TypeAnnKind::KeyOf(t) => concat(vec![text("keyof "), self.print_type_ann(t, 0)]),
+            TypeAnnKind::Rest(t) => concat(vec![text("..."), self.print_type_ann(t, 0)]),
+            TypeAnnKind::TypeOf(Ident { name, .. }) => text(format!("typeof {name}")),
+            TypeAnnKind::Condition(condition) => self.print_condition_type(condition),
+            TypeAnnKind::Match(MatchType { matchable, cases }) => {
+                let cases = cases
+                    .iter()
+                    .map(|case| {
+                        concat(vec![
+                            self.print_type_ann(&case.extends, 0),
+                            text(" => "),
+                            self.print_type_ann(&case.true_type, 0),
+                            text(","),
+                        ])
+                    })
+                    .collect();
+                concat(vec![
+                    text("match ("),
+                    self.print_type_ann(matchable, 0),
+                    text(") {"),
+                    indent(vec![
+                        Doc::HardLine,
+                        concat(join(cases, vec![Doc::HardLine])),
+                    ]),
+                    Doc::HardLine,
+                    text("}"),
+                ])
+            }
+            TypeAnnKind::Wildcard => text("_"),
+            TypeAnnKind::Infer(name) => text(format!("infer {name}")),
+            TypeAnnKind::Binary(BinaryTypeAnn { left, op, right }) => {
+                let prec = get_precedence(type_ann);
+                concat(vec![
+                    self.print_type_ann(left, prec),
+                    text(format!(" {} ", print_binary_op(op))),
+                    self.print_type_ann(right, prec + 1),
+                ])
+            }
+        }
+    }
+
+    fn print_condition_type(&mut self, condition: &ConditionType) -> Doc {
+        let ConditionType {
+            check,
+            extends,
+            true_type,
+            false_type,
+        } = condition;
+
+        let mut docs = vec![
+            text("if ("),
+            self.print_type_ann(check, 0),
+            text(": "),
+            self.print_type_ann(extends, 0),
+            text(") { "),
+            self.print_type_ann(true_type, 0),
+            text(" } else "),
+        ];
+        match &false_type.kind {
+            TypeAnnKind::Condition(condition) => docs.push(self.print_condition_type(condition)),
+            _ => {
+                docs.push(text("{ "));
+                docs.push(self.print_type_ann(false_type, 0));
+                docs.push(text(" }"));
+            }
+        }
+        concat(docs)
+    }
+
+    fn print_object_prop(&mut self, prop: &ObjectProp) -> Doc {
+        match prop {
+            // Comments aren't attached to call and constructor signatures.
+            ObjectProp::Call(function) => {
+                let comments = self.take_comments(function.span.start);
+                let mut docs = self.print_leading_comments(&comments);
+                docs.push(text("fn "));
+                docs.push(self.print_function_type(function));
+                concat(docs)
+            }
+            ObjectProp::Constructor(function) => {
+                let comments = self.take_comments(function.span.start);
+                let mut docs = self.print_leading_comments(&comments);
+                docs.push(text("new fn "));
+                docs.push(self.print_function_type(function));
+                concat(docs)
+            }
+            ObjectProp::Method(MethodType {
+                name,
+                type_params,
+                params,
+                ret,
+                throws,
+                mutates,
+                ..
+            }) => {
+                let self_param = if *mutates { "mut self" } else { "self" };
+                concat(vec![
+                    text(format!("fn {name}")),
+                    self.print_type_params(type_params),
+                    self.print_type_ann_params(params, Some(self_param)),
+                    self.print_ret_and_throws(ret, throws),
+                ])
+            }
+            ObjectProp::Getter(GetterType { name, ret, .. }) => concat(vec![
+                text(format!("get {name}(self) -> ")),
+                self.print_type_ann(ret, 0),
+            ]),
+            ObjectProp::Setter(SetterType { name, param, .. }) => concat(vec![
+                text(format!("set {name}")),
+                self.print_type_ann_params(std::slice::from_ref(param), Some("mut self")),
+                text(" -> undefined"),
+            ]),
+            ObjectProp::Mapped(Mapped {
+                key,
+                value,
+                target,
+                source,
+                optional,
+                ..
+            }) => {
+                let optional = match optional {
+                    Some(MappedModifier::Add) => "+?",
+                    Some(MappedModifier::Remove) => "-?",
+                    None => "",
+                };
+                concat(vec![
+                    text("["),
+                    self.print_type_ann(key, 0),
+                    text(format!("]{optional}: ")),
+                    self.print_type_ann(value, 0),
+                    text(format!(" for {target} in ")),
+                    self.print_type_ann(source, 0),
+                ])
+            }
+            ObjectProp::Prop(type_ann::Prop {
+                name,
+                modifier,
+                optional,
+                type_ann,
+                ..
+            }) => {
+                let optional = if *optional { "?" } else { "" };
+                let mut docs = vec![text(format!("{name}{optional}: "))];
+                match (modifier, &type_ann.kind) {
+                    (Some(modifier), TypeAnnKind::Function(function)) => {
+                        docs.push(text(match modifier {
+                            PropModifier::Getter => "get ",
+                            PropModifier::Setter => "set ",
+                        }));
+                        docs.push(self.print_function_type(function));
+                    }
+                    _ => docs.push(self.print_type_ann(type_ann, 0)),
+                }
+                concat(docs)
+            }
+        }
+    }
+
+    // Prints everything in a function type after the `fn`.
+    fn print_function_type(&mut self, function: &FunctionType) -> Doc {
+        let FunctionType {
+            type_params,
+            params,
+            ret,
+            throws,
+            ..
+        } = function;
+
+        concat(vec![
+            self.print_type_params(type_params),
+            self.print_type_ann_params(params, None),
+            self.print_ret_and_throws(ret, throws),
+        ])
+    }
+
+    fn print_ret_and_throws(&mut self, ret: &TypeAnn, throws: &Option<Box<TypeAnn>>) -> Doc {
+        // A function type in the return position would take the `throws`.
+        let min_prec = if throws.is_some() { PREFIX + 1 } else { 0 };
+        let mut docs = vec![text(" -> "), self.print_type_ann(ret, min_prec)];
+        if let Some(throws) = throws {
+            docs.push(text(" throws "));
+            docs.push(self.print_type_ann(throws, 0));
+        }
+        concat(docs)
+    }
+
+    fn print_type_ann_params(
+        &mut self,
+        params: &[TypeAnnFuncParam],
+        self_param: Option<&str>,
+    ) -> Doc {
+        let mut items = vec![];
+        if let Some(self_param) = self_param {
+            items.push(text(self_param));
+        }
+        for param in params {
+            let optional = if param.optional { "?" } else { "" };
+            items.push(concat(vec![
+                self.print_pattern(&param.pattern),
+                text(format!("{optional}: ")),
+                self.print_type_ann(&param.type_ann, 0),
+            ]));
+        }
+        comma_list("(", items, ")")
+    }
+}
@@ -0,0 +1,663 @@
+use std::fs;
+
+use escalier_fmt::{format_module, format_script};
+use escalier_parser::Parser;
+
+// Spans change when code is formatted so we remove them before comparing.
+fn strip_spans(debug: &str) -> String {
+    let mut result = String::new();
+    let mut chars = debug.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c.is_ascii_digit() {
+            let mut token = String::from(c);
+            while let Some(c) = chars.next_if(|c| c.is_ascii_digit() || *c == '.') {
+                token.push(c);
+            }
+            match token.split_once("..") {
+                Some((start, end)) if !start.is_empty() && !end.is_empty() => (),
+                _ => result.push_str(&token),
+            }
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+fn parse_script(src: &str) -> String {
+    let mut parser = Parser::new(src);
+    strip_spans(&format!("{:#?}", parser.parse_script().unwrap()))
+}
+
+fn format(src: &str) -> String {
+    let output = format_script(src).unwrap();
+
+    assert_eq!(parse_script(src), parse_script(&output));
+    pretty_assertions::assert_eq!(format_script(&output).unwrap(), output);
+
+    output
+}
+
+#[test]
+fn format_fixtures() {
+    for entry in fs::read_dir("../escalier/tests/pass").unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().unwrap() != "esc" {
+            continue;
+        }
+        let src = fs::read_to_string(&path).unwrap();
+        format(&src);
+    }
+}
+
+#[test]
+fn format_decls() {
+    let src = r#"
+    let   x=5
+    var mut y : number=10
+    declare let foo: fn (a: number) -> string
+    type Point<T: number> = {x: T, y: T}
+    "#;
+
+    insta::assert_snapshot!(format(src), @r###"
+    let x = 5
+    var mut y: number = 10
+    declare let foo: fn (a: number) -> string
+    type Point<T: number> = {x: T, y: T}
+    "###);
+}
+
+#[test]
+fn format_operators() {
+    let src = r#"
+    let a = (b + c) * d - (e - f)
+    let g = a - b - c
+    let h = -(-x) + !y
+    let i = (a || b) && c == d
+    let j = await (a + b)
+    let k = (fn (x) => x)(5)
+    let l = (1).toString()
+    let m = (new Foo()).bar
+    "#;
+
+    insta::assert_snapshot!(format(src), @r###"
+    let a = (b + c) * d - (e - f)
+    let g = a - b - c
+    let h = -(-x) + !y
+    let i = (a || b) && c == d
+    let j = await (a + b)
+    let k = (fn (x) => x)(5)
+    let l = (1).toString()
+    let m = (new Foo()).bar
+    "###);
+}
+
+#[test]
+fn format_literals() {
+    let src = r#"
+    let str = "quote: \" backslash: \\ newline: \n tab: \t"
+    let template = `hello, ${name}!`
+    let escaped = `backtick: \` dollar: \u0024{`
+    let tagged = sql`SELECT * FROM ${table}`
+    let tuple = [1, ...rest]
+    let obj = {a, b: 1, "c": 2, 3: 4, [d]: 5, ...e}
+    "#;
+
+    insta::assert_snapshot!(format(src), @r###"
+    let str = "quote: \" backslash: \\ newline: \n tab: \t"
+    let template = `hello, ${name}!`
+    let escaped = `backtick: \` dollar: \u0024{`
+    let tagged = sql`SELECT * FROM ${table}`
+    let tuple = [1, ...rest]
+    let obj = {a, b: 1, "c": 2, 3: 4, [d]: 5, ...e}
+    "###);
+}
+
+#[test]
+fn format_long_lists() {
+    let src = r#"
+    let obj = {alpha: "alpha", bravo: "bravo", charlie: "charlie", delta: "delta", echo: "echo"}
+    let result = someFunction(firstArgument, secondArgument, thirdArgument, fourthArgument)
+    let nested = foo({alpha: "alpha", bravo: "bravo", charlie: "charlie", delta: "delta"})
+    "#;
+
+    insta::assert_snapshot!(format(src), @r###"
+    let obj = {
+        alpha: "alpha",
+        bravo: "bravo",
+        charlie: "charlie",
+        delta: "delta",
+        echo: "echo",
+    }
+    let result = someFunction(
+        firstArgument,
+        secondArgument,
+        thirdArgument,
+        fourthArgument,
+    )
+    let nested = foo({
+        alpha: "alpha",
+        bravo: "bravo",
+        charlie: "charlie",
+        delta: "delta",
+    })
+    "###);
+}
+
+#[test]
+fn format_functions() {
+    let src = r#"
+    let add = fn(a: number, b: number) -> number { return a + b }
+    let id = fn <T>(x: T) -> T => x
+    let fetch = async fn (url: string) -> Promise<string> throws Error {
+        let res = await request(url)
+        return res.text
+    }
+    let counter = gen fn () { yield 5 }
+    let opt = fn (a?: number) => a
+    let cb = items.map(fn (item) {
+        return item * 2
+    })
+    let ret = fn () -> (fn () -> number) throws string => fn () => 5
+    "#;
+
+    insta::assert_snapshot!(format(src), @r###"
+    let add = fn (a: number, b: number) -> number {
+        return a + b
+    }
+    let id = fn <T>(x: T) -> T => x
+    let fetch = async fn (url: string) -> Promise<string> throws Error {
+        let res = await request(url)
+        return res.text
+    }
+    let counter = gen fn () {
+        yield 5
+    }
+    let opt = fn (a?: number) => a
+    let cb = items.map(fn (item) {
+        return item * 2
+    })
+    let ret = fn () -> (fn () -> number) throws string => fn () => 5
+    "###);
+}
+
+#[test]
+fn format_control_flow() {
+    let src = r#"
+    let a = if (x > 0) { "positive" } else if (x < 0) { "negative" } else { "zero" }
+    let b = match (x) {
+        0 => "zero",
+        n if (n > 0) => { "positive" },
+        _ => ({a: 1})
+    }
+    let c = try { foo() } catch (e) { bar(e) } finally { baz() }
+    let d = do { let y = 5
+    y * 2 }
+    for (x in xs) { print(x) }
+    let e = fn () => throw (a + b)
+    "#;
+
+    insta::assert_snapshot!(format(src), @r###"
+    let a = if (x > 0) {
+        "positive"
+    } else if (x < 0) {
+        "negative"
+    } else {
+        "zero"
+    }
+    let b = match (x) {
+        0 => "zero",
+        n if (n > 0) => {
+            "positive"
+        },
+        _ => ({a: 1}),
+    }
+    let c = try {
+        foo()
+    } catch (e) {
+        bar(e)
+    } finally {
+        baz()
+    }
+    let d = do {
+        let y = 5
+        y * 2
+    }
+    for (x in xs) {
+        print(x)
+    }
+    let e = fn () => throw (a + b)
+    "###);
+}
+
+#[test]
+fn format_enums() {
+    let src = r#"
+    enum Option<T>{Some(T),None}
+    enum Shape {
+        Circle(number),
+        Rect(number,number),
+    }
+    let area = match (shape) {
+        Shape.Circle(r) => r*r,
+        Shape.Rect(w,h) => w*h
+    }
+    "#;
+
+    insta::assert_snapshot!(format(src), @r###"
+    enum Option<T> {Some(T), None}
+    enum Shape {Circle(number), Rect(number, number)}
+    let area = match (shape) {
+        Shape.Circle(r) => r * r,
+        Shape.Rect(w, h) => w * h,
+    }
+    "###);
+}
+
+#[test]
+fn format_structs() {
+    let src = r#"
+    struct User{pub name:string,ssn:number}
+    let user = User{name:"John Doe",ssn:123456789}
+    let name = match (user) {
+        User{name} => name
+    }
+    "#;
+
+    insta::assert_snapshot!(format(src), @r###"
+    struct User {pub name: string, ssn: number}
+    let user = User {name: "John Doe", ssn: 123456789}
+    let name = match (user) {
+        User {name} => name,
+    }
+    "###);
+}
+
+#[test]
+fn format_traits() {
+    let src = r#"
+    trait Shape {
+        fn area(self)->number
+        // Defaults to the area.
+        fn describe(self)->string{
+            return `area = ${self.area()}`
+        }
+    }
+    impl Shape for Circle{
+        fn area(self)->number{return 3.14 * self.radius * self.radius}
+    }
+    "#;
+
+    insta::assert_snapshot!(format(src), @r###"
+    trait Shape {
+        fn area(self) -> number
+        // Defaults to the area.
+        fn describe(self) -> string {
+            return `area = ${self.area()}`
+        }
+    }
+    impl Shape for Circle {
+        fn area(self) -> number {
+            return 3.14 * self.radius * self.radius
+        }
+    }
+    "###);
+}
+
+#[test]
+fn format_patterns() {
+    let src = r#"
+    let [a, _, ...rest] = tuple
+    let {x, z: {w}, mut y} = obj
+    let {a, ...others} = obj
+    let result = match (value) {
+        n is number => n,
+        "hello" => 0,
+        [first, second] => first,
+        {kind: "circle", radius} => radius,
+        _ => -1
+    }
+    "#;
+
+    insta::assert_snapshot!(format(src), @r###"
+    let [a, _, ...rest] = tuple
+    let {x, z: {w}, mut y} = obj
+    let {a, ...others} = obj
+    let result = match (value) {
+        n is number => n,
+        "hello" => 0,
+        [first, second] => first,
+        {kind: "circle", radius} => radius,
+        _ => -1,
+    }
+    "###);
+}
+
+#[test]
+fn format_type_anns() {
+    let src = r#"
+    type A = (string | number)[]
+    type B = (keyof T) | U
+    type C = fn (a: number) -> string | undefined
+    type D = (fn () -> number) | string
+    type E = T[K] & {a: number}
+    type F = [number, ...string[]]
+    type G = if (T: string) { "string" } else if (T: number) { "number" } else { "other" }
+    type H = match (T) { string => "string", _ => "other" }
+    type I = typeof foo
+    type J = if (T: Array<infer U>) { U } else { never }
+    type K = A + B * C
+    "#;
+
+    insta::assert_snapshot!(format(src), @r###"
+    type A = (string | number)[]
+    type B = (keyof T) | U
+    type C = fn (a: number) -> string | undefined
+    type D = (fn () -> number) | string
+    type E = T[K] & {a: number}
+    type F = [number, ...string[]]
+    type G = if (T: string) { "string" } else if (T: number) { "number" } else { "other" }
+    type H = match (T) {
+        string => "string",
+        _ => "other",
+    }
+    type I = typeof foo
+    type J = if (T: Array<infer U>) { U } else { never }
+    type K = A + B * C
+    "###);
+}
+
+#[test]
+fn format_object_types() {
+    let src = r#"
+    type Obj = {
+        a: number,
+        b?: string,
+        fn method<T>(mut self, x: T) -> T throws string,
+        fn (a: number) -> string,
+        get x(self) -> number,
+        set x(mut self, value: number) -> undefined,
+    }
+    type Mapped = {[K]+?: T[K] for K in keyof T}
+    "#;
+
+    insta::assert_snapshot!(format(src), @r###"
+    type Obj = {
+        a: number,
+        b?: string,
+        fn method<T>(mut self, x: T) -> T throws string,
+        fn (a: number) -> string,
+        get x(self) -> number,
+        set x(mut self, value: number) -> undefined,
+    }
+    type Mapped = {[K]+?: T[K] for K in keyof T}
+    "###);
+}
+
+#[test]
+fn format_classes() {
+    let src = r#"
+    let Foo = class<T> extends Bar {
+        pub x: number
+        static count = 0
+        pub fn getX(self) -> number { return self.x }
+        async fn update(mut self, x: number) { self.x = x }
+        static fn create<T>(x: T) { return new Foo(x) }
+        get value(self) { return self.x }
+        set value(mut self, x: number) { self.x = x }
+    }
+    let Empty = class {}
+    "#;
+
+    insta::assert_snapshot!(format(src), @r###"
+    let Foo = class<T> extends Bar {
+        pub x: number
+        static count = 0
+        pub fn getX(self) -> number {
+            return self.x
+        }
+        async fn update(mut self, x: number) {
+            self.x = x
+        }
+        static fn create<T>(x: T) {
+            return new Foo(x)
+        }
+        get value(self) {
+            return self.x
+        }
+        set value(mut self, x: number) {
+            self.x = x
+        }
+    }
+    let Empty = class {}
+    "###);
+}
+
+#[test]
+fn format_jsx() {
+    let src = r#"
+    let a = <div className="foo" id={id} hidden>Hello, {name}!</div>
+    let b = <Foo />
+    let c = <>
+        <span>one</span>
+        <span>two</span>
+    </>
+    "#;
+
+    insta::assert_snapshot!(format(src), @r###"
+    let a = <div className="foo" id={id} hidden>Hello, {name}!</div>
+    let b = <Foo />
+    let c = <>
+            <span>one</span>
+            <span>two</span>
+        </>
+    "###);
+}
+
+#[test]
+fn format_comments() {
+    let src = r#"
+    // leading comment
+    let x = 5 // trailing comment
+
+
+    // after blank lines
+    let foo = fn () {
+        // inside block
+        let y = x
+        return y // trailing in block
+        // end of block
+    }
+    let bar = fn () {
+        // only comment
+    }
+    // end of file
+    "#;
+
+    insta::assert_snapshot!(format(src), @r###"
+    // leading comment
+    let x = 5 // trailing comment
+
+    // after blank lines
+    let foo = fn () {
+        // inside block
+        let y = x
+        return y // trailing in block
+        // end of block
+    }
+    let bar = fn () {
+        // only comment
+    }
+    // end of file
+    "###);
+}
+
+#[test]
+fn format_member_comments() {
+    let src = r#"
+    let obj = {
+        // leading
+        a: 1, // trailing
+        /// doc
+        b,
+        // before spread
+        ...rest
+        // after last
+    }
+    let Foo = class {
+        /// The x-coord
+        x: number // trailing
+        // end of class
+    }
+    type Obj = {
+        /**
+           * The y-coord
+         */
+        y: number, // trailing
+        // call signature
+        fn (a: number) -> string,
+        // end of type
+    }
+    "#;
+
+    // Re-indenting block comments changes their text so we can't compare ASTs.
+    let output = format_script(src).unwrap();
+    assert_eq!(format_script(&output).unwrap(), output);
+
+    insta::assert_snapshot!(output, @r###"
+    let obj = {
+        // leading
+        a: 1, // trailing
+        /// doc
+        b,
+        // before spread
+        ...rest,
+        // after last
+    }
+    let Foo = class {
+        /// The x-coord
+        x: number // trailing
+        // end of class
+    }
+    type Obj = {
+        /**
+         * The y-coord
+         */
+        y: number, // trailing
+        // call signature
+        fn (a: number) -> string,
+        // end of type
+    }
+    "###);
+}
+
+#[test]
+fn format_modules() {
+    let src = r#"
+    import {a, b as c} from "./foo"
+    // exported
+    export let x = a + c
+    export type Point = {x: number, y: number}
+    let y = 5
+    "#;
+
+    let output = format_module(src).unwrap();
+    assert_eq!(format_module(&output).unwrap(), output);
+
+    insta::assert_snapshot!(output, @r###"
+    import {a, b as c} from "./foo"
+    // exported
+    export let x = a + c
+    export type Point = {x: number, y: number}
+    let y = 5
+    "###);
+}
+
+#[test]
+fn format_empty_script() {
+    assert_eq!(format_script("").unwrap(), "");
+    assert_eq!(format_script("\n\n").unwrap(), "");
+}
+
+#[test]
+fn format_parse_error() {
+    assert!(format_script("let x = {a: 1").is_err());
+}
+
+#[test]
+fn format_ranges_and_slices() {
+    let src = r#"
+    for (i in 0..n+1) {}
+    let r = (a..b)..c
+    let s = xs[1 : 3]
+    let t = xs?.[:n - 1]
+    let u = xs[:]
+    "#;
+
+    insta::assert_snapshot!(format(src), @r###"
+    for (i in 0..n + 1) {}
+    let r = a..b..c
+    let s = xs[1:3]
+    let t = xs?.[:n - 1]
+    let u = xs[:]
+    "###);
+}
+
+#[test]
+fn format_pipelines() {
+    let src = r#"
+    let result = 5|>double(_)|>show(_, 16)
+    let fallback = (a || b) |> _ || c
+    let grouped = (x |> f(_)) |> g(_)
+    "#;
+
+    insta::assert_snapshot!(format(src), @r###"
+    let result = 5 |> double(_) |> show(_, 16)
+    let fallback = (a || b) |> _ || c
+    let grouped = (x |> f(_)) |> g(_)
+    "###);
+}
+
+#[test]
+fn format_regex_literals() {
+    let src = r#"
+    let digits = /\d+/g
+    let color = /rgb\((?<r>\d+),(?<g>\d+),(?<b>\d+)\)/
+    let ratio = a / b / /[/\]]+\//i
+    "#;
+
+    insta::assert_snapshot!(format(src), @r###"
+    let digits = /\d+/g
+    let color = /rgb\((?<r>\d+),(?<g>\d+),(?<b>\d+)\)/
+    let ratio = a / b / /[/\]]+\//i
+    "###);
+}
+
+#[test]
+fn format_loops() {
+    let src = r#"
+    let result = loop { let n = next()
+    if (n > 10) { break n } }
+    outer: for (row in rows) {
+        inner: while (running()) {
+            continue   outer
+        }
+        break
+    }
+    "#;
+
+    insta::assert_snapshot!(format(src), @r###"
+    let result = loop {
+        let n = next()
+        if (n > 10) {
+            break n
+        }
+    }
+    outer: for (row in rows) {
+        inner: while (running()) {
+            continue outer
+        }
+        break
+    }
+    "###);
+}
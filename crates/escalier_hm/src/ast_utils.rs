@@ -6,6 +6,15 @@ struct ReturnVisitor {
 }
 
 impl Visitor for ReturnVisitor {
+    fn visit_block(&mut self, block: &Block) {
+        for stmt in &block.stmts {
+            self.visit_stmt(stmt);
+            // Statements after this one are unreachable.
+            if diverges(stmt) {
+                break;
+            }
+        }
+    }
     fn visit_stmt(&mut self, stmt: &Stmt) {
         if let StmtKind::Return(ReturnStmt { arg: Some(arg) }) = &stmt.kind {
             self.returns.push(arg.to_owned());
@@ -26,17 +35,125 @@ pub fn find_returns(body: &BlockOrExpr) -> Vec<Expr> {
     let mut visitor = ReturnVisitor { returns: vec![] };
 
     match body {
-        BlockOrExpr::Block(block) => {
-            for stmt in &block.stmts {
-                visitor.visit_stmt(stmt);
-            }
-        }
+        BlockOrExpr::Block(block) => visitor.visit_block(block),
         BlockOrExpr::Expr(expr) => visitor.visit_expr(expr),
     }
 
     visitor.returns
 }
 
+struct BreakVisitor<'a> {
+    pub label: &'a Option<String>,
+    // The number of nested loops we're inside of.  Unlabeled breaks inside of
+    // a nested loop exit that loop instead.
+    pub depth: usize,
+    pub breaks: Vec<Option<Index>>,
+}
+
+impl<'a> BreakVisitor<'a> {
+    fn visit_loop_body(&mut self, label: &Option<String>, body: &Block) {
+        // A nested loop with the same label shadows ours.
+        if label.is_some() && label == self.label {
+            return;
+        }
+        self.depth += 1;
+        self.visit_block(body);
+        self.depth -= 1;
+    }
+}
+
+impl<'a> Visitor for BreakVisitor<'a> {
+    fn visit_stmt(&mut self, stmt: &Stmt) {
+        match &stmt.kind {
+            StmtKind::Break(BreakStmt { label, arg }) => {
+                let is_target = match label {
+                    Some(_) => label == self.label,
+                    None => self.depth == 0,
+                };
+                if is_target {
+                    self.breaks
+                        .push(arg.as_ref().and_then(|arg| arg.inferred_type));
+                }
+                walk_stmt(self, stmt);
+            }
+            StmtKind::For(ForStmt {
+                label, right, body, ..
+            }) => {
+                self.visit_expr(right);
+                self.visit_loop_body(label, body);
+            }
+            StmtKind::While(WhileStmt { label, test, body }) => {
+                self.visit_expr(test);
+                self.visit_loop_body(label, body);
+            }
+            _ => walk_stmt(self, stmt),
+        }
+    }
+    fn visit_expr(&mut self, expr: &Expr) {
+        match &expr.kind {
+            // Breaks can't exit functions
+            ExprKind::Function(_) => {}
+            ExprKind::Loop(Loop { label, body }) => self.visit_loop_body(label, body),
+            _ => walk_expr(self, expr),
+        }
+    }
+}
+
+/// Finds the `break`s that exit the loop with the given `label` and `body`.
+/// Each entry is the type of the value passed to `break` if there is one.
+pub fn find_breaks(label: &Option<String>, body: &Block) -> Vec<Option<Index>> {
+    let mut visitor = BreakVisitor {
+        label,
+        depth: 0,
+        breaks: vec![],
+    };
+    visitor.visit_block(body);
+    visitor.breaks
+}
+
+/// Returns true if control never reaches the statement following `stmt`.
+pub fn diverges(stmt: &Stmt) -> bool {
+    match &stmt.kind {
+        StmtKind::Return(_) | StmtKind::Break(_) | StmtKind::Continue(_) => true,
+        StmtKind::Expr(ExprStmt { expr }) => expr_diverges(expr),
+        _ => false,
+    }
+}
+
+fn block_diverges(block: &Block) -> bool {
+    block.stmts.iter().any(diverges)
+}
+
+fn expr_diverges(expr: &Expr) -> bool {
+    match &expr.kind {
+        // A `loop` without any `break`s never exits.
+        ExprKind::Loop(Loop { label, body }) => find_breaks(label, body).is_empty(),
+        ExprKind::IfElse(IfElse {
+            consequent,
+            alternate: Some(alternate),
+            ..
+        }) => {
+            block_diverges(consequent)
+                && match alternate {
+                    BlockOrExpr::Block(block) => block_diverges(block),
+                    BlockOrExpr::Expr(expr) => expr_diverges(expr),
+                }
+        }
+        ExprKind::Try(Try {
+            body,
+            catch,
+            finally,
+        }) => {
+            let finally_diverges = finally.as_ref().is_some_and(block_diverges);
+            let catch_diverges = catch
+                .as_ref()
+                .map_or(true, |catch| block_diverges(&catch.body));
+            finally_diverges || (block_diverges(body) && catch_diverges)
+        }
+        _ => false,
+    }
+}
+
 struct ThrowsVisitor {
     pub throws: Vec<Index>,
 }
@@ -114,3 +231,40 @@ pub fn find_throws_in_block(block: &Block) -> Vec<Index> {
 
     visitor.throws
 }
+
+struct InferredTypesVisitor {
+    pub types: Vec<Index>,
+}
+
+impl Visitor for InferredTypesVisitor {
+    fn visit_expr(&mut self, expr: &Expr) {
+        self.types.extend(expr.inferred_type);
+        match &expr.kind {
+            ExprKind::Call(Call { throws, .. })
+            | ExprKind::New(New { throws, .. })
+            | ExprKind::TaggedTemplateLiteral(TaggedTemplateLiteral { throws, .. })
+            | ExprKind::Throw(Throw { throws, .. })
+            | ExprKind::Await(Await { throws, .. }) => self.types.extend(*throws),
+            _ => (),
+        }
+        walk_expr(self, expr);
+    }
+    fn visit_pattern(&mut self, pattern: &Pattern) {
+        self.types.extend(pattern.inferred_type);
+        walk_pattern(self, pattern);
+    }
+    fn visit_stmt(&mut self, stmt: &Stmt) {
+        self.types.extend(stmt.inferred_type);
+        walk_stmt(self, stmt);
+    }
+    fn visit_type_ann(&mut self, type_ann: &TypeAnn) {
+        self.types.extend(type_ann.inferred_type);
+        walk_type_ann(self, type_ann);
+    }
+}
+
+pub fn find_inferred_types(script: &Script) -> Vec<Index> {
+    let mut visitor = InferredTypesVisitor { types: vec![] };
+    visitor.visit_program(script);
+    visitor.types
+}
@@ -1,13 +1,19 @@
-use generational_arena::Arena;
+use escalier_ast::Script;
+use generational_arena::{Arena, Index};
+use std::collections::HashMap;
 use std::fmt;
 use std::mem;
 
+use crate::ast_utils::find_inferred_types;
 use crate::diagnostic::Diagnostic;
 use crate::types::Type;
 
 #[derive(Default, Clone, Debug)]
 pub struct Report {
     pub diagnostics: Vec<Diagnostic>,
+    // Warnings, e.g. calls to deprecated functions, don't stop a script from
+    // compiling.
+    pub warnings: Vec<Diagnostic>,
 }
 
 impl fmt::Display for Report {
@@ -19,7 +25,7 @@ impl fmt::Display for Report {
     }
 }
 
-#[derive(Default, Debug)]
+#[derive(Default, Clone, Debug)]
 pub struct Checker {
     pub arena: Arena<Type>,
     pub current_report: Report,
@@ -40,6 +46,7 @@ impl Checker {
         self.current_report
             .diagnostics
             .append(&mut report.diagnostics);
+        self.current_report.warnings.append(&mut report.warnings);
     }
 
     pub fn with_report<T>(&mut self, f: impl FnOnce(&mut Self) -> T) -> T {
@@ -48,4 +55,14 @@ impl Checker {
         self.pop_report();
         result
     }
+
+    /// Returns the printed type of each `Index` in `script`.  These can be
+    /// passed to `escalier_ast::with_type_names` to include the types when
+    /// serializing the AST.
+    pub fn get_type_names(&self, script: &Script) -> HashMap<Index, String> {
+        find_inferred_types(script)
+            .into_iter()
+            .map(|index| (index, self.print_type(&index)))
+            .collect()
+    }
 }
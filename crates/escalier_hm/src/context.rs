@@ -1,20 +1,29 @@
 use generational_arena::Index;
 use im::hashmap::HashMap;
 use im::hashset::HashSet;
+use serde::{Deserialize, Serialize};
 
 use crate::checker::Checker;
+use crate::docs::JsDoc;
 use crate::folder::walk_index;
 use crate::folder::{self, Folder};
 use crate::key_value_store::KeyValueStore;
 use crate::type_error::TypeError;
 use crate::types::*;
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Binding {
     pub index: Index,
     pub is_mut: bool,
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LoopScope {
+    pub label: Option<String>,
+    // Only `loop`s can be exited with a value.
+    pub is_loop_expr: bool,
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct Context {
     // Maps variables to their types.
@@ -28,9 +37,37 @@ pub struct Context {
     pub non_generic: HashSet<Index>,
     // Whether we're in an async function body or not.
     pub is_async: bool,
+    // Maps each struct to the names of its private fields.
+    pub structs: HashMap<String, Vec<String>>,
+    // Maps each enum to the names of its variants.
+    pub enums: HashMap<String, Vec<String>>,
+    // Maps each trait to the names of its methods with default implementations.
+    pub traits: HashMap<String, Vec<String>>,
+    // Maps each type to the traits that have been implemented for it.
+    pub impls: HashMap<String, Vec<String>>,
+    // The loops enclosing the current statement, innermost last.
+    pub loops: Vec<LoopScope>,
+    // Maps values, schemes and their members, e.g. "Array.push", to their
+    // documentation.  See `Checker::get_doc_key`.
+    pub docs: HashMap<String, JsDoc>,
+    // Maps module specifiers, e.g. "react", to the declarations in the module.
+    // The types in these contexts must be in the same arena as this context's.
+    pub modules: HashMap<String, Context>,
 }
 
 impl Context {
+    /// Returns true if `name` is an enum, e.g. `Color`, or one of its
+    /// variants, e.g. `Color.Red`.
+    pub fn is_enum(&self, name: &str) -> bool {
+        match name.split_once('.') {
+            Some((name, variant)) => self
+                .enums
+                .get(name)
+                .map_or(false, |variants| variants.iter().any(|v| v == variant)),
+            None => self.enums.contains_key(name),
+        }
+    }
+
     pub fn get_scheme(&self, name: &str) -> Result<Scheme, TypeError> {
         match self.schemes.get(name) {
             Some(scheme) => Ok(scheme.to_owned()),
@@ -40,6 +77,29 @@ impl Context {
         }
     }
 
+    /// Finds the loop that a `break` or `continue` with the given `label`
+    /// refers to.
+    pub fn get_loop(&self, label: &Option<String>, keyword: &str) -> Result<LoopScope, TypeError> {
+        let scope = match label {
+            Some(label) => self
+                .loops
+                .iter()
+                .rev()
+                .find(|scope| scope.label.as_ref() == Some(label)),
+            None => self.loops.last(),
+        };
+
+        match (scope, label) {
+            (Some(scope), _) => Ok(scope.to_owned()),
+            (None, Some(label)) => Err(TypeError {
+                message: format!("label `{label}` is not in scope"),
+            }),
+            (None, None) => Err(TypeError {
+                message: format!("`{keyword}` can only be used inside of a loop"),
+            }),
+        }
+    }
+
     pub fn get_binding(&self, name: &str) -> Result<Binding, TypeError> {
         match self.values.get(name) {
             Some(binding) => Ok(binding.to_owned()),
@@ -155,6 +215,43 @@ impl Checker {
             throws,
         })
     }
+
+    /// Stores the schemes in `ctx` on the type references in `ctx` that refer
+    /// to them by name so that the values and schemes in `ctx` can be used
+    /// from other contexts, e.g. when they're imported from another module.
+    /// References to the names in `shadowed`, e.g. type params, are left as is.
+    pub fn link_type_refs(
+        &mut self,
+        ctx: &mut Context,
+        shadowed: &std::collections::HashSet<String>,
+    ) {
+        let schemes = ctx
+            .schemes
+            .iter()
+            .filter(|(name, scheme)| !scheme.is_type_param && !shadowed.contains(*name))
+            .map(|(name, scheme)| (name.to_owned(), scheme.to_owned()))
+            .collect::<std::collections::HashMap<_, _>>();
+
+        let mut link = Link {
+            checker: self,
+            schemes: &schemes,
+        };
+
+        for scheme in schemes.values() {
+            let t = link.fold_index(&scheme.t);
+            // Schemes are updated in place since the schemes stored on the
+            // type references, including references to the scheme itself,
+            // point to the original type.
+            if t != scheme.t {
+                let linked = link.checker.arena[t].clone();
+                link.checker.arena[scheme.t] = linked;
+            }
+        }
+
+        for (_, binding) in ctx.values.iter_mut() {
+            binding.index = link.fold_index(&binding.index);
+        }
+    }
 }
 
 struct Fresh<'a, 'b> {
@@ -200,6 +297,49 @@ impl<'a, 'b> Folder for Fresh<'a, 'b> {
     }
 }
 
+struct Link<'a> {
+    checker: &'a mut Checker,
+    schemes: &'a std::collections::HashMap<String, Scheme>,
+}
+
+impl<'a> KeyValueStore<Index, Type> for Link<'a> {
+    fn get_type(&mut self, idx: &Index) -> Type {
+        self.checker.arena[*idx].clone()
+    }
+    fn put_type(&mut self, t: Type) -> Index {
+        self.checker.arena.insert(t)
+    }
+}
+
+impl<'a> Folder for Link<'a> {
+    fn fold_index(&mut self, index: &Index) -> Index {
+        let t = self.get_type(index);
+
+        match &t.kind {
+            // The schemes' types aren't walked since they're linked
+            // separately and may refer to themselves.
+            TypeKind::TypeRef(TypeRef {
+                name,
+                scheme,
+                type_args,
+            }) => {
+                let new_type_args = folder::walk_indexes(self, type_args);
+                let new_scheme = match scheme {
+                    Some(_) => scheme.to_owned(),
+                    None => self.schemes.get(name).cloned(),
+                };
+
+                if new_type_args == *type_args && new_scheme == *scheme {
+                    *index
+                } else {
+                    self.checker.new_type_ref(name, new_scheme, &new_type_args)
+                }
+            }
+            _ => walk_index(self, index),
+        }
+    }
+}
+
 pub struct Instantiate<'a> {
     pub checker: &'a mut Checker,
     pub mapping: &'a std::collections::HashMap<String, Index>,
@@ -1,12 +1,27 @@
 use std::fmt;
 
+use escalier_ast::Span;
+
 use crate::type_error::TypeError;
 
+/// Describes what went wrong in a structured way so that tools, e.g. the
+/// language server, can offer fixes without having to parse `message`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DiagnosticKind {
+    IncorrectArgs,
+    MutabilityViolation { name: String },
+    NonExhaustiveMatch { missing: Vec<String> },
+    MissingThrows { throws: String },
+    Deprecated { name: String },
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Diagnostic {
     pub code: u32,
+    pub kind: DiagnosticKind,
     pub message: String,
     pub reasons: Vec<TypeError>,
+    pub span: Option<Span>,
 }
 
 impl fmt::Display for Diagnostic {
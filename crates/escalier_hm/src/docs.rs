@@ -0,0 +1,76 @@
+use generational_arena::Index;
+use serde::{Deserialize, Serialize};
+
+use escalier_ast::{Expr, ExprKind, Member, MemberProp};
+
+use crate::checker::Checker;
+use crate::context::Context;
+use crate::diagnostic::{Diagnostic, DiagnosticKind};
+use crate::types::TypeKind;
+
+/// The documentation for a declaration, e.g. from a JSDoc comment in a .d.ts
+/// file.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JsDoc {
+    pub text: String,
+    // The reason for the deprecation, which may be empty.
+    pub deprecated: Option<String>,
+    // The description of each param that has one.
+    pub params: Vec<(String, String)>,
+    pub returns: Option<String>,
+}
+
+impl Checker {
+    /// Returns the key of the docs in `ctx.docs` for `expr`.  Values are keyed
+    /// by their name, e.g. "parseInt", and members by the name of the type
+    /// they belong to, e.g. "Array.push".
+    pub fn get_doc_key(&mut self, expr: &Expr) -> Option<String> {
+        match &expr.kind {
+            ExprKind::Ident(ident) => Some(ident.name.to_owned()),
+            ExprKind::Member(Member {
+                object,
+                property: MemberProp::Ident(prop),
+                ..
+            }) => {
+                let name = self.get_doc_type_name(object.inferred_type?)?;
+                Some(format!("{name}.{}", prop.name))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the name that the docs for the members of `t` are keyed by,
+    /// e.g. "String" for `string` and "Array" for `number[]`.
+    pub fn get_doc_type_name(&mut self, t: Index) -> Option<String> {
+        let t = self.prune(t);
+        match &self.arena[t].kind {
+            TypeKind::TypeRef(type_ref) => Some(type_ref.name.to_owned()),
+            TypeKind::Array(_) | TypeKind::Tuple(_) => Some("Array".to_string()),
+            TypeKind::Primitive(primitive) => Some(primitive.get_scheme_name().to_string()),
+            TypeKind::Literal(lit) => Some(lit.get_scheme_name()?.to_string()),
+            _ => None,
+        }
+    }
+
+    /// Reports a warning if `callee` has been marked as `@deprecated`.
+    pub fn check_deprecated(&mut self, ctx: &Context, callee: &Expr) {
+        let Some(key) = self.get_doc_key(callee) else {
+            return;
+        };
+        let Some(reason) = ctx.docs.get(&key).and_then(|docs| docs.deprecated.as_ref()) else {
+            return;
+        };
+
+        let message = match reason.is_empty() {
+            true => format!("{key} is deprecated"),
+            false => format!("{key} is deprecated: {reason}"),
+        };
+        self.current_report.warnings.push(Diagnostic {
+            code: 1004,
+            kind: DiagnosticKind::Deprecated { name: key },
+            message,
+            reasons: vec![],
+            span: Some(callee.span),
+        });
+    }
+}
@@ -77,14 +77,17 @@ pub fn walk_index<F: Folder>(folder: &mut F, index: &Index) -> Index {
 
             TypeKind::Intersection(Intersection { types: new_types })
         }
-        TypeKind::Tuple(Tuple { types }) => {
+        TypeKind::Tuple(Tuple { types, optional }) => {
             let new_types = walk_indexes(folder, types);
 
             if new_types == *types {
                 return *index;
             }
 
-            TypeKind::Tuple(Tuple { types: new_types })
+            TypeKind::Tuple(Tuple {
+                types: new_types,
+                optional: optional.to_owned(),
+            })
         }
         TypeKind::Array(Array { t }) => {
             let new_t = folder.fold_index(t);
@@ -113,6 +116,7 @@ pub fn walk_index<F: Folder>(folder: &mut F, index: &Index) -> Index {
                         target,
                         source,
                         optional,
+                        readonly,
                         check,
                         extends,
                     }) => {
@@ -129,6 +133,7 @@ pub fn walk_index<F: Folder>(folder: &mut F, index: &Index) -> Index {
                             target: target.to_owned(),
                             source: new_source,
                             optional: optional.to_owned(),
+                            readonly: readonly.to_owned(),
                             check: new_check,
                             extends: new_extends,
                         })
@@ -224,6 +229,19 @@ pub fn walk_index<F: Folder>(folder: &mut F, index: &Index) -> Index {
         }
         TypeKind::Infer(_) => return *index,
         TypeKind::Wildcard => return *index,
+        TypeKind::TypePredicate(TypePredicate { param, t, asserts }) => {
+            let new_t = t.map(|t| folder.fold_index(&t));
+
+            if new_t == *t {
+                return *index;
+            }
+
+            TypeKind::TypePredicate(TypePredicate {
+                param: param.to_owned(),
+                t: new_t,
+                asserts: *asserts,
+            })
+        }
         TypeKind::Binary(BinaryT { op, left, right }) => {
             let new_left = folder.fold_index(left);
             let new_right = folder.fold_index(right);
@@ -4,9 +4,10 @@ use std::collections::{BTreeMap, HashMap, HashSet};
 
 use escalier_ast::{self as syntax, *};
 
-use crate::ast_utils::{find_returns, find_throws, find_throws_in_block};
+use crate::ast_utils::{diverges, find_breaks, find_returns, find_throws, find_throws_in_block};
 use crate::checker::Checker;
 use crate::context::*;
+use crate::diagnostic::{Diagnostic, DiagnosticKind};
 use crate::folder::{self, Folder};
 use crate::infer_pattern::*;
 use crate::key_value_store::KeyValueStore;
@@ -45,10 +46,19 @@ impl Checker {
         self.with_report(|checker| -> Result<Index, TypeError> {
             let idx: Index =
                 match &mut node.kind {
+                    ExprKind::Ident(Ident { name, .. })
+                        if name == "_" && !ctx.values.contains_key(name) =>
+                    {
+                        return Err(TypeError {
+                            message: "`_` can only be used in the right side of `|>`"
+                                .to_string(),
+                        })
+                    }
                     ExprKind::Ident(Ident { name, .. }) => checker.get_type(name, ctx)?,
                     ExprKind::Str(str) => checker.arena.insert(Type::from(TypeKind::Literal(
                         syntax::Literal::String(str.value.to_owned()),
                     ))),
+                    ExprKind::Regex(regex) => checker.infer_regex(regex),
                     ExprKind::Num(num) => checker.arena.insert(Type::from(TypeKind::Literal(
                         syntax::Literal::Number(num.value.to_owned()),
                     ))),
@@ -77,48 +87,37 @@ impl Checker {
                     ExprKind::Object(syntax::Object {
                         properties: props, ..
                     }) => {
-                        let mut prop_types: Vec<types::TObjElem> = vec![];
-                        for prop_or_spread in props.iter_mut() {
-                            match prop_or_spread {
-                                PropOrSpread::Spread(_) => todo!(),
-                                PropOrSpread::Prop(prop) => match prop {
-                                    expr::Prop::Shorthand(Ident { name, span: _ }) => {
-                                        prop_types.push(types::TObjElem::Prop(types::TProp {
-                                            name: TPropKey::StringKey(name.to_owned()),
-                                            readonly: false,
-                                            optional: false,
-                                            t: checker.get_type(name, ctx)?,
-                                        }));
-                                    }
-                                    expr::Prop::Property { key, value } => {
-                                        let prop = match key {
-                                            ObjectKey::Ident(ident) => types::TProp {
-                                                name: TPropKey::StringKey(ident.name.to_owned()),
-                                                readonly: false,
-                                                optional: false,
-                                                t: checker.infer_expression(value, ctx)?,
-                                            },
-                                            ObjectKey::String(name) => types::TProp {
-                                                name: TPropKey::StringKey(name.to_owned()),
-                                                readonly: false,
-                                                optional: false,
-                                                t: checker.infer_expression(value, ctx)?,
-                                            },
-                                            ObjectKey::Number(name) => types::TProp {
-                                                name: TPropKey::StringKey(name.to_owned()),
-                                                readonly: false,
-                                                optional: false,
-                                                t: checker.infer_expression(value, ctx)?,
-                                            },
-                                            ObjectKey::Computed(_) => todo!(),
-                                        };
-                                        prop_types.push(types::TObjElem::Prop(prop));
-                                    }
-                                },
-                            }
-                        }
+                        let prop_types = checker.infer_props(props, ctx)?;
                         checker.new_object_type(&prop_types)
                     }
+                    ExprKind::Struct(syntax::Struct { name, properties }) => {
+                        checker.infer_struct(&name.name, properties, ctx)?
+                    }
+                    ExprKind::Range(syntax::Range { start, end }) => {
+                        let number = checker.new_primitive(Primitive::Number);
+                        let start_t = checker.infer_expression(start, ctx)?;
+                        checker.unify(ctx, start_t, number)?;
+                        let end_t = checker.infer_expression(end, ctx)?;
+                        checker.unify(ctx, end_t, number)?;
+
+                        // The bounds are kept as type args so that literal
+                        // bounds are preserved, e.g. `0..10` is `Range<0, 10>`.
+                        checker.new_type_ref("Range", None, &[start_t, end_t])
+                    }
+                    ExprKind::Slice(slice) => checker.infer_slice(slice, ctx)?,
+                    ExprKind::Pipeline(syntax::Pipeline { left, right }) => {
+                        let left_t = checker.infer_expression(left, ctx)?;
+
+                        // `_` is only in scope within the right side.
+                        let mut topic_ctx = ctx.clone();
+                        let binding = Binding {
+                            index: left_t,
+                            is_mut: false,
+                        };
+                        topic_ctx.values.insert("_".to_string(), binding);
+
+                        checker.infer_expression(right, &mut topic_ctx)?
+                    }
                     ExprKind::Call(syntax::Call {
                         callee,
                         args,
@@ -128,6 +127,7 @@ impl Checker {
                     }) => {
                         // TODO: Check if the callee in an object with a callable signature.
                         let mut func_idx = checker.infer_expression(callee, ctx)?;
+                        checker.check_deprecated(ctx, callee);
                         let mut has_undefined = false;
                         if *opt_chain {
                             if let TypeKind::Union(union) = &checker.arena[func_idx].kind {
@@ -153,6 +153,16 @@ impl Checker {
                             throws.replace(new_throws);
                         }
 
+                        // Type guards narrow their argument, see `narrow_by_type_guard`,
+                        // but the calls themselves evaluate to `boolean`s.
+                        let result = match &checker.arena[result].kind {
+                            TypeKind::TypePredicate(TypePredicate { asserts: true, .. }) => {
+                                checker.new_lit_type(&Literal::Undefined)
+                            }
+                            TypeKind::TypePredicate(_) => checker.new_primitive(Primitive::Boolean),
+                            _ => result,
+                        };
+
                         match *opt_chain && has_undefined {
                             true => {
                                 let undefined = checker.new_lit_type(&Literal::Undefined);
@@ -249,6 +259,8 @@ impl Checker {
 
                         let mut body_ctx = sig_ctx.clone();
                         body_ctx.is_async = *is_async;
+                        // `break` and `continue` can't exit a function.
+                        body_ctx.loops = vec![];
 
                         let mut body_t = 'outer: {
                             match body {
@@ -256,7 +268,7 @@ impl Checker {
                                     for stmt in stmts.iter_mut() {
                                         body_ctx = body_ctx.clone();
                                         checker.infer_statement(stmt, &mut body_ctx)?;
-                                        if let StmtKind::Return(_) = stmt.kind {
+                                        if diverges(stmt) {
                                             let ret_types: Vec<Index> = find_returns(body)
                                                 .iter()
                                                 .filter_map(|ret| ret.inferred_type)
@@ -267,9 +279,14 @@ impl Checker {
                                         }
                                     }
 
-                                    // If we don't encounter a return statement, we assume
-                                    // the return type is `undefined`.
-                                    checker.new_lit_type(&Literal::Undefined)
+                                    // If we reach the end of the body, we return `undefined`
+                                    // in addition to anything returned from nested blocks.
+                                    let mut ret_types: Vec<Index> = find_returns(body)
+                                        .iter()
+                                        .filter_map(|ret| ret.inferred_type)
+                                        .collect();
+                                    ret_types.push(checker.new_lit_type(&Literal::Undefined));
+                                    checker.new_union_type(&ret_types)
                                 }
                                 BlockOrExpr::Expr(expr) => {
                                     // TODO: use `find_returns` here as well
@@ -298,7 +315,27 @@ impl Checker {
                                 checker.unify(&sig_ctx, call_throws, sig_throws)?;
                                 Some(sig_throws)
                             }
-                            (Some(call_throws), None) => Some(call_throws),
+                            (Some(call_throws), None) => {
+                                // Functions with an explicit return type should also
+                                // declare what they throw.
+                                if let Some(return_type) = return_type {
+                                    // This is only a hint so it shouldn't stop the
+                                    // script from compiling.
+                                    let throws = checker.print_type(&call_throws);
+                                    checker.current_report.warnings.push(Diagnostic {
+                                        code: 1003,
+                                        kind: DiagnosticKind::MissingThrows {
+                                            throws: throws.clone(),
+                                        },
+                                        message: format!(
+                                            "Function throws {throws} but its signature doesn't include a throws clause"
+                                        ),
+                                        reasons: vec![],
+                                        span: Some(return_type.span),
+                                    });
+                                }
+                                Some(call_throws)
+                            }
                             // This should probably be a warning.  If the function doesn't
                             // throw anything, then it shouldn't be marked as such.
                             (None, Some(sig_throws)) => Some(sig_throws),
@@ -343,7 +380,9 @@ impl Checker {
                         let cond_type = checker.infer_expression(cond, ctx)?;
                         let bool_type = checker.new_primitive(Primitive::Boolean);
                         checker.unify(ctx, cond_type, bool_type)?;
-                        let consequent_type = checker.infer_block(consequent, ctx)?;
+                        let mut consequent_ctx = ctx.clone();
+                        checker.narrow_by_type_guard(cond, false, &mut consequent_ctx);
+                        let consequent_type = checker.infer_block(consequent, &mut consequent_ctx)?;
                         let alternate_type = match alternate {
                             Some(alternate) => match alternate {
                                 BlockOrExpr::Block(block) => checker.infer_block(block, ctx)?,
@@ -375,7 +414,21 @@ impl Checker {
                             MemberProp::Ident(Ident { name, .. }) => {
                                 let key_idx =
                                     checker.new_lit_type(&Literal::String(name.to_owned()));
-                                checker.get_ident_member(ctx, obj_idx, key_idx, is_mut)?
+                                match checker.get_ident_member(ctx, obj_idx, key_idx, is_mut) {
+                                    Ok(t) => t,
+                                    Err(error) => {
+                                        // If the lookup succeeds when the object is mutable
+                                        // then we were trying to call a mutating method.
+                                        if !is_mut
+                                            && checker
+                                                .get_ident_member(ctx, obj_idx, key_idx, true)
+                                                .is_ok()
+                                        {
+                                            checker.report_mutability_violation(obj, &error);
+                                        }
+                                        return Err(error);
+                                    }
+                                }
                             }
                             MemberProp::Computed(ComputedPropName { expr, .. }) => {
                                 let prop_type = checker.infer_expression(expr, ctx)?;
@@ -409,9 +462,11 @@ impl Checker {
                     ExprKind::JSXElement(_) => todo!(),
                     ExprKind::Assign(Assign { left, op: _, right }) => {
                         if !is_expr_mutable(ctx, left)? {
-                            return Err(TypeError {
+                            let error = TypeError {
                                 message: "Cannot assign to immutable lvalue".to_string(),
-                            });
+                            };
+                            checker.report_mutability_violation(left, &error);
+                            return Err(error);
                         }
 
                         let l_t = checker.infer_expression(left, ctx)?;
@@ -655,16 +710,42 @@ impl Checker {
                             body_types.push(body_type);
                         }
 
-                        let t0 = checker.prune(body_types[0]);
-                        eprintln!("t0 = {}", checker.print_type(&t0));
-
-                        let t1 = checker.prune(body_types[1]);
-                        eprintln!("t1 = {}", checker.print_type(&t1));
+                        let missing = checker.get_missing_match_arms(expr_idx, arms);
+                        if !missing.is_empty() {
+                            checker.current_report.diagnostics.push(Diagnostic {
+                                code: 1002,
+                                kind: DiagnosticKind::NonExhaustiveMatch {
+                                    missing: missing.clone(),
+                                },
+                                message: format!(
+                                    "Match is not exhaustive, missing: {}",
+                                    missing.join(", ")
+                                ),
+                                reasons: vec![],
+                                span: Some(node.span),
+                            });
+                        }
 
                         checker.new_union_type(&body_types)
                     }
                     ExprKind::Class(class) => checker.infer_class(class, ctx)?,
                     ExprKind::Do(Do { body }) => checker.infer_block(body, ctx)?,
+                    ExprKind::Loop(Loop { label, body }) => {
+                        let mut new_ctx = ctx.clone();
+                        new_ctx.loops.push(LoopScope {
+                            label: label.to_owned(),
+                            is_loop_expr: true,
+                        });
+                        checker.infer_block(body, &mut new_ctx)?;
+
+                        // The loop evaluates to the values passed to the `break`s
+                        // that exit it.  It's `never` if there are no `break`s.
+                        let break_types: Vec<Index> = find_breaks(label, body)
+                            .into_iter()
+                            .map(|t| t.unwrap_or_else(|| checker.new_lit_type(&Literal::Undefined)))
+                            .collect();
+                        checker.new_union_type(&break_types)
+                    }
                     ExprKind::Try(Try {
                         body,
                         catch,
@@ -729,6 +810,75 @@ impl Checker {
         Ok(result_t)
     }
 
+    /// Narrows the argument passed to a type guard, e.g. `isFoo(x)`, or an
+    /// assertion function, e.g. `assertIsFoo(x)`, to the guarded type.
+    // TODO: handle generic type guards, the narrowed type comes from the
+    // callee's declared type and isn't instantiated.
+    pub fn narrow_by_type_guard(&mut self, expr: &Expr, asserts: bool, ctx: &mut Context) {
+        let ExprKind::Call(syntax::Call { callee, args, .. }) = &expr.kind else {
+            return;
+        };
+        let Some(callee_t) = callee.inferred_type else {
+            return;
+        };
+        let callee_t = self.prune(callee_t);
+        let TypeKind::Function(types::Function { params, ret, .. }) =
+            self.arena[callee_t].kind.clone()
+        else {
+            return;
+        };
+        let ret = self.prune(ret);
+        let TypeKind::TypePredicate(predicate) = self.arena[ret].kind.clone() else {
+            return;
+        };
+        if predicate.asserts != asserts {
+            return;
+        }
+
+        // `self is Foo` narrows the receiver of a method call.
+        let arg = match predicate.param.as_str() {
+            "self" => match &callee.kind {
+                ExprKind::Member(Member { object, .. }) => Some(object.as_ref()),
+                _ => None,
+            },
+            param => params
+                .iter()
+                .position(|p| matches!(&p.pattern, TPat::Ident(BindingIdent { name, .. }) if name == param))
+                .and_then(|i| args.get(i)),
+        };
+        let Some(Expr {
+            kind: ExprKind::Ident(Ident { name, .. }),
+            ..
+        }) = arg
+        else {
+            return;
+        };
+        let Some(binding) = ctx.values.get(name) else {
+            return;
+        };
+
+        let t = match predicate.t {
+            Some(t) => t,
+            // `asserts x` narrows `x` to be non-nullable.
+            None => {
+                let t = self.prune(binding.index);
+                match &self.arena[t].kind {
+                    TypeKind::Union(Union { types }) => {
+                        let types = filter_nullables(&self.arena, types);
+                        self.new_union_type(&types)
+                    }
+                    _ => t,
+                }
+            }
+        };
+
+        let binding = Binding {
+            index: t,
+            is_mut: binding.is_mut,
+        };
+        ctx.values.insert(name.to_owned(), binding);
+    }
+
     pub fn infer_type_ann(
         &mut self,
         type_ann: &mut TypeAnn,
@@ -798,6 +948,7 @@ impl Checker {
                             optional,
                             check,
                             extends,
+                            comments: _,
                         }) => {
                             let mut type_ctx = ctx.clone();
 
@@ -832,6 +983,7 @@ impl Checker {
                                 target: target.to_owned(),
                                 source,
                                 optional,
+                                readonly: None,
                                 check,
                                 extends,
                             }));
@@ -883,6 +1035,7 @@ impl Checker {
                             span: _,
                             name,
                             param,
+                            comments: _,
                         }) => {
                             // TODO: create an `infer_func_param` function
                             let t = self.infer_type_ann(&mut param.type_ann, &mut obj_ctx)?;
@@ -1175,22 +1328,78 @@ impl Checker {
     ) -> Result<Index, TypeError> {
         self.with_report(|checker| -> Result<Index, TypeError> {
             let t = match &mut statement.kind {
-                StmtKind::Expr(ExprStmt { expr }) => checker.infer_expression(expr, ctx)?,
-                StmtKind::For(ForStmt { left, right, body }) => {
+                StmtKind::Expr(ExprStmt { expr }) => {
+                    let t = checker.infer_expression(expr, ctx)?;
+                    // Assertion functions narrow their argument for the rest
+                    // of the block.
+                    checker.narrow_by_type_guard(expr, true, ctx);
+                    t
+                }
+                StmtKind::For(ForStmt {
+                    label,
+                    left,
+                    right,
+                    body,
+                }) => {
                     let right_t = checker.infer_expression(right, ctx)?;
                     let (bindings, left_t) = checker.infer_pattern(left, ctx)?;
-                    let array_t = checker.new_array_type(left_t);
-                    // The expression we're iterating over must be assignable
-                    // to an array.
-                    checker.unify(ctx, right_t, array_t)?;
+                    let right_t = checker.prune(right_t);
+                    match &checker.arena[right_t].kind {
+                        // Ranges are only iterable in `for` loops.
+                        TypeKind::TypeRef(types::TypeRef { name, .. }) if name == "Range" => {
+                            let number = checker.new_primitive(Primitive::Number);
+                            checker.unify(ctx, number, left_t)?;
+                        }
+                        _ => {
+                            let array_t = checker.new_array_type(left_t);
+                            // The expression we're iterating over must be assignable
+                            // to an array.
+                            checker.unify(ctx, right_t, array_t)?;
+                        }
+                    }
 
                     let mut new_ctx = ctx.clone();
+                    new_ctx.loops.push(LoopScope {
+                        label: label.to_owned(),
+                        is_loop_expr: false,
+                    });
 
                     for (name, binding) in bindings {
                         new_ctx.values.insert(name, binding);
                     }
 
-                    checker.infer_block(body, &mut new_ctx)?
+                    checker.infer_block(body, &mut new_ctx)?;
+                    checker.new_lit_type(&Literal::Undefined)
+                }
+                StmtKind::While(WhileStmt { label, test, body }) => {
+                    let test_t = checker.infer_expression(test, ctx)?;
+                    let boolean = checker.new_primitive(Primitive::Boolean);
+                    checker.unify(ctx, test_t, boolean)?;
+
+                    let mut new_ctx = ctx.clone();
+                    new_ctx.loops.push(LoopScope {
+                        label: label.to_owned(),
+                        is_loop_expr: false,
+                    });
+
+                    checker.infer_block(body, &mut new_ctx)?;
+                    checker.new_lit_type(&Literal::Undefined)
+                }
+                StmtKind::Break(BreakStmt { label, arg }) => {
+                    let scope = ctx.get_loop(label, "break")?;
+                    if let Some(arg) = arg {
+                        if !scope.is_loop_expr {
+                            return Err(TypeError {
+                                message: "only `loop`s can be exited with a value".to_string(),
+                            });
+                        }
+                        checker.infer_expression(arg, ctx)?;
+                    }
+                    checker.new_keyword(Keyword::Never)
+                }
+                StmtKind::Continue(ContinueStmt { label }) => {
+                    ctx.get_loop(label, "continue")?;
+                    checker.new_keyword(Keyword::Never)
                 }
                 StmtKind::Return(ReturnStmt { arg: expr }) => {
                     // TODO: handle multiple return statements
@@ -1205,12 +1414,14 @@ impl Checker {
                 }
                 StmtKind::Decl(decl) => match &mut decl.kind {
                     DeclKind::TypeDecl(decl) => checker.infer_type_decl(decl, ctx)?,
+                    DeclKind::EnumDecl(decl) => checker.infer_enum_decl(decl, ctx)?,
+                    DeclKind::StructDecl(decl) => checker.infer_struct_decl(decl, ctx)?,
+                    DeclKind::TraitDecl(decl) => checker.infer_trait_decl(decl, ctx)?,
+                    DeclKind::ImplDecl(decl) => checker.infer_impl_decl(decl, ctx)?,
                     DeclKind::VarDecl(decl) => {
                         checker.infer_var_decl(decl, ctx)?;
                         checker.new_lit_type(&Literal::Undefined)
-                    }
-                    // DeclKind::ClassDecl(_) => todo!(),
-                    // DeclKind::StructDecl(_) => todo!(),
+                    } // DeclKind::ClassDecl(_) => todo!(),
                 },
             };
 
@@ -1341,6 +1552,179 @@ impl Checker {
         Ok(t)
     }
 
+    /// Each variant `V` of `enum E<T>` is a nominal type `E.V<T>` whose
+    /// fields are stored in a `payload` property (as a tuple when there's more
+    /// than one field).  `E<T>` itself is a union of its variants.  The value
+    /// `E` is an object containing a constructor for each variant with fields
+    /// and a value for each variant without.
+    pub fn infer_enum_decl(
+        &mut self,
+        decl: &mut EnumDecl,
+        ctx: &mut Context,
+    ) -> Result<Index, TypeError> {
+        let EnumDecl {
+            name,
+            type_params,
+            variants,
+        } = decl;
+
+        // NOTE: We clone `ctx` so that type params don't escape the signature
+        let mut sig_ctx = ctx.clone();
+
+        let type_params = self.infer_type_params(type_params, &mut sig_ctx)?;
+        let type_args: Vec<Index> = match &type_params {
+            Some(type_params) => type_params
+                .iter()
+                .map(|tp| self.new_type_ref(&tp.name, None, &[]))
+                .collect(),
+            None => vec![],
+        };
+
+        let mut variant_types: Vec<Index> = vec![];
+        let mut elems: Vec<TObjElem> = vec![];
+
+        for variant in variants.iter_mut() {
+            let mut field_types: Vec<Index> = vec![];
+            for field in variant.fields.iter_mut() {
+                field_types.push(self.infer_type_ann(field, &mut sig_ctx)?);
+            }
+
+            let payload = match field_types.as_slice() {
+                [] => None,
+                [t] => Some(*t),
+                _ => Some(self.new_tuple_type(&field_types)),
+            };
+            let props: Vec<TObjElem> = payload
+                .map(|t| {
+                    TObjElem::Prop(TProp {
+                        name: TPropKey::StringKey("payload".to_string()),
+                        optional: false,
+                        readonly: true,
+                        t,
+                    })
+                })
+                .into_iter()
+                .collect();
+
+            let variant_name = format!("{name}.{}", variant.name);
+            let scheme = Scheme {
+                t: self.new_object_type(&props),
+                type_params: type_params.clone(),
+                is_type_param: false,
+            };
+            ctx.schemes.insert(variant_name.clone(), scheme.clone());
+
+            let variant_t = self.new_type_ref(&variant_name, Some(scheme.clone()), &type_args);
+            variant_types.push(variant_t);
+
+            let value = match field_types.is_empty() {
+                // Variants without fields aren't constructed so we use free
+                // type variables for the type args which are then instantiated
+                // each time the variant is referenced.
+                true => {
+                    let type_args: Vec<Index> =
+                        type_args.iter().map(|_| self.new_type_var(None)).collect();
+                    self.new_type_ref(&variant_name, Some(scheme), &type_args)
+                }
+                false => {
+                    let params: Vec<types::FuncParam> = field_types
+                        .iter()
+                        .enumerate()
+                        .map(|(i, t)| types::FuncParam {
+                            pattern: TPat::Ident(BindingIdent {
+                                name: format!("arg{i}"),
+                                mutable: false,
+                                span: Span { start: 0, end: 0 },
+                            }),
+                            t: *t,
+                            optional: false,
+                        })
+                        .collect();
+                    self.new_func_type(&params, variant_t, &type_params, None)
+                }
+            };
+
+            elems.push(TObjElem::Prop(TProp {
+                name: TPropKey::StringKey(variant.name.to_owned()),
+                optional: false,
+                readonly: true,
+                t: value,
+            }));
+        }
+
+        let t = self.new_union_type(&variant_types);
+        let scheme = Scheme {
+            t,
+            type_params,
+            is_type_param: false,
+        };
+        ctx.schemes.insert(name.to_owned(), scheme);
+        ctx.enums.insert(
+            name.to_owned(),
+            variants.iter().map(|v| v.name.to_owned()).collect(),
+        );
+
+        let value = self.new_object_type(&elems);
+        let binding = Binding {
+            index: value,
+            is_mut: false,
+        };
+        if ctx.values.insert(name.to_owned(), binding).is_some() {
+            return Err(TypeError {
+                message: format!("{name} cannot be redeclared"),
+            });
+        }
+
+        Ok(t)
+    }
+
+    /// A struct is a nominal type whose scheme is the object type of its
+    /// fields.  Its private fields are tracked in `ctx.structs` so that they
+    /// can't be accessed outside of the struct.
+    pub fn infer_struct_decl(
+        &mut self,
+        decl: &mut StructDecl,
+        ctx: &mut Context,
+    ) -> Result<Index, TypeError> {
+        let StructDecl {
+            name,
+            type_params,
+            fields,
+        } = decl;
+
+        // NOTE: We clone `ctx` so that type params don't escape the signature
+        let mut sig_ctx = ctx.clone();
+
+        let type_params = self.infer_type_params(type_params, &mut sig_ctx)?;
+
+        let mut elems: Vec<TObjElem> = vec![];
+        for field in fields.iter_mut() {
+            elems.push(TObjElem::Prop(TProp {
+                name: TPropKey::StringKey(field.name.to_owned()),
+                optional: false,
+                readonly: false,
+                t: self.infer_type_ann(&mut field.type_ann, &mut sig_ctx)?,
+            }));
+        }
+
+        let t = self.new_object_type(&elems);
+        let scheme = Scheme {
+            t,
+            type_params,
+            is_type_param: false,
+        };
+        ctx.schemes.insert(name.to_owned(), scheme);
+
+        let private_fields: Vec<String> = fields
+            .iter()
+            .filter(|field| !field.is_public)
+            .map(|field| field.name.to_owned())
+            .collect();
+        ctx.structs.insert(name.to_owned(), private_fields);
+
+        Ok(t)
+    }
+
     // TODO: write tests for this
     pub fn infer_module(&mut self, node: &mut Module, ctx: &mut Context) -> Result<(), TypeError> {
         // Prebindings are used to handle recursive and mutually recursive
@@ -1354,7 +1738,10 @@ impl Checker {
                 }
                 ModuleItemKind::Export(_) => (),
                 ModuleItemKind::Decl(decl) => match &mut decl.kind {
-                    DeclKind::TypeDecl(TypeDecl { name, .. }) => {
+                    DeclKind::TypeDecl(TypeDecl { name, .. })
+                    | DeclKind::EnumDecl(EnumDecl { name, .. })
+                    | DeclKind::StructDecl(StructDecl { name, .. })
+                    | DeclKind::TraitDecl(TraitDecl { name, .. }) => {
                         let placeholder_scheme = Scheme {
                             t: self.new_keyword(Keyword::Unknown),
                             type_params: None,
@@ -1371,6 +1758,7 @@ impl Checker {
                             });
                         }
                     }
+                    DeclKind::ImplDecl(_) => (),
                     DeclKind::VarDecl(VarDecl { pattern, .. }) => {
                         let (bindings, _) = self.infer_pattern(pattern, ctx)?;
 
@@ -1400,6 +1788,22 @@ impl Checker {
                         // NOTE: This updates ctx.schemes.
                         self.infer_type_decl(decl, ctx)?;
                     }
+                    DeclKind::EnumDecl(decl) => {
+                        // NOTE: This updates ctx.schemes and ctx.values.
+                        self.infer_enum_decl(decl, ctx)?;
+                    }
+                    DeclKind::StructDecl(decl) => {
+                        // NOTE: This updates ctx.schemes and ctx.structs.
+                        self.infer_struct_decl(decl, ctx)?;
+                    }
+                    DeclKind::TraitDecl(decl) => {
+                        // NOTE: This updates ctx.schemes and ctx.traits.
+                        self.infer_trait_decl(decl, ctx)?;
+                    }
+                    DeclKind::ImplDecl(decl) => {
+                        // NOTE: This updates ctx.impls.
+                        self.infer_impl_decl(decl, ctx)?;
+                    }
                     DeclKind::VarDecl(decl) => {
                         // TODO: figure out how to avoid parsing patterns twice
                         bindings.append(&mut self.infer_var_decl(decl, ctx)?);
@@ -1455,9 +1859,15 @@ impl Checker {
                 // VarDecls, TypeDecls, Imports, and Exports
                 StmtKind::Expr(_) => (),
                 StmtKind::For(_) => (),
+                StmtKind::While(_) => (),
+                StmtKind::Break(_) => (),
+                StmtKind::Continue(_) => (),
                 StmtKind::Return(_) => (),
                 StmtKind::Decl(decl) => match &mut decl.kind {
-                    DeclKind::TypeDecl(TypeDecl { name, .. }) => {
+                    DeclKind::TypeDecl(TypeDecl { name, .. })
+                    | DeclKind::EnumDecl(EnumDecl { name, .. })
+                    | DeclKind::StructDecl(StructDecl { name, .. })
+                    | DeclKind::TraitDecl(TraitDecl { name, .. }) => {
                         let placeholder_scheme = Scheme {
                             t: self.new_keyword(Keyword::Unknown),
                             type_params: None,
@@ -1474,6 +1884,7 @@ impl Checker {
                             });
                         }
                     }
+                    DeclKind::ImplDecl(_) => (),
                     DeclKind::VarDecl(VarDecl { pattern, .. }) => {
                         let (bindings, _) = self.infer_pattern(pattern, ctx)?;
 
@@ -1537,6 +1948,341 @@ impl Checker {
         Ok(())
     }
 
+    fn report_mutability_violation(&mut self, expr: &Expr, error: &TypeError) {
+        if let Some(ident) = get_root_ident(expr) {
+            self.current_report.diagnostics.push(Diagnostic {
+                code: 1001,
+                kind: DiagnosticKind::MutabilityViolation {
+                    name: ident.name.to_owned(),
+                },
+                message: error.message.to_owned(),
+                reasons: vec![],
+                span: Some(expr.span),
+            });
+        }
+    }
+
+    // Returns the literals in `t` that aren't handled by any of the `arms`.
+    // We only check unions of literals (and booleans) since those are the
+    // only types whose values we can enumerate.
+    fn get_missing_match_arms(&mut self, t: Index, arms: &[MatchArm]) -> Vec<String> {
+        let t = self.prune(t);
+
+        if arms
+            .iter()
+            .any(|arm| matches!(arm.pattern.kind, PatternKind::Enum(_)))
+        {
+            return self.get_missing_enum_variants(t, arms);
+        }
+        let types = match &self.arena[t].kind {
+            TypeKind::Union(union) => union.types.clone(),
+            _ => vec![t],
+        };
+
+        let mut cases: Vec<Literal> = vec![];
+        for t in types {
+            let t = self.prune(t);
+            let new_cases = match &self.arena[t].kind {
+                TypeKind::Literal(lit) => vec![lit.to_owned()],
+                TypeKind::Primitive(Primitive::Boolean) => {
+                    vec![Literal::Boolean(true), Literal::Boolean(false)]
+                }
+                _ => return vec![],
+            };
+            for case in new_cases {
+                if !cases.contains(&case) {
+                    cases.push(case);
+                }
+            }
+        }
+
+        for arm in arms {
+            if arm.guard.is_some() {
+                continue;
+            }
+            match &arm.pattern.kind {
+                PatternKind::Ident(_) | PatternKind::Wildcard => return vec![],
+                PatternKind::Lit(LitPat { lit }) => cases.retain(|case| case != lit),
+                PatternKind::Is(IsPat { is_id, .. }) => cases.retain(|case| {
+                    case.get_scheme_name()
+                        .map_or(true, |name| name.to_lowercase() != is_id.name)
+                }),
+                PatternKind::Object(_)
+                | PatternKind::Tuple(_)
+                | PatternKind::Rest(_)
+                | PatternKind::Enum(_)
+                | PatternKind::Struct(_) => (),
+            }
+        }
+
+        cases.iter().map(|case| case.to_string()).collect()
+    }
+
+    // Returns the variants of the enum `t` that aren't handled by any of the
+    // `arms`.  A variant is only handled if none of its fields are refutable.
+    fn get_missing_enum_variants(&mut self, t: Index, arms: &[MatchArm]) -> Vec<String> {
+        let (name, scheme) = match &self.arena[t].kind {
+            TypeKind::TypeRef(types::TypeRef {
+                name,
+                scheme: Some(scheme),
+                ..
+            }) => (name.to_owned(), scheme.to_owned()),
+            _ => return vec![],
+        };
+
+        let members = match &self.arena[scheme.t].kind {
+            TypeKind::Union(union) => union.types.clone(),
+            _ => vec![scheme.t],
+        };
+        let prefix = format!("{name}.");
+        let mut variants: Vec<String> = vec![];
+        for member in members {
+            match &self.arena[member].kind {
+                TypeKind::TypeRef(tref) if tref.name.starts_with(&prefix) => {
+                    variants.push(tref.name.to_owned())
+                }
+                _ => return vec![],
+            }
+        }
+
+        for arm in arms {
+            if arm.guard.is_some() {
+                continue;
+            }
+            match &arm.pattern.kind {
+                PatternKind::Ident(_) | PatternKind::Wildcard => return vec![],
+                PatternKind::Enum(EnumPat {
+                    enum_name,
+                    variant,
+                    args,
+                }) => {
+                    if args.iter().all(is_irrefutable) {
+                        let name = format!("{}.{}", enum_name.name, variant.name);
+                        variants.retain(|v| v != &name);
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        variants
+    }
+
+    fn infer_props(
+        &mut self,
+        props: &mut [PropOrSpread],
+        ctx: &mut Context,
+    ) -> Result<Vec<types::TObjElem>, TypeError> {
+        let mut prop_types: Vec<types::TObjElem> = vec![];
+        for prop_or_spread in props.iter_mut() {
+            match prop_or_spread {
+                PropOrSpread::Spread(_) => todo!(),
+                PropOrSpread::Prop(prop) => match prop {
+                    expr::Prop::Shorthand {
+                        ident: Ident { name, span: _ },
+                        comments: _,
+                    } => {
+                        prop_types.push(types::TObjElem::Prop(types::TProp {
+                            name: TPropKey::StringKey(name.to_owned()),
+                            readonly: false,
+                            optional: false,
+                            t: self.get_type(name, ctx)?,
+                        }));
+                    }
+                    expr::Prop::Property {
+                        key,
+                        value,
+                        comments: _,
+                    } => {
+                        let prop = match key {
+                            ObjectKey::Ident(ident) => types::TProp {
+                                name: TPropKey::StringKey(ident.name.to_owned()),
+                                readonly: false,
+                                optional: false,
+                                t: self.infer_expression(value, ctx)?,
+                            },
+                            ObjectKey::String(name) => types::TProp {
+                                name: TPropKey::StringKey(name.to_owned()),
+                                readonly: false,
+                                optional: false,
+                                t: self.infer_expression(value, ctx)?,
+                            },
+                            ObjectKey::Number(name) => types::TProp {
+                                name: TPropKey::StringKey(name.to_owned()),
+                                readonly: false,
+                                optional: false,
+                                t: self.infer_expression(value, ctx)?,
+                            },
+                            ObjectKey::Computed(_) => todo!(),
+                        };
+                        prop_types.push(types::TObjElem::Prop(prop));
+                    }
+                },
+            }
+        }
+        Ok(prop_types)
+    }
+
+    // Structs are nominal so the result is a reference to the struct and not
+    // the object type of its fields.
+    fn infer_struct(
+        &mut self,
+        name: &str,
+        props: &mut [PropOrSpread],
+        ctx: &mut Context,
+    ) -> Result<Index, TypeError> {
+        if !ctx.structs.contains_key(name) {
+            return Err(TypeError {
+                message: format!("{name} is not a struct"),
+            });
+        }
+        let scheme = ctx.get_scheme(name)?;
+        let type_args: Vec<Index> = match &scheme.type_params {
+            Some(type_params) => type_params
+                .iter()
+                .map(|tp| self.new_type_var(tp.constraint))
+                .collect(),
+            None => vec![],
+        };
+
+        let fields = self.expand_scheme(ctx, &scheme, &type_args, name)?;
+        let field_names: Vec<String> = match &self.arena[fields].kind {
+            TypeKind::Object(object) => object
+                .elems
+                .iter()
+                .filter_map(|elem| match elem {
+                    TObjElem::Prop(prop) => Some(prop.name.to_string()),
+                    _ => None,
+                })
+                .collect(),
+            _ => vec![],
+        };
+
+        let prop_types = self.infer_props(props, ctx)?;
+        for elem in &prop_types {
+            if let TObjElem::Prop(prop) = elem {
+                let key = prop.name.to_string();
+                if !field_names.contains(&key) {
+                    return Err(TypeError {
+                        message: format!("{name} doesn't have a field named {key}"),
+                    });
+                }
+            }
+        }
+
+        let obj = self.new_object_type(&prop_types);
+        self.unify(ctx, obj, fields)?;
+
+        Ok(self.new_type_ref(name, Some(scheme), &type_args))
+    }
+
+    // `xs[start:end]` is typed like `xs.slice(start, end)` except that slicing
+    // a tuple with literal bounds produces a tuple.
+    fn infer_slice(
+        &mut self,
+        slice: &mut syntax::Slice,
+        ctx: &mut Context,
+    ) -> Result<Index, TypeError> {
+        let syntax::Slice {
+            object,
+            start,
+            end,
+            opt_chain,
+        } = slice;
+
+        let mut obj_t = self.infer_expression(object, ctx)?;
+        let mut has_undefined = false;
+        if *opt_chain {
+            if let TypeKind::Union(union) = &self.arena[obj_t].kind {
+                let types = filter_nullables(&self.arena, &union.types);
+                has_undefined = types.len() != union.types.len();
+                obj_t = self.new_union_type(&types);
+            }
+        }
+
+        let number = self.new_primitive(Primitive::Number);
+        let mut bounds: Vec<Option<i64>> = vec![];
+        for bound in [start.as_mut(), end.as_mut()] {
+            bounds.push(match bound {
+                Some(bound) => {
+                    let t = self.infer_expression(bound, ctx)?;
+                    self.unify(ctx, t, number)?;
+                    self.get_int_literal(bound, t)
+                }
+                None => None,
+            });
+        }
+
+        let obj_t = self.prune(obj_t);
+        let result = match &self.arena[obj_t].kind.clone() {
+            TypeKind::Tuple(types::Tuple { types, .. }) => {
+                let len = types.len() as i64;
+                // Negative indices count back from the end like they do
+                // with `.slice()`.
+                let normalize = |index: i64| match index < 0 {
+                    true => (len + index).max(0),
+                    false => index.min(len),
+                };
+                let start = match start {
+                    None => Some(0),
+                    Some(_) => bounds[0].map(normalize),
+                };
+                let end = match end {
+                    None => Some(len),
+                    Some(_) => bounds[1].map(normalize),
+                };
+                match (start, end) {
+                    (Some(start), Some(end)) => {
+                        let types = match start < end {
+                            true => types[start as usize..end as usize].to_vec(),
+                            false => vec![],
+                        };
+                        self.new_tuple_type(&types)
+                    }
+                    _ => {
+                        let t = self.new_union_type(types);
+                        self.new_array_type(t)
+                    }
+                }
+            }
+            TypeKind::Array(_) => obj_t,
+            TypeKind::Primitive(Primitive::String) | TypeKind::Literal(Literal::String(_)) => {
+                self.new_primitive(Primitive::String)
+            }
+            _ => {
+                return Err(TypeError {
+                    message: format!("{} can't be sliced", self.print_type(&obj_t)),
+                })
+            }
+        };
+
+        match has_undefined {
+            true => {
+                let undefined = self.new_lit_type(&Literal::Undefined);
+                Ok(self.new_union_type(&[result, undefined]))
+            }
+            false => Ok(result),
+        }
+    }
+
+    // Returns the value of `expr` if it's an integer literal.  Negative numbers
+    // are parsed as unary minus so we look at the syntax for those.
+    fn get_int_literal(&self, expr: &Expr, t: Index) -> Option<i64> {
+        if let TypeKind::Literal(Literal::Number(value)) = &self.arena[t].kind {
+            return value.parse().ok();
+        }
+        match &expr.kind {
+            ExprKind::Unary(Unary {
+                op: UnaryOp::Minus,
+                right,
+            }) => match &right.kind {
+                ExprKind::Num(Num { value }) => value.parse::<i64>().ok().map(|value| -value),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
     fn get_ident_member(
         &mut self,
         ctx: &mut Context,
@@ -1582,17 +2328,41 @@ impl Checker {
                 type_args,
                 ..
             }) => {
+                if let (Some(private_fields), TypeKind::Literal(Literal::String(key))) =
+                    (ctx.structs.get(name), &self.arena[key_idx].kind)
+                {
+                    if private_fields.contains(key) {
+                        return Err(TypeError {
+                            message: format!("{key} is a private field of {name}"),
+                        });
+                    }
+                }
                 let obj_idx = match scheme {
                     Some(scheme) => self.expand_scheme(ctx, scheme, type_args, name)?,
                     None => self.expand_alias(ctx, name, type_args)?,
                 };
+                // Methods from traits implemented for this type are looked up
+                // along with its own members.
+                let obj_idx = match (ctx.impls.get(name), &self.arena[obj_idx].kind) {
+                    (Some(traits), TypeKind::Object(obj)) => {
+                        let mut elems = obj.elems.clone();
+                        for trait_name in traits {
+                            let trait_t = self.expand_alias(ctx, trait_name, &[])?;
+                            if let TypeKind::Object(trait_obj) = &self.arena[trait_t].kind {
+                                elems.extend(trait_obj.elems.clone());
+                            }
+                        }
+                        self.new_object_type(&elems)
+                    }
+                    _ => obj_idx,
+                };
                 self.get_ident_member(ctx, obj_idx, key_idx, is_mut)
             }
             TypeKind::Array(types::Array { t }) => {
                 let obj_idx = self.expand_alias(ctx, "Array", &[*t])?;
                 self.get_ident_member(ctx, obj_idx, key_idx, is_mut)
             }
-            TypeKind::Tuple(types::Tuple { types }) => {
+            TypeKind::Tuple(types::Tuple { types, .. }) => {
                 let t = self.new_union_type(types);
                 let obj_idx = self.expand_alias(ctx, "Array", &[t])?;
                 self.get_ident_member(ctx, obj_idx, key_idx, is_mut)
@@ -1693,6 +2463,25 @@ fn is_promise(t: &Type) -> bool {
     )
 }
 
+// Returns true if `pattern` matches all values of the type it's matched against.
+fn is_irrefutable(pattern: &Pattern) -> bool {
+    match &pattern.kind {
+        PatternKind::Ident(_) | PatternKind::Wildcard => true,
+        PatternKind::Rest(syntax::RestPat { arg }) => is_irrefutable(arg),
+        PatternKind::Tuple(syntax::TuplePat { elems, .. }) => elems
+            .iter()
+            .flatten()
+            .all(|elem| is_irrefutable(&elem.pattern)),
+        PatternKind::Object(ObjectPat { props, .. })
+        | PatternKind::Struct(StructPat { props, .. }) => props.iter().all(|prop| match prop {
+            ObjectPatProp::KeyValue(KeyValuePatProp { value, .. }) => is_irrefutable(value),
+            ObjectPatProp::Shorthand(_) => true,
+            ObjectPatProp::Rest(syntax::RestPat { arg }) => is_irrefutable(arg),
+        }),
+        PatternKind::Lit(_) | PatternKind::Is(_) | PatternKind::Enum(_) => false,
+    }
+}
+
 // NOTE: It's possible to have a mix of mutable and immutable bindings be
 // introduced.  In that situation, we only need to check certain parts of
 // the initializer for mutability.
@@ -1737,6 +2526,14 @@ fn find_identifiers(expr: &Expr) -> Result<Vec<Ident>, TypeError> {
     Ok(idents)
 }
 
+fn get_root_ident(expr: &Expr) -> Option<&Ident> {
+    match &expr.kind {
+        ExprKind::Ident(ident) => Some(ident),
+        ExprKind::Member(member) => get_root_ident(&member.object),
+        _ => None,
+    }
+}
+
 // TODO: separate mutability checks from lvalue checks
 fn is_expr_mutable(ctx: &Context, expr: &Expr) -> Result<bool, TypeError> {
     match &expr.kind {
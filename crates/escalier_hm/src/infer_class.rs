@@ -4,7 +4,7 @@ use std::collections::HashMap;
 
 use escalier_ast::{self as syntax, *};
 
-use crate::ast_utils::{find_returns, find_throws};
+use crate::ast_utils::{diverges, find_returns, find_throws};
 use crate::checker::Checker;
 use crate::context::*;
 use crate::infer::generalize_func;
@@ -38,6 +38,7 @@ impl Checker {
             match member {
                 ClassMember::Method(Method {
                     span: _,
+                    comments: _,
                     name,
                     is_public: _, // TODO
                     is_mutating,
@@ -97,34 +98,7 @@ impl Checker {
                     let mut body_ctx = sig_ctx.clone();
                     body_ctx.is_async = *is_async;
 
-                    // TODO: dedupe with infer_expression
-                    let body_t = 'outer: {
-                        match body {
-                            BlockOrExpr::Block(Block { stmts, .. }) => {
-                                for stmt in stmts.iter_mut() {
-                                    body_ctx = body_ctx.clone();
-                                    self.infer_statement(stmt, &mut body_ctx)?;
-                                    if let StmtKind::Return(_) = stmt.kind {
-                                        let ret_types: Vec<Index> = find_returns(body)
-                                            .iter()
-                                            .filter_map(|ret| ret.inferred_type)
-                                            .collect();
-
-                                        // TODO: warn about unreachable code.
-                                        break 'outer self.new_union_type(&ret_types);
-                                    }
-                                }
-
-                                // If we don't encounter a return statement, we assume
-                                // the return type is `undefined`.
-                                self.new_lit_type(&Literal::Undefined)
-                            }
-                            BlockOrExpr::Expr(expr) => {
-                                // TODO: use `find_returns` here as well
-                                self.infer_expression(expr, &mut body_ctx)?
-                            }
-                        }
-                    };
+                    let body_t = self.infer_func_body(body, &mut body_ctx)?;
 
                     let body_throws = find_throws(body);
                     let body_throws = if body_throws.is_empty() {
@@ -199,6 +173,7 @@ impl Checker {
                 ClassMember::Setter(_) => todo!(),
                 ClassMember::Field(Field {
                     span: _,
+                    comments: _,
                     name,
                     is_public: _, // TODO
                     is_static,
@@ -312,6 +287,7 @@ impl Checker {
                 // so that we can reuse some of the logic around function inference
                 ClassMember::Method(Method {
                     span: _,
+                    comments: _,
                     name,
                     is_public: _,
                     is_mutating,
@@ -394,6 +370,7 @@ impl Checker {
                 }
                 ClassMember::Getter(Getter {
                     span: _,
+                    comments: _,
                     name,
                     is_public: _,
                     type_ann,
@@ -423,6 +400,7 @@ impl Checker {
                 }
                 ClassMember::Setter(Setter {
                     span: _,
+                    comments: _,
                     name,
                     is_public: _, // TODO: change to private
                     type_ann: _,  // should always be `undefined` or `void`
@@ -447,6 +425,7 @@ impl Checker {
                 }
                 ClassMember::Field(Field {
                     span: _,
+                    comments: _,
                     name,
                     is_public: _, // TODO
                     is_static,
@@ -490,7 +469,53 @@ impl Checker {
         Ok((instance_scheme, static_type))
     }
 
-    fn infer_func_param(
+    /// Infers the return type of a function or method body.  `ctx` must
+    /// already contain the function's params.
+    // TODO: dedupe with infer_expression
+    pub(crate) fn infer_func_body(
+        &mut self,
+        body: &mut BlockOrExpr,
+        ctx: &mut Context,
+    ) -> Result<Index, TypeError> {
+        // `break` and `continue` can't exit a function.
+        ctx.loops = vec![];
+
+        let body_t = 'outer: {
+            match body {
+                BlockOrExpr::Block(Block { stmts, .. }) => {
+                    for stmt in stmts.iter_mut() {
+                        self.infer_statement(stmt, ctx)?;
+                        if diverges(stmt) {
+                            let ret_types: Vec<Index> = find_returns(body)
+                                .iter()
+                                .filter_map(|ret| ret.inferred_type)
+                                .collect();
+
+                            // TODO: warn about unreachable code.
+                            break 'outer self.new_union_type(&ret_types);
+                        }
+                    }
+
+                    // If we reach the end of the body, we return `undefined`
+                    // in addition to anything returned from nested blocks.
+                    let mut ret_types: Vec<Index> = find_returns(body)
+                        .iter()
+                        .filter_map(|ret| ret.inferred_type)
+                        .collect();
+                    ret_types.push(self.new_lit_type(&Literal::Undefined));
+                    self.new_union_type(&ret_types)
+                }
+                BlockOrExpr::Expr(expr) => {
+                    // TODO: use `find_returns` here as well
+                    self.infer_expression(expr, ctx)?
+                }
+            }
+        };
+
+        Ok(body_t)
+    }
+
+    pub(crate) fn infer_func_param(
         &mut self,
         param: &mut syntax::FuncParam,
         sig_ctx: &mut Context,
@@ -47,80 +47,45 @@ impl Checker {
                     checker.new_rest_type(arg_type)
                 }
                 PatternKind::Object(ObjectPat { props, .. }) => {
-                    let mut rest_opt_ty: Option<Index> = None;
-                    let mut elems: Vec<types::TObjElem> = vec![];
-
-                    for prop in props.iter_mut() {
-                        match prop {
-                            // re-assignment, e.g. {x: new_x, y: new_y} = point
-                            ObjectPatProp::KeyValue(KeyValuePatProp { key, value, .. }) => {
-                                // We ignore `init` for now, we can come back later to handle
-                                // default values.
-                                // TODO: handle default values
-
-                                // TODO: bubble the error up from infer_patter_rec() if there is one.
-                                let value_type =
-                                    infer_pattern_rec(checker, value.as_mut(), assump, ctx)?;
-
-                                elems.push(types::TObjElem::Prop(types::TProp {
-                                    name: TPropKey::StringKey(key.name.to_owned()),
-                                    optional: false,
-                                    readonly: false,
-                                    t: value_type,
-                                }))
-                            }
-                            ObjectPatProp::Shorthand(ShorthandPatProp { ident, .. }) => {
-                                // We ignore `init` for now, we can come back later to handle
-                                // default values.
-                                // TODO: handle default values
-
-                                let t = checker.new_type_var(None);
-                                if assump
-                                    .insert(
-                                        ident.name.to_owned(),
-                                        Binding {
-                                            index: t,
-                                            is_mut: false,
-                                        },
-                                    )
-                                    .is_some()
-                                {
-                                    todo!("return an error");
-                                }
-
-                                elems.push(types::TObjElem::Prop(types::TProp {
-                                    name: TPropKey::StringKey(ident.name.to_owned()),
-                                    optional: false,
-                                    readonly: false,
-                                    t,
-                                }))
-                            }
-                            ObjectPatProp::Rest(rest) => {
-                                if rest_opt_ty.is_some() {
-                                    return Err(TypeError {
-                                        message:
-                                            "Maximum one rest pattern allowed in object patterns"
-                                                .to_string(),
-                                    });
-                                }
-                                // TypeScript doesn't support spreading/rest in types so instead we
-                                // do the following conversion:
-                                // {x, y, ...rest} -> {x: A, y: B} & C
-                                // TODO: bubble the error up from infer_patter_rec() if there is one.
-                                rest_opt_ty =
-                                    Some(infer_pattern_rec(checker, &mut rest.arg, assump, ctx)?);
-                            }
+                    infer_obj_pat_props(checker, props, assump, ctx)?
+                }
+                PatternKind::Struct(ast::StructPat { name, props }) => {
+                    let name = &name.name;
+                    let private_fields = match ctx.structs.get(name) {
+                        Some(private_fields) => private_fields,
+                        None => {
+                            return Err(TypeError {
+                                message: format!("{name} is not a struct"),
+                            })
+                        }
+                    };
+                    for prop in props.iter() {
+                        let key = match prop {
+                            ObjectPatProp::KeyValue(KeyValuePatProp { key, .. }) => &key.name,
+                            ObjectPatProp::Shorthand(ShorthandPatProp { ident, .. }) => &ident.name,
+                            ObjectPatProp::Rest(_) => continue,
+                        };
+                        if private_fields.contains(key) {
+                            return Err(TypeError {
+                                message: format!("{key} is a private field of {name}"),
+                            });
                         }
                     }
 
-                    let obj_type = checker.new_object_type(&elems);
+                    let scheme = ctx.get_scheme(name)?;
+                    let type_args: Vec<Index> = match &scheme.type_params {
+                        Some(type_params) => type_params
+                            .iter()
+                            .map(|tp| checker.new_type_var(tp.constraint))
+                            .collect(),
+                        None => vec![],
+                    };
 
-                    match rest_opt_ty {
-                        // TODO: Replace this with a proper Rest/Spread type
-                        // See https://github.com/microsoft/TypeScript/issues/10727
-                        Some(rest_ty) => checker.new_intersection_type(&[obj_type, rest_ty]),
-                        None => obj_type,
-                    }
+                    let fields = checker.expand_scheme(ctx, &scheme, &type_args, name)?;
+                    let obj_type = infer_obj_pat_props(checker, props, assump, ctx)?;
+                    checker.unify(ctx, fields, obj_type)?;
+
+                    checker.new_type_ref(name, Some(scheme), &type_args)
                 }
                 PatternKind::Tuple(ast::TuplePat { elems, optional: _ }) => {
                     let mut elem_types = vec![];
@@ -158,12 +123,144 @@ impl Checker {
 
                     t
                 }
+                PatternKind::Enum(ast::EnumPat {
+                    enum_name,
+                    variant,
+                    args,
+                }) => {
+                    let name = format!("{}.{}", enum_name.name, variant.name);
+                    let scheme = ctx.get_scheme(&name)?;
+                    let type_args: Vec<Index> = match &scheme.type_params {
+                        Some(type_params) => type_params
+                            .iter()
+                            .map(|tp| checker.new_type_var(tp.constraint))
+                            .collect(),
+                        None => vec![],
+                    };
+
+                    let obj = checker.expand_scheme(ctx, &scheme, &type_args, &name)?;
+                    let payload = match &checker.arena[obj].kind {
+                        TypeKind::Object(types::Object { elems }) => {
+                            elems.iter().find_map(|elem| match elem {
+                                TObjElem::Prop(prop) if prop.name.to_string() == "payload" => {
+                                    Some(prop.t)
+                                }
+                                _ => None,
+                            })
+                        }
+                        _ => None,
+                    };
+
+                    let mut arg_types: Vec<Index> = vec![];
+                    for arg in args.iter_mut() {
+                        arg_types.push(infer_pattern_rec(checker, arg, assump, ctx)?);
+                    }
+
+                    // A single arg matches the whole payload while multiple
+                    // args destructure the payload's fields.
+                    match (payload, arg_types.as_slice()) {
+                        (_, []) => (),
+                        (Some(payload), [t]) => checker.unify(ctx, *t, payload)?,
+                        (Some(payload), _) => {
+                            let tuple = checker.new_tuple_type(&arg_types);
+                            checker.unify(ctx, tuple, payload)?;
+                        }
+                        (None, _) => {
+                            return Err(TypeError {
+                                message: format!("{name} doesn't have any fields"),
+                            })
+                        }
+                    }
+
+                    checker.new_type_ref(&name, Some(scheme), &type_args)
+                }
                 PatternKind::Wildcard => checker.new_type_var(None),
             };
 
+            pattern.inferred_type = Some(t);
+
             Ok(t)
         }
 
+        fn infer_obj_pat_props(
+            checker: &mut Checker,
+            props: &mut [ObjectPatProp],
+            assump: &mut Assump,
+            ctx: &Context,
+        ) -> Result<Index, TypeError> {
+            let mut rest_opt_ty: Option<Index> = None;
+            let mut elems: Vec<types::TObjElem> = vec![];
+
+            for prop in props.iter_mut() {
+                match prop {
+                    // re-assignment, e.g. {x: new_x, y: new_y} = point
+                    ObjectPatProp::KeyValue(KeyValuePatProp { key, value, .. }) => {
+                        // We ignore `init` for now, we can come back later to handle
+                        // default values.
+                        // TODO: handle default values
+
+                        // TODO: bubble the error up from infer_patter_rec() if there is one.
+                        let value_type = infer_pattern_rec(checker, value.as_mut(), assump, ctx)?;
+
+                        elems.push(types::TObjElem::Prop(types::TProp {
+                            name: TPropKey::StringKey(key.name.to_owned()),
+                            optional: false,
+                            readonly: false,
+                            t: value_type,
+                        }))
+                    }
+                    ObjectPatProp::Shorthand(ShorthandPatProp { ident, .. }) => {
+                        // We ignore `init` for now, we can come back later to handle
+                        // default values.
+                        // TODO: handle default values
+
+                        let t = checker.new_type_var(None);
+                        if assump
+                            .insert(
+                                ident.name.to_owned(),
+                                Binding {
+                                    index: t,
+                                    is_mut: false,
+                                },
+                            )
+                            .is_some()
+                        {
+                            todo!("return an error");
+                        }
+
+                        elems.push(types::TObjElem::Prop(types::TProp {
+                            name: TPropKey::StringKey(ident.name.to_owned()),
+                            optional: false,
+                            readonly: false,
+                            t,
+                        }))
+                    }
+                    ObjectPatProp::Rest(rest) => {
+                        if rest_opt_ty.is_some() {
+                            return Err(TypeError {
+                                message: "Maximum one rest pattern allowed in object patterns"
+                                    .to_string(),
+                            });
+                        }
+                        // TypeScript doesn't support spreading/rest in types so instead we
+                        // do the following conversion:
+                        // {x, y, ...rest} -> {x: A, y: B} & C
+                        // TODO: bubble the error up from infer_patter_rec() if there is one.
+                        rest_opt_ty = Some(infer_pattern_rec(checker, &mut rest.arg, assump, ctx)?);
+                    }
+                }
+            }
+
+            let obj_type = checker.new_object_type(&elems);
+
+            Ok(match rest_opt_ty {
+                // TODO: Replace this with a proper Rest/Spread type
+                // See https://github.com/microsoft/TypeScript/issues/10727
+                Some(rest_ty) => checker.new_intersection_type(&[obj_type, rest_ty]),
+                None => obj_type,
+            })
+        }
+
         let mut assump = Assump::default();
         let pat_type = infer_pattern_rec(self, pattern, &mut assump, ctx)?;
 
@@ -181,10 +278,13 @@ pub fn pattern_to_tpat(pattern: &Pattern, is_func_param: bool) -> TPat {
         PatternKind::Rest(e_rest) => TPat::Rest(types::RestPat {
             arg: Box::from(pattern_to_tpat(e_rest.arg.as_ref(), is_func_param)),
         }),
-        PatternKind::Object(e_obj) => {
+        PatternKind::Struct(_) if is_func_param => {
+            panic!("Struct patterns not allowed in function params")
+        }
+        PatternKind::Object(ObjectPat { props, .. })
+        | PatternKind::Struct(ast::StructPat { props, .. }) => {
             // TODO: replace TProp with the type equivalent of EFnParamObjectPatProp
-            let props: Vec<types::TObjectPatProp> = e_obj
-                .props
+            let props: Vec<types::TObjectPatProp> = props
                 .iter()
                 .map(|e_prop| {
                     match e_prop {
@@ -242,6 +342,24 @@ pub fn pattern_to_tpat(pattern: &Pattern, is_func_param: bool) -> TPat {
                 })
             }
         }
+        PatternKind::Enum(EnumPat {
+            enum_name,
+            variant,
+            args,
+        }) => {
+            if is_func_param {
+                panic!("Enum patterns not allowed in function params")
+            } else {
+                TPat::Enum(TEnumPat {
+                    enum_name: enum_name.name.to_owned(),
+                    variant: variant.name.to_owned(),
+                    args: args
+                        .iter()
+                        .map(|arg| pattern_to_tpat(arg, is_func_param))
+                        .collect(),
+                })
+            }
+        }
         PatternKind::Wildcard => {
             if is_func_param {
                 panic!("Wildcard patterns not allowed in function params")
@@ -0,0 +1,226 @@
+use generational_arena::Index;
+
+use escalier_ast::{self as syntax, *};
+
+use crate::checker::Checker;
+use crate::context::*;
+use crate::type_error::TypeError;
+use crate::types::{self, *};
+
+impl Checker {
+    pub fn infer_trait_decl(
+        &mut self,
+        decl: &mut TraitDecl,
+        ctx: &mut Context,
+    ) -> Result<Index, TypeError> {
+        let TraitDecl { name, methods } = decl;
+
+        let mut elems: Vec<TObjElem> = vec![];
+        let mut sig_ctxs: Vec<Context> = vec![];
+
+        // The signatures are inferred first so that default methods can call
+        // any of the trait's methods.
+        for method in methods.iter_mut() {
+            // NOTE: We clone `ctx` so that type params don't escape the signature
+            let mut sig_ctx = ctx.clone();
+
+            let type_params = self.infer_type_params(&mut method.type_params, &mut sig_ctx)?;
+            let params = method
+                .params
+                .iter_mut()
+                .map(|param| self.infer_func_param(param, &mut sig_ctx))
+                .collect::<Result<Vec<_>, _>>()?;
+            let ret = self.infer_type_ann(&mut method.type_ann, &mut sig_ctx)?;
+
+            elems.push(TObjElem::Method(TMethod {
+                name: TPropKey::StringKey(method.name.name.to_owned()),
+                mutates: method.is_mutating,
+                function: types::Function {
+                    params,
+                    ret,
+                    type_params,
+                    throws: None,
+                },
+            }));
+            sig_ctxs.push(sig_ctx);
+        }
+
+        let t = self.new_object_type(&elems);
+        let scheme = Scheme {
+            t,
+            type_params: None,
+            is_type_param: false,
+        };
+        ctx.schemes.insert(name.to_owned(), scheme.clone());
+
+        let default_methods: Vec<String> = methods
+            .iter()
+            .filter(|method| method.body.is_some())
+            .map(|method| method.name.name.to_owned())
+            .collect();
+        ctx.traits.insert(name.to_owned(), default_methods);
+
+        for (method, sig_ctx) in methods.iter_mut().zip(sig_ctxs) {
+            if let Some(body) = &mut method.body {
+                let mut body_ctx = sig_ctx.clone();
+                let binding = Binding {
+                    index: self.new_type_ref(name, Some(scheme.clone()), &[]),
+                    is_mut: method.is_mutating,
+                };
+                body_ctx.values.insert("self".to_string(), binding);
+
+                let body_t = self.infer_func_body(body, &mut body_ctx)?;
+                let ret = self.infer_type_ann(&mut method.type_ann, &mut body_ctx)?;
+                self.unify(&body_ctx, body_t, ret)?;
+            }
+        }
+
+        Ok(t)
+    }
+
+    pub fn infer_impl_decl(
+        &mut self,
+        decl: &mut ImplDecl,
+        ctx: &mut Context,
+    ) -> Result<Index, TypeError> {
+        let ImplDecl {
+            trait_name,
+            type_name,
+            methods,
+        } = decl;
+        let trait_name = &trait_name.name;
+        let type_name = &type_name.name;
+
+        let default_methods = match ctx.traits.get(trait_name) {
+            Some(default_methods) => default_methods.to_owned(),
+            None => {
+                return Err(TypeError {
+                    message: format!("{trait_name} is not a trait"),
+                })
+            }
+        };
+        if !ctx.structs.contains_key(type_name) {
+            return Err(TypeError {
+                message: format!("{type_name} is not a struct"),
+            });
+        }
+
+        let trait_t = self.expand_alias(ctx, trait_name, &[])?;
+        let trait_elems = match &self.arena[trait_t].kind {
+            TypeKind::Object(obj) => obj.elems.clone(),
+            _ => unreachable!("traits are always object types"),
+        };
+
+        // NOTE: Methods in an `impl` block don't have access to the struct's
+        // private fields since they're installed on the class's prototype
+        // outside of the class body.
+        let mut impl_ctx = ctx.clone();
+
+        // `self` is typed as the struct with its own type params as type args.
+        let scheme = ctx.get_scheme(type_name)?;
+        let mut type_args: Vec<Index> = vec![];
+        for tp in scheme.type_params.iter().flatten() {
+            let tp_scheme = Scheme {
+                t: match tp.constraint {
+                    Some(constraint) => constraint,
+                    None => self.new_keyword(Keyword::Unknown),
+                },
+                type_params: None,
+                is_type_param: true,
+            };
+            impl_ctx.schemes.insert(tp.name.to_owned(), tp_scheme);
+            type_args.push(self.new_type_ref(&tp.name, None, &[]));
+        }
+        let self_t = self.new_type_ref(type_name, Some(scheme), &type_args);
+
+        let mut implemented: Vec<String> = vec![];
+        for method in methods.iter_mut() {
+            let syntax::Method {
+                name,
+                is_mutating,
+                function,
+                ..
+            } = method;
+
+            let name = match name {
+                PropName::Ident(Ident { name, span: _ }) => name.to_owned(),
+                PropName::Computed(_) => todo!(),
+            };
+
+            let trait_method = trait_elems.iter().find_map(|elem| match elem {
+                TObjElem::Method(m) if m.name == TPropKey::StringKey(name.clone()) => {
+                    Some(m.to_owned())
+                }
+                _ => None,
+            });
+            let trait_method = match trait_method {
+                Some(trait_method) => trait_method,
+                None => {
+                    return Err(TypeError {
+                        message: format!("{name} is not a member of trait {trait_name}"),
+                    })
+                }
+            };
+
+            let mut sig_ctx = impl_ctx.clone();
+            let type_params = self.infer_type_params(&mut function.type_params, &mut sig_ctx)?;
+            let params = function
+                .params
+                .iter_mut()
+                .map(|param| self.infer_func_param(param, &mut sig_ctx))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let mut body_ctx = sig_ctx.clone();
+            body_ctx.is_async = function.is_async;
+            let binding = Binding {
+                index: self_t,
+                is_mut: *is_mutating,
+            };
+            body_ctx.values.insert("self".to_string(), binding);
+
+            let body_t = self.infer_func_body(&mut function.body, &mut body_ctx)?;
+            let ret = match &mut function.type_ann {
+                Some(type_ann) => self.infer_type_ann(type_ann, &mut sig_ctx)?,
+                None => self.new_type_var(None),
+            };
+            self.unify(&body_ctx, body_t, ret)?;
+
+            let method_t = self.new_func_type(&params, ret, &type_params, None);
+            let trait_method_t = self.new_func_type(
+                &trait_method.function.params,
+                trait_method.function.ret,
+                &trait_method.function.type_params,
+                None,
+            );
+            self.unify(&sig_ctx, method_t, trait_method_t)?;
+
+            implemented.push(name);
+        }
+
+        for elem in &trait_elems {
+            if let TObjElem::Method(TMethod {
+                name: TPropKey::StringKey(name),
+                ..
+            }) = elem
+            {
+                if !implemented.contains(name) && !default_methods.contains(name) {
+                    return Err(TypeError {
+                        message: format!(
+                            "{type_name} is missing method {name} from trait {trait_name}"
+                        ),
+                    });
+                }
+            }
+        }
+
+        let impls = ctx.impls.entry(type_name.to_owned()).or_default();
+        if impls.contains(trait_name) {
+            return Err(TypeError {
+                message: format!("{trait_name} is already implemented for {type_name}"),
+            });
+        }
+        impls.push(trait_name.to_owned());
+
+        Ok(self_t)
+    }
+}
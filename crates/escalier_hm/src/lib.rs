@@ -3,15 +3,19 @@ mod ast_utils;
 mod folder;
 mod infer_class;
 mod infer_pattern;
+mod infer_trait;
 mod key_value_store;
 mod provenance;
+mod regex;
 mod unify;
 mod visitor;
 
 pub mod checker;
 pub mod context;
 pub mod diagnostic;
+pub mod docs;
 pub mod infer;
+pub mod snapshot;
 pub mod type_error;
 pub mod types;
 pub mod util;
@@ -0,0 +1,155 @@
+use generational_arena::Index;
+use regex_syntax::hir::{self, Hir, HirKind, RepetitionKind, RepetitionRange};
+
+use escalier_ast::{Literal, Regex};
+
+use crate::checker::Checker;
+use crate::types::*;
+
+struct CaptureGroup {
+    name: Option<String>,
+    // Whether the group can be skipped while the regex as a whole matches,
+    // e.g. `(a)?` or `(a)|(b)`.
+    optional: bool,
+}
+
+// Returns the capture groups in the order in which they appear in `pattern` or
+// `None` if we don't understand the pattern.
+fn get_capture_groups(pattern: &str) -> Option<Vec<CaptureGroup>> {
+    // regex_syntax uses Python's syntax for named groups and doesn't allow
+    // `/` to be escaped.
+    let pattern = pattern
+        .replace("(?<=", "(?\0=")
+        .replace("(?<!", "(?\0!")
+        .replace("(?<", "(?P<")
+        .replace("(?\0", "(?<")
+        .replace("\\/", "/");
+    let hir = regex_syntax::Parser::new().parse(&pattern).ok()?;
+
+    let mut groups = vec![];
+    collect_capture_groups(&hir, false, &mut groups);
+    Some(groups)
+}
+
+fn collect_capture_groups(hir: &Hir, optional: bool, groups: &mut Vec<CaptureGroup>) {
+    match hir.kind() {
+        HirKind::Group(group) => {
+            match &group.kind {
+                hir::GroupKind::CaptureIndex(_) => groups.push(CaptureGroup {
+                    name: None,
+                    optional,
+                }),
+                hir::GroupKind::CaptureName { name, .. } => groups.push(CaptureGroup {
+                    name: Some(name.to_owned()),
+                    optional,
+                }),
+                hir::GroupKind::NonCapturing => (),
+            }
+            collect_capture_groups(&group.hir, optional, groups);
+        }
+        HirKind::Repetition(repetition) => {
+            let can_skip = matches!(
+                repetition.kind,
+                RepetitionKind::ZeroOrOne
+                    | RepetitionKind::ZeroOrMore
+                    | RepetitionKind::Range(RepetitionRange::Exactly(0))
+                    | RepetitionKind::Range(RepetitionRange::AtLeast(0))
+                    | RepetitionKind::Range(RepetitionRange::Bounded(0, _))
+            );
+            collect_capture_groups(&repetition.hir, optional || can_skip, groups);
+        }
+        HirKind::Concat(hirs) => {
+            for hir in hirs {
+                collect_capture_groups(hir, optional, groups);
+            }
+        }
+        HirKind::Alternation(hirs) => {
+            for hir in hirs {
+                collect_capture_groups(hir, true, groups);
+            }
+        }
+        _ => (),
+    }
+}
+
+impl Checker {
+    // `/pattern/flags` is typed as `RegExp<"pattern", "flags">` so that the
+    // pattern is available when typing the results of `match()` and `exec()`.
+    pub fn infer_regex(&mut self, regex: &Regex) -> Index {
+        let pattern = self.new_lit_type(&Literal::String(regex.pattern.to_owned()));
+        let flags = regex.flags.to_owned().unwrap_or_default();
+        let flags = self.new_lit_type(&Literal::String(flags));
+        self.new_type_ref("RegExp", None, &[pattern, flags])
+    }
+
+    // Expands `RegExpMatchArray<TPattern, TFlags>` and `RegExpExecArray<TPattern,
+    // TFlags>` into types describing the capture groups in `TPattern`, e.g.
+    // `RegExpMatchArray<"(?<a>a)(b)?", "">` is expanded to:
+    // `[string, string, string | undefined] & {groups: {a: string}, index: number, input: string}`
+    pub fn expand_regex_match(&mut self, name: &str, type_args: &[Index]) -> Option<Index> {
+        if name != "RegExpMatchArray" && name != "RegExpExecArray" {
+            return None;
+        }
+        let (pattern, flags) = match type_args {
+            [pattern, flags] => (self.prune(*pattern), self.prune(*flags)),
+            _ => return None,
+        };
+        let (pattern, flags) = match (&self.arena[pattern].kind, &self.arena[flags].kind) {
+            (
+                TypeKind::Literal(Literal::String(pattern)),
+                TypeKind::Literal(Literal::String(flags)),
+            ) => (pattern.to_owned(), flags.to_owned()),
+            _ => return None,
+        };
+
+        let string = self.new_primitive(Primitive::String);
+
+        // `str.match(re)` returns all of the matches when `re` is global.
+        if name == "RegExpMatchArray" && flags.contains('g') {
+            return Some(self.new_array_type(string));
+        }
+
+        let groups = get_capture_groups(&pattern)?;
+
+        let undefined = self.new_lit_type(&Literal::Undefined);
+        let maybe_string = self.new_union_type(&[string, undefined]);
+
+        let mut types = vec![string];
+        let mut named_groups: Vec<TObjElem> = vec![];
+        for group in groups {
+            let t = if group.optional { maybe_string } else { string };
+            types.push(t);
+            if let Some(name) = group.name {
+                named_groups.push(TObjElem::Prop(TProp {
+                    name: TPropKey::StringKey(name),
+                    optional: false,
+                    readonly: false,
+                    t,
+                }));
+            }
+        }
+
+        let number = self.new_primitive(Primitive::Number);
+        let mut elems = vec![];
+        if !named_groups.is_empty() {
+            elems.push(TObjElem::Prop(TProp {
+                name: TPropKey::StringKey("groups".to_string()),
+                optional: false,
+                readonly: false,
+                t: self.new_object_type(&named_groups),
+            }));
+        }
+        for (name, t) in [("index", number), ("input", string)] {
+            elems.push(TObjElem::Prop(TProp {
+                name: TPropKey::StringKey(name.to_string()),
+                optional: false,
+                readonly: false,
+                t,
+            }));
+        }
+
+        let tuple = self.new_tuple_type(&types);
+        let obj = self.new_object_type(&elems);
+        Some(self.new_intersection_type(&[tuple, obj]))
+    }
+}
@@ -0,0 +1,101 @@
+use generational_arena::Arena;
+use im::hashmap::HashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::checker::Checker;
+use crate::context::{Binding, Context};
+use crate::docs::JsDoc;
+use crate::types::{Scheme, Type};
+
+// Snapshots start with these bytes so that we can tell them apart from other
+// files.
+static MAGIC: &[u8; 4] = b"ESCS";
+
+// This should be bumped whenever the types in types.rs or the layout of
+// `Snapshot` change so that stale snapshots are rejected instead of being
+// loaded incorrectly.
+const VERSION: u32 = 5;
+
+#[derive(Serialize, Deserialize)]
+struct Snapshot {
+    arena: Arena<Type>,
+    scope: Scope,
+}
+
+// The parts of a `Context` that are filled in when loading a lib.  Ambient
+// modules, e.g. `declare module "fs" {}`, have scopes of their own.
+#[derive(Clone, Serialize, Deserialize)]
+struct Scope {
+    values: HashMap<String, Binding>,
+    schemes: HashMap<String, Scheme>,
+    docs: HashMap<String, JsDoc>,
+    modules: HashMap<String, Scope>,
+}
+
+impl From<&Context> for Scope {
+    fn from(ctx: &Context) -> Self {
+        Scope {
+            values: ctx.values.to_owned(),
+            schemes: ctx.schemes.to_owned(),
+            docs: ctx.docs.to_owned(),
+            modules: ctx
+                .modules
+                .iter()
+                .map(|(name, module)| (name.to_owned(), Scope::from(module)))
+                .collect(),
+        }
+    }
+}
+
+impl From<Scope> for Context {
+    fn from(scope: Scope) -> Self {
+        Context {
+            values: scope.values,
+            schemes: scope.schemes,
+            docs: scope.docs,
+            modules: scope
+                .modules
+                .into_iter()
+                .map(|(name, module)| (name, Context::from(module)))
+                .collect(),
+            ..Context::default()
+        }
+    }
+}
+
+/// Serializes the types in `checker` along with the values, schemes, docs and
+/// ambient modules in `ctx`, e.g. after parsing lib.es5.d.ts, so that they can
+/// be loaded later with `load_snapshot` instead of being inferred again.
+pub fn save_snapshot(checker: &Checker, ctx: &Context) -> Result<Vec<u8>, String> {
+    let snapshot = Snapshot {
+        arena: checker.arena.to_owned(),
+        scope: Scope::from(ctx),
+    };
+
+    let mut bytes = MAGIC.to_vec();
+    bytes.extend(VERSION.to_le_bytes());
+    bincode::serialize_into(&mut bytes, &snapshot).map_err(|err| err.to_string())?;
+
+    Ok(bytes)
+}
+
+/// Loads a snapshot that was created by `save_snapshot`.
+pub fn load_snapshot(bytes: &[u8]) -> Result<(Checker, Context), String> {
+    let Some(bytes) = bytes.strip_prefix(MAGIC) else {
+        return Err("not a snapshot".to_string());
+    };
+    let (version, bytes) = bytes.split_at(4.min(bytes.len()));
+    if version != VERSION.to_le_bytes() {
+        return Err("snapshot was created by a different version".to_string());
+    }
+
+    let snapshot: Snapshot = bincode::deserialize(bytes).map_err(|err| err.to_string())?;
+
+    let checker = Checker {
+        arena: snapshot.arena,
+        ..Checker::default()
+    };
+    let ctx = Context::from(snapshot.scope);
+
+    Ok((checker, ctx))
+}
@@ -1,5 +1,6 @@
 // Types and type constructors
 use generational_arena::Index;
+use serde::{Deserialize, Serialize};
 use std::convert::From;
 use std::fmt;
 
@@ -10,7 +11,7 @@ use escalier_ast::{BindingIdent, Literal as Lit};
 use crate::checker::Checker;
 use crate::provenance::Provenance;
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct TypeVar {
     pub id: usize,
     pub instance: Option<Index>,
@@ -18,7 +19,7 @@ pub struct TypeVar {
 }
 
 // TODO: rename this TypeRef
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct TypeRef {
     pub name: String,
     // NOTE: if `scheme` is `None` then we need to look up the type in the
@@ -27,7 +28,7 @@ pub struct TypeRef {
     pub type_args: Vec<Index>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Keyword {
     Never,
     Object,
@@ -45,7 +46,7 @@ impl fmt::Display for Keyword {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Primitive {
     Number,
     Boolean,
@@ -76,7 +77,7 @@ impl fmt::Display for Primitive {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct Function {
     pub params: Vec<FuncParam>,
     pub ret: Index,
@@ -86,7 +87,7 @@ pub struct Function {
     pub throws: Option<Index>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct FuncParam {
     pub pattern: TPat,
     pub t: Index,
@@ -109,7 +110,7 @@ impl FuncParam {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum TPat {
     Ident(BindingIdent),
     Rest(RestPat),
@@ -117,97 +118,105 @@ pub enum TPat {
     Object(TObjectPat),
     Lit(TLitPat),
     Is(TIsPat),
+    Enum(TEnumPat),
     Wildcard,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct RestPat {
     pub arg: Box<TPat>,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct TuplePat {
     pub elems: Vec<Option<TPat>>,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct TObjectPat {
     pub props: Vec<TObjectPatProp>,
 }
 
 // TODO: update this to match AST changes to ObjectPatProp
-#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum TObjectPatProp {
     KeyValue(TObjectKeyValuePatProp),
     Assign(TObjectAssignPatProp),
     Rest(RestPat),
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct TObjectKeyValuePatProp {
     pub key: String,
     pub value: TPat,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct TObjectAssignPatProp {
     pub key: String,
     pub value: Option<Index>,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct TLitPat {
     pub lit: Lit,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct TIsPat {
     pub ident: String,
     pub is_id: String,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct TEnumPat {
+    pub enum_name: String,
+    pub variant: String,
+    pub args: Vec<TPat>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct TypeParam {
     pub name: String,
     pub constraint: Option<Index>,
     pub default: Option<Index>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Call {
     pub args: Vec<Index>,
     pub ret: Index,
 }
 
 // TODO: update this struct to use `Function` internally
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct TMethod {
     pub name: TPropKey,
     pub mutates: bool,
     pub function: Function,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct TGetter {
     pub name: TPropKey,
     pub ret: Index,
     pub throws: Option<Index>,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct TSetter {
     pub name: TPropKey,
     pub param: FuncParam,
     pub throws: Option<Index>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct TIndexKey {
     pub name: String,
     pub t: Index,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum TPropKey {
     StringKey(String),
     NumberKey(String),
@@ -222,7 +231,7 @@ impl fmt::Display for TPropKey {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct TProp {
     pub name: TPropKey,
     pub optional: bool,
@@ -244,7 +253,7 @@ impl TProp {
     }
 }
 
-// #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+// #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 // pub struct TCallable {
 //     pub params: Vec<FuncParam>,
 //     pub ret: Index,
@@ -252,26 +261,27 @@ impl TProp {
 //     pub throws: Option<Index>,
 // }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum MappedModifier {
     Add,
     Remove,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct MappedType {
     pub key: Index,
     pub value: Index,
     pub target: String,
     pub source: Index,
     pub optional: Option<MappedModifier>,
+    pub readonly: Option<MappedModifier>,
 
     // First half of a Conditional
     pub check: Option<Index>,
     pub extends: Option<Index>,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum TObjElem {
     Call(Function),
     // NOTE: type_params on constructors should be a subset of type_params on
@@ -285,68 +295,77 @@ pub enum TObjElem {
     // RestSpread - we can use this instead of converting {a, ...x} to {a} & tvar
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Object {
     pub elems: Vec<TObjElem>,
 }
 
 // NOTE: this is only used for the rest element in array patterns since we
 // treat `{a, ...x}` as `{a} & x` where `x` is a type variable.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Rest {
     pub arg: Index,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum UtilityKind {
     KeyOf,
     Index,
     Cond,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Utility {
     pub kind: UtilityKind,
     pub types: Vec<Index>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Mutable {
     pub t: Index,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Union {
     pub types: Vec<Index>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Intersection {
     pub types: Vec<Index>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Tuple {
     pub types: Vec<Index>,
+    // Whether each element can be omitted, e.g. `string?` in `[number, string?]`.
+    // Only tuples from .d.ts files have optional elements.
+    pub optional: Vec<bool>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+impl Tuple {
+    pub fn is_optional(&self, i: usize) -> bool {
+        self.optional.get(i).copied().unwrap_or(false)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Array {
     pub t: Index,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct KeyOf {
     pub t: Index,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct IndexedAccess {
     pub obj: Index,
     pub index: Index,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Conditional {
     pub check: Index,
     pub extends: Index,
@@ -354,17 +373,26 @@ pub struct Conditional {
     pub false_type: Index,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Infer {
     pub name: String,
     // TODO
     // pub constraint: Option<Index>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Wildcard {}
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// The return type of a user-defined type guard, e.g. `x is Foo`, or an
+/// assertion function, e.g. `asserts x is Foo` or `asserts x`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TypePredicate {
+    pub param: String,
+    pub t: Option<Index>,
+    pub asserts: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum TBinaryOp {
     Add,
     Sub,
@@ -374,14 +402,14 @@ pub enum TBinaryOp {
     // TODO: fill this out with more operators
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct BinaryT {
     pub op: TBinaryOp,
     pub left: Index,
     pub right: Index,
 }
 
-#[derive(Debug, Clone, Hash)]
+#[derive(Debug, Clone, Hash, Serialize, Deserialize)]
 pub enum TypeKind {
     TypeVar(TypeVar),
     TypeRef(TypeRef),
@@ -401,11 +429,14 @@ pub enum TypeKind {
     Infer(Infer),
     Wildcard,
     Binary(BinaryT),
+    TypePredicate(TypePredicate),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Type {
     pub kind: TypeKind,
+    // Provenance points back into the AST so it isn't included in snapshots.
+    #[serde(skip)]
     pub provenance: Option<Provenance>,
 }
 
@@ -418,7 +449,7 @@ impl From<TypeKind> for Type {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Scheme {
     pub t: Index,
     pub type_params: Option<Vec<TypeParam>>,
@@ -462,8 +493,17 @@ impl Checker {
             },
             TypeKind::Union(Union { types }) => self.print_types(types).join(" | "),
             TypeKind::Intersection(Intersection { types }) => self.print_types(types).join(" & "),
-            TypeKind::Tuple(Tuple { types }) => {
-                format!("[{}]", self.print_types(types).join(", "))
+            TypeKind::Tuple(tuple) => {
+                let elems = tuple
+                    .types
+                    .iter()
+                    .enumerate()
+                    .map(|(i, t)| match tuple.is_optional(i) {
+                        true => format!("{}?", self.print_optional_elem(t)),
+                        false => self.print_type(t),
+                    })
+                    .collect::<Vec<_>>();
+                format!("[{}]", elems.join(", "))
             }
             TypeKind::Array(Array { t }) => format!("{}[]", self.print_type(t)),
             TypeKind::TypeRef(TypeRef {
@@ -568,6 +608,7 @@ impl Checker {
                             target,
                             source,
                             optional: _, // TODO
+                            readonly: _, // TODO
                             // TODO: handle `if`-clause
                             check: _,
                             extends: _,
@@ -707,6 +748,13 @@ impl Checker {
             }
             TypeKind::Infer(Infer { name }) => format!("infer {}", name),
             TypeKind::Wildcard => "_".to_string(),
+            TypeKind::TypePredicate(TypePredicate { param, t, asserts }) => {
+                let prefix = if *asserts { "asserts " } else { "" };
+                match t {
+                    Some(t) => format!("{prefix}{param} is {}", self.print_type(t)),
+                    None => format!("{prefix}{param}"),
+                }
+            }
             TypeKind::Binary(BinaryT { op, left, right }) => {
                 let op = match op {
                     TBinaryOp::Add => "+",
@@ -725,6 +773,23 @@ impl Checker {
         }
     }
 
+    // The `?` on optional tuple elements already implies `undefined` so it's
+    // left out of the element's type.
+    fn print_optional_elem(&self, index: &Index) -> String {
+        let TypeKind::Union(Union { types }) = &self.arena[*index].kind else {
+            return self.print_type(index);
+        };
+        let types: Vec<Index> = types
+            .iter()
+            .filter(|t| !matches!(self.arena[**t].kind, TypeKind::Literal(Lit::Undefined)))
+            .copied()
+            .collect();
+        match types.as_slice() {
+            [t] => self.print_type(t),
+            _ => format!("({})", self.print_types(&types).join(" | ")),
+        }
+    }
+
     fn print_types(&self, indexes: &[Index]) -> Vec<String> {
         let mut result = vec![];
         for index in indexes {
@@ -741,7 +806,7 @@ impl Checker {
         strings
     }
 
-    fn print_param(&self, param: &FuncParam) -> String {
+    pub fn print_param(&self, param: &FuncParam) -> String {
         let name = Self::tpat_to_string(&param.pattern);
         match param.optional {
             true => format!("{name}?: {}", self.print_type(&param.t)),
@@ -792,6 +857,17 @@ impl Checker {
             TPat::Is(TIsPat { ident, is_id }) => {
                 format!("{ident} is {is_id}")
             }
+            TPat::Enum(TEnumPat {
+                enum_name,
+                variant,
+                args,
+            }) => match args.is_empty() {
+                true => format!("{enum_name}.{variant}"),
+                false => {
+                    let args: Vec<String> = args.iter().map(Self::tpat_to_string).collect();
+                    format!("{enum_name}.{variant}({})", args.join(", "))
+                }
+            },
             TPat::Wildcard => "_".to_string(),
         }
     }
@@ -837,6 +913,15 @@ impl Checker {
                         .all(|p1| o2.elems.iter().any(|p2| self.obj_elem_equals(p1, p2)))
             }
             (TypeKind::Rest(r1), TypeKind::Rest(r2)) => self.equals(&r1.arg, &r2.arg),
+            (TypeKind::TypePredicate(p1), TypeKind::TypePredicate(p2)) => {
+                p1.param == p2.param
+                    && p1.asserts == p2.asserts
+                    && match (&p1.t, &p2.t) {
+                        (Some(t1), Some(t2)) => self.equals(t1, t2),
+                        (None, None) => true,
+                        _ => false,
+                    }
+            }
             // TODO:
             // - unification of object and intersection
             _ => false,
@@ -902,8 +987,13 @@ impl Checker {
     }
 
     pub fn new_tuple_type(&mut self, types: &[Index]) -> Index {
+        self.new_tuple_type_with_optional(types, &vec![false; types.len()])
+    }
+
+    pub fn new_tuple_type_with_optional(&mut self, types: &[Index], optional: &[bool]) -> Index {
         self.arena.insert(Type::from(TypeKind::Tuple(Tuple {
             types: types.to_owned(),
+            optional: optional.to_owned(),
         })))
     }
 
@@ -990,6 +1080,15 @@ impl Checker {
         self.arena.insert(Type::from(TypeKind::Wildcard))
     }
 
+    pub fn new_type_predicate(&mut self, param: &str, t: Option<Index>, asserts: bool) -> Index {
+        self.arena
+            .insert(Type::from(TypeKind::TypePredicate(TypePredicate {
+                param: param.to_string(),
+                t,
+                asserts,
+            })))
+    }
+
     pub fn from_type_kind(&mut self, kind: TypeKind) -> Index {
         self.arena.insert(Type::from(kind))
     }
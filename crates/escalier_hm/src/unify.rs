@@ -8,7 +8,7 @@ use escalier_ast::{BindingIdent, Expr, Literal as Lit, Span};
 
 use crate::checker::Checker;
 use crate::context::*;
-use crate::diagnostic::Diagnostic;
+use crate::diagnostic::{Diagnostic, DiagnosticKind};
 use crate::infer::check_mutability;
 use crate::type_error::TypeError;
 use crate::types::*;
@@ -44,6 +44,38 @@ impl Checker {
             (TypeKind::Wildcard, _) => Ok(()),
             (_, TypeKind::Wildcard) => Ok(()),
 
+            (TypeKind::TypePredicate(p1), TypeKind::TypePredicate(p2)) => {
+                if p1.asserts != p2.asserts {
+                    return Err(TypeError {
+                        message: format!(
+                            "type mismatch: {} != {}",
+                            self.print_type(&a),
+                            self.print_type(&b),
+                        ),
+                    });
+                }
+                match (p1.t, p2.t) {
+                    (Some(t1), Some(t2)) => self.unify(ctx, t1, t2),
+                    (_, None) => Ok(()),
+                    (None, Some(_)) => Err(TypeError {
+                        message: format!(
+                            "type mismatch: {} != {}",
+                            self.print_type(&a),
+                            self.print_type(&b),
+                        ),
+                    }),
+                }
+            }
+            // Type guards evaluate to `boolean` and assertion functions
+            // evaluate to `undefined`, but not the other way around.
+            (TypeKind::TypePredicate(TypePredicate { asserts, .. }), _) => {
+                let t = match asserts {
+                    true => self.new_lit_type(&Lit::Undefined),
+                    false => self.new_primitive(Primitive::Boolean),
+                };
+                self.unify(ctx, t, b)
+            }
+
             (TypeKind::Keyword(kw1), TypeKind::Keyword(kw2)) => {
                 if kw1 == kw2 {
                     Ok(())
@@ -98,6 +130,16 @@ impl Checker {
                             }
                         }
 
+                        // Optional elements, e.g. `[number, string?]`, and
+                        // rest elements can be omitted.
+                        let omittable = (tuple1.types.len()..tuple2.types.len()).all(|i| {
+                            tuple2.is_optional(i)
+                                || matches!(self.arena[tuple2.types[i]].kind, TypeKind::Rest(_))
+                        });
+                        if omittable {
+                            break 'outer;
+                        }
+
                         return Err(TypeError {
                             message: format!(
                                 "Expected tuple of length {}, got tuple of length {}",
@@ -117,11 +159,17 @@ impl Checker {
                             })
                         }
                         (TypeKind::Rest(_), _) => {
-                            let rest_q = self.new_tuple_type(&tuple2.types[i..]);
+                            let rest_q = self.new_tuple_type_with_optional(
+                                &tuple2.types[i..],
+                                &tuple2.optional[i..],
+                            );
                             self.unify(ctx, *p, rest_q)?;
                         }
                         (_, TypeKind::Rest(_)) => {
-                            let rest_p = self.new_tuple_type(&tuple1.types[i..]);
+                            let rest_p = self.new_tuple_type_with_optional(
+                                &tuple1.types[i..],
+                                &tuple1.optional[i..],
+                            );
                             self.unify(ctx, rest_p, *q)?;
                         }
                         (_, _) => self.unify(ctx, *p, *q)?,
@@ -168,6 +216,37 @@ impl Checker {
                 self.unify(ctx, array_a.t, array_b.t)
             }
             (TypeKind::TypeRef(con_a), TypeKind::TypeRef(con_b)) => {
+                // Enum variants, e.g. `IpAddr.V4`, are subtypes of their enum.
+                if con_a.name.starts_with(&format!("{}.", con_b.name)) {
+                    let expanded_b = self.expand_type(ctx, b)?;
+                    return self.unify(ctx, a, expanded_b);
+                }
+
+                // Only types with an `impl` of a trait are subtypes of it.  Type
+                // params satisfy a trait if their constraint does.
+                if con_a.name != con_b.name && ctx.traits.contains_key(&con_b.name) {
+                    if let Some(traits) = ctx.impls.get(&con_a.name) {
+                        if traits.contains(&con_b.name) {
+                            return Ok(());
+                        }
+                    }
+                    if let Some(Scheme {
+                        is_type_param: true,
+                        ..
+                    }) = ctx.schemes.get(&con_a.name)
+                    {
+                        let expanded_a = self.expand_type(ctx, a)?;
+                        return self.unify(ctx, expanded_a, b);
+                    }
+                    return Err(TypeError {
+                        message: format!(
+                            "{} doesn't implement {}",
+                            self.print_type(&a),
+                            self.print_type(&b),
+                        ),
+                    });
+                }
+
                 // TODO: support type constructors with optional and default type params
                 if con_a.name != con_b.name || con_a.type_args.len() != con_b.type_args.len() {
                     return Err(TypeError {
@@ -643,7 +722,7 @@ impl Checker {
                 let obj_type = simplify_intersection(self, &obj_types);
 
                 match rest_types.len() {
-                    0 => self.unify(ctx, t1, obj_type),
+                    0 => self.unify(ctx, obj_type, t2),
                     1 => {
                         let all_obj_elems = match &self.arena[obj_type].kind {
                             TypeKind::Object(obj) => obj.elems.to_owned(),
@@ -673,6 +752,41 @@ impl Checker {
                     }),
                 }
             }
+            // Intersections of tuples and objects, e.g. the results of
+            // `str.match(regex)`, can be used as their tuple.
+            (TypeKind::Intersection(intersection), TypeKind::Tuple(_) | TypeKind::Array(_))
+                if intersection.types.iter().any(|t| {
+                    matches!(self.arena[*t].kind, TypeKind::Tuple(_) | TypeKind::Array(_))
+                }) =>
+            {
+                let tuple = intersection
+                    .types
+                    .iter()
+                    .find(|t| {
+                        matches!(
+                            self.arena[**t].kind,
+                            TypeKind::Tuple(_) | TypeKind::Array(_)
+                        )
+                    })
+                    .unwrap();
+                self.unify(ctx, *tuple, b)
+            }
+            // Structs, traits and enums are nominal so only instances of the
+            // struct itself (or types implementing the trait or values created
+            // by the enum's variants) can be unified with it.
+            (_, TypeKind::TypeRef(TypeRef { name, .. }))
+                if ctx.structs.contains_key(name)
+                    || ctx.traits.contains_key(name)
+                    || ctx.is_enum(name) =>
+            {
+                Err(TypeError {
+                    message: format!(
+                        "type mismatch: {} != {}",
+                        self.print_type(&a),
+                        self.print_type(&b),
+                    ),
+                })
+            }
             _ => {
                 let expanded_a = self.expand(ctx, a)?;
                 let expanded_b = self.expand(ctx, b)?;
@@ -781,12 +895,13 @@ impl Checker {
 
                     // TODO: if there are multiple overloads that unify, pick the
                     // best one.
-                    let (ret_type, maybe_throws_type) =
-                        self.unify_call(ctx, args, type_args, newable, *t)?;
+                    let result = self.unify_call(ctx, args, type_args, newable, *t);
 
-                    if self.current_report.diagnostics.is_empty() {
-                        self.pop_report();
-                        return Ok((ret_type, maybe_throws_type));
+                    if let Ok((ret_type, maybe_throws_type)) = result {
+                        if self.current_report.diagnostics.is_empty() {
+                            self.pop_report();
+                            return Ok((ret_type, maybe_throws_type));
+                        }
                     }
 
                     // We just throw away reports that don't unify until we find
@@ -946,6 +1061,11 @@ impl Checker {
                     message: "_ is not callable".to_string(),
                 });
             }
+            TypeKind::TypePredicate(_) => {
+                return Err(TypeError {
+                    message: format!("{} is not callable", self.print_type(&b)),
+                });
+            }
             TypeKind::Binary(BinaryT {
                 op: _,
                 left: _,
@@ -1081,8 +1201,10 @@ impl Checker {
         if !reasons.is_empty() {
             self.current_report.diagnostics.push(Diagnostic {
                 code: 1000,
+                kind: DiagnosticKind::IncorrectArgs,
                 message: "Function arguments are incorrect".to_string(),
                 reasons,
+                span: None,
             });
         }
 
@@ -103,6 +103,10 @@ impl Checker {
                 TObjElem::Prop(prop) => self.occurs_in_type(v, prop.t),
             }),
             TypeKind::Rest(Rest { arg }) => self.occurs_in_type(v, arg),
+            TypeKind::TypePredicate(TypePredicate { t, .. }) => match t {
+                Some(t) => self.occurs_in_type(v, t),
+                None => false,
+            },
             TypeKind::Function(Function {
                 params,
                 ret,
@@ -115,7 +119,7 @@ impl Checker {
             }
             TypeKind::Union(Union { types }) => self.occurs_in(v, &types),
             TypeKind::Intersection(Intersection { types }) => self.occurs_in(v, &types),
-            TypeKind::Tuple(Tuple { types }) => self.occurs_in(v, &types),
+            TypeKind::Tuple(Tuple { types, .. }) => self.occurs_in(v, &types),
             TypeKind::Array(Array { t }) => self.occurs_in_type(v, t),
             TypeKind::TypeRef(TypeRef {
                 type_args: types, ..
@@ -209,6 +213,9 @@ impl Checker {
         name: &str,
         type_args: &[Index],
     ) -> Result<Index, TypeError> {
+        if let Some(t) = self.expand_regex_match(name, type_args) {
+            return Ok(t);
+        }
         let scheme = ctx.get_scheme(name)?;
         self.expand_scheme(ctx, &scheme, type_args, name)
     }
@@ -220,6 +227,9 @@ impl Checker {
         type_args: &[Index],
         name: &str,
     ) -> Result<Index, TypeError> {
+        if let Some(t) = self.expand_regex_match(name, type_args) {
+            return Ok(t);
+        }
         match &scheme.type_params {
             Some(type_params) => {
                 if type_params.len() != type_args.len() {
@@ -345,7 +355,16 @@ impl Checker {
                 None => self.expand_alias(ctx, name, type_args)?,
             },
             TypeKind::Binary(binary) => self.expand_binary(ctx, binary)?,
-            TypeKind::Object(object) => return self.expand_object(ctx, object),
+            // Only objects with mapped types need expanding, returning other
+            // objects as is avoids creating a new type on every expansion.
+            TypeKind::Object(object)
+                if object
+                    .elems
+                    .iter()
+                    .any(|elem| matches!(elem, TObjElem::Mapped(_))) =>
+            {
+                return self.expand_object(ctx, object)
+            }
             _ => return Ok(t), // Early return to avoid infinite loop
         };
 
@@ -746,10 +765,12 @@ impl Checker {
                                     }
                                 }
 
+                                let readonly = matches!(mapped.readonly, Some(MappedModifier::Add));
+
                                 new_elems.push(TObjElem::Prop(TProp {
                                     name,
                                     optional,
-                                    readonly: false,
+                                    readonly,
                                     t: self.expand_type(ctx, value)?,
                                 }));
                             }
@@ -762,6 +783,7 @@ impl Checker {
                                     value: mapped.value,
                                     source: mapped.source,
                                     optional: mapped.optional.to_owned(),
+                                    readonly: mapped.readonly.to_owned(),
                                     check: mapped.check,
                                     extends: mapped.extends,
                                 }));
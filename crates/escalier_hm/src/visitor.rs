@@ -34,7 +34,7 @@ pub fn walk_index<V: Visitor>(visitor: &mut V, index: &Index) {
         TypeKind::Intersection(Intersection { types }) => {
             walk_indexes(visitor, types);
         }
-        TypeKind::Tuple(Tuple { types }) => {
+        TypeKind::Tuple(Tuple { types, .. }) => {
             walk_indexes(visitor, types);
         }
         TypeKind::Array(Array { t }) => {
@@ -104,6 +104,11 @@ pub fn walk_index<V: Visitor>(visitor: &mut V, index: &Index) {
         }
         TypeKind::Infer(_) => (),
         TypeKind::Wildcard => (),
+        TypeKind::TypePredicate(TypePredicate { t, .. }) => {
+            if let Some(t) = t {
+                visitor.visit_index(t);
+            }
+        }
         TypeKind::Binary(BinaryT { op: _, left, right }) => {
             visitor.visit_index(left);
             visitor.visit_index(right);
@@ -35,6 +35,7 @@ fn test_env() -> (Checker, Context) {
         target: "P".to_string(),
         source: checker.new_primitive(Primitive::Number),
         optional: None,
+        readonly: None,
         check: None,
         extends: None,
     });
@@ -423,10 +424,9 @@ fn try_catches_throw_return_inside_try_catch() -> Result<(), TypeError> {
     let binding = my_ctx.values.get("div").unwrap();
     assert_eq!(
         checker.print_type(&binding.index),
-        // TODO: the return type should be `number` because all `return` statements
-        // return numbers and code appearing after the `try-catch` is
-        // unreachable.
-        r#"(a: number, b: number) -> undefined"#
+        // TODO: simplify the return type to `number` since `0` is a subtype of
+        // `number`.
+        r#"(a: number, b: number) -> number | 0"#
     );
 
     Ok(())
@@ -1,10 +1,12 @@
 use generational_arena::{Arena, Index};
+use std::collections::HashMap;
 
 use escalier_ast::{self as syntax, Literal as Lit, *};
 use escalier_parser::{ParseError, Parser};
 
 use escalier_hm::checker::Checker;
 use escalier_hm::context::*;
+use escalier_hm::diagnostic::DiagnosticKind;
 use escalier_hm::type_error::TypeError;
 use escalier_hm::types::{self, *};
 
@@ -68,6 +70,7 @@ fn test_env() -> (Checker, Context) {
         target: "P".to_string(),
         source: checker.new_primitive(Primitive::Number),
         optional: None,
+        readonly: None,
         check: None,
         extends: None,
     });
@@ -1010,6 +1013,45 @@ fn tuple_subtyping_not_enough_elements() -> Result<(), TypeError> {
     Ok(())
 }
 
+#[test]
+fn tuple_subtyping_undefined_elements_are_required() -> Result<(), TypeError> {
+    let (mut checker, mut my_ctx) = test_env();
+
+    let src = r#"
+    declare let foo: fn (x: [number, string | undefined]) -> boolean
+    let result = foo([5])
+    "#;
+    let mut script = parse_script(src).unwrap();
+
+    checker.infer_script(&mut script, &mut my_ctx)?;
+
+    insta::assert_display_snapshot!(checker.current_report, @r###"
+    ESC_1000 - Function arguments are incorrect:
+    └ TypeError: Expected tuple of length 2, got tuple of length 1
+    "###);
+
+    Ok(())
+}
+
+#[test]
+fn print_tuple_with_optional_elements() {
+    let mut checker = Checker::default();
+
+    let number = checker.new_primitive(Primitive::Number);
+    let string = checker.new_primitive(Primitive::String);
+    let boolean = checker.new_primitive(Primitive::Boolean);
+    let undefined = checker.new_lit_type(&Lit::Undefined);
+    let maybe_string = checker.new_union_type(&[string, undefined]);
+    let maybe_either = checker.new_union_type(&[string, boolean, undefined]);
+    let tuple = checker
+        .new_tuple_type_with_optional(&[number, maybe_string, maybe_either], &[false, true, true]);
+
+    assert_eq!(
+        checker.print_type(&tuple),
+        "[number, string?, (string | boolean)?]"
+    );
+}
+
 #[test]
 fn infer_basic_object() -> Result<(), TypeError> {
     let (mut checker, mut my_ctx) = test_env();
@@ -4486,316 +4528,760 @@ fn test_mutating_immutable_object_errors() -> Result<(), TypeError> {
         })
     );
 
-    assert_no_errors(&checker)
+    let kinds: Vec<_> = checker
+        .current_report
+        .diagnostics
+        .iter()
+        .map(|diagnostic| diagnostic.kind.to_owned())
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![DiagnosticKind::MutabilityViolation {
+            name: "p".to_string()
+        }]
+    );
+
+    Ok(())
 }
 
 #[test]
-fn conditional_type_exclude() -> Result<(), TypeError> {
+fn test_calling_mutating_method_on_immutable_binding_errors() -> Result<(), TypeError> {
     let (mut checker, mut my_ctx) = test_env();
 
     let src = r#"
-    type Exclude<T, U> = if (T: U) { never } else { T }
-    type Result = Exclude<"a" | "b" | "c" | "d" | "e", "a" | "e">
+    type Counter = {
+        count: number,
+        fn inc(mut self) -> undefined,
+    }
+    declare let counter: Counter
+    counter.inc()
     "#;
     let mut script = parse_script(src).unwrap();
 
-    checker.infer_script(&mut script, &mut my_ctx)?;
+    let result = checker.infer_script(&mut script, &mut my_ctx);
 
-    let result = my_ctx.schemes.get("Result").unwrap();
-    let t = checker.expand_type(&my_ctx, result.t)?;
-    assert_eq!(checker.print_type(&t), r#""b" | "c" | "d""#);
+    assert!(result.is_err());
+    assert_eq!(
+        checker.current_report.diagnostics[0].kind,
+        DiagnosticKind::MutabilityViolation {
+            name: "counter".to_string()
+        }
+    );
 
-    assert_no_errors(&checker)
+    Ok(())
 }
 
 #[test]
-fn chained_conditional_types() -> Result<(), TypeError> {
+fn test_non_exhaustive_match() -> Result<(), TypeError> {
     let (mut checker, mut my_ctx) = test_env();
 
     let src = r#"
-    type Foo<T> = if (T: string) { 
-        "str"
-    } else if (T: number) {
-        "num"
-    } else {
-        "?"
+    declare let dir: "up" | "down" | "left" | "right"
+    let x = match (dir) {
+        "up" => 1,
+        "down" => 2
     }
-    type Result = Foo<5 | "hello">
     "#;
     let mut script = parse_script(src).unwrap();
-
     checker.infer_script(&mut script, &mut my_ctx)?;
 
-    let result = my_ctx.schemes.get("Result").unwrap();
-    let t = checker.expand_type(&my_ctx, result.t)?;
-    assert_eq!(checker.print_type(&t), r#""num" | "str""#);
+    assert_eq!(
+        checker.current_report.diagnostics[0].kind,
+        DiagnosticKind::NonExhaustiveMatch {
+            missing: vec!["\"left\"".to_string(), "\"right\"".to_string()]
+        }
+    );
 
-    assert_no_errors(&checker)
+    Ok(())
 }
 
 #[test]
-fn match_type_with_catchall() -> Result<(), TypeError> {
+fn test_exhaustive_match() -> Result<(), TypeError> {
     let (mut checker, mut my_ctx) = test_env();
 
     let src = r#"
-    type Foo<T> = match (T) {
-        string => "str",
-        number => "num",
-        _ => "other",
+    declare let flag: boolean
+    declare let dir: "up" | "down" | "left" | "right"
+    let x = match (flag) {
+        true => 1,
+        false => 0
+    }
+    let y = match (dir) {
+        "up" => 1,
+        _ => 0
     }
-    type Result = Foo<5 | "hello">
     "#;
     let mut script = parse_script(src).unwrap();
-
     checker.infer_script(&mut script, &mut my_ctx)?;
 
-    let result = my_ctx.schemes.get("Result").unwrap();
-    let t = checker.expand_type(&my_ctx, result.t)?;
-    assert_eq!(checker.print_type(&t), r#""num" | "str""#);
-
     assert_no_errors(&checker)
 }
 
 #[test]
-fn match_type_without_catchall() -> Result<(), TypeError> {
+fn test_enum_with_payloads() -> Result<(), TypeError> {
     let (mut checker, mut my_ctx) = test_env();
 
     let src = r#"
-    type Foo<T> = match (T) {
-        string => "str",
-        number => "num",
+    enum IpAddr { V4(string), V6(string) }
+    let home = IpAddr.V4("127.0.0.1")
+    let loopback: IpAddr = IpAddr.V6("::1")
+    declare let addr: IpAddr
+    let addr_str = match (addr) {
+        IpAddr.V4(address) => address,
+        IpAddr.V6(address) => address
     }
-    type True = Foo<5 | true>
-    type Never = Foo<true>
     "#;
     let mut script = parse_script(src).unwrap();
-
     checker.infer_script(&mut script, &mut my_ctx)?;
 
-    let result = my_ctx.schemes.get("True").unwrap();
-    let t = checker.expand_type(&my_ctx, result.t)?;
-    assert_eq!(checker.print_type(&t), r#""num""#);
-
-    let result = my_ctx.schemes.get("Never").unwrap();
-    let t = checker.expand_type(&my_ctx, result.t)?;
-    assert_eq!(checker.print_type(&t), r#"never"#);
+    let binding = my_ctx.values.get("home").unwrap();
+    assert_eq!(checker.print_type(&binding.index), r#"IpAddr.V4"#);
+    let binding = my_ctx.values.get("addr_str").unwrap();
+    assert_eq!(checker.print_type(&binding.index), r#"string | string"#);
 
     assert_no_errors(&checker)
 }
 
 #[test]
-fn match_type_with_tuples() -> Result<(), TypeError> {
+fn test_generic_enum() -> Result<(), TypeError> {
     let (mut checker, mut my_ctx) = test_env();
 
-    // NOTE: The arrays need to be matched in reverse order
-    // because they're open.  This means that the first match
-    // all arrays size 4 and greater.
     let src = r#"
-    type Bar<T: Array<_>> = match (T) {
-        [_, _, _, _] => "n-tuple",
-        [_, _, _] => "triplet",
-        [_, _] => "pair",
-        [_] => "1-tuple",
-        [] => "unit",
+    enum Option<T> { Some(T), None }
+    let some = Option.Some(5)
+    let none: Option<string> = Option.None
+    declare let opt: Option<number>
+    let value = match (opt) {
+        Option.Some(value) => value,
+        Option.None => 0
     }
-    type Tuple0 = Bar<[]>
-    type Tuple1 = Bar<[5]>
-    type Tuple2 = Bar<[5, "hello"]>
-    type Tuple3 = Bar<[5, "hello", true]>
-    type Tuple4 = Bar<[5, "hello", true, 10]>
-    type Tuple5 = Bar<[5, "hello", true, 10, "world"]>
     "#;
     let mut script = parse_script(src).unwrap();
-
     checker.infer_script(&mut script, &mut my_ctx)?;
 
-    let result = my_ctx.schemes.get("Tuple0").unwrap();
-    let t = checker.expand_type(&my_ctx, result.t)?;
-    assert_eq!(checker.print_type(&t), r#""unit""#);
-
-    let result = my_ctx.schemes.get("Tuple1").unwrap();
-    let t = checker.expand_type(&my_ctx, result.t)?;
-    assert_eq!(checker.print_type(&t), r#""1-tuple""#);
+    let binding = my_ctx.values.get("some").unwrap();
+    assert_eq!(checker.print_type(&binding.index), r#"Option.Some<5>"#);
+    let binding = my_ctx.values.get("value").unwrap();
+    assert_eq!(checker.print_type(&binding.index), r#"number | 0"#);
 
-    let result = my_ctx.schemes.get("Tuple2").unwrap();
-    let t = checker.expand_type(&my_ctx, result.t)?;
-    assert_eq!(checker.print_type(&t), r#""pair""#);
+    assert_no_errors(&checker)
+}
 
-    let result = my_ctx.schemes.get("Tuple3").unwrap();
-    let t = checker.expand_type(&my_ctx, result.t)?;
-    assert_eq!(checker.print_type(&t), r#""triplet""#);
+#[test]
+fn test_enum_variant_with_multiple_fields() -> Result<(), TypeError> {
+    let (mut checker, mut my_ctx) = test_env();
 
-    let result = my_ctx.schemes.get("Tuple4").unwrap();
-    let t = checker.expand_type(&my_ctx, result.t)?;
-    assert_eq!(checker.print_type(&t), r#""n-tuple""#);
+    let src = r#"
+    enum Shape { Circle(number), Rect(number, number) }
+    declare let shape: Shape
+    let area = match (shape) {
+        Shape.Circle(r) => 3.14 * r * r,
+        Shape.Rect(w, h) => w * h
+    }
+    "#;
+    let mut script = parse_script(src).unwrap();
+    checker.infer_script(&mut script, &mut my_ctx)?;
 
-    let result = my_ctx.schemes.get("Tuple5").unwrap();
-    let t = checker.expand_type(&my_ctx, result.t)?;
-    assert_eq!(checker.print_type(&t), r#""n-tuple""#);
+    let binding = my_ctx.values.get("area").unwrap();
+    assert_eq!(checker.print_type(&binding.index), r#"number | number"#);
 
     assert_no_errors(&checker)
 }
 
 #[test]
-fn conditional_type_with_placeholders() -> Result<(), TypeError> {
+fn test_enum_payload_type_mismatch() -> Result<(), TypeError> {
     let (mut checker, mut my_ctx) = test_env();
 
     let src = r#"
-        type IsArray<T> = if (T: Array<_>) { true } else { false }
-        type T = IsArray<Array<number>>
-        type F = IsArray<number>
+    enum IpAddr { V4(string), V6(string) }
+    let home = IpAddr.V4(127)
     "#;
     let mut script = parse_script(src).unwrap();
-
     checker.infer_script(&mut script, &mut my_ctx)?;
 
-    let result = my_ctx.schemes.get("T").unwrap();
-    let t = checker.expand_type(&my_ctx, result.t)?;
-    assert_eq!(checker.print_type(&t), r#"true"#);
-
-    let result = my_ctx.schemes.get("F").unwrap();
-    let t = checker.expand_type(&my_ctx, result.t)?;
-    assert_eq!(checker.print_type(&t), r#"false"#);
+    insta::assert_display_snapshot!(checker.current_report, @r###"
+    ESC_1000 - Function arguments are incorrect:
+    └ TypeError: type mismatch: unify(127, string) failed
+    "###);
 
-    assert_no_errors(&checker)
+    Ok(())
 }
 
 #[test]
-fn conditional_type_with_constraint() -> Result<(), TypeError> {
+fn test_non_exhaustive_enum_match() -> Result<(), TypeError> {
     let (mut checker, mut my_ctx) = test_env();
 
     let src = r#"
-        type IsArrayOfNumbers<T> = if (T: Array<number>) { true } else { false }
-        type T = IsArrayOfNumbers<[1, 2, 3]>
-        type F = IsArrayOfNumbers<["hello", "world"]>
+    enum Shape { Circle(number), Square(number), Rect(number, number) }
+    declare let shape: Shape
+    let x = match (shape) {
+        Shape.Circle(r) => r,
+        Shape.Rect(w, 0) => w
+    }
     "#;
     let mut script = parse_script(src).unwrap();
-
     checker.infer_script(&mut script, &mut my_ctx)?;
 
-    let result = my_ctx.schemes.get("T").unwrap();
-    let t = checker.expand_type(&my_ctx, result.t)?;
-    assert_eq!(checker.print_type(&t), r#"true"#);
-
-    let result = my_ctx.schemes.get("F").unwrap();
-    let t = checker.expand_type(&my_ctx, result.t)?;
-    assert_eq!(checker.print_type(&t), r#"false"#);
+    assert_eq!(
+        checker.current_report.diagnostics[0].kind,
+        DiagnosticKind::NonExhaustiveMatch {
+            missing: vec!["Shape.Square".to_string(), "Shape.Rect".to_string()]
+        }
+    );
 
-    assert_no_errors(&checker)
+    Ok(())
 }
 
 #[test]
-fn unify_tuple_and_array() -> Result<(), TypeError> {
+fn test_enums_are_nominal() -> Result<(), TypeError> {
     let (mut checker, mut my_ctx) = test_env();
 
     let src = r#"
-        let tuple = [1, 2, 3]
-        let array: Array<number> = tuple
+    enum Color { Red, Green, Blue }
+    let c: Color = {}
     "#;
     let mut script = parse_script(src).unwrap();
+    let result = checker.infer_script(&mut script, &mut my_ctx);
 
-    checker.infer_script(&mut script, &mut my_ctx)?;
+    assert_eq!(
+        result,
+        Err(TypeError {
+            message: "type mismatch: {} != Color".to_string()
+        })
+    );
 
-    assert_no_errors(&checker)
+    Ok(())
 }
 
 #[test]
-fn conditional_type_with_function_subtyping() -> Result<(), TypeError> {
+fn test_generic_enums_are_nominal() -> Result<(), TypeError> {
     let (mut checker, mut my_ctx) = test_env();
 
     let src = r#"
-        type IsFunction<T> = if (T: fn (...args: _) -> _) { true } else { false }
-        type T = IsFunction<fn (a: number) -> string>
-        type F = IsFunction<number>
+    enum Opt<T> { Some(T), None }
+    let o: Opt<number> = {payload: 5}
     "#;
     let mut script = parse_script(src).unwrap();
+    let result = checker.infer_script(&mut script, &mut my_ctx);
 
-    checker.infer_script(&mut script, &mut my_ctx)?;
-
-    let result = my_ctx.schemes.get("T").unwrap();
-    let t = checker.expand_type(&my_ctx, result.t)?;
-    assert_eq!(checker.print_type(&t), r#"true"#);
-
-    let result = my_ctx.schemes.get("F").unwrap();
-    let t = checker.expand_type(&my_ctx, result.t)?;
-    assert_eq!(checker.print_type(&t), r#"false"#);
+    assert_eq!(
+        result,
+        Err(TypeError {
+            message: "type mismatch: {payload: 5} != Opt<number>".to_string()
+        })
+    );
 
-    assert_no_errors(&checker)
+    Ok(())
 }
 
 #[test]
-fn return_type_rest_placeholder() -> Result<(), TypeError> {
+fn test_enum_params_only_accept_variants() -> Result<(), TypeError> {
     let (mut checker, mut my_ctx) = test_env();
 
-    // TODO: introduce a placeholder type that will unify with anything
-    // we need to make sure we aren't adding `_` to non_generic
     let src = r#"
-        type ReturnType<
-            T: fn (...args: _) -> _
-        > = if (T: fn (...args: _) -> infer R) {
-            R
-        } else {
-            never
-        }
-        type RT1 = ReturnType<fn (a: string, b: number) -> boolean>
-        type RT2 = ReturnType<fn (a: string) -> number>
-        type RT3 = ReturnType<fn () -> string>
+    enum Color { Red, Green, Blue }
+    declare let f: fn (c: Color) -> undefined
+    f(Color.Red)
+    f({})
+    f({foo: 1})
     "#;
     let mut script = parse_script(src).unwrap();
-
     checker.infer_script(&mut script, &mut my_ctx)?;
 
-    let result = my_ctx.schemes.get("RT1").unwrap();
-    let t = checker.expand_type(&my_ctx, result.t)?;
-    assert_eq!(checker.print_type(&t), r#"boolean"#);
-
-    let result = my_ctx.schemes.get("RT2").unwrap();
-    let t = checker.expand_type(&my_ctx, result.t)?;
-    assert_eq!(checker.print_type(&t), r#"number"#);
+    insta::assert_display_snapshot!(checker.current_report, @r###"
+    ESC_1000 - Function arguments are incorrect:
+    └ TypeError: type mismatch: {} != Color
 
-    let result = my_ctx.schemes.get("RT3").unwrap();
-    let t = checker.expand_type(&my_ctx, result.t)?;
-    assert_eq!(checker.print_type(&t), r#"string"#);
+    ESC_1000 - Function arguments are incorrect:
+    └ TypeError: type mismatch: {foo: 1} != Color
+    "###);
 
-    assert_no_errors(&checker)
+    Ok(())
 }
 
 #[test]
-fn return_type_of_union() -> Result<(), TypeError> {
+fn test_struct_construction() -> Result<(), TypeError> {
     let (mut checker, mut my_ctx) = test_env();
 
     let src = r#"
-        type ReturnType<
-            T: fn (...args: _) -> _
-        > = if (T: fn (...args: _) -> infer R) {
-            R
-        } else {
-            never
-        }
-        type Result = ReturnType<(fn () -> number) | (fn () -> string)>
+    struct User { pub name: string, pub age: number, ssn: number }
+    let user = User { name: "John Doe", age: 43, ssn: 123456789 }
+    let name = user.name
     "#;
     let mut script = parse_script(src).unwrap();
-
     checker.infer_script(&mut script, &mut my_ctx)?;
 
-    let result = my_ctx.schemes.get("Result").unwrap();
-    let t = checker.expand_type(&my_ctx, result.t)?;
-    assert_eq!(checker.print_type(&t), r#"number | string"#);
+    let binding = my_ctx.values.get("user").unwrap();
+    assert_eq!(checker.print_type(&binding.index), r#"User"#);
+    let binding = my_ctx.values.get("name").unwrap();
+    assert_eq!(checker.print_type(&binding.index), r#"string"#);
 
     assert_no_errors(&checker)
 }
 
 #[test]
-fn parameters_utility_type() -> Result<(), TypeError> {
+fn test_generic_struct() -> Result<(), TypeError> {
     let (mut checker, mut my_ctx) = test_env();
 
     let src = r#"
-    type Parameters<T : fn (...args: _) -> _> = if (
-        T: fn (...args: infer P) -> _
-    ) { 
-        P
-    } else { 
-        never
+    struct Box<T> { pub value: T }
+    let box = Box { value: "hello" }
+    let value = box.value
+    "#;
+    let mut script = parse_script(src).unwrap();
+    checker.infer_script(&mut script, &mut my_ctx)?;
+
+    let binding = my_ctx.values.get("box").unwrap();
+    assert_eq!(checker.print_type(&binding.index), r#"Box<"hello">"#);
+    let binding = my_ctx.values.get("value").unwrap();
+    assert_eq!(checker.print_type(&binding.index), r#""hello""#);
+
+    assert_no_errors(&checker)
+}
+
+#[test]
+fn test_structs_are_nominal() -> Result<(), TypeError> {
+    let (mut checker, mut my_ctx) = test_env();
+
+    let src = r#"
+    struct Point { pub x: number, pub y: number }
+    let p: Point = { x: 5, y: 10 }
+    "#;
+    let mut script = parse_script(src).unwrap();
+    let result = checker.infer_script(&mut script, &mut my_ctx);
+
+    assert_eq!(
+        result,
+        Err(TypeError {
+            message: "type mismatch: {x: 5, y: 10} != Point".to_string()
+        })
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_struct_private_field_access() -> Result<(), TypeError> {
+    let (mut checker, mut my_ctx) = test_env();
+
+    let src = r#"
+    struct User { pub name: string, ssn: number }
+    let user = User { name: "John Doe", ssn: 123456789 }
+    let ssn = user.ssn
+    "#;
+    let mut script = parse_script(src).unwrap();
+    let result = checker.infer_script(&mut script, &mut my_ctx);
+
+    assert_eq!(
+        result,
+        Err(TypeError {
+            message: "ssn is a private field of User".to_string()
+        })
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_struct_unknown_field() -> Result<(), TypeError> {
+    let (mut checker, mut my_ctx) = test_env();
+
+    let src = r#"
+    struct User { pub name: string }
+    let user = User { name: "John Doe", email: "john@example.com" }
+    "#;
+    let mut script = parse_script(src).unwrap();
+    let result = checker.infer_script(&mut script, &mut my_ctx);
+
+    assert_eq!(
+        result,
+        Err(TypeError {
+            message: "User doesn't have a field named email".to_string()
+        })
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_struct_patterns() -> Result<(), TypeError> {
+    let (mut checker, mut my_ctx) = test_env();
+
+    let src = r#"
+    struct Circle { pub radius: number }
+    struct Rect { pub width: number, pub height: number }
+    declare let shape: Circle | Rect
+    let size = match (shape) {
+        Circle {radius} => radius,
+        Rect {width: w, height: h} => w * h
+    }
+    "#;
+    let mut script = parse_script(src).unwrap();
+    checker.infer_script(&mut script, &mut my_ctx)?;
+
+    let binding = my_ctx.values.get("size").unwrap();
+    assert_eq!(checker.print_type(&binding.index), r#"number | number"#);
+
+    assert_no_errors(&checker)
+}
+
+#[test]
+fn test_struct_pattern_with_private_field() -> Result<(), TypeError> {
+    let (mut checker, mut my_ctx) = test_env();
+
+    let src = r#"
+    struct User { pub name: string, ssn: number }
+    declare let user: User
+    let ssn = match (user) {
+        User {ssn} => ssn
+    }
+    "#;
+    let mut script = parse_script(src).unwrap();
+    let result = checker.infer_script(&mut script, &mut my_ctx);
+
+    assert_eq!(
+        result,
+        Err(TypeError {
+            message: "ssn is a private field of User".to_string()
+        })
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_missing_throws_clause() -> Result<(), TypeError> {
+    let (mut checker, mut my_ctx) = test_env();
+
+    let src = r#"
+    let div = fn (a: number, b: number) -> number {
+        if (b == 0) {
+            throw "division by zero"
+        }
+        return a / b
+    }
+    "#;
+    let mut script = parse_script(src).unwrap();
+    checker.infer_script(&mut script, &mut my_ctx)?;
+
+    assert!(checker.current_report.diagnostics.is_empty());
+    assert_eq!(
+        checker.current_report.warnings[0].kind,
+        DiagnosticKind::MissingThrows {
+            throws: "\"division by zero\"".to_string()
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn conditional_type_exclude() -> Result<(), TypeError> {
+    let (mut checker, mut my_ctx) = test_env();
+
+    let src = r#"
+    type Exclude<T, U> = if (T: U) { never } else { T }
+    type Result = Exclude<"a" | "b" | "c" | "d" | "e", "a" | "e">
+    "#;
+    let mut script = parse_script(src).unwrap();
+
+    checker.infer_script(&mut script, &mut my_ctx)?;
+
+    let result = my_ctx.schemes.get("Result").unwrap();
+    let t = checker.expand_type(&my_ctx, result.t)?;
+    assert_eq!(checker.print_type(&t), r#""b" | "c" | "d""#);
+
+    assert_no_errors(&checker)
+}
+
+#[test]
+fn chained_conditional_types() -> Result<(), TypeError> {
+    let (mut checker, mut my_ctx) = test_env();
+
+    let src = r#"
+    type Foo<T> = if (T: string) { 
+        "str"
+    } else if (T: number) {
+        "num"
+    } else {
+        "?"
+    }
+    type Result = Foo<5 | "hello">
+    "#;
+    let mut script = parse_script(src).unwrap();
+
+    checker.infer_script(&mut script, &mut my_ctx)?;
+
+    let result = my_ctx.schemes.get("Result").unwrap();
+    let t = checker.expand_type(&my_ctx, result.t)?;
+    assert_eq!(checker.print_type(&t), r#""num" | "str""#);
+
+    assert_no_errors(&checker)
+}
+
+#[test]
+fn match_type_with_catchall() -> Result<(), TypeError> {
+    let (mut checker, mut my_ctx) = test_env();
+
+    let src = r#"
+    type Foo<T> = match (T) {
+        string => "str",
+        number => "num",
+        _ => "other",
+    }
+    type Result = Foo<5 | "hello">
+    "#;
+    let mut script = parse_script(src).unwrap();
+
+    checker.infer_script(&mut script, &mut my_ctx)?;
+
+    let result = my_ctx.schemes.get("Result").unwrap();
+    let t = checker.expand_type(&my_ctx, result.t)?;
+    assert_eq!(checker.print_type(&t), r#""num" | "str""#);
+
+    assert_no_errors(&checker)
+}
+
+#[test]
+fn match_type_without_catchall() -> Result<(), TypeError> {
+    let (mut checker, mut my_ctx) = test_env();
+
+    let src = r#"
+    type Foo<T> = match (T) {
+        string => "str",
+        number => "num",
+    }
+    type True = Foo<5 | true>
+    type Never = Foo<true>
+    "#;
+    let mut script = parse_script(src).unwrap();
+
+    checker.infer_script(&mut script, &mut my_ctx)?;
+
+    let result = my_ctx.schemes.get("True").unwrap();
+    let t = checker.expand_type(&my_ctx, result.t)?;
+    assert_eq!(checker.print_type(&t), r#""num""#);
+
+    let result = my_ctx.schemes.get("Never").unwrap();
+    let t = checker.expand_type(&my_ctx, result.t)?;
+    assert_eq!(checker.print_type(&t), r#"never"#);
+
+    assert_no_errors(&checker)
+}
+
+#[test]
+fn match_type_with_tuples() -> Result<(), TypeError> {
+    let (mut checker, mut my_ctx) = test_env();
+
+    // NOTE: The arrays need to be matched in reverse order
+    // because they're open.  This means that the first match
+    // all arrays size 4 and greater.
+    let src = r#"
+    type Bar<T: Array<_>> = match (T) {
+        [_, _, _, _] => "n-tuple",
+        [_, _, _] => "triplet",
+        [_, _] => "pair",
+        [_] => "1-tuple",
+        [] => "unit",
+    }
+    type Tuple0 = Bar<[]>
+    type Tuple1 = Bar<[5]>
+    type Tuple2 = Bar<[5, "hello"]>
+    type Tuple3 = Bar<[5, "hello", true]>
+    type Tuple4 = Bar<[5, "hello", true, 10]>
+    type Tuple5 = Bar<[5, "hello", true, 10, "world"]>
+    "#;
+    let mut script = parse_script(src).unwrap();
+
+    checker.infer_script(&mut script, &mut my_ctx)?;
+
+    let result = my_ctx.schemes.get("Tuple0").unwrap();
+    let t = checker.expand_type(&my_ctx, result.t)?;
+    assert_eq!(checker.print_type(&t), r#""unit""#);
+
+    let result = my_ctx.schemes.get("Tuple1").unwrap();
+    let t = checker.expand_type(&my_ctx, result.t)?;
+    assert_eq!(checker.print_type(&t), r#""1-tuple""#);
+
+    let result = my_ctx.schemes.get("Tuple2").unwrap();
+    let t = checker.expand_type(&my_ctx, result.t)?;
+    assert_eq!(checker.print_type(&t), r#""pair""#);
+
+    let result = my_ctx.schemes.get("Tuple3").unwrap();
+    let t = checker.expand_type(&my_ctx, result.t)?;
+    assert_eq!(checker.print_type(&t), r#""triplet""#);
+
+    let result = my_ctx.schemes.get("Tuple4").unwrap();
+    let t = checker.expand_type(&my_ctx, result.t)?;
+    assert_eq!(checker.print_type(&t), r#""n-tuple""#);
+
+    let result = my_ctx.schemes.get("Tuple5").unwrap();
+    let t = checker.expand_type(&my_ctx, result.t)?;
+    assert_eq!(checker.print_type(&t), r#""n-tuple""#);
+
+    assert_no_errors(&checker)
+}
+
+#[test]
+fn conditional_type_with_placeholders() -> Result<(), TypeError> {
+    let (mut checker, mut my_ctx) = test_env();
+
+    let src = r#"
+        type IsArray<T> = if (T: Array<_>) { true } else { false }
+        type T = IsArray<Array<number>>
+        type F = IsArray<number>
+    "#;
+    let mut script = parse_script(src).unwrap();
+
+    checker.infer_script(&mut script, &mut my_ctx)?;
+
+    let result = my_ctx.schemes.get("T").unwrap();
+    let t = checker.expand_type(&my_ctx, result.t)?;
+    assert_eq!(checker.print_type(&t), r#"true"#);
+
+    let result = my_ctx.schemes.get("F").unwrap();
+    let t = checker.expand_type(&my_ctx, result.t)?;
+    assert_eq!(checker.print_type(&t), r#"false"#);
+
+    assert_no_errors(&checker)
+}
+
+#[test]
+fn conditional_type_with_constraint() -> Result<(), TypeError> {
+    let (mut checker, mut my_ctx) = test_env();
+
+    let src = r#"
+        type IsArrayOfNumbers<T> = if (T: Array<number>) { true } else { false }
+        type T = IsArrayOfNumbers<[1, 2, 3]>
+        type F = IsArrayOfNumbers<["hello", "world"]>
+    "#;
+    let mut script = parse_script(src).unwrap();
+
+    checker.infer_script(&mut script, &mut my_ctx)?;
+
+    let result = my_ctx.schemes.get("T").unwrap();
+    let t = checker.expand_type(&my_ctx, result.t)?;
+    assert_eq!(checker.print_type(&t), r#"true"#);
+
+    let result = my_ctx.schemes.get("F").unwrap();
+    let t = checker.expand_type(&my_ctx, result.t)?;
+    assert_eq!(checker.print_type(&t), r#"false"#);
+
+    assert_no_errors(&checker)
+}
+
+#[test]
+fn unify_tuple_and_array() -> Result<(), TypeError> {
+    let (mut checker, mut my_ctx) = test_env();
+
+    let src = r#"
+        let tuple = [1, 2, 3]
+        let array: Array<number> = tuple
+    "#;
+    let mut script = parse_script(src).unwrap();
+
+    checker.infer_script(&mut script, &mut my_ctx)?;
+
+    assert_no_errors(&checker)
+}
+
+#[test]
+fn conditional_type_with_function_subtyping() -> Result<(), TypeError> {
+    let (mut checker, mut my_ctx) = test_env();
+
+    let src = r#"
+        type IsFunction<T> = if (T: fn (...args: _) -> _) { true } else { false }
+        type T = IsFunction<fn (a: number) -> string>
+        type F = IsFunction<number>
+    "#;
+    let mut script = parse_script(src).unwrap();
+
+    checker.infer_script(&mut script, &mut my_ctx)?;
+
+    let result = my_ctx.schemes.get("T").unwrap();
+    let t = checker.expand_type(&my_ctx, result.t)?;
+    assert_eq!(checker.print_type(&t), r#"true"#);
+
+    let result = my_ctx.schemes.get("F").unwrap();
+    let t = checker.expand_type(&my_ctx, result.t)?;
+    assert_eq!(checker.print_type(&t), r#"false"#);
+
+    assert_no_errors(&checker)
+}
+
+#[test]
+fn return_type_rest_placeholder() -> Result<(), TypeError> {
+    let (mut checker, mut my_ctx) = test_env();
+
+    // TODO: introduce a placeholder type that will unify with anything
+    // we need to make sure we aren't adding `_` to non_generic
+    let src = r#"
+        type ReturnType<
+            T: fn (...args: _) -> _
+        > = if (T: fn (...args: _) -> infer R) {
+            R
+        } else {
+            never
+        }
+        type RT1 = ReturnType<fn (a: string, b: number) -> boolean>
+        type RT2 = ReturnType<fn (a: string) -> number>
+        type RT3 = ReturnType<fn () -> string>
+    "#;
+    let mut script = parse_script(src).unwrap();
+
+    checker.infer_script(&mut script, &mut my_ctx)?;
+
+    let result = my_ctx.schemes.get("RT1").unwrap();
+    let t = checker.expand_type(&my_ctx, result.t)?;
+    assert_eq!(checker.print_type(&t), r#"boolean"#);
+
+    let result = my_ctx.schemes.get("RT2").unwrap();
+    let t = checker.expand_type(&my_ctx, result.t)?;
+    assert_eq!(checker.print_type(&t), r#"number"#);
+
+    let result = my_ctx.schemes.get("RT3").unwrap();
+    let t = checker.expand_type(&my_ctx, result.t)?;
+    assert_eq!(checker.print_type(&t), r#"string"#);
+
+    assert_no_errors(&checker)
+}
+
+#[test]
+fn return_type_of_union() -> Result<(), TypeError> {
+    let (mut checker, mut my_ctx) = test_env();
+
+    let src = r#"
+        type ReturnType<
+            T: fn (...args: _) -> _
+        > = if (T: fn (...args: _) -> infer R) {
+            R
+        } else {
+            never
+        }
+        type Result = ReturnType<(fn () -> number) | (fn () -> string)>
+    "#;
+    let mut script = parse_script(src).unwrap();
+
+    checker.infer_script(&mut script, &mut my_ctx)?;
+
+    let result = my_ctx.schemes.get("Result").unwrap();
+    let t = checker.expand_type(&my_ctx, result.t)?;
+    assert_eq!(checker.print_type(&t), r#"number | string"#);
+
+    assert_no_errors(&checker)
+}
+
+#[test]
+fn parameters_utility_type() -> Result<(), TypeError> {
+    let (mut checker, mut my_ctx) = test_env();
+
+    let src = r#"
+    type Parameters<T : fn (...args: _) -> _> = if (
+        T: fn (...args: infer P) -> _
+    ) { 
+        P
+    } else { 
+        never
     }
     type P1 = Parameters<fn (a: string, b: number) -> boolean>
     type P2 = Parameters<fn (a: string, ...rest: Array<number>) -> boolean>
@@ -5217,6 +5703,346 @@ fn for_in_loop_with_patterns() -> Result<(), TypeError> {
     assert_no_errors(&checker)
 }
 
+#[test]
+fn for_in_range() -> Result<(), TypeError> {
+    let (mut checker, mut my_ctx) = test_env();
+
+    let src = r#"
+    declare let n: number
+    let range = 0..10
+    let dynamic_range = 1..n
+    let mut sum: number = 0
+    for (i in 0..n) {
+        sum = sum + i
+    }
+    "#;
+    let mut script = parse_script(src).unwrap();
+
+    checker.infer_script(&mut script, &mut my_ctx)?;
+
+    let binding = my_ctx.values.get("range").unwrap();
+    assert_eq!(checker.print_type(&binding.index), r#"Range<0, 10>"#);
+    let binding = my_ctx.values.get("dynamic_range").unwrap();
+    assert_eq!(checker.print_type(&binding.index), r#"Range<1, number>"#);
+
+    assert_no_errors(&checker)
+}
+
+#[test]
+fn range_bounds_must_be_numbers() -> Result<(), TypeError> {
+    let (mut checker, mut my_ctx) = test_env();
+
+    let src = r#"
+    let range = 0.."hello"
+    "#;
+    let mut script = parse_script(src).unwrap();
+
+    let result = checker.infer_script(&mut script, &mut my_ctx);
+
+    assert_eq!(
+        result,
+        Err(TypeError {
+            message: r#"type mismatch: unify("hello", number) failed"#.to_string()
+        })
+    );
+
+    Ok(())
+}
+
+#[test]
+fn while_loop() -> Result<(), TypeError> {
+    let (mut checker, mut my_ctx) = test_env();
+
+    let src = r#"
+    let mut i: number = 0
+    let mut sum: number = 0
+    while (i < 10) {
+        sum = sum + i
+        i = i + 1
+    }
+    "#;
+    let mut script = parse_script(src).unwrap();
+
+    checker.infer_script(&mut script, &mut my_ctx)?;
+
+    assert_no_errors(&checker)
+}
+
+#[test]
+fn while_condition_must_be_boolean() -> Result<(), TypeError> {
+    let (mut checker, mut my_ctx) = test_env();
+
+    let src = r#"
+    while (5) {}
+    "#;
+    let mut script = parse_script(src).unwrap();
+
+    let result = checker.infer_script(&mut script, &mut my_ctx);
+
+    assert_eq!(
+        result,
+        Err(TypeError {
+            message: r#"type mismatch: unify(5, boolean) failed"#.to_string()
+        })
+    );
+
+    Ok(())
+}
+
+#[test]
+fn loop_evaluates_to_break_values() -> Result<(), TypeError> {
+    let (mut checker, mut my_ctx) = test_env();
+
+    let src = r#"
+    declare let next: fn () -> number
+    let result = loop {
+        let n = next()
+        if (n > 10) {
+            break n
+        }
+    }
+    let maybe = loop {
+        if (next() > 10) {
+            break "found"
+        }
+        if (next() < 0) {
+            break
+        }
+    }
+    "#;
+    let mut script = parse_script(src).unwrap();
+
+    checker.infer_script(&mut script, &mut my_ctx)?;
+
+    let binding = my_ctx.values.get("result").unwrap();
+    assert_eq!(checker.print_type(&binding.index), r#"number"#);
+    let binding = my_ctx.values.get("maybe").unwrap();
+    assert_eq!(checker.print_type(&binding.index), r#""found" | undefined"#);
+
+    assert_no_errors(&checker)
+}
+
+#[test]
+fn loop_without_break_never_returns() -> Result<(), TypeError> {
+    let (mut checker, mut my_ctx) = test_env();
+
+    let src = r#"
+    declare let tick: fn () -> undefined
+    let run = fn () {
+        loop {
+            tick()
+        }
+    }
+    let poll = fn (ready: fn () -> boolean) {
+        loop {
+            if (ready()) {
+                return 5
+            }
+        }
+    }
+    "#;
+    let mut script = parse_script(src).unwrap();
+
+    checker.infer_script(&mut script, &mut my_ctx)?;
+
+    let binding = my_ctx.values.get("run").unwrap();
+    assert_eq!(checker.print_type(&binding.index), r#"() -> never"#);
+    let binding = my_ctx.values.get("poll").unwrap();
+    assert_eq!(
+        checker.print_type(&binding.index),
+        r#"(ready: () -> boolean) -> 5"#
+    );
+
+    assert_no_errors(&checker)
+}
+
+#[test]
+fn returns_inside_of_loops() -> Result<(), TypeError> {
+    let (mut checker, mut my_ctx) = test_env();
+
+    let src = r#"
+    let find = fn (xs: number[], target: number) {
+        for (x in xs) {
+            if (x == target) {
+                return x
+            }
+        }
+    }
+    "#;
+    let mut script = parse_script(src).unwrap();
+
+    checker.infer_script(&mut script, &mut my_ctx)?;
+
+    let binding = my_ctx.values.get("find").unwrap();
+    assert_eq!(
+        checker.print_type(&binding.index),
+        r#"(xs: number[], target: number) -> number | undefined"#
+    );
+
+    assert_no_errors(&checker)
+}
+
+#[test]
+fn labeled_break_and_continue() -> Result<(), TypeError> {
+    let (mut checker, mut my_ctx) = test_env();
+
+    let src = r#"
+    declare let rows: Array<Array<number>>
+    let mut total: number = 0
+    outer: for (row in rows) {
+        for (cell in row) {
+            if (cell < 0) {
+                continue outer
+            }
+            if (cell == 0) {
+                break outer
+            }
+            total = total + cell
+        }
+    }
+    "#;
+    let mut script = parse_script(src).unwrap();
+
+    checker.infer_script(&mut script, &mut my_ctx)?;
+
+    assert_no_errors(&checker)
+}
+
+#[test]
+fn break_outside_of_loop() -> Result<(), TypeError> {
+    let (mut checker, mut my_ctx) = test_env();
+
+    let src = r#"
+    for (x in [1, 2, 3]) {
+        let f = fn () {
+            break
+        }
+    }
+    "#;
+    let mut script = parse_script(src).unwrap();
+
+    let result = checker.infer_script(&mut script, &mut my_ctx);
+
+    assert_eq!(
+        result,
+        Err(TypeError {
+            message: r#"`break` can only be used inside of a loop"#.to_string()
+        })
+    );
+
+    Ok(())
+}
+
+#[test]
+fn continue_with_unknown_label() -> Result<(), TypeError> {
+    let (mut checker, mut my_ctx) = test_env();
+
+    let src = r#"
+    while (true) {
+        continue outer
+    }
+    "#;
+    let mut script = parse_script(src).unwrap();
+
+    let result = checker.infer_script(&mut script, &mut my_ctx);
+
+    assert_eq!(
+        result,
+        Err(TypeError {
+            message: r#"label `outer` is not in scope"#.to_string()
+        })
+    );
+
+    Ok(())
+}
+
+#[test]
+fn only_loops_can_break_with_a_value() -> Result<(), TypeError> {
+    let (mut checker, mut my_ctx) = test_env();
+
+    let src = r#"
+    for (x in [1, 2, 3]) {
+        break x
+    }
+    "#;
+    let mut script = parse_script(src).unwrap();
+
+    let result = checker.infer_script(&mut script, &mut my_ctx);
+
+    assert_eq!(
+        result,
+        Err(TypeError {
+            message: r#"only `loop`s can be exited with a value"#.to_string()
+        })
+    );
+
+    Ok(())
+}
+
+#[test]
+fn tuple_slices() -> Result<(), TypeError> {
+    let (mut checker, mut my_ctx) = test_env();
+
+    let src = r#"
+    let tuple = [1, "two", true, 4, 5]
+    let slice1 = tuple[:3]
+    let slice2 = tuple[1:3]
+    let slice3 = tuple[1:]
+    let slice4 = tuple[:-1]
+    let slice5 = tuple[:]
+    let slice6 = tuple[3:1]
+    declare let i: number
+    let slice7 = tuple[i:]
+    "#;
+    let mut script = parse_script(src).unwrap();
+
+    checker.infer_script(&mut script, &mut my_ctx)?;
+
+    let expected = [
+        ("slice1", r#"[1, "two", true]"#),
+        ("slice2", r#"["two", true]"#),
+        ("slice3", r#"["two", true, 4, 5]"#),
+        ("slice4", r#"[1, "two", true, 4]"#),
+        ("slice5", r#"[1, "two", true, 4, 5]"#),
+        ("slice6", r#"[]"#),
+        ("slice7", r#"1 | "two" | true | 4 | 5[]"#),
+    ];
+    for (name, t) in expected {
+        let binding = my_ctx.values.get(name).unwrap();
+        assert_eq!(checker.print_type(&binding.index), t);
+    }
+
+    assert_no_errors(&checker)
+}
+
+#[test]
+fn array_and_string_slices() -> Result<(), TypeError> {
+    let (mut checker, mut my_ctx) = test_env();
+
+    let src = r#"
+    declare let array: Array<number>
+    declare let maybe_array: Array<number> | undefined
+    let slice1 = array[1:3]
+    let slice2 = "hello"[1:]
+    let slice3 = maybe_array?.[1:]
+    "#;
+    let mut script = parse_script(src).unwrap();
+
+    checker.infer_script(&mut script, &mut my_ctx)?;
+
+    let binding = my_ctx.values.get("slice1").unwrap();
+    assert_eq!(checker.print_type(&binding.index), r#"number[]"#);
+    let binding = my_ctx.values.get("slice2").unwrap();
+    assert_eq!(checker.print_type(&binding.index), r#"string"#);
+    let binding = my_ctx.values.get("slice3").unwrap();
+    assert_eq!(
+        checker.print_type(&binding.index),
+        r#"number[] | undefined"#
+    );
+
+    assert_no_errors(&checker)
+}
+
 #[test]
 fn function_call_func_wth_rest_arg_array() -> Result<(), TypeError> {
     let (mut checker, mut my_ctx) = test_env();
@@ -5968,3 +6794,373 @@ fn use_value_with_private_type_on_obj() -> Result<(), TypeError> {
 
     assert_no_errors(&checker)
 }
+
+#[test]
+fn serialize_typed_ast() -> Result<(), TypeError> {
+    let (mut checker, mut my_ctx) = test_env();
+
+    let src = r#"
+    let add = fn (a: number, b: number) => a + b
+    let sum = add(5, 10)
+    "#;
+    let mut script = parse_script(src).unwrap();
+
+    checker.infer_script(&mut script, &mut my_ctx)?;
+
+    let json = script_to_json(&script, checker.get_type_names(&script));
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+    let decl = &value["stmts"][1]["kind"]["Decl"]["kind"]["VarDecl"];
+    assert_eq!(decl["pattern"]["inferred_type"], "number");
+    assert_eq!(
+        decl["expr"]["kind"]["Call"]["callee"]["inferred_type"],
+        "(a: number, b: number) -> number"
+    );
+
+    // Types are omitted when deserializing.
+    let result: Script = serde_json::from_str(&json).unwrap();
+    let json = script_to_json(&result, HashMap::new());
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let decl = &value["stmts"][1]["kind"]["Decl"]["kind"]["VarDecl"];
+    assert_eq!(decl["pattern"]["inferred_type"], serde_json::Value::Null);
+
+    assert_no_errors(&checker)
+}
+
+#[test]
+fn test_trait_impl() -> Result<(), TypeError> {
+    let (mut checker, mut my_ctx) = test_env();
+
+    let src = r#"
+    trait Shape {
+        fn area(self) -> number
+        fn describe(self) -> string {
+            return `area = ${self.area()}`
+        }
+    }
+    struct Circle { pub radius: number }
+    impl Shape for Circle {
+        fn area(self) -> number {
+            return 3.14 * self.radius * self.radius
+        }
+    }
+    let circle = Circle { radius: 5 }
+    let area = circle.area()
+    let desc = circle.describe()
+    "#;
+    let mut script = parse_script(src).unwrap();
+    checker.infer_script(&mut script, &mut my_ctx)?;
+
+    let binding = my_ctx.values.get("area").unwrap();
+    assert_eq!(checker.print_type(&binding.index), r#"number"#);
+    let binding = my_ctx.values.get("desc").unwrap();
+    assert_eq!(checker.print_type(&binding.index), r#"string"#);
+
+    assert_no_errors(&checker)
+}
+
+#[test]
+fn test_trait_bounds() -> Result<(), TypeError> {
+    let (mut checker, mut my_ctx) = test_env();
+
+    let src = r#"
+    trait Shape {
+        fn area(self) -> number
+    }
+    struct Square { pub size: number }
+    impl Shape for Square {
+        fn area(self) -> number {
+            return self.size * self.size
+        }
+    }
+    let getArea = fn <T: Shape>(shape: T) => shape.area()
+    let area = getArea(Square { size: 5 })
+    "#;
+    let mut script = parse_script(src).unwrap();
+    checker.infer_script(&mut script, &mut my_ctx)?;
+
+    let binding = my_ctx.values.get("getArea").unwrap();
+    assert_eq!(
+        checker.print_type(&binding.index),
+        r#"<T:Shape>(shape: T) -> number"#
+    );
+    let binding = my_ctx.values.get("area").unwrap();
+    assert_eq!(checker.print_type(&binding.index), r#"number"#);
+
+    assert_no_errors(&checker)
+}
+
+#[test]
+fn test_trait_bound_not_implemented() -> Result<(), TypeError> {
+    let (mut checker, mut my_ctx) = test_env();
+
+    let src = r#"
+    trait Shape {
+        fn area(self) -> number
+    }
+    struct Square { pub size: number }
+    let getArea = fn <T: Shape>(shape: T) => shape.area()
+    let area = getArea(Square { size: 5 })
+    "#;
+    let mut script = parse_script(src).unwrap();
+    checker.infer_script(&mut script, &mut my_ctx)?;
+
+    let diagnostic = &checker.current_report.diagnostics[0];
+    assert_eq!(diagnostic.kind, DiagnosticKind::IncorrectArgs);
+    assert_eq!(
+        diagnostic.reasons,
+        vec![TypeError {
+            message: "Square doesn't implement Shape".to_string()
+        }]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_impl_missing_method() -> Result<(), TypeError> {
+    let (mut checker, mut my_ctx) = test_env();
+
+    let src = r#"
+    trait Shape {
+        fn area(self) -> number
+        fn perimeter(self) -> number
+    }
+    struct Square { pub size: number }
+    impl Shape for Square {
+        fn area(self) -> number {
+            return self.size * self.size
+        }
+    }
+    "#;
+    let mut script = parse_script(src).unwrap();
+    let result = checker.infer_script(&mut script, &mut my_ctx);
+
+    assert_eq!(
+        result,
+        Err(TypeError {
+            message: "Square is missing method perimeter from trait Shape".to_string()
+        })
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_impl_method_signature_mismatch() -> Result<(), TypeError> {
+    let (mut checker, mut my_ctx) = test_env();
+
+    let src = r#"
+    trait Shape {
+        fn area(self) -> number
+    }
+    struct Square { pub size: number }
+    impl Shape for Square {
+        fn area(self) -> string {
+            return "big"
+        }
+    }
+    "#;
+    let mut script = parse_script(src).unwrap();
+    let result = checker.infer_script(&mut script, &mut my_ctx);
+
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_impl_cannot_access_private_fields() -> Result<(), TypeError> {
+    let (mut checker, mut my_ctx) = test_env();
+
+    let src = r#"
+    trait Shape {
+        fn area(self) -> number
+    }
+    struct Square { size: number }
+    impl Shape for Square {
+        fn area(self) -> number {
+            return self.size * self.size
+        }
+    }
+    "#;
+    let mut script = parse_script(src).unwrap();
+    let result = checker.infer_script(&mut script, &mut my_ctx);
+
+    assert_eq!(
+        result,
+        Err(TypeError {
+            message: "size is a private field of Square".to_string()
+        })
+    );
+
+    Ok(())
+}
+
+#[test]
+fn pipelines() -> Result<(), TypeError> {
+    let (mut checker, mut my_ctx) = test_env();
+
+    let src = r#"
+    declare let double: fn(x: number) -> number
+    declare let show: fn(x: number, radix: number) -> string
+    let result = 5 |> double(_) |> show(_, 16)
+    let tuple = "hello" |> [_, _]
+    "#;
+    let mut script = parse_script(src).unwrap();
+
+    checker.infer_script(&mut script, &mut my_ctx)?;
+
+    let binding = my_ctx.values.get("result").unwrap();
+    assert_eq!(checker.print_type(&binding.index), r#"string"#);
+    let binding = my_ctx.values.get("tuple").unwrap();
+    assert_eq!(checker.print_type(&binding.index), r#"["hello", "hello"]"#);
+
+    assert_no_errors(&checker)
+}
+
+#[test]
+fn pipeline_stage_type_mismatch() -> Result<(), TypeError> {
+    let (mut checker, mut my_ctx) = test_env();
+
+    let src = r#"
+    declare let double: fn(x: number) -> number
+    let result = "hello" |> double(_)
+    "#;
+    let mut script = parse_script(src).unwrap();
+
+    checker.infer_script(&mut script, &mut my_ctx)?;
+
+    assert_eq!(
+        checker.current_report.diagnostics[0].reasons,
+        vec![TypeError {
+            message: r#"type mismatch: unify("hello", number) failed"#.to_string()
+        }]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn pipelines_propagate_throws() -> Result<(), TypeError> {
+    let (mut checker, mut my_ctx) = test_env();
+
+    let src = r#"
+    declare let parse: fn(x: string) -> number throws "SyntaxError"
+    declare let check: fn(x: number) -> number throws "RangeError"
+    let run = fn(input: string) {
+        return input |> parse(_) |> check(_)
+    }
+    "#;
+    let mut script = parse_script(src).unwrap();
+
+    checker.infer_script(&mut script, &mut my_ctx)?;
+
+    let binding = my_ctx.values.get("run").unwrap();
+    assert_eq!(
+        checker.print_type(&binding.index),
+        r#"(input: string) -> number throws "SyntaxError" | "RangeError""#
+    );
+
+    assert_no_errors(&checker)
+}
+
+#[test]
+fn topic_outside_of_pipeline() -> Result<(), TypeError> {
+    let (mut checker, mut my_ctx) = test_env();
+
+    let src = r#"
+    declare let double: fn(x: number) -> number
+    let result = double(_)
+    "#;
+    let mut script = parse_script(src).unwrap();
+
+    let result = checker.infer_script(&mut script, &mut my_ctx);
+
+    assert_eq!(
+        result,
+        Err(TypeError {
+            message: "`_` can only be used in the right side of `|>`".to_string()
+        })
+    );
+
+    Ok(())
+}
+
+#[test]
+fn regex_literals() -> Result<(), TypeError> {
+    let (mut checker, mut my_ctx) = test_env();
+
+    let src = r#"
+    let regex = /(?<foo>foo)(?<bar>bar)/
+    let global = /\d+/g
+    "#;
+    let mut script = parse_script(src).unwrap();
+
+    checker.infer_script(&mut script, &mut my_ctx)?;
+
+    let binding = my_ctx.values.get("regex").unwrap();
+    assert_eq!(
+        checker.print_type(&binding.index),
+        r#"RegExp<"(?<foo>foo)(?<bar>bar)", "">"#
+    );
+    let binding = my_ctx.values.get("global").unwrap();
+    assert_eq!(checker.print_type(&binding.index), r#"RegExp<"\d+", "g">"#);
+
+    assert_no_errors(&checker)
+}
+
+#[test]
+fn regex_capture_groups() -> Result<(), TypeError> {
+    let (mut checker, mut my_ctx) = test_env();
+
+    let src = r#"
+    type RegExp<TPattern, TFlags> = {lastIndex: number}
+    type RegExpMatchArray<TPattern, TFlags> = string[]
+    declare let exec: fn <P, F>(str: string, regex: RegExp<P, F>) -> RegExpMatchArray<P, F>
+    let result = exec("rgb(0,128,255)", /rgb\((?<r>\d+),(?<g>\d+),(?<b>\d+)\)(?: (\w+))?/)
+    let {groups: {r, g, b}} = result
+    let [all, red, green, blue, alpha] = result
+    "#;
+    let mut script = parse_script(src).unwrap();
+
+    checker.infer_script(&mut script, &mut my_ctx)?;
+
+    let binding = my_ctx.values.get("result").unwrap();
+    assert_eq!(
+        checker.print_type(&binding.index),
+        r#"RegExpMatchArray<"rgb\((?<r>\d+),(?<g>\d+),(?<b>\d+)\)(?: (\w+))?", "">"#
+    );
+    let t = checker.expand_type(&my_ctx, binding.index)?;
+    assert_eq!(
+        checker.print_type(&t),
+        r#"[string, string, string, string, string | undefined] & {groups: {r: string, g: string, b: string}, index: number, input: string}"#
+    );
+    let binding = my_ctx.values.get("r").unwrap();
+    assert_eq!(checker.print_type(&binding.index), r#"string"#);
+    let binding = my_ctx.values.get("alpha").unwrap();
+    assert_eq!(checker.print_type(&binding.index), r#"string | undefined"#);
+
+    assert_no_errors(&checker)
+}
+
+#[test]
+fn regex_global_match() -> Result<(), TypeError> {
+    let (mut checker, mut my_ctx) = test_env();
+
+    let src = r#"
+    type RegExp<TPattern, TFlags> = {lastIndex: number}
+    type RegExpMatchArray<TPattern, TFlags> = string[]
+    declare let exec: fn <P, F>(str: string, regex: RegExp<P, F>) -> RegExpMatchArray<P, F>
+    let result = exec("foobar", /(?<foo>foo)|(?<bar>bar)/g)
+    "#;
+    let mut script = parse_script(src).unwrap();
+
+    checker.infer_script(&mut script, &mut my_ctx)?;
+
+    let binding = my_ctx.values.get("result").unwrap();
+    let t = checker.expand_type(&my_ctx, binding.index)?;
+    assert_eq!(checker.print_type(&t), r#"string[]"#);
+
+    assert_no_errors(&checker)
+}
@@ -0,0 +1,526 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use generational_arena::Index;
+use swc_common::comments::SingleThreadedComments;
+use swc_common::{FileName, SourceMap};
+use swc_ecma_ast as ts;
+use swc_ecma_parser::error::Error;
+
+use escalier_ast::*;
+use escalier_fmt::print_script;
+use escalier_hm::checker::Checker;
+use escalier_hm::context::Context;
+use escalier_hm::docs::JsDoc;
+use escalier_hm::types::{self, TObjElem, TPat, TPropKey, TypeKind};
+use escalier_parser::KEYWORDS;
+
+use crate::parse::{infer_modules, parse_module};
+
+/// Converts the declarations in a .d.ts file into Escalier declarations.
+/// Interfaces and type aliases become `type` declarations, variables and
+/// functions become `declare let` declarations, and classes and enums become
+/// both.  JSDoc comments are preserved.  Namespaces and ambient modules, e.g.
+/// `declare module "foo" {}`, aren't supported yet and are skipped with a
+/// warning.
+pub fn convert_dts(d_ts_source: &str) -> Result<String, Error> {
+    let cm = Arc::<SourceMap>::default();
+    let comments = SingleThreadedComments::default();
+
+    let module = parse_module(&cm, &comments, FileName::Anon, d_ts_source)?;
+    let names = get_decl_names(&module);
+    let mut checker = Checker::default();
+    let ctx = infer_modules(&mut checker, Context::default(), &[module], comments);
+
+    let mut stmts = vec![];
+    for name in &names {
+        let mut has_docs = false;
+        if let Some(scheme) = ctx.schemes.get(name) {
+            if !scheme.is_type_param {
+                stmts.push(build_type_decl(&mut checker, &ctx, name, scheme));
+                has_docs = true;
+            }
+        }
+        if let Some(binding) = ctx.values.get(name) {
+            // The docs for classes and enums are attached to their types.
+            let docs = if has_docs { None } else { ctx.docs.get(name) };
+            let type_ann = build_type_ann(&mut checker, binding.index);
+            stmts.push(build_decl_stmt(
+                DeclKind::VarDecl(VarDecl {
+                    is_declare: true,
+                    is_var: false,
+                    pattern: build_ident_pattern(name, false),
+                    expr: None,
+                    type_ann: Some(type_ann),
+                }),
+                docs,
+            ));
+        }
+    }
+
+    Ok(print_script(&Script { stmts }))
+}
+
+// Returns the names of the top-level declarations in the order they appear.
+// Interfaces that are declared more than once are only included once.
+fn get_decl_names(module: &ts::Module) -> Vec<String> {
+    let mut names: Vec<String> = vec![];
+    let mut seen: HashSet<String> = HashSet::new();
+
+    for item in &module.body {
+        let decl = match item {
+            ts::ModuleItem::Stmt(ts::Stmt::Decl(decl)) => decl,
+            ts::ModuleItem::ModuleDecl(ts::ModuleDecl::ExportDecl(export)) => &export.decl,
+            _ => continue,
+        };
+        let decl_names = match decl {
+            ts::Decl::Class(decl) => vec![decl.ident.sym.to_string()],
+            ts::Decl::Fn(decl) => vec![decl.ident.sym.to_string()],
+            ts::Decl::Var(decl) => decl
+                .decls
+                .iter()
+                .filter_map(|d| match &d.name {
+                    ts::Pat::Ident(bi) => Some(bi.id.sym.to_string()),
+                    _ => None,
+                })
+                .collect(),
+            ts::Decl::TsInterface(decl) => vec![decl.id.sym.to_string()],
+            ts::Decl::TsTypeAlias(decl) => vec![decl.id.sym.to_string()],
+            ts::Decl::TsEnum(decl) => vec![decl.id.sym.to_string()],
+            ts::Decl::TsModule(decl) => {
+                let name = match &decl.id {
+                    ts::TsModuleName::Ident(id) => id.sym.to_string(),
+                    ts::TsModuleName::Str(str) => format!("\"{}\"", str.value),
+                };
+                eprintln!("skipping namespace {name}, namespaces can't be converted yet");
+                continue;
+            }
+            ts::Decl::Using(_) => continue,
+        };
+        for name in decl_names {
+            if seen.insert(name.to_owned()) {
+                names.push(name);
+            }
+        }
+    }
+
+    names
+}
+
+fn build_type_decl(
+    checker: &mut Checker,
+    ctx: &Context,
+    name: &str,
+    scheme: &types::Scheme,
+) -> Stmt {
+    let mut type_ann = build_type_ann(checker, scheme.t);
+    if let TypeAnnKind::Object(props) = &mut type_ann.kind {
+        for prop in props.iter_mut() {
+            let (key, comments) = match prop {
+                ObjectProp::Method(MethodType {
+                    name: key,
+                    comments,
+                    ..
+                })
+                | ObjectProp::Getter(GetterType {
+                    name: key,
+                    comments,
+                    ..
+                })
+                | ObjectProp::Setter(SetterType {
+                    name: key,
+                    comments,
+                    ..
+                })
+                | ObjectProp::Prop(type_ann::Prop {
+                    name: key,
+                    comments,
+                    ..
+                }) => (key, comments),
+                ObjectProp::Call(_) | ObjectProp::Constructor(_) | ObjectProp::Mapped(_) => {
+                    continue
+                }
+            };
+            let docs = ctx.docs.get(&format!("{name}.{key}"));
+            if let Some(comment) = docs.and_then(build_doc_comment) {
+                comments.leading.push(comment);
+            }
+        }
+    }
+
+    let type_params = scheme.type_params.as_ref().map(|type_params| {
+        type_params
+            .iter()
+            .map(|type_param| build_type_param(checker, type_param))
+            .collect()
+    });
+
+    build_decl_stmt(
+        DeclKind::TypeDecl(TypeDecl {
+            name: name.to_owned(),
+            type_ann,
+            type_params,
+        }),
+        ctx.docs.get(name),
+    )
+}
+
+fn build_decl_stmt(kind: DeclKind, docs: Option<&JsDoc>) -> Stmt {
+    let mut comments = Comments::default();
+    if let Some(comment) = docs.and_then(build_doc_comment) {
+        comments.leading.push(comment);
+    }
+
+    Stmt {
+        kind: StmtKind::Decl(Decl {
+            kind,
+            span: DUMMY_SPAN,
+            comments,
+        }),
+        span: DUMMY_SPAN,
+        inferred_type: None,
+    }
+}
+
+// Builds a `/** ... */` comment from `docs`.  Tags that are only used for
+// interop, e.g. `@mutates`, aren't included so the comment may be empty.
+fn build_doc_comment(docs: &JsDoc) -> Option<Comment> {
+    let mut lines: Vec<String> = vec![];
+    if !docs.text.is_empty() {
+        lines.extend(docs.text.lines().map(|line| line.to_string()));
+    }
+    if let Some(reason) = &docs.deprecated {
+        lines.push(format!("@deprecated {reason}").trim_end().to_string());
+    }
+    for (name, description) in &docs.params {
+        let name = escape_keyword(name);
+        lines.push(
+            format!("@param {name} {description}")
+                .trim_end()
+                .to_string(),
+        );
+    }
+    if let Some(returns) = &docs.returns {
+        lines.push(format!("@returns {returns}").trim_end().to_string());
+    }
+
+    let text = match lines.as_slice() {
+        [] => return None,
+        [line] => format!("* {line} "),
+        lines => {
+            let mut text = String::from("*\n");
+            for line in lines {
+                text.push_str(format!(" * {line}").trim_end());
+                text.push('\n');
+            }
+            text.push(' ');
+            text
+        }
+    };
+
+    Some(Comment {
+        kind: CommentKind::Block,
+        text,
+        span: DUMMY_SPAN,
+    })
+}
+
+/// Builds a type annotation for `t` that can be printed as Escalier source.
+pub fn build_type_ann(checker: &mut Checker, t: Index) -> TypeAnn {
+    let t = checker.prune(t);
+
+    // It's okay to clone here because we aren't mutating the type
+    let kind = match &checker.arena[t].kind.clone() {
+        // Type variables that haven't been bound can be anything.
+        TypeKind::TypeVar(_) => TypeAnnKind::Unknown,
+        TypeKind::TypeRef(types::TypeRef {
+            name, type_args, ..
+        }) => {
+            let type_args = match type_args.is_empty() {
+                true => None,
+                false => Some(build_type_anns(checker, type_args)),
+            };
+            TypeAnnKind::TypeRef(name.to_owned(), type_args)
+        }
+        TypeKind::Union(types::Union { types }) => {
+            TypeAnnKind::Union(build_type_anns(checker, types))
+        }
+        TypeKind::Intersection(types::Intersection { types }) => {
+            TypeAnnKind::Intersection(build_type_anns(checker, types))
+        }
+        TypeKind::Array(types::Array { t }) => {
+            TypeAnnKind::Array(Box::new(build_type_ann(checker, *t)))
+        }
+        TypeKind::Tuple(types::Tuple { types, .. }) => {
+            TypeAnnKind::Tuple(build_type_anns(checker, types))
+        }
+        TypeKind::Keyword(keyword) => match keyword {
+            types::Keyword::Never => TypeAnnKind::Never,
+            types::Keyword::Unknown => TypeAnnKind::Unknown,
+            types::Keyword::Object => TypeAnnKind::TypeRef("object".to_string(), None),
+        },
+        TypeKind::Primitive(primitive) => match primitive {
+            types::Primitive::Number => TypeAnnKind::Number,
+            types::Primitive::Boolean => TypeAnnKind::Boolean,
+            types::Primitive::String => TypeAnnKind::String,
+            types::Primitive::Symbol => TypeAnnKind::Symbol,
+        },
+        TypeKind::Literal(lit) => match lit {
+            Literal::Number(value) => TypeAnnKind::NumLit(value.to_owned()),
+            Literal::String(value) => TypeAnnKind::StrLit(value.to_owned()),
+            Literal::Boolean(value) => TypeAnnKind::BoolLit(*value),
+            Literal::Null => TypeAnnKind::Null,
+            Literal::Undefined => TypeAnnKind::Undefined,
+        },
+        TypeKind::Function(function) => {
+            TypeAnnKind::Function(build_function_type(checker, function))
+        }
+        TypeKind::Object(types::Object { elems }) => {
+            let props = elems
+                .iter()
+                .map(|elem| build_object_prop(checker, elem))
+                .collect();
+            TypeAnnKind::Object(props)
+        }
+        TypeKind::Rest(types::Rest { arg }) => {
+            TypeAnnKind::Rest(Box::new(build_type_ann(checker, *arg)))
+        }
+        TypeKind::KeyOf(types::KeyOf { t }) => {
+            TypeAnnKind::KeyOf(Box::new(build_type_ann(checker, *t)))
+        }
+        TypeKind::IndexedAccess(types::IndexedAccess { obj, index }) => TypeAnnKind::IndexedAccess(
+            Box::new(build_type_ann(checker, *obj)),
+            Box::new(build_type_ann(checker, *index)),
+        ),
+        TypeKind::Conditional(types::Conditional {
+            check,
+            extends,
+            true_type,
+            false_type,
+        }) => TypeAnnKind::Condition(ConditionType {
+            check: Box::new(build_type_ann(checker, *check)),
+            extends: Box::new(build_type_ann(checker, *extends)),
+            true_type: Box::new(build_type_ann(checker, *true_type)),
+            false_type: Box::new(build_type_ann(checker, *false_type)),
+        }),
+        TypeKind::Infer(types::Infer { name }) => TypeAnnKind::Infer(name.to_owned()),
+        TypeKind::Wildcard => TypeAnnKind::Wildcard,
+        TypeKind::Binary(types::BinaryT { op, left, right }) => {
+            TypeAnnKind::Binary(BinaryTypeAnn {
+                left: Box::new(build_type_ann(checker, *left)),
+                op: match op {
+                    types::TBinaryOp::Add => BinaryOp::Plus,
+                    types::TBinaryOp::Sub => BinaryOp::Minus,
+                    types::TBinaryOp::Mul => BinaryOp::Times,
+                    types::TBinaryOp::Div => BinaryOp::Divide,
+                    types::TBinaryOp::Mod => BinaryOp::Modulo,
+                },
+                right: Box::new(build_type_ann(checker, *right)),
+            })
+        }
+        // Escalier doesn't have type predicates so we use the type of the
+        // value they evaluate to.
+        // Escalier doesn't have type predicates yet.  Assertions don't return
+        // anything.
+        TypeKind::TypePredicate(types::TypePredicate { asserts, .. }) => match asserts {
+            true => TypeAnnKind::Undefined,
+            false => TypeAnnKind::Boolean,
+        },
+    };
+
+    TypeAnn {
+        kind,
+        span: DUMMY_SPAN,
+        inferred_type: None,
+    }
+}
+
+fn build_type_anns(checker: &mut Checker, types: &[Index]) -> Vec<TypeAnn> {
+    types.iter().map(|t| build_type_ann(checker, *t)).collect()
+}
+
+fn build_object_prop(checker: &mut Checker, elem: &TObjElem) -> ObjectProp {
+    match elem {
+        TObjElem::Call(function) => ObjectProp::Call(build_function_type(checker, function)),
+        TObjElem::Constructor(function) => {
+            ObjectProp::Constructor(build_function_type(checker, function))
+        }
+        TObjElem::Method(types::TMethod {
+            name,
+            mutates,
+            function,
+        }) => {
+            let function = build_function_type(checker, function);
+            ObjectProp::Method(MethodType {
+                span: DUMMY_SPAN,
+                name: get_key_name(name),
+                type_params: function.type_params,
+                params: function.params,
+                ret: function.ret,
+                throws: function.throws,
+                mutates: *mutates,
+                comments: Comments::default(),
+            })
+        }
+        TObjElem::Getter(types::TGetter { name, ret, .. }) => ObjectProp::Getter(GetterType {
+            span: DUMMY_SPAN,
+            name: get_key_name(name),
+            ret: Box::new(build_type_ann(checker, *ret)),
+            comments: Comments::default(),
+        }),
+        TObjElem::Setter(types::TSetter { name, param, .. }) => ObjectProp::Setter(SetterType {
+            span: DUMMY_SPAN,
+            name: get_key_name(name),
+            param: Box::new(build_func_param(checker, param)),
+            comments: Comments::default(),
+        }),
+        TObjElem::Mapped(types::MappedType {
+            key,
+            value,
+            target,
+            source,
+            optional,
+            readonly,
+            check,
+            extends,
+        }) => ObjectProp::Mapped(Mapped {
+            key: Box::new(build_type_ann(checker, *key)),
+            value: Box::new(build_type_ann(checker, *value)),
+            target: target.to_owned(),
+            source: Box::new(build_type_ann(checker, *source)),
+            optional: optional.as_ref().map(|optional| match optional {
+                types::MappedModifier::Add => MappedModifier::Add,
+                types::MappedModifier::Remove => MappedModifier::Remove,
+            }),
+            check: check.map(|check| Box::new(build_type_ann(checker, check))),
+            extends: extends.map(|extends| Box::new(build_type_ann(checker, extends))),
+            // Escalier doesn't have syntax for readonly mapped types yet so we
+            // leave a note instead of silently changing the type.
+            comments: Comments {
+                leading: readonly
+                    .as_ref()
+                    .map(|readonly| {
+                        let modifier = match readonly {
+                            types::MappedModifier::Add => "readonly",
+                            types::MappedModifier::Remove => "-readonly",
+                        };
+                        Comment {
+                            kind: CommentKind::Line,
+                            text: format!(" TODO: `{modifier}` was dropped from this mapped type"),
+                            span: DUMMY_SPAN,
+                        }
+                    })
+                    .into_iter()
+                    .collect(),
+                trailing: vec![],
+            },
+        }),
+        TObjElem::Prop(types::TProp {
+            name,
+            optional,
+            readonly,
+            t,
+        }) => ObjectProp::Prop(type_ann::Prop {
+            span: DUMMY_SPAN,
+            name: get_key_name(name),
+            modifier: None,
+            optional: *optional,
+            readonly: *readonly,
+            type_ann: Box::new(build_type_ann(checker, *t)),
+            comments: Comments::default(),
+        }),
+    }
+}
+
+fn build_function_type(checker: &mut Checker, function: &types::Function) -> FunctionType {
+    let type_params = function.type_params.as_ref().map(|type_params| {
+        type_params
+            .iter()
+            .map(|type_param| build_type_param(checker, type_param))
+            .collect()
+    });
+    let params = function
+        .params
+        .iter()
+        .map(|param| build_func_param(checker, param))
+        .collect();
+
+    FunctionType {
+        span: DUMMY_SPAN,
+        type_params,
+        params,
+        ret: Box::new(build_type_ann(checker, function.ret)),
+        throws: function
+            .throws
+            .map(|throws| Box::new(build_type_ann(checker, throws))),
+    }
+}
+
+fn build_func_param(checker: &mut Checker, param: &types::FuncParam) -> TypeAnnFuncParam {
+    TypeAnnFuncParam {
+        pattern: build_pattern(&param.pattern),
+        type_ann: build_type_ann(checker, param.t),
+        optional: param.optional,
+    }
+}
+
+fn build_type_param(checker: &mut Checker, type_param: &types::TypeParam) -> TypeParam {
+    TypeParam {
+        span: DUMMY_SPAN,
+        name: type_param.name.to_owned(),
+        bound: type_param
+            .constraint
+            .map(|constraint| build_type_ann(checker, constraint)),
+        default: type_param
+            .default
+            .map(|default| build_type_ann(checker, default)),
+    }
+}
+
+// Params in .d.ts files are almost always identifiers.  Destructured params
+// are replaced with a wildcard since their names don't affect the type.
+fn build_pattern(pattern: &TPat) -> Pattern {
+    match pattern {
+        TPat::Ident(BindingIdent { name, mutable, .. }) => build_ident_pattern(name, *mutable),
+        TPat::Rest(types::RestPat { arg }) => Pattern {
+            kind: PatternKind::Rest(RestPat {
+                arg: Box::new(build_pattern(arg)),
+            }),
+            span: DUMMY_SPAN,
+            inferred_type: None,
+        },
+        _ => Pattern {
+            kind: PatternKind::Wildcard,
+            span: DUMMY_SPAN,
+            inferred_type: None,
+        },
+    }
+}
+
+fn build_ident_pattern(name: &str, mutable: bool) -> Pattern {
+    Pattern {
+        kind: PatternKind::Ident(BindingIdent {
+            name: escape_keyword(name),
+            span: DUMMY_SPAN,
+            mutable,
+        }),
+        span: DUMMY_SPAN,
+        inferred_type: None,
+    }
+}
+
+// Names that are keywords in Escalier, e.g. `string`, can't be used as
+// identifiers so we add a suffix to them.
+fn escape_keyword(name: &str) -> String {
+    match KEYWORDS.contains_key(name) {
+        true => format!("{name}_"),
+        false => name.to_owned(),
+    }
+}
+
+fn get_key_name(key: &TPropKey) -> String {
+    match key {
+        TPropKey::StringKey(name) => name.to_owned(),
+        TPropKey::NumberKey(name) => name.to_owned(),
+    }
+}
@@ -0,0 +1,94 @@
+use swc_common::comments::{Comment, CommentKind, SingleThreadedComments};
+use swc_common::BytePos;
+
+use escalier_hm::docs::JsDoc;
+
+// Returns the text of the JSDoc comment before `pos` without the leading `*`
+// on each line.  If there's more than one we use the one closest to `pos`.
+fn get_jsdoc_text(comments: &SingleThreadedComments, pos: BytePos) -> Option<String> {
+    comments.with_leading(pos, |comments| {
+        comments
+            .iter()
+            .rev()
+            .find(|comment| is_jsdoc(comment))
+            .map(|comment| {
+                comment.text[1..]
+                    .lines()
+                    .map(|line| {
+                        let line = line.trim();
+                        let line = line.strip_prefix('*').unwrap_or(line);
+                        line.strip_prefix(' ').unwrap_or(line)
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+    })
+}
+
+fn is_jsdoc(comment: &Comment) -> bool {
+    comment.kind == CommentKind::Block && comment.text.starts_with('*')
+}
+
+/// Returns true if the JSDoc comment before `pos` has the given tag, e.g.
+/// `@mutates`.
+pub fn has_jsdoc_tag(comments: &SingleThreadedComments, pos: BytePos, tag: &str) -> bool {
+    match get_jsdoc_text(comments, pos) {
+        Some(text) => text.split_whitespace().any(|word| word == tag),
+        None => false,
+    }
+}
+
+/// Parses the JSDoc comment before `pos`.  Tags other than `@deprecated`,
+/// `@param` and `@returns` are ignored.
+pub fn get_jsdoc(comments: &SingleThreadedComments, pos: BytePos) -> Option<JsDoc> {
+    let text = get_jsdoc_text(comments, pos)?;
+    Some(parse_jsdoc(&text))
+}
+
+fn parse_jsdoc(text: &str) -> JsDoc {
+    let mut doc = JsDoc::default();
+    let mut description: Vec<&str> = vec![];
+    // Tags can span multiple lines.
+    let mut tags: Vec<String> = vec![];
+
+    for line in text.lines() {
+        if line.starts_with('@') {
+            tags.push(line.to_string());
+        } else if let Some(tag) = tags.last_mut() {
+            tag.push('\n');
+            tag.push_str(line);
+        } else {
+            description.push(line);
+        }
+    }
+
+    doc.text = description.join("\n").trim().to_string();
+
+    for tag in tags {
+        let (name, rest) = tag.split_once(char::is_whitespace).unwrap_or((&tag, ""));
+        let rest = rest.trim();
+        match name {
+            "@deprecated" => doc.deprecated = Some(rest.to_string()),
+            "@param" => {
+                // The type is optional, e.g. `@param {string} name`.
+                let rest = match rest.strip_prefix('{') {
+                    Some(rest) => rest.split_once('}').map_or("", |(_, rest)| rest.trim()),
+                    None => rest,
+                };
+                let (name, description) =
+                    rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+                // Optional params are written as `[name]` or `[name=default]`.
+                let name = name.trim_start_matches('[').trim_end_matches(']');
+                let name = name.split('=').next().unwrap_or(name);
+                let description = description.trim().trim_start_matches("- ");
+                if !name.is_empty() {
+                    doc.params.push((name.to_string(), description.to_string()));
+                }
+            }
+            "@returns" | "@return" => doc.returns = Some(rest.to_string()),
+            _ => (),
+        }
+    }
+
+    doc
+}
@@ -1,2 +1,6 @@
+pub mod convert;
+mod jsdoc;
+mod overrides;
 pub mod parse;
+pub mod resolver;
 mod util;
@@ -0,0 +1,118 @@
+use std::collections::{HashMap, HashSet};
+
+use escalier_hm::checker::Checker;
+use escalier_hm::context::Context;
+use escalier_hm::types::{TObjElem, TPropKey, TypeKind};
+
+static TYPED_ARRAYS: &[&str] = &[
+    "Int8Array",
+    "Uint8Array",
+    "Uint8ClampedArray",
+    "Int16Array",
+    "Uint16Array",
+    "Int32Array",
+    "Uint32Array",
+    "Float32Array",
+    "Float64Array",
+    "BigInt64Array",
+    "BigUint64Array",
+];
+
+// Methods in the standard library that mutate `self`.  Methods that only appear
+// in the mutable half of a readonly/mutable pair of interfaces, e.g. `push` in
+// `Array` but not in `ReadonlyArray`, are already marked as mutating when the
+// pair is merged, but types like `Date` don't have a readonly counterpart.
+static MUTATING_METHODS: &[(&str, &[&str])] = &[
+    (
+        "Array",
+        &[
+            "copyWithin",
+            "fill",
+            "pop",
+            "push",
+            "reverse",
+            "shift",
+            "sort",
+            "splice",
+            "unshift",
+        ],
+    ),
+    (
+        "Date",
+        &[
+            "setDate",
+            "setFullYear",
+            "setHours",
+            "setMilliseconds",
+            "setMinutes",
+            "setMonth",
+            "setSeconds",
+            "setTime",
+            "setUTCDate",
+            "setUTCFullYear",
+            "setUTCHours",
+            "setUTCMilliseconds",
+            "setUTCMinutes",
+            "setUTCMonth",
+            "setUTCSeconds",
+        ],
+    ),
+    ("Map", &["clear", "delete", "set"]),
+    ("Set", &["add", "clear", "delete"]),
+    ("WeakMap", &["delete", "set"]),
+    ("WeakSet", &["add", "delete"]),
+    ("RegExp", &["compile"]),
+];
+
+static TYPED_ARRAY_MUTATING_METHODS: &[&str] = &["copyWithin", "fill", "reverse", "set", "sort"];
+
+/// Returns the names of the methods that mutate `self` for each type in the
+/// standard library.
+pub fn get_std_mutating_methods() -> HashMap<String, HashSet<String>> {
+    let mut result: HashMap<String, HashSet<String>> = HashMap::new();
+
+    for (name, methods) in MUTATING_METHODS {
+        let entry = result.entry(name.to_string()).or_default();
+        entry.extend(methods.iter().map(|method| method.to_string()));
+    }
+
+    for name in TYPED_ARRAYS {
+        let entry = result.entry(name.to_string()).or_default();
+        entry.extend(
+            TYPED_ARRAY_MUTATING_METHODS
+                .iter()
+                .map(|method| method.to_string()),
+        );
+    }
+
+    result
+}
+
+/// Marks the given methods as mutating in the schemes in `ctx`.  Types that
+/// aren't in `ctx` are ignored so that the same table can be used no matter
+/// which libs have been loaded.
+pub fn mark_mutating_methods(
+    checker: &mut Checker,
+    ctx: &Context,
+    mutating_methods: &HashMap<String, HashSet<String>>,
+) {
+    for (name, methods) in mutating_methods {
+        let Some(scheme) = ctx.schemes.get(name) else {
+            continue;
+        };
+
+        if let TypeKind::Object(obj) = &mut checker.arena[scheme.t].kind {
+            for elem in obj.elems.iter_mut() {
+                if let TObjElem::Method(method) = elem {
+                    let key = match &method.name {
+                        TPropKey::StringKey(key) => key,
+                        TPropKey::NumberKey(key) => key,
+                    };
+                    if methods.contains(key) {
+                        method.mutates = true;
+                    }
+                }
+            }
+        }
+    }
+}
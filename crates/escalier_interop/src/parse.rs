@@ -1,9 +1,11 @@
 use escalier_hm::infer::generalize_func;
 use generational_arena::Index;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
 use std::sync::Arc;
 
-use swc_common::{comments::SingleThreadedComments, FileName, SourceMap};
+use swc_common::comments::{Comments, SingleThreadedComments};
+use swc_common::{BytePos, FileName, SourceMap, Spanned, DUMMY_SP};
 use swc_ecma_ast::*;
 use swc_ecma_parser::{error::Error, parse_file_as_module, Syntax, TsConfig};
 use swc_ecma_visit::*;
@@ -15,10 +17,13 @@ use escalier_hm::checker::Checker;
 use escalier_hm::context::{Binding, Context};
 use escalier_hm::types::{
     self, Conditional, FuncParam, Function, IndexedAccess, Keyword, MappedType, Object, Primitive,
-    RestPat, Scheme, TObjElem, TPat, TProp, TPropKey, TypeKind, TypeParam, TypeRef,
+    RestPat, Scheme, TGetter, TObjElem, TPat, TProp, TPropKey, TSetter, TypeKind, TypeParam,
+    TypeRef,
 };
 
-// use crate::overrides::maybe_override_string_methods;
+use crate::jsdoc::{get_jsdoc, has_jsdoc_tag};
+use crate::overrides::{get_std_mutating_methods, mark_mutating_methods};
+use crate::resolver::is_relative;
 use crate::util::{merge_readonly_and_mutable_schemes, new_merge_schemes};
 
 pub fn infer_ts_type_ann(
@@ -156,6 +161,20 @@ pub fn infer_ts_type_ann(
                         type_args: result.ok().unwrap(),
                     })))
                 }
+                // References to these types pass along the pattern and flags
+                // so that regex literals can be typed precisely, see
+                // `is_regexp_type`.
+                None if is_regexp_type(&name) => {
+                    let type_args = vec![
+                        checker.new_type_ref("TPattern", None, &[]),
+                        checker.new_type_ref("TFlags", None, &[]),
+                    ];
+                    Ok(checker.from_type_kind(TypeKind::TypeRef(TypeRef {
+                        name,
+                        scheme: None,
+                        type_args,
+                    })))
+                }
                 None => Ok(checker.from_type_kind(TypeKind::TypeRef(TypeRef {
                     name,
                     scheme: None,
@@ -163,7 +182,21 @@ pub fn infer_ts_type_ann(
                 }))),
             }
         }
-        TsType::TsTypeQuery(_) => Err(String::from("can't parse type query yet")),
+        TsType::TsTypeQuery(TsTypeQuery {
+            span: _,
+            expr_name,
+            type_args,
+        }) => {
+            if type_args.is_some() {
+                return Err(String::from("can't parse instantiation expressions yet"));
+            }
+            match expr_name {
+                TsTypeQueryExpr::TsEntityName(entity_name) => {
+                    infer_type_query(checker, ctx, entity_name)
+                }
+                TsTypeQueryExpr::Import(_) => Err(String::from("can't parse typeof import yet")),
+            }
+        }
         TsType::TsTypeLit(TsTypeLit { span: _, members }) => {
             let elems: Vec<TObjElem> = members
                 .iter()
@@ -194,9 +227,43 @@ pub fn infer_ts_type_ann(
             // t.mutable = true;
             Ok(array_type)
         }
-        TsType::TsTupleType(_) => Err(String::from("can't parse tuple type yet")),
-        TsType::TsOptionalType(_) => Err(String::from("can't parse optional type yet")),
-        TsType::TsRestType(_) => Err(String::from("can't parse rest type yet")),
+        TsType::TsTupleType(TsTupleType {
+            span: _,
+            elem_types,
+        }) => {
+            let mut types = vec![];
+            let mut optional = vec![];
+            for TsTupleElement { span: _, label, ty } in elem_types {
+                let t = infer_ts_type_ann(checker, ctx, ty)?;
+                // Labeled elements, e.g. `[name?: string, ...rest: T[]]`,
+                // put the `?` and `...` on the label instead of the type.
+                let t = match label {
+                    Some(Pat::Ident(BindingIdent { id, .. })) if id.optional => {
+                        let undefined = checker.new_lit_type(&Lit::Undefined);
+                        checker.new_union_type(&[t, undefined])
+                    }
+                    Some(Pat::Rest(_)) => checker.new_rest_type(t),
+                    _ => t,
+                };
+                types.push(t);
+                optional.push(
+                    matches!(label, Some(Pat::Ident(BindingIdent { id, .. })) if id.optional)
+                        || matches!(ty.as_ref(), TsType::TsOptionalType(_)),
+                );
+            }
+            Ok(checker.new_tuple_type_with_optional(&types, &optional))
+        }
+        // NOTE: Optional types only appear as tuple elements.  The tuple marks
+        // these elements as optional so that they can be omitted.
+        TsType::TsOptionalType(TsOptionalType { span: _, type_ann }) => {
+            let t = infer_ts_type_ann(checker, ctx, type_ann)?;
+            let undefined = checker.new_lit_type(&Lit::Undefined);
+            Ok(checker.new_union_type(&[t, undefined]))
+        }
+        TsType::TsRestType(TsRestType { span: _, type_ann }) => {
+            let t = infer_ts_type_ann(checker, ctx, type_ann)?;
+            Ok(checker.new_rest_type(t))
+        }
         TsType::TsUnionOrIntersectionType(union_or_intersection) => match union_or_intersection {
             TsUnionOrIntersectionType::TsUnionType(union) => {
                 let types = union
@@ -234,8 +301,16 @@ pub fn infer_ts_type_ann(
             }));
             Ok(t)
         }
-        TsType::TsInferType(_) => Err(String::from("can't parse infer type yet")),
-        TsType::TsParenthesizedType(_) => Err(String::from("can't parse parenthesized yet")),
+        TsType::TsInferType(TsInferType {
+            span: _,
+            type_param,
+        }) => {
+            // TODO: handle constraints, e.g. `infer T extends string`
+            Ok(checker.new_infer_type(&type_param.name.sym))
+        }
+        TsType::TsParenthesizedType(TsParenthesizedType { span: _, type_ann }) => {
+            infer_ts_type_ann(checker, ctx, type_ann)
+        }
         TsType::TsTypeOperator(TsTypeOperator {
             op,
             type_ann,
@@ -285,7 +360,7 @@ pub fn infer_ts_type_ann(
             span: _,
             type_param,
             type_ann,
-            readonly: _,
+            readonly,
             optional,
             ..
         }) => {
@@ -299,16 +374,8 @@ pub fn infer_ts_type_ann(
                 }
             };
 
-            let optional = match optional {
-                Some(mode) => match mode {
-                    TruePlusMinus::True => Some(types::MappedModifier::Add),
-                    TruePlusMinus::Plus => Some(types::MappedModifier::Add),
-                    TruePlusMinus::Minus => Some(types::MappedModifier::Remove),
-                },
-                None => None,
-            };
-
-            // TODO: handle readonly modifier
+            let optional = optional.map(infer_mapped_modifier);
+            let readonly = readonly.map(infer_mapped_modifier);
 
             let name = type_param.name.sym.to_string();
 
@@ -318,6 +385,7 @@ pub fn infer_ts_type_ann(
                 source: constraint,
                 value: type_ann,
                 optional,
+                readonly,
                 check: None,
                 extends: None,
             })];
@@ -333,8 +401,79 @@ pub fn infer_ts_type_ann(
             TsLit::BigInt(_) => Err(String::from("can't parse BigInt literal yet")),
             TsLit::Tpl(_) => Err(String::from("can't parse Tpl literal yet")),
         },
-        TsType::TsTypePredicate(_) => Err(String::from("can't parse type predicate yet")),
-        TsType::TsImportType(_) => Err(String::from("can't parse import type yet")),
+        TsType::TsTypePredicate(TsTypePredicate {
+            span: _,
+            asserts,
+            param_name,
+            type_ann,
+        }) => {
+            let param = match param_name {
+                TsThisTypeOrIdent::TsThisType(_) => "self".to_string(),
+                TsThisTypeOrIdent::Ident(id) => id.sym.to_string(),
+            };
+            let t = match type_ann {
+                Some(type_ann) => Some(infer_ts_type_ann(checker, ctx, &type_ann.type_ann)?),
+                None => None,
+            };
+            Ok(checker.new_type_predicate(&param, t, *asserts))
+        }
+        TsType::TsImportType(TsImportType {
+            span,
+            arg,
+            qualifier,
+            type_args,
+        }) => {
+            let qualifier = match qualifier {
+                Some(qualifier) => qualifier,
+                None => return Err(String::from("can't parse import type without a name")),
+            };
+            let specifier = arg.value.to_string();
+
+            // Relative specifiers refer to other files in the same package
+            // and all of a package's files share the same context.
+            if is_relative(&specifier) {
+                let type_ref = TsType::TsTypeRef(TsTypeRef {
+                    span: *span,
+                    type_name: qualifier.to_owned(),
+                    type_params: type_args.to_owned(),
+                });
+                return infer_ts_type_ann(checker, ctx, &type_ref);
+            }
+
+            let name = match qualifier {
+                TsEntityName::Ident(id) => id.sym.to_string(),
+                TsEntityName::TsQualifiedName(_) => {
+                    return Err(String::from("can't parse qualified import types yet"))
+                }
+            };
+            let scheme = match ctx.modules.get(&specifier) {
+                Some(module) => module.get_scheme(&name).map_err(|err| err.message)?,
+                None => return Err(format!("can't find module \"{specifier}\"")),
+            };
+            let type_args = match type_args {
+                Some(type_args) => type_args
+                    .params
+                    .iter()
+                    .map(|t| infer_ts_type_ann(checker, ctx, t))
+                    .collect::<Result<Vec<_>, String>>()?,
+                None => vec![],
+            };
+            // The scheme is stored on the type reference so that it isn't
+            // looked up by name in whichever context the type is used in.
+            Ok(checker.from_type_kind(TypeKind::TypeRef(TypeRef {
+                name,
+                scheme: Some(scheme),
+                type_args,
+            })))
+        }
+    }
+}
+
+fn infer_mapped_modifier(mode: TruePlusMinus) -> types::MappedModifier {
+    match mode {
+        TruePlusMinus::True => types::MappedModifier::Add,
+        TruePlusMinus::Plus => types::MappedModifier::Add,
+        TruePlusMinus::Minus => types::MappedModifier::Remove,
     }
 }
 
@@ -395,6 +534,70 @@ fn infer_fn_params(
     Ok(params)
 }
 
+// `typeof x` is resolved to the placeholder for `x`, see `ValueNameFinder`, and
+// `typeof x.y` looks up `y` on the type of `x`.
+fn infer_type_query(
+    checker: &'_ mut Checker,
+    ctx: &mut Context,
+    entity_name: &TsEntityName,
+) -> Result<Index, String> {
+    match entity_name {
+        TsEntityName::Ident(id) => match ctx.values.get(id.sym.as_ref()) {
+            Some(binding) => Ok(binding.index),
+            None => Err(format!("can't find value `{}`", id.sym)),
+        },
+        TsEntityName::TsQualifiedName(q_name) => {
+            let obj = infer_type_query(checker, ctx, &q_name.left)?;
+            let key = checker.new_lit_type(&Lit::String(q_name.right.sym.to_string()));
+            Ok(checker.new_indexed_access_type(obj, key))
+        }
+    }
+}
+
+// `RegExp`, `RegExpExecArray`, and `RegExpMatchArray` have extra `TPattern` and
+// `TFlags` type params.  Within these interfaces, references to each other use
+// the interface's type params, elsewhere these type params are added to the
+// method referencing them, e.g. `String.prototype.match` becomes:
+// match<TPattern, TFlags>(regexp: string | RegExp<TPattern, TFlags>): RegExpMatchArray<TPattern, TFlags> | null
+fn is_regexp_type(name: &str) -> bool {
+    name == "RegExp" || name == "RegExpExecArray" || name == "RegExpMatchArray"
+}
+
+struct RegExpRefFinder {
+    found: bool,
+}
+
+impl Visit for RegExpRefFinder {
+    fn visit_ts_type_ref(&mut self, type_ref: &TsTypeRef) {
+        if let (TsEntityName::Ident(ident), None) = (&type_ref.type_name, &type_ref.type_params) {
+            if is_regexp_type(&ident.sym) {
+                self.found = true;
+            }
+        }
+        type_ref.visit_children_with(self);
+    }
+}
+
+fn get_regexp_type_params(ctx: &Context, sig: &TsMethodSignature) -> Vec<TypeParam> {
+    if ctx.schemes.contains_key("TPattern") {
+        return vec![];
+    }
+    let mut finder = RegExpRefFinder { found: false };
+    sig.params.visit_with(&mut finder);
+    sig.type_ann.visit_with(&mut finder);
+    match finder.found {
+        true => ["TPattern", "TFlags"]
+            .iter()
+            .map(|name| TypeParam {
+                name: name.to_string(),
+                constraint: None,
+                default: None,
+            })
+            .collect(),
+        false => vec![],
+    }
+}
+
 fn infer_method_sig(
     checker: &'_ mut Checker,
     ctx: &mut Context,
@@ -427,7 +630,8 @@ fn infer_method_sig(
 
     // TODO: check to see if there are any type variables in the signature
     // that can be turned into type params.
-    let type_params = match &sig.type_params {
+    let mut type_params = get_regexp_type_params(ctx, sig);
+    type_params.extend(match &sig.type_params {
         Some(type_param_decl) => type_param_decl
             .params
             .iter()
@@ -456,7 +660,7 @@ fn infer_method_sig(
             })
             .collect::<Result<Vec<TypeParam>, String>>()?,
         None => vec![],
-    };
+    });
 
     let name = get_key_name(sig.key.as_ref())?;
 
@@ -620,6 +824,7 @@ fn infer_ts_type_element(
                         value: t,
                         source: key.t,
                         optional: None,
+                        readonly: None,
                         check: None,
                         extends: None,
                     }))
@@ -697,6 +902,17 @@ fn infer_interface_decl(
     }
     let self_type = checker.new_type_ref(&decl.id.sym, None, &type_args);
 
+    if is_regexp_type(&decl.id.sym) {
+        for name in ["TPattern", "TFlags"] {
+            let scheme = Scheme {
+                t: checker.new_keyword(Keyword::Unknown),
+                type_params: None,
+                is_type_param: true,
+            };
+            sig_ctx.schemes.insert(name.to_string(), scheme);
+        }
+    }
+
     sig_ctx.schemes.insert(
         "Self".to_string(),
         Scheme {
@@ -761,11 +977,7 @@ fn infer_interface_decl(
 
     // Add `TPattern` and `TFlags` type params to `RegExp`, `RegExpExecArray`,
     // and `RegExpMatchArray`.
-    let interface_name = decl.id.sym.to_string();
-    if interface_name == "RegExp"
-        || interface_name == "RegExpExecArray"
-        || interface_name == "RegExpMatchArray"
-    {
+    if is_regexp_type(&decl.id.sym) {
         type_params = Some(vec![
             TypeParam {
                 name: "TPattern".to_string(),
@@ -789,6 +1001,289 @@ fn infer_interface_decl(
     Ok(scheme)
 }
 
+fn params_to_fn_params<'a>(
+    params: impl Iterator<Item = &'a Pat>,
+) -> Result<Vec<TsFnParam>, String> {
+    params
+        .map(|pat| match pat {
+            Pat::Ident(ident) => Ok(TsFnParam::Ident(ident.to_owned())),
+            Pat::Array(array) => Ok(TsFnParam::Array(array.to_owned())),
+            Pat::Rest(rest) => Ok(TsFnParam::Rest(rest.to_owned())),
+            Pat::Object(object) => Ok(TsFnParam::Object(object.to_owned())),
+            _ => Err(String::from("unexpected pattern in function params")),
+        })
+        .collect()
+}
+
+fn infer_function(
+    checker: &'_ mut Checker,
+    ctx: &mut Context,
+    function: &swc_ecma_ast::Function,
+) -> Result<Function, String> {
+    let params = params_to_fn_params(function.params.iter().map(|param| &param.pat))?;
+    match &function.return_type {
+        Some(type_ann) => infer_callable(
+            checker,
+            ctx,
+            &params,
+            &type_ann.type_ann,
+            &function.type_params,
+        ),
+        None => Err(String::from("function has no return type")),
+    }
+}
+
+// Each overload of a function is inferred separately, `parse_dts` combines them
+// into an intersection once all of the declarations have been collected.
+fn infer_fn_decl(
+    checker: &'_ mut Checker,
+    ctx: &mut Context,
+    decl: &FnDecl,
+) -> Result<Index, String> {
+    let function = infer_function(checker, ctx, &decl.function)?;
+    Ok(checker.from_type_kind(TypeKind::Function(function)))
+}
+
+// Classes are split into a scheme for the instance type and a value containing
+// the constructor and static members.
+fn infer_class_decl(
+    checker: &'_ mut Checker,
+    ctx: &mut Context,
+    name: &str,
+    class: &Class,
+) -> Result<(Scheme, Index), String> {
+    let mut sig_ctx = ctx.clone();
+
+    let type_params = get_type_params(checker, ctx, &class.type_params)?;
+    let type_args: Vec<Index> = type_params
+        .iter()
+        .map(|type_param| checker.new_type_ref(&type_param.name, None, &[]))
+        .collect();
+    let self_type = checker.new_type_ref(name, None, &type_args);
+
+    sig_ctx.schemes.insert(
+        "Self".to_string(),
+        Scheme {
+            t: self_type,
+            type_params: None,
+            is_type_param: false,
+        },
+    );
+
+    let type_params = if type_params.is_empty() {
+        None
+    } else {
+        Some(type_params)
+    };
+
+    // TODO: handle `extends` the same way as we handle interfaces extending
+    // other interfaces.  `implements` clauses don't contribute any members.
+    let mut instance_elems: Vec<TObjElem> = vec![];
+    let mut static_elems: Vec<TObjElem> = vec![];
+
+    for member in &class.body {
+        let result = match member {
+            ClassMember::Constructor(constructor) => {
+                let params = constructor
+                    .params
+                    .iter()
+                    .map(|param| match param {
+                        ParamOrTsParamProp::Param(param) => Ok(&param.pat),
+                        ParamOrTsParamProp::TsParamProp(_) => {
+                            Err(String::from("can't parse parameter properties yet"))
+                        }
+                    })
+                    .collect::<Result<Vec<_>, String>>()?;
+                let params = params_to_fn_params(params.into_iter())?;
+                let params = infer_fn_params(checker, &mut sig_ctx, &params)?;
+                static_elems.push(TObjElem::Constructor(Function {
+                    params,
+                    ret: self_type,
+                    type_params: type_params.to_owned(),
+                    throws: None,
+                }));
+                continue;
+            }
+            // Private and protected members aren't accessible from outside
+            // of the class.
+            ClassMember::Method(ClassMethod {
+                accessibility: Some(Accessibility::Private | Accessibility::Protected),
+                ..
+            })
+            | ClassMember::ClassProp(ClassProp {
+                accessibility: Some(Accessibility::Private | Accessibility::Protected),
+                ..
+            })
+            | ClassMember::PrivateMethod(_)
+            | ClassMember::PrivateProp(_)
+            | ClassMember::Empty(_)
+            | ClassMember::StaticBlock(_) => continue,
+            ClassMember::Method(method) => infer_class_method(checker, &mut sig_ctx, method)
+                .map(|elem| (method.is_static, elem)),
+            ClassMember::ClassProp(prop) => match &prop.type_ann {
+                Some(type_ann) => get_prop_name(&prop.key).and_then(|name| {
+                    let t = infer_ts_type_ann(checker, &mut sig_ctx, &type_ann.type_ann)?;
+                    let elem = TObjElem::Prop(TProp {
+                        name: TPropKey::StringKey(name),
+                        optional: prop.is_optional,
+                        readonly: prop.readonly,
+                        t,
+                    });
+                    Ok((prop.is_static, elem))
+                }),
+                None => Err(String::from("Property is missing type annotation")),
+            },
+            ClassMember::TsIndexSignature(sig) => {
+                let elem = TsTypeElement::TsIndexSignature(sig.to_owned());
+                infer_ts_type_element(checker, &mut sig_ctx, &elem)
+                    .map(|elem| (sig.is_static, elem))
+            }
+            ClassMember::AutoAccessor(_) => Err(String::from("can't parse auto-accessors yet")),
+        };
+
+        match result {
+            Ok((true, elem)) => static_elems.push(elem),
+            Ok((false, elem)) => instance_elems.push(elem),
+            Err(msg) => eprintln!("Err: {msg}"),
+        }
+    }
+
+    // Classes without a constructor can be instantiated without any args.
+    if !static_elems
+        .iter()
+        .any(|elem| matches!(elem, TObjElem::Constructor(_)))
+    {
+        static_elems.push(TObjElem::Constructor(Function {
+            params: vec![],
+            ret: self_type,
+            type_params: type_params.to_owned(),
+            throws: None,
+        }));
+    }
+
+    // Interfaces with the same name as a class are merged into the class.
+    if let Some(scheme) = ctx.schemes.get(name) {
+        if let TypeKind::Object(Object { elems }) = &checker.arena[scheme.t].kind {
+            instance_elems.extend(elems.to_owned());
+        }
+    }
+
+    let scheme = Scheme {
+        t: checker.new_object_type(&instance_elems),
+        type_params,
+        is_type_param: false,
+    };
+    let static_type = checker.new_object_type(&static_elems);
+
+    Ok((scheme, static_type))
+}
+
+fn infer_class_method(
+    checker: &'_ mut Checker,
+    ctx: &mut Context,
+    method: &ClassMethod,
+) -> Result<TObjElem, String> {
+    let name = TPropKey::StringKey(get_prop_name(&method.key)?);
+    let function = &method.function;
+
+    match method.kind {
+        MethodKind::Method => Ok(TObjElem::Method(types::TMethod {
+            name,
+            mutates: false,
+            function: infer_function(checker, ctx, function)?,
+        })),
+        MethodKind::Getter => match &function.return_type {
+            Some(type_ann) => Ok(TObjElem::Getter(TGetter {
+                name,
+                ret: infer_ts_type_ann(checker, ctx, &type_ann.type_ann)?,
+                throws: None,
+            })),
+            None => Err(String::from("getter has no return type")),
+        },
+        MethodKind::Setter => {
+            let params = params_to_fn_params(function.params.iter().map(|param| &param.pat))?;
+            let mut params = infer_fn_params(checker, ctx, &params)?;
+            match params.len() {
+                1 => Ok(TObjElem::Setter(TSetter {
+                    name,
+                    param: params.remove(0),
+                    throws: None,
+                })),
+                _ => Err(String::from("setters must have exactly one param")),
+            }
+        }
+    }
+}
+
+// Enums are split into a scheme for the union of the members' types and a value
+// containing each of the members.
+fn infer_enum_decl(checker: &'_ mut Checker, decl: &TsEnumDecl) -> Result<(Scheme, Index), String> {
+    let mut types: Vec<Index> = vec![];
+    let mut elems: Vec<TObjElem> = vec![];
+    // Members without initializers are numbered starting from the previous
+    // numeric member.
+    let mut next_value: Option<f64> = Some(0.0);
+
+    for member in &decl.members {
+        let name = match &member.id {
+            TsEnumMemberId::Ident(id) => id.sym.to_string(),
+            TsEnumMemberId::Str(str) => str.value.to_string(),
+        };
+
+        let value = match member.init.as_deref() {
+            Some(Expr::Lit(swc_ecma_ast::Lit::Num(num))) => Some(num.value),
+            Some(Expr::Unary(UnaryExpr {
+                op: UnaryOp::Minus,
+                arg,
+                ..
+            })) => match arg.as_ref() {
+                Expr::Lit(swc_ecma_ast::Lit::Num(num)) => Some(-num.value),
+                _ => None,
+            },
+            Some(_) => None,
+            None => match next_value {
+                Some(value) => Some(value),
+                None => return Err(format!("enum member {name} must have an initializer")),
+            },
+        };
+
+        let t = match (value, member.init.as_deref()) {
+            (Some(value), _) => checker.new_lit_type(&Lit::Number(value.to_string())),
+            (None, Some(Expr::Lit(swc_ecma_ast::Lit::Str(str)))) => {
+                checker.new_lit_type(&Lit::String(str.value.to_string()))
+            }
+            // Computed members, e.g. `A = 1 << 2`
+            (None, _) => checker.new_primitive(Primitive::Number),
+        };
+        next_value = value.map(|value| value + 1.0);
+
+        types.push(t);
+        elems.push(TObjElem::Prop(TProp {
+            name: TPropKey::StringKey(name),
+            optional: false,
+            readonly: true,
+            t,
+        }));
+    }
+
+    let scheme = Scheme {
+        t: checker.new_union_type(&types),
+        type_params: None,
+        is_type_param: false,
+    };
+
+    Ok((scheme, checker.new_object_type(&elems)))
+}
+
+fn get_prop_name(key: &PropName) -> Result<String, String> {
+    match key {
+        PropName::Ident(Ident { sym, .. }) => Ok(sym.to_string()),
+        PropName::Str(Str { value, .. }) => Ok(value.to_string()),
+        PropName::Num(Number { value, .. }) => Ok(value.to_string()),
+        _ => Err(format!("get_prop_name: {key:#?}")),
+    }
+}
+
 fn get_key_name(key: &Expr) -> Result<String, String> {
     match key {
         Expr::Ident(Ident { sym, .. }) => Ok(sym.to_string()),
@@ -804,11 +1299,153 @@ pub struct InterfaceCollector {
     pub comments: SingleThreadedComments,
     pub namespace: Vec<String>,
     pub interfaces: HashMap<String, Vec<TsInterfaceDecl>>,
+    // Classes are inferred after interfaces so that they can be merged.
+    pub classes: Vec<(String, Class)>,
+    // Maps each function to the types of its overloads.
+    pub functions: HashMap<String, Vec<Index>>,
+    // The name of the declaration exported by `export =` or `export default`.
+    pub default_export: Option<String>,
+}
+
+impl InterfaceCollector {
+    fn add_docs(&mut self, key: &str, pos: BytePos) {
+        // Declarations that are merged only use the first JSDoc comment.
+        if self.ctx.docs.contains_key(key) {
+            return;
+        }
+        if let Some(docs) = get_jsdoc(&self.comments, pos) {
+            self.ctx.docs.insert(key.to_string(), docs);
+        }
+    }
+
+    // Copies the declaration `name` from the module `specifier` into the
+    // current context as `local` along with its docs.
+    fn import_decl(&mut self, specifier: &str, name: &str, local: &str) {
+        let Some(module) = self.ctx.modules.get(specifier) else {
+            eprintln!("couldn't find module \"{specifier}\"");
+            return;
+        };
+        let binding = module.values.get(name).cloned();
+        let scheme = module.schemes.get(name).cloned();
+        let prefix = format!("{name}.");
+        let docs = module
+            .docs
+            .iter()
+            .filter_map(|(key, docs)| match key.strip_prefix(&prefix) {
+                Some(member) => Some((format!("{local}.{member}"), docs.to_owned())),
+                None if key == name => Some((local.to_string(), docs.to_owned())),
+                None => None,
+            })
+            .collect::<Vec<_>>();
+
+        if binding.is_none() && scheme.is_none() {
+            eprintln!("module \"{specifier}\" doesn't export {name}");
+        }
+        if let Some(binding) = binding {
+            self.ctx.values.insert(local.to_string(), binding);
+        }
+        if let Some(scheme) = scheme {
+            self.ctx.schemes.insert(local.to_string(), scheme);
+        }
+        self.ctx.docs.extend(docs);
+    }
+}
+
+fn get_export_name(name: &ModuleExportName) -> String {
+    match name {
+        ModuleExportName::Ident(id) => id.sym.to_string(),
+        ModuleExportName::Str(str) => str.value.to_string(),
+    }
 }
 
 impl Visit for InterfaceCollector {
+    // Relative imports refer to files in the same package whose declarations
+    // are already in the current context.
+    fn visit_import_decl(&mut self, decl: &ImportDecl) {
+        let specifier = decl.src.value.to_string();
+        if is_relative(&specifier) {
+            return;
+        }
+        for spec in &decl.specifiers {
+            match spec {
+                ImportSpecifier::Named(ImportNamedSpecifier {
+                    local, imported, ..
+                }) => {
+                    let name = match imported {
+                        Some(imported) => get_export_name(imported),
+                        None => local.sym.to_string(),
+                    };
+                    self.import_decl(&specifier, &name, &local.sym);
+                }
+                ImportSpecifier::Default(ImportDefaultSpecifier { local, .. }) => {
+                    self.import_decl(&specifier, "default", &local.sym);
+                }
+                ImportSpecifier::Namespace(_) => {
+                    eprintln!("skipping namespace import of \"{specifier}\"")
+                }
+            }
+        }
+    }
+
+    fn visit_export_all(&mut self, export: &ExportAll) {
+        let specifier = export.src.value.to_string();
+        if is_relative(&specifier) {
+            return;
+        }
+        let Some(module) = self.ctx.modules.get(&specifier).cloned() else {
+            eprintln!("couldn't find module \"{specifier}\"");
+            return;
+        };
+        // `export *` doesn't re-export the default export.
+        for (name, binding) in module.values {
+            if name != "default" {
+                self.ctx.values.insert(name, binding);
+            }
+        }
+        for (name, scheme) in module.schemes {
+            if name != "default" {
+                self.ctx.schemes.insert(name, scheme);
+            }
+        }
+        self.ctx.docs.extend(module.docs);
+    }
+
+    fn visit_named_export(&mut self, export: &NamedExport) {
+        let Some(src) = &export.src else {
+            return;
+        };
+        let specifier = src.value.to_string();
+        if is_relative(&specifier) {
+            return;
+        }
+        for spec in &export.specifiers {
+            match spec {
+                ExportSpecifier::Named(ExportNamedSpecifier { orig, exported, .. }) => {
+                    let name = get_export_name(orig);
+                    let local = match exported {
+                        Some(exported) => get_export_name(exported),
+                        None => name.to_owned(),
+                    };
+                    self.import_decl(&specifier, &name, &local);
+                }
+                _ => eprintln!("skipping namespace re-export of \"{specifier}\""),
+            }
+        }
+    }
+
+    fn visit_export_decl(&mut self, export: &ExportDecl) {
+        // JSDoc comments are attached to the `export` keyword so we move them
+        // to the declaration.
+        if let Some(comments) = self.comments.take_leading(export.span.lo) {
+            self.comments
+                .add_leading_comments(export.decl.span_lo(), comments);
+        }
+        export.visit_children_with(self);
+    }
+
     fn visit_ts_type_alias_decl(&mut self, decl: &TsTypeAliasDecl) {
         let name = decl.id.sym.to_string();
+        self.add_docs(&name, decl.span.lo);
         match infer_type_alias_decl(&mut self.checker, &mut self.ctx, decl) {
             Ok(scheme) => {
                 // eprintln!("inferring: {name} as scheme: {scheme}");
@@ -844,7 +1481,9 @@ impl Visit for InterfaceCollector {
                 decl.visit_children_with(self);
                 self.namespace.pop();
             }
-            TsModuleName::Str(_) => todo!(),
+            // Ambient modules, e.g. `declare module "foo" {}`, are inferred
+            // separately, see `get_ambient_modules`.
+            TsModuleName::Str(_) => (),
         }
     }
 
@@ -855,44 +1494,344 @@ impl Visit for InterfaceCollector {
         for d in &decl.decls {
             match &d.name {
                 Pat::Ident(bi) => {
-                    match &bi.type_ann {
-                        Some(type_ann) => {
-                            // TODO: capture errors and store them in self.errors
-                            let t = infer_ts_type_ann(
-                                &mut self.checker,
-                                &mut self.ctx,
-                                &type_ann.type_ann,
-                            )
-                            .unwrap();
-                            let name = bi.id.sym.to_string();
-                            let binding = Binding {
-                                index: t.to_owned(),
-                                is_mut: false,
-                            };
-                            self.ctx.values.insert(name, binding);
+                    let name = bi.id.sym.to_string();
+                    self.add_docs(&name, decl.span.lo);
+                    let t = match &bi.type_ann {
+                        // TODO: capture errors and store them in self.errors
+                        Some(type_ann) => match infer_ts_type_ann(
+                            &mut self.checker,
+                            &mut self.ctx,
+                            &type_ann.type_ann,
+                        ) {
+                            Ok(t) => t,
+                            Err(err) => {
+                                eprintln!("couldn't infer {name}, {err:#?}");
+                                continue;
+                            }
+                        },
+                        // Variables without type annotations are `any`.
+                        None => self.checker.new_type_var(None),
+                    };
+                    let binding = Binding {
+                        index: t.to_owned(),
+                        is_mut: false,
+                    };
+                    self.ctx.values.insert(name, binding);
+                }
+                // Destructuring isn't allowed in ambient contexts.
+                _ => eprintln!("skipping destructured declaration"),
+            }
+        }
+    }
+
+    fn visit_fn_decl(&mut self, decl: &FnDecl) {
+        // Only functions without a body are declarations.
+        if decl.function.body.is_some() {
+            return;
+        }
+        let name = decl.ident.sym.to_string();
+        self.add_docs(&name, decl.function.span.lo);
+        match infer_fn_decl(&mut self.checker, &mut self.ctx, decl) {
+            Ok(t) => self.functions.entry(name).or_default().push(t),
+            Err(err) => eprintln!("couldn't infer {name}, {err:#?}"),
+        }
+    }
+
+    fn visit_class_decl(&mut self, decl: &ClassDecl) {
+        let name = decl.ident.sym.to_string();
+        self.classes.push((name, decl.class.as_ref().to_owned()));
+    }
+
+    fn visit_ts_enum_decl(&mut self, decl: &TsEnumDecl) {
+        let name = decl.id.sym.to_string();
+        self.add_docs(&name, decl.span.lo);
+        for member in &decl.members {
+            let key = match &member.id {
+                TsEnumMemberId::Ident(id) => id.sym.to_string(),
+                TsEnumMemberId::Str(str) => str.value.to_string(),
+            };
+            self.add_docs(&format!("{name}.{key}"), member.span.lo);
+        }
+        match infer_enum_decl(&mut self.checker, decl) {
+            Ok((scheme, t)) => {
+                self.ctx.schemes.insert(name.to_owned(), scheme);
+                let binding = Binding {
+                    index: t,
+                    is_mut: false,
+                };
+                self.ctx.values.insert(name, binding);
+            }
+            Err(err) => eprintln!("couldn't infer {name}, {err:#?}"),
+        }
+    }
+
+    fn visit_ts_export_assignment(&mut self, export: &TsExportAssignment) {
+        match export.expr.as_ref() {
+            Expr::Ident(id) => self.default_export = Some(id.sym.to_string()),
+            _ => eprintln!("skipping `export =` of an expression"),
+        }
+    }
+
+    fn visit_export_default_expr(&mut self, export: &ExportDefaultExpr) {
+        match export.expr.as_ref() {
+            Expr::Ident(id) => self.default_export = Some(id.sym.to_string()),
+            _ => eprintln!("skipping `export default` of an expression"),
+        }
+    }
+
+    fn visit_export_default_decl(&mut self, export: &ExportDefaultDecl) {
+        // Anonymous declarations are only accessible as `default`.
+        let name = match &export.decl {
+            DefaultDecl::Class(ClassExpr { ident, class }) => {
+                let name = ident
+                    .as_ref()
+                    .map_or("default".to_string(), |id| id.sym.to_string());
+                self.classes
+                    .push((name.to_owned(), class.as_ref().to_owned()));
+                name
+            }
+            DefaultDecl::Fn(FnExpr { ident, function }) => {
+                let ident = ident
+                    .to_owned()
+                    .unwrap_or_else(|| Ident::new("default".into(), DUMMY_SP));
+                let name = ident.sym.to_string();
+                let decl = FnDecl {
+                    ident,
+                    declare: true,
+                    function: function.to_owned(),
+                };
+                self.visit_fn_decl(&decl);
+                name
+            }
+            DefaultDecl::TsInterfaceDecl(decl) => {
+                self.visit_ts_interface_decl(decl);
+                decl.id.sym.to_string()
+            }
+        };
+        self.default_export = Some(name);
+    }
+}
+
+// Finds the names of the values that are declared.  `typeof x` can refer to a
+// value that's declared later on so each of these values starts out as a type
+// variable that's bound to the value's type once all of the declarations have
+// been inferred.
+struct ValueNameFinder {
+    names: Vec<String>,
+}
+
+impl Visit for ValueNameFinder {
+    fn visit_var_decl(&mut self, decl: &VarDecl) {
+        if !decl.declare {
+            return;
+        }
+        for d in &decl.decls {
+            if let Pat::Ident(bi) = &d.name {
+                self.names.push(bi.id.sym.to_string());
+            }
+        }
+    }
+
+    fn visit_fn_decl(&mut self, decl: &FnDecl) {
+        if decl.function.body.is_none() {
+            self.names.push(decl.ident.sym.to_string());
+        }
+    }
+
+    fn visit_class_decl(&mut self, decl: &ClassDecl) {
+        self.names.push(decl.ident.sym.to_string());
+    }
+
+    fn visit_ts_enum_decl(&mut self, decl: &TsEnumDecl) {
+        self.names.push(decl.id.sym.to_string());
+    }
+
+    fn visit_ts_module_decl(&mut self, decl: &TsModuleDecl) {
+        if let TsModuleName::Ident(_) = decl.id {
+            decl.visit_children_with(self);
+        }
+    }
+}
+
+// Finds the bodies of ambient modules, e.g. `declare module "foo" {}`.  The
+// bodies of ambient modules with the same name are merged.
+fn get_ambient_modules(modules: &[Module]) -> Vec<(String, Module)> {
+    let mut result: Vec<(String, Module)> = vec![];
+
+    for module in modules {
+        for item in &module.body {
+            let ModuleItem::Stmt(Stmt::Decl(Decl::TsModule(decl))) = item else {
+                continue;
+            };
+            let (TsModuleName::Str(name), Some(TsNamespaceBody::TsModuleBlock(block))) =
+                (&decl.id, &decl.body)
+            else {
+                continue;
+            };
+            let name = name.value.to_string();
+
+            // Variables in ambient modules are implicitly declared.
+            let mut body = block.body.to_owned();
+            for item in body.iter_mut() {
+                match item {
+                    ModuleItem::Stmt(Stmt::Decl(Decl::Var(decl)))
+                    | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                        decl: Decl::Var(decl),
+                        ..
+                    })) => decl.declare = true,
+                    _ => (),
+                }
+            }
+
+            match result.iter_mut().find(|(key, _)| *key == name) {
+                Some((_, module)) => module.body.extend(body),
+                None => result.push((
+                    name,
+                    Module {
+                        span: block.span,
+                        body,
+                        shebang: None,
+                    },
+                )),
+            }
+        }
+    }
+
+    result
+}
+
+// Finds the names of all of the type params, including the names of `infer`
+// types and the keys in mapped types.
+struct TypeParamFinder {
+    names: HashSet<String>,
+}
+
+impl Visit for TypeParamFinder {
+    fn visit_ts_type_param(&mut self, param: &TsTypeParam) {
+        self.names.insert(param.name.sym.to_string());
+        param.visit_children_with(self);
+    }
+}
+
+// Finds the positions of the JSDoc comments for interfaces, classes and their
+// members.  Members are keyed by the name of the type they belong to, e.g.
+// "Array.push".
+fn get_decl_doc_positions(collector: &InterfaceCollector) -> Vec<(String, BytePos)> {
+    let mut result: Vec<(String, BytePos)> = vec![];
+
+    for (name, decls) in &collector.interfaces {
+        for decl in decls {
+            result.push((name.to_owned(), decl.span.lo));
+            for elem in &decl.body.body {
+                let (key, pos) = match elem {
+                    TsTypeElement::TsPropertySignature(sig) => (&sig.key, sig.span.lo),
+                    TsTypeElement::TsMethodSignature(sig) => (&sig.key, sig.span.lo),
+                    TsTypeElement::TsGetterSignature(sig) => (&sig.key, sig.span.lo),
+                    TsTypeElement::TsSetterSignature(sig) => (&sig.key, sig.span.lo),
+                    _ => continue,
+                };
+                if let Ok(key) = get_key_name(key) {
+                    result.push((format!("{name}.{key}"), pos));
+                }
+            }
+        }
+    }
+
+    for (name, class) in &collector.classes {
+        result.push((name.to_owned(), class.span.lo));
+        for member in &class.body {
+            let (key, pos) = match member {
+                ClassMember::Method(method) => (&method.key, method.span.lo),
+                ClassMember::ClassProp(prop) => (&prop.key, prop.span.lo),
+                _ => continue,
+            };
+            if let Ok(key) = get_prop_name(key) {
+                result.push((format!("{name}.{key}"), pos));
+            }
+        }
+    }
+
+    result
+}
+
+// Methods can be marked as mutating with a `/** @mutates */` comment.  This
+// also works for types from other packages since interfaces are merged, e.g.
+// `interface Foo { /** @mutates */ bar(): void }` marks `Foo.bar`.
+fn get_jsdoc_mutating_methods(collector: &InterfaceCollector) -> HashMap<String, HashSet<String>> {
+    let mut result: HashMap<String, HashSet<String>> = HashMap::new();
+
+    for (name, decls) in &collector.interfaces {
+        for decl in decls {
+            for elem in &decl.body.body {
+                if let TsTypeElement::TsMethodSignature(sig) = elem {
+                    if has_jsdoc_tag(&collector.comments, sig.span.lo, "@mutates") {
+                        if let Ok(key) = get_key_name(&sig.key) {
+                            result.entry(name.to_owned()).or_default().insert(key);
                         }
-                        None => todo!(),
                     }
                 }
-                Pat::Array(_) => todo!(),
-                Pat::Rest(_) => todo!(),
-                Pat::Object(_) => todo!(),
-                Pat::Assign(_) => todo!(),
-                Pat::Invalid(_) => todo!(),
-                Pat::Expr(_) => todo!(),
             }
         }
     }
+
+    for (name, class) in &collector.classes {
+        for member in &class.body {
+            if let ClassMember::Method(method) = member {
+                if has_jsdoc_tag(&collector.comments, method.span.lo, "@mutates") {
+                    if let Ok(key) = get_prop_name(&method.key) {
+                        result.entry(name.to_owned()).or_default().insert(key);
+                    }
+                }
+            }
+        }
+    }
+
+    result
 }
 
 pub fn parse_dts(d_ts_source: &str) -> Result<(Checker, Context), Error> {
     let cm = Arc::<SourceMap>::default();
-    let fm = cm.new_source_file(FileName::Anon, d_ts_source.to_owned());
+    let comments = SingleThreadedComments::default();
 
-    let mut errors: Vec<Error> = vec![];
+    let module = parse_module(&cm, &comments, FileName::Anon, d_ts_source)?;
+
+    let mut checker = Checker::default();
+    let ctx = infer_modules(&mut checker, Context::default(), &[module], comments);
+
+    Ok((checker, ctx))
+}
+
+/// Parses multiple .d.ts files, e.g. lib.es5.d.ts and lib.dom.d.ts, into the
+/// same context.  Interfaces that are declared in more than one file are
+/// merged.
+pub fn parse_dts_files(files: &[(PathBuf, String)]) -> Result<(Checker, Context), Error> {
+    let cm = Arc::<SourceMap>::default();
     let comments = SingleThreadedComments::default();
 
-    let module = parse_file_as_module(
+    let modules = files
+        .iter()
+        .map(|(path, src)| parse_module(&cm, &comments, FileName::Real(path.to_owned()), src))
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    let mut checker = Checker::default();
+    let ctx = infer_modules(&mut checker, Context::default(), &modules, comments);
+
+    Ok((checker, ctx))
+}
+
+// The same `SourceMap` and `comments` should be used for all of the modules
+// that are passed to `infer_modules` together.
+pub(crate) fn parse_module(
+    cm: &SourceMap,
+    comments: &SingleThreadedComments,
+    file_name: FileName,
+    d_ts_source: &str,
+) -> Result<Module, Error> {
+    let fm = cm.new_source_file(file_name, d_ts_source.to_owned());
+
+    let mut errors: Vec<Error> = vec![];
+
+    parse_file_as_module(
         &fm,
         Syntax::Typescript(TsConfig {
             tsx: false,
@@ -902,19 +1841,73 @@ pub fn parse_dts(d_ts_source: &str) -> Result<(Checker, Context), Error> {
             disallow_ambiguous_jsx_like: false,
         }),
         EsVersion::Es2020,
-        Some(&comments),
+        Some(comments),
         &mut errors,
-    )?;
+    )
+}
+
+// Declarations from all of the modules are collected before any of them are
+// inferred so that they can reference each other.  The declarations are added
+// to `ctx` whose `modules` should contain the declarations for any packages
+// that `modules` import.
+pub(crate) fn infer_modules(
+    checker: &mut Checker,
+    mut ctx: Context,
+    modules: &[Module],
+    comments: SingleThreadedComments,
+) -> Context {
+    // Ambient modules are inferred into their own contexts first so that
+    // their declarations can be imported by the rest of the declarations.
+    for (name, module) in get_ambient_modules(modules) {
+        let module_ctx = Context {
+            modules: ctx.modules.to_owned(),
+            ..Context::default()
+        };
+        let module_ctx = infer_scoped_modules(checker, module_ctx, &[module], comments.clone());
+        ctx.modules.insert(name, module_ctx);
+    }
 
     let mut collector = InterfaceCollector {
-        checker: Checker::default(),
-        ctx: Context::default(),
+        checker: std::mem::take(checker),
+        ctx,
         comments,
         namespace: vec![],
         interfaces: HashMap::new(),
+        classes: vec![],
+        functions: HashMap::new(),
+        default_export: None,
     };
 
-    module.visit_with(&mut collector);
+    let mut finder = ValueNameFinder { names: vec![] };
+    for module in modules {
+        module.visit_with(&mut finder);
+    }
+    let mut placeholders: Vec<(String, Index)> = vec![];
+    for name in finder.names {
+        if collector.ctx.values.contains_key(&name) {
+            continue;
+        }
+        let t = collector.checker.new_type_var(None);
+        let binding = Binding {
+            index: t,
+            is_mut: false,
+        };
+        collector.ctx.values.insert(name.to_owned(), binding);
+        placeholders.push((name, t));
+    }
+
+    for module in modules {
+        module.visit_with(&mut collector);
+    }
+
+    for (key, pos) in get_decl_doc_positions(&collector) {
+        collector.add_docs(&key, pos);
+    }
+
+    let mut mutating_methods = get_std_mutating_methods();
+    for (name, methods) in get_jsdoc_mutating_methods(&collector) {
+        mutating_methods.entry(name).or_default().extend(methods);
+    }
 
     for (name, decls) in collector.interfaces {
         let schemes = decls
@@ -935,16 +1928,98 @@ pub fn parse_dts(d_ts_source: &str) -> Result<(Checker, Context), Error> {
         collector.ctx.schemes.insert(name.to_owned(), scheme);
     }
 
-    if let Some(array) = collector.ctx.schemes.get("Array") {
-        if let Some(readonly_array) = collector.ctx.schemes.get("ReadonlyArray") {
-            let array =
-                merge_readonly_and_mutable_schemes(readonly_array, array, &mut collector.checker);
-            collector.ctx.schemes.insert("Array".to_string(), array);
+    for (name, class) in collector.classes {
+        match infer_class_decl(&mut collector.checker, &mut collector.ctx, &name, &class) {
+            Ok((scheme, t)) => {
+                collector.ctx.schemes.insert(name.to_owned(), scheme);
+                let binding = Binding {
+                    index: t,
+                    is_mut: false,
+                };
+                collector.ctx.values.insert(name, binding);
+            }
+            Err(err) => eprintln!("couldn't infer {name}, {err:#?}"),
+        }
+    }
+
+    for (name, overloads) in collector.functions {
+        let t = match overloads.as_slice() {
+            [t] => *t,
+            _ => collector.checker.new_intersection_type(&overloads),
+        };
+        let binding = Binding {
+            index: t,
+            is_mut: false,
+        };
+        collector.ctx.values.insert(name, binding);
+    }
+
+    for (name, placeholder) in placeholders {
+        let Some(binding) = collector.ctx.values.get(&name) else {
+            continue;
+        };
+        let t = binding.index;
+        // Values that couldn't be inferred are removed, `typeof` queries
+        // that refer to them are left as unbound type variables.
+        if t == placeholder {
+            collector.ctx.values.remove(&name);
+        } else if let Err(err) = collector.checker.bind(&collector.ctx, placeholder, t) {
+            eprintln!("couldn't infer typeof {name}, {err:#?}");
         }
     }
 
-    // TODO: maintain a list of standard library methods that mutate and update
-    // those methods here.
+    if let Some(name) = collector.default_export {
+        if let Some(binding) = collector.ctx.values.get(&name) {
+            let binding = binding.to_owned();
+            collector.ctx.values.insert("default".to_string(), binding);
+        }
+        if let Some(scheme) = collector.ctx.schemes.get(&name) {
+            let scheme = scheme.to_owned();
+            collector.ctx.schemes.insert("default".to_string(), scheme);
+        }
+    }
+
+    // Map and Set are declared in lib.es2015.collection.d.ts.
+    for (name, readonly_name) in [
+        ("Array", "ReadonlyArray"),
+        ("Map", "ReadonlyMap"),
+        ("Set", "ReadonlySet"),
+    ] {
+        if let Some(mutable) = collector.ctx.schemes.get(name) {
+            if let Some(readonly) = collector.ctx.schemes.get(readonly_name) {
+                let merged =
+                    merge_readonly_and_mutable_schemes(readonly, mutable, &mut collector.checker);
+                collector.ctx.schemes.insert(name.to_string(), merged);
+            }
+        }
+    }
+
+    mark_mutating_methods(&mut collector.checker, &collector.ctx, &mutating_methods);
+
+    *checker = collector.checker;
+    collector.ctx
+}
+
+// Infers modules whose declarations are only visible where they're imported,
+// e.g. packages and ambient modules.  The modules' declarations refer to each
+// other by name so these references are linked to the declarations, otherwise
+// they'd be looked up in the context of whatever imports them.
+pub(crate) fn infer_scoped_modules(
+    checker: &mut Checker,
+    ctx: Context,
+    modules: &[Module],
+    comments: SingleThreadedComments,
+) -> Context {
+    let mut ctx = infer_modules(checker, ctx, modules, comments);
+
+    // Type params can shadow the modules' declarations so they aren't linked.
+    let mut finder = TypeParamFinder {
+        names: HashSet::new(),
+    };
+    for module in modules {
+        module.visit_with(&mut finder);
+    }
+    checker.link_type_refs(&mut ctx, &finder.names);
 
-    Ok((collector.checker, collector.ctx))
+    ctx
 }
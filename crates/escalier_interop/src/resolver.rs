@@ -0,0 +1,474 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+
+use serde_json::Value;
+use swc_common::{comments::SingleThreadedComments, FileName, SourceMap};
+use swc_ecma_ast::*;
+use swc_ecma_visit::*;
+
+use escalier_hm::checker::Checker;
+use escalier_hm::context::Context;
+
+use crate::parse::{infer_scoped_modules, parse_dts_files, parse_module};
+
+/// A triple-slash directive, e.g. `/// <reference path="./globals.d.ts" />`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reference {
+    Path(String),
+    Types(String),
+    Lib(String),
+}
+
+// Other packages that a package depends on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Dependency {
+    // A bare import specifier, e.g. `import {foo} from "foo"`.
+    Import(String),
+    // The entry file of a package referenced with `/// <reference types />`.
+    Types(PathBuf),
+}
+
+/// Finds the typings for packages in `node_modules` directories the same way
+/// that TypeScript does.
+#[derive(Debug, Default)]
+pub struct Resolver {
+    // The directory to start looking for `node_modules` directories in.
+    root: PathBuf,
+    // All packages are inferred using the same checker so that the types in
+    // one package's context can be used with another package's types.
+    checker: Checker,
+    // Maps the entry file of each package to the package's declarations.
+    packages: HashMap<PathBuf, Context>,
+    // Maps each set of libs to the declarations loaded for them.
+    libs: HashMap<Vec<String>, (Checker, Context)>,
+}
+
+impl Resolver {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Resolver {
+            root: root.into(),
+            ..Resolver::default()
+        }
+    }
+
+    /// Finds the entry `.d.ts` file for a bare import specifier, e.g. "react",
+    /// "@scope/pkg", or "lodash/fp".
+    pub fn resolve(&self, specifier: &str) -> Result<PathBuf, String> {
+        let (name, subpath) = split_specifier(specifier);
+
+        for dir in self.root.ancestors() {
+            let node_modules = dir.join("node_modules");
+
+            let pkg_dir = node_modules.join(name);
+            if let Some(path) = resolve_package(&pkg_dir, subpath) {
+                return Ok(path);
+            }
+
+            // Scoped packages, e.g. `@scope/pkg`, are published as
+            // `@types/scope__pkg`.
+            let types_name = name.trim_start_matches('@').replace('/', "__");
+            let types_dir = node_modules.join("@types").join(types_name);
+            if let Some(path) = resolve_package(&types_dir, subpath) {
+                return Ok(path);
+            }
+        }
+
+        Err(format!("couldn't find types for \"{specifier}\""))
+    }
+
+    /// Finds the file for a TypeScript lib, e.g. "es2015.collection" or "dom",
+    /// in the version of TypeScript that's installed.
+    pub fn resolve_lib(&self, name: &str) -> Result<PathBuf, String> {
+        let name = name.to_lowercase();
+        self.resolve(&format!("typescript/lib/lib.{name}.d.ts"))
+            .map_err(|_| format!("couldn't find lib \"{name}\""))
+    }
+
+    /// Finds the files for the given libs along with the files for any libs
+    /// that they reference.  Referenced libs come before the libs that
+    /// reference them.
+    pub fn lib_files(&self, libs: &[String]) -> Result<Vec<PathBuf>, String> {
+        let mut files: Vec<PathBuf> = vec![];
+        let mut visited: HashSet<PathBuf> = HashSet::new();
+
+        for lib in libs {
+            let path = self.resolve_lib(lib)?;
+            self.collect_lib_files(path, &mut visited, &mut files)?;
+        }
+
+        Ok(files)
+    }
+
+    fn collect_lib_files(
+        &self,
+        path: PathBuf,
+        visited: &mut HashSet<PathBuf>,
+        files: &mut Vec<PathBuf>,
+    ) -> Result<(), String> {
+        if !visited.insert(path.to_owned()) {
+            return Ok(());
+        }
+
+        let src = fs::read_to_string(&path).map_err(|err| format!("{}: {err}", path.display()))?;
+        let dir = path.parent().unwrap_or(Path::new(""));
+
+        for reference in get_references(&src) {
+            let reference = match reference {
+                Reference::Lib(name) => self.resolve_lib(&name)?,
+                Reference::Path(path) => resolve_relative(dir, &path)
+                    .ok_or(format!("couldn't resolve reference to \"{path}\""))?,
+                Reference::Types(specifier) => self.resolve(&specifier)?,
+            };
+            self.collect_lib_files(reference, visited, files)?;
+        }
+
+        files.push(path);
+
+        Ok(())
+    }
+
+    /// Loads the declarations for the given libs, e.g. ["es2015", "dom"], into
+    /// a single context.
+    pub fn load_libs(&mut self, libs: &[String]) -> Result<(Checker, Context), String> {
+        if let Some(result) = self.libs.get(libs) {
+            return Ok(result.to_owned());
+        }
+
+        let files = self
+            .lib_files(libs)?
+            .into_iter()
+            .map(|path| match fs::read_to_string(&path) {
+                Ok(src) => Ok((path, src)),
+                Err(err) => Err(format!("{}: {err}", path.display())),
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        let result = parse_dts_files(&files).map_err(|err| err.kind().msg().to_string())?;
+        self.libs.insert(libs.to_vec(), result.to_owned());
+
+        Ok(result)
+    }
+
+    /// Loads the declarations for a bare import specifier along with the
+    /// declarations from every file in the same package that they reference.
+    /// Other packages that are imported are loaded into their own contexts
+    /// which are stored in the returned context's `modules`.
+    pub fn load(&mut self, specifier: &str) -> Result<(Checker, Context), String> {
+        let entry = self.resolve(specifier)?;
+        let ctx = self.load_package(&entry)?;
+
+        Ok((self.checker.to_owned(), ctx))
+    }
+
+    fn load_package(&mut self, entry: &Path) -> Result<Context, String> {
+        if let Some(ctx) = self.packages.get(entry) {
+            return Ok(ctx.to_owned());
+        }
+
+        // Packages that import each other see an empty context for the
+        // package that's being loaded.
+        self.packages.insert(entry.to_owned(), Context::default());
+
+        let result = self.infer_package(entry);
+        match &result {
+            Ok(ctx) => self.packages.insert(entry.to_owned(), ctx.to_owned()),
+            Err(_) => self.packages.remove(entry),
+        };
+
+        result
+    }
+
+    fn infer_package(&mut self, entry: &Path) -> Result<Context, String> {
+        let cm = Arc::<SourceMap>::default();
+        let comments = SingleThreadedComments::default();
+        let (modules, dependencies) = self.collect_modules(&cm, &comments, entry)?;
+
+        let mut ctx = Context::default();
+        for dependency in dependencies {
+            match dependency {
+                Dependency::Import(specifier) => {
+                    // Modules like "fs" are declared inside of other packages
+                    // using `declare module "fs" {}` so it's okay if we can't
+                    // find them.
+                    let Ok(path) = self.resolve(&specifier) else {
+                        continue;
+                    };
+                    let module = self.load_package(&path)?;
+                    ctx.modules.insert(specifier, module);
+                }
+                // The declarations in packages that are referenced with
+                // `/// <reference types />` are global.
+                Dependency::Types(path) => {
+                    let module = self.load_package(&path)?;
+                    ctx.values.extend(module.values);
+                    ctx.schemes.extend(module.schemes);
+                    ctx.docs.extend(module.docs);
+                    ctx.modules.extend(module.modules);
+                }
+            }
+        }
+
+        Ok(infer_scoped_modules(
+            &mut self.checker,
+            ctx,
+            &modules,
+            comments,
+        ))
+    }
+
+    // Follows `/// <reference>` directives and relative imports starting from
+    // `entry`.  Other packages that are imported or referenced are returned as
+    // dependencies.
+    fn collect_modules(
+        &self,
+        cm: &SourceMap,
+        comments: &SingleThreadedComments,
+        entry: &Path,
+    ) -> Result<(Vec<Module>, Vec<Dependency>), String> {
+        let mut modules: Vec<Module> = vec![];
+        let mut dependencies: Vec<Dependency> = vec![];
+        let mut visited: HashSet<PathBuf> = HashSet::new();
+        let mut queue: Vec<PathBuf> = vec![entry.to_owned()];
+
+        while let Some(path) = queue.pop() {
+            if !visited.insert(path.to_owned()) {
+                continue;
+            }
+
+            let src =
+                fs::read_to_string(&path).map_err(|err| format!("{}: {err}", path.display()))?;
+            let dir = path.parent().unwrap_or(Path::new(""));
+
+            for reference in get_references(&src) {
+                match reference {
+                    Reference::Path(path) => match resolve_relative(dir, &path) {
+                        Some(path) => queue.push(path),
+                        None => eprintln!("couldn't resolve reference to \"{path}\""),
+                    },
+                    Reference::Types(specifier) => match self.resolve(&specifier) {
+                        Ok(path) => {
+                            let dependency = Dependency::Types(path);
+                            if !dependencies.contains(&dependency) {
+                                dependencies.push(dependency);
+                            }
+                        }
+                        Err(err) => eprintln!("{err}"),
+                    },
+                    Reference::Lib(name) => match self.resolve_lib(&name) {
+                        Ok(path) => queue.push(path),
+                        Err(err) => eprintln!("{err}"),
+                    },
+                }
+            }
+
+            let module = parse_module(cm, comments, FileName::Real(path.to_owned()), &src)
+                .map_err(|err| format!("{}: {}", path.display(), err.kind().msg()))?;
+
+            let mut finder = SpecifierFinder { specifiers: vec![] };
+            module.visit_with(&mut finder);
+
+            for specifier in finder.specifiers {
+                if is_relative(&specifier) {
+                    match resolve_relative(dir, &specifier) {
+                        Some(path) => queue.push(path),
+                        None => eprintln!("couldn't resolve import of \"{specifier}\""),
+                    }
+                } else {
+                    let dependency = Dependency::Import(specifier);
+                    if !dependencies.contains(&dependency) {
+                        dependencies.push(dependency);
+                    }
+                }
+            }
+
+            modules.push(module);
+        }
+
+        Ok((modules, dependencies))
+    }
+}
+
+/// Whether `specifier` refers to a file in the same package, e.g. "./foo".
+pub fn is_relative(specifier: &str) -> bool {
+    specifier.starts_with("./") || specifier.starts_with("../")
+}
+
+/// Parses the triple-slash directives at the start of a `.d.ts` file.
+pub fn get_references(src: &str) -> Vec<Reference> {
+    let mut references = vec![];
+
+    for line in src.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with("//") && !line.starts_with("///") {
+            continue;
+        }
+        // Directives are only valid before the first statement.
+        let Some(directive) = line.strip_prefix("///") else {
+            break;
+        };
+        let directive = directive.trim();
+        if !directive.starts_with("<reference") {
+            continue;
+        }
+
+        if let Some(path) = get_attr(directive, "path") {
+            references.push(Reference::Path(path));
+        } else if let Some(types) = get_attr(directive, "types") {
+            references.push(Reference::Types(types));
+        } else if let Some(lib) = get_attr(directive, "lib") {
+            references.push(Reference::Lib(lib));
+        }
+    }
+
+    references
+}
+
+fn get_attr(directive: &str, name: &str) -> Option<String> {
+    for quote in ['"', '\''] {
+        let prefix = format!("{name}={quote}");
+        if let Some(start) = directive.find(&prefix) {
+            let rest = &directive[start + prefix.len()..];
+            let end = rest.find(quote)?;
+            return Some(rest[..end].to_string());
+        }
+    }
+    None
+}
+
+// Splits "@scope/pkg/sub/path" into ("@scope/pkg", Some("sub/path")).
+fn split_specifier(specifier: &str) -> (&str, Option<&str>) {
+    let name_len = match specifier.starts_with('@') {
+        true => 2,
+        false => 1,
+    };
+    let mut parts = specifier.splitn(name_len + 1, '/');
+    let name_end = parts
+        .by_ref()
+        .take(name_len)
+        .map(|part| part.len())
+        .sum::<usize>()
+        + name_len
+        - 1;
+
+    match parts.next() {
+        Some(subpath) => (&specifier[..name_end], Some(subpath)),
+        None => (specifier, None),
+    }
+}
+
+fn resolve_package(pkg_dir: &Path, subpath: Option<&str>) -> Option<PathBuf> {
+    if !pkg_dir.is_dir() {
+        return None;
+    }
+
+    let package_json = fs::read_to_string(pkg_dir.join("package.json"))
+        .ok()
+        .and_then(|src| serde_json::from_str::<Value>(&src).ok())
+        .unwrap_or(Value::Null);
+
+    let key = match subpath {
+        Some(subpath) => format!("./{subpath}"),
+        None => ".".to_string(),
+    };
+
+    // `exports` takes precedence over `types` and `typings`.
+    let exports = &package_json["exports"];
+    let export = match exports {
+        Value::Object(map) if map.keys().any(|key| key.starts_with('.')) => map.get(&key),
+        _ if key == "." && !exports.is_null() => Some(exports),
+        _ => None,
+    };
+    if let Some(path) = export.and_then(get_export_types) {
+        return resolve_relative(pkg_dir, &path);
+    }
+
+    match subpath {
+        Some(subpath) => resolve_relative(pkg_dir, &format!("./{subpath}")),
+        None => {
+            for field in ["types", "typings"] {
+                if let Some(Value::String(path)) = package_json.get(field) {
+                    return resolve_relative(pkg_dir, path);
+                }
+            }
+            resolve_relative(pkg_dir, "./index.d.ts")
+        }
+    }
+}
+
+// Finds the path to the typings in an entry in `exports`, e.g.
+// `{"types": "./index.d.ts", "default": "./index.js"}`.
+fn get_export_types(export: &Value) -> Option<String> {
+    match export {
+        Value::String(path) => Some(path.to_owned()),
+        Value::Object(conditions) => ["types", "import", "require", "node", "default"]
+            .iter()
+            .find_map(|condition| conditions.get(*condition).and_then(get_export_types)),
+        Value::Array(exports) => exports.iter().find_map(get_export_types),
+        _ => None,
+    }
+}
+
+// Finds the `.d.ts` file for a relative path, e.g. "./foo" could be
+// "./foo.d.ts" or "./foo/index.d.ts".  Paths to JavaScript files are mapped to
+// the `.d.ts` file next to them.
+fn resolve_relative(dir: &Path, path: &str) -> Option<PathBuf> {
+    let path = normalize(&dir.join(path));
+    let path_str = path.to_string_lossy();
+
+    let mut candidates = vec![];
+    if path_str.ends_with(".d.ts") {
+        candidates.push(path.to_owned());
+    } else {
+        let stem = [".js", ".mjs", ".cjs", ".ts"]
+            .iter()
+            .find_map(|ext| path_str.strip_suffix(ext))
+            .unwrap_or(&path_str);
+        candidates.push(PathBuf::from(format!("{stem}.d.ts")));
+        candidates.push(path.join("index.d.ts"));
+    }
+
+    candidates.into_iter().find(|path| path.is_file())
+}
+
+// Removes `.` and `..` components from a path without touching the file system.
+fn normalize(path: &Path) -> PathBuf {
+    let mut result = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => (),
+            Component::ParentDir => {
+                result.pop();
+            }
+            _ => result.push(component),
+        }
+    }
+    result
+}
+
+// Finds the specifiers of imports, re-exports and import types.
+struct SpecifierFinder {
+    specifiers: Vec<String>,
+}
+
+impl Visit for SpecifierFinder {
+    fn visit_import_decl(&mut self, decl: &ImportDecl) {
+        self.specifiers.push(decl.src.value.to_string());
+    }
+
+    fn visit_export_all(&mut self, export: &ExportAll) {
+        self.specifiers.push(export.src.value.to_string());
+    }
+
+    fn visit_named_export(&mut self, export: &NamedExport) {
+        if let Some(src) = &export.src {
+            self.specifiers.push(src.value.to_string());
+        }
+    }
+
+    fn visit_ts_import_type(&mut self, import: &TsImportType) {
+        self.specifiers.push(import.arg.value.to_string());
+        import.visit_children_with(self);
+    }
+}
@@ -2,8 +2,13 @@ use std::fs;
 
 use escalier_hm::checker::Checker;
 use escalier_hm::context::Context;
+use escalier_hm::docs::JsDoc;
+use escalier_hm::snapshot::{load_snapshot, save_snapshot};
 use escalier_hm::type_error::TypeError;
+use escalier_hm::types::{TypeKind, Union};
+use escalier_interop::convert::convert_dts;
 use escalier_interop::parse::*;
+use escalier_interop::resolver::Resolver;
 use escalier_parser::parse;
 
 pub fn messages(report: &[TypeError]) -> Vec<String> {
@@ -411,6 +416,23 @@ fn infer_required() {
     assert_eq!(result, "{a: number, b: string, c: boolean, d: number}");
 }
 
+#[test]
+fn infer_readonly_mapped_type() {
+    let lib = r#"
+    type Readonly<T> = {
+        readonly [P in keyof T]: T[P];
+    };
+    type Obj = {a: number, b?: string};
+    type ReadonlyObj = Readonly<Obj>;
+    "#;
+    let (mut checker, ctx) = parse_dts(lib).unwrap();
+    let scheme = ctx.schemes.get("ReadonlyObj").unwrap();
+    let t = checker.expand_type(&ctx, scheme.t).unwrap();
+
+    let result = checker.print_type(&t);
+    assert_eq!(result, "{readonly a: number, readonly b?: string}");
+}
+
 // #[test]
 // fn infer_readonly() {
 //     let src = r#"
@@ -724,46 +746,110 @@ fn infer_infer_type() {
     infer_prog(src);
 }
 
-// #[test]
-// fn regex_with_named_capture_groups() {
-//     let src = r#"
-//     let regex = /(?<foo>foo)(?<bar>bar)/;
-//     let result = "foobar".match(regex);
-//     "#;
+static REGEXP_D_TS: &str = r#"
+interface RegExp {
+    exec(string: string): RegExpExecArray | null;
+    test(string: string): boolean;
+    lastIndex: number;
+}
+interface RegExpMatchArray extends Array<string> {
+    index?: number;
+}
+interface RegExpExecArray extends Array<string> {
+    index: number;
+}
+interface String {
+    match(regexp: string | RegExp): RegExpMatchArray | null;
+}
+"#;
 
-//     let (_, ctx) = infer_prog(src);
+#[test]
+fn regex_with_named_capture_groups() -> Result<(), String> {
+    let (mut checker, mut ctx) = parse_dts(REGEXP_D_TS).unwrap();
 
-//     let regex = ctx.lookup_value("regex").unwrap();
-//     assert_eq!(
-//         regex.to_string(),
-//         "RegExp<\"(?<foo>foo)(?<bar>bar)\", \"\">"
-//     );
+    let src = r#"
+    let regex = /(?<foo>foo)(?<bar>bar)/
+    let result = "foobar".match(regex)
+    let exec = regex.exec("foobar")
+    "#;
 
-//     let result = ctx.lookup_value("result").unwrap();
-//     assert_eq!(
-//         result.to_string(),
-//         "[string, string, string] & {groups: {foo: string, bar: string}} | null"
-//     );
-// }
+    infer_script_with_checker(src, &mut checker, &mut ctx)?;
 
-// #[test]
-// fn regex_with_g_flag_returns_only_matches() {
-//     let src = r#"
-//     let regex = /(?<foo>foo)(?<bar>bar)/g;
-//     let result = "foobar".match(regex);
-//     "#;
+    let binding = ctx.values.get("regex").unwrap();
+    assert_eq!(
+        checker.print_type(&binding.index),
+        r#"RegExp<"(?<foo>foo)(?<bar>bar)", "">"#
+    );
 
-//     let (_, ctx) = infer_prog(src);
+    let binding = ctx.values.get("result").unwrap();
+    assert_eq!(
+        checker.print_type(&binding.index),
+        r#"RegExpMatchArray<"(?<foo>foo)(?<bar>bar)", ""> | null"#
+    );
 
-//     let regex = ctx.lookup_value("regex").unwrap();
-//     assert_eq!(
-//         regex.to_string(),
-//         "RegExp<\"(?<foo>foo)(?<bar>bar)\", \"g\">"
-//     );
+    let binding = ctx.values.get("exec").unwrap();
+    assert_eq!(
+        checker.print_type(&binding.index),
+        r#"RegExpExecArray<"(?<foo>foo)(?<bar>bar)", ""> | null"#
+    );
 
-//     let result = ctx.lookup_value("result").unwrap();
-//     assert_eq!(result.to_string(), "null | string[]");
-// }
+    Ok(())
+}
+
+#[test]
+fn regex_groups_can_be_destructured() -> Result<(), String> {
+    let (mut checker, mut ctx) = parse_dts(REGEXP_D_TS).unwrap();
+
+    let src = r#"
+    declare let str: string
+    let color = match (str.match(/rgb\((?<r>\d+),(?<g>\d+),(?<b>\d+)\)/)) {
+        {groups: {r, g, b}, index, input} => [r, g, b],
+        _ => null
+    }
+    "#;
+
+    infer_script_with_checker(src, &mut checker, &mut ctx)?;
+
+    let binding = ctx.values.get("color").unwrap();
+    assert_eq!(
+        checker.print_type(&binding.index),
+        "[string, string, string] | null"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn regex_with_g_flag_returns_only_matches() -> Result<(), String> {
+    let (mut checker, mut ctx) = parse_dts(REGEXP_D_TS).unwrap();
+
+    let src = r#"
+    let regex = /(?<foo>foo)(?<bar>bar)/g
+    let result = "foobar".match(regex)
+    "#;
+
+    infer_script_with_checker(src, &mut checker, &mut ctx)?;
+
+    let binding = ctx.values.get("regex").unwrap();
+    assert_eq!(
+        checker.print_type(&binding.index),
+        r#"RegExp<"(?<foo>foo)(?<bar>bar)", "g">"#
+    );
+
+    let binding = ctx.values.get("result").unwrap();
+    assert_eq!(
+        checker.print_type(&binding.index),
+        r#"RegExpMatchArray<"(?<foo>foo)(?<bar>bar)", "g"> | null"#
+    );
+    let t = checker.prune(binding.index);
+    let TypeKind::Union(Union { types }) = checker.arena[t].kind.clone() else {
+        panic!("expected a union");
+    };
+    let t = checker.expand_type(&ctx, types[0]).unwrap();
+    assert_eq!(checker.print_type(&t), "string[]");
+
+    Ok(())
+}
 
 #[test]
 fn parses_constructor_interfaces() {
@@ -800,3 +886,1053 @@ fn calling_callables() {
     let result = checker.print_type(&binding.index);
     assert_eq!(result, "string");
 }
+
+#[test]
+fn infer_tuple_types() -> Result<(), String> {
+    let lib = r#"
+    interface Tuples {
+        pair: [number, string];
+        labeled: [x: number, y?: number];
+        optional: [number, string?];
+        rest: [string, ...number[]];
+        labeled_rest: [first: string, ...rest: boolean[]];
+        callback: (() => void) | null;
+    }
+    "#;
+    let (mut checker, mut ctx) = parse_dts(lib).unwrap();
+
+    let src = r#"
+    declare let tuples: Tuples
+    let pair = tuples.pair
+    let labeled = tuples.labeled
+    let optional = tuples.optional
+    let rest = tuples.rest
+    let labeled_rest = tuples.labeled_rest
+    let callback = tuples.callback
+    "#;
+
+    infer_script_with_checker(src, &mut checker, &mut ctx)?;
+
+    let binding = ctx.values.get("pair").unwrap();
+    assert_eq!(checker.print_type(&binding.index), "[number, string]");
+    let binding = ctx.values.get("labeled").unwrap();
+    assert_eq!(checker.print_type(&binding.index), "[number, number?]");
+    let binding = ctx.values.get("optional").unwrap();
+    assert_eq!(checker.print_type(&binding.index), "[number, string?]");
+    let binding = ctx.values.get("rest").unwrap();
+    assert_eq!(checker.print_type(&binding.index), "[string, ...number[]]");
+    let binding = ctx.values.get("labeled_rest").unwrap();
+    assert_eq!(checker.print_type(&binding.index), "[string, ...boolean[]]");
+    let binding = ctx.values.get("callback").unwrap();
+    assert_eq!(checker.print_type(&binding.index), "() -> undefined | null");
+
+    Ok(())
+}
+
+#[test]
+fn optional_tuple_elements_can_be_omitted() -> Result<(), String> {
+    let lib = r#"
+    interface Foo {
+        bar(x: [number, string?]): boolean;
+        baz(x: [number, ...string[]]): boolean;
+    }
+    "#;
+    let (mut checker, mut ctx) = parse_dts(lib).unwrap();
+
+    let src = r#"
+    declare let foo: Foo
+    let a = foo.bar([5])
+    let b = foo.bar([5, "hello"])
+    let c = foo.baz([5])
+    let d = foo.baz([5, "hello", "world"])
+    "#;
+
+    infer_script_with_checker(src, &mut checker, &mut ctx)?;
+
+    let src = r#"
+    let e = foo.bar([])
+    "#;
+
+    let error = infer_script_with_checker(src, &mut checker, &mut ctx);
+    assert!(error.is_err());
+    let diagnostic = &checker.current_report.diagnostics[0];
+    assert_eq!(diagnostic.message, "Function arguments are incorrect");
+    assert_eq!(
+        messages(&diagnostic.reasons),
+        vec!["TypeError: Expected tuple of length 2, got tuple of length 0"]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn infer_ts_infer_types() -> Result<(), String> {
+    let lib = r#"
+    type ReturnType<T extends (...args: any) => any> = T extends (...args: any) => infer R ? R : any;
+    type Unwrap<T> = T extends Promise<(infer U)> ? U : T;
+    "#;
+    let (mut checker, mut ctx) = parse_dts(lib).unwrap();
+
+    let src = r#"
+    let foo = fn () => 5
+    let num: ReturnType<typeof foo> = 5
+    let str: Unwrap<string> = "hello"
+    "#;
+
+    infer_script_with_checker(src, &mut checker, &mut ctx)?;
+
+    let binding = ctx.values.get("num").unwrap();
+    let t = checker.expand_type(&ctx, binding.index).unwrap();
+    assert_eq!(checker.print_type(&t), "5");
+    let binding = ctx.values.get("str").unwrap();
+    let t = checker.expand_type(&ctx, binding.index).unwrap();
+    assert_eq!(checker.print_type(&t), "string");
+
+    Ok(())
+}
+
+#[test]
+fn infer_typeof_queries() -> Result<(), String> {
+    let lib = r#"
+    declare var foo: { bar: number; baz: string };
+    declare var alias: typeof foo;
+    interface Qux {
+        baz: typeof foo.baz;
+        qux: typeof qux;
+    }
+    declare var qux: Qux;
+    "#;
+    let (mut checker, mut ctx) = parse_dts(lib).unwrap();
+
+    let src = r#"
+    let bar = alias.bar
+    let baz = qux.qux.baz
+    "#;
+
+    infer_script_with_checker(src, &mut checker, &mut ctx)?;
+
+    let binding = ctx.values.get("bar").unwrap();
+    assert_eq!(checker.print_type(&binding.index), "number");
+    let binding = ctx.values.get("baz").unwrap();
+    let t = checker.expand_type(&ctx, binding.index).unwrap();
+    assert_eq!(checker.print_type(&t), "string");
+
+    Ok(())
+}
+
+#[test]
+fn typeof_queries_can_refer_to_later_declarations() -> Result<(), String> {
+    let lib = r#"
+    declare var alias: typeof foo;
+    type Greet = typeof greet;
+    declare var foo: { bar: number };
+    declare function greet(name: string): string;
+    "#;
+    let (mut checker, mut ctx) = parse_dts(lib).unwrap();
+
+    let src = r#"
+    declare let g: Greet
+    let bar = alias.bar
+    let greeting = g("world")
+    "#;
+
+    infer_script_with_checker(src, &mut checker, &mut ctx)?;
+
+    let binding = ctx.values.get("bar").unwrap();
+    assert_eq!(checker.print_type(&binding.index), "number");
+    let binding = ctx.values.get("greeting").unwrap();
+    assert_eq!(checker.print_type(&binding.index), "string");
+
+    Ok(())
+}
+
+static TYPE_GUARDS_D_TS: &str = r#"
+interface Fish {
+    swim(): void;
+}
+interface Bird {
+    fly(): void;
+}
+declare var isFish: (pet: Fish | Bird) => pet is Fish;
+declare var assertIsFish: (pet: Fish | Bird) => asserts pet is Fish;
+declare var assertIsDefined: (value: any) => asserts value;
+"#;
+
+#[test]
+fn type_guards_narrow_arguments() -> Result<(), String> {
+    let (mut checker, mut ctx) = parse_dts(TYPE_GUARDS_D_TS).unwrap();
+
+    let src = r#"
+    declare let pet: Fish | Bird
+    let is_fish = isFish(pet)
+    let fish = if (isFish(pet)) {
+        pet.swim()
+        pet
+    } else {
+        null
+    }
+    "#;
+
+    infer_script_with_checker(src, &mut checker, &mut ctx)?;
+
+    let binding = ctx.values.get("isFish").unwrap();
+    assert_eq!(
+        checker.print_type(&binding.index),
+        "(pet: Fish | Bird) -> pet is Fish"
+    );
+    let binding = ctx.values.get("is_fish").unwrap();
+    assert_eq!(checker.print_type(&binding.index), "boolean");
+    let binding = ctx.values.get("fish").unwrap();
+    assert_eq!(checker.print_type(&binding.index), "Fish | null");
+
+    let src = r#"
+    let fly = pet.fly
+    "#;
+
+    let error = infer_script_with_checker(src, &mut checker, &mut ctx);
+    assert_eq!(
+        error,
+        Err("TypeError: Couldn't find property \"fly\" on object".to_string())
+    );
+
+    Ok(())
+}
+
+#[test]
+fn assertion_functions_narrow_arguments() -> Result<(), String> {
+    let (mut checker, mut ctx) = parse_dts(TYPE_GUARDS_D_TS).unwrap();
+
+    let src = r#"
+    declare let pet: Fish | Bird
+    declare let maybe_pet: Fish | null
+    assertIsFish(pet)
+    assertIsDefined(maybe_pet)
+    let swim = pet.swim
+    let fish = maybe_pet
+    "#;
+
+    infer_script_with_checker(src, &mut checker, &mut ctx)?;
+
+    let binding = ctx.values.get("assertIsFish").unwrap();
+    assert_eq!(
+        checker.print_type(&binding.index),
+        "(pet: Fish | Bird) -> asserts pet is Fish"
+    );
+    let binding = ctx.values.get("swim").unwrap();
+    assert_eq!(checker.print_type(&binding.index), "() -> undefined");
+    let binding = ctx.values.get("fish").unwrap();
+    assert_eq!(checker.print_type(&binding.index), "Fish");
+
+    Ok(())
+}
+
+#[test]
+fn infer_import_types() -> Result<(), String> {
+    let lib = r#"
+    interface Point<T> {
+        x: T;
+        y: T;
+    }
+    interface Line {
+        start: import("./point").Point<number>;
+        end: import("./point").Point<number>;
+    }
+    "#;
+    let (mut checker, mut ctx) = parse_dts(lib).unwrap();
+
+    let src = r#"
+    declare let line: Line
+    let x = line.start.x
+    "#;
+
+    infer_script_with_checker(src, &mut checker, &mut ctx)?;
+
+    let binding = ctx.values.get("x").unwrap();
+    assert_eq!(checker.print_type(&binding.index), "number");
+
+    Ok(())
+}
+
+#[test]
+fn ambient_modules_are_scoped_to_the_module() -> Result<(), String> {
+    let lib = r#"
+    import { value } from "a";
+    declare module "a" {
+        export interface T {
+            a: number;
+        }
+        export const value: T;
+    }
+    declare module "b" {
+        export interface T {
+            b: string;
+        }
+    }
+    export interface Both {
+        a: import("a").T;
+        b: import("b").T;
+    }
+    export declare const copy: typeof value;
+    "#;
+    let (mut checker, mut ctx) = parse_dts(lib).unwrap();
+
+    assert!(!ctx.schemes.contains_key("T"));
+    assert!(ctx.modules.contains_key("a"));
+    assert!(ctx.modules.contains_key("b"));
+
+    let src = r#"
+    declare let both: Both
+    let a = both.a.a
+    let b = both.b.b
+    let c = copy.a
+    "#;
+
+    infer_script_with_checker(src, &mut checker, &mut ctx)?;
+
+    let binding = ctx.values.get("a").unwrap();
+    assert_eq!(checker.print_type(&binding.index), "number");
+    let binding = ctx.values.get("b").unwrap();
+    assert_eq!(checker.print_type(&binding.index), "string");
+    let binding = ctx.values.get("c").unwrap();
+    assert_eq!(checker.print_type(&binding.index), "number");
+
+    Ok(())
+}
+
+#[test]
+fn infer_class_declarations() -> Result<(), String> {
+    let lib = r#"
+    interface Disposable {
+        dispose(): void;
+    }
+    declare class Counter<T> implements Disposable {
+        constructor(initial: T);
+        value: T;
+        readonly step: number;
+        private secret: string;
+        protected hidden: string;
+        #internal: number;
+        get doubled(): number;
+        increment(by?: number): number;
+        dispose(): void;
+        static create(): Counter<number>;
+        static readonly instances: number;
+    }
+    interface Counter<T> {
+        reset(): void;
+    }
+    declare class Empty {}
+    "#;
+    let (mut checker, mut ctx) = parse_dts(lib).unwrap();
+
+    let src = r#"
+    let counter = new Counter("hello")
+    let value = counter.value
+    let doubled = counter.doubled
+    let incremented = counter.increment(5)
+    let created = Counter.create()
+    let instances = Counter.instances
+    let empty = new Empty()
+    "#;
+
+    infer_script_with_checker(src, &mut checker, &mut ctx)?;
+
+    let binding = ctx.values.get("counter").unwrap();
+    assert_eq!(checker.print_type(&binding.index), "Counter<\"hello\">");
+    let t = checker.expand_type(&ctx, binding.index).unwrap();
+    assert_eq!(checker.print_type(&t), "{value: \"hello\", readonly step: number, get doubled(self) -> number, increment(self, by?: number) -> number, dispose(self) -> undefined, reset(self) -> undefined}");
+    let binding = ctx.values.get("value").unwrap();
+    assert_eq!(checker.print_type(&binding.index), "\"hello\"");
+    let binding = ctx.values.get("doubled").unwrap();
+    assert_eq!(checker.print_type(&binding.index), "number");
+    let binding = ctx.values.get("incremented").unwrap();
+    assert_eq!(checker.print_type(&binding.index), "number");
+    let binding = ctx.values.get("created").unwrap();
+    assert_eq!(checker.print_type(&binding.index), "Counter<number>");
+    let binding = ctx.values.get("instances").unwrap();
+    assert_eq!(checker.print_type(&binding.index), "number");
+    let binding = ctx.values.get("empty").unwrap();
+    assert_eq!(checker.print_type(&binding.index), "Empty");
+
+    let src = r#"
+    let secret = counter.secret
+    "#;
+
+    let error = infer_script_with_checker(src, &mut checker, &mut ctx);
+    assert_eq!(
+        error,
+        Err("TypeError: Couldn't find property 'secret' on object".to_string())
+    );
+
+    Ok(())
+}
+
+#[test]
+fn infer_overloaded_function_declarations() -> Result<(), String> {
+    let lib = r#"
+    declare function parse(input: string): number;
+    declare function parse(input: string, radix: number): number;
+    declare function parse(input: boolean): boolean;
+    declare function identity<T>(x: T): T;
+    "#;
+    let (mut checker, mut ctx) = parse_dts(lib).unwrap();
+
+    let src = r#"
+    let a = parse("10")
+    let b = parse("10", 16)
+    let c = parse(true)
+    let d = identity("hello")
+    "#;
+
+    infer_script_with_checker(src, &mut checker, &mut ctx)?;
+
+    let binding = ctx.values.get("parse").unwrap();
+    assert_eq!(checker.print_type(&binding.index), "(input: string) -> number & (input: string, radix: number) -> number & (input: boolean) -> boolean");
+    let binding = ctx.values.get("a").unwrap();
+    assert_eq!(checker.print_type(&binding.index), "number");
+    let binding = ctx.values.get("b").unwrap();
+    assert_eq!(checker.print_type(&binding.index), "number");
+    let binding = ctx.values.get("c").unwrap();
+    assert_eq!(checker.print_type(&binding.index), "boolean");
+    let binding = ctx.values.get("d").unwrap();
+    assert_eq!(checker.print_type(&binding.index), "\"hello\"");
+
+    Ok(())
+}
+
+#[test]
+fn infer_enum_declarations() -> Result<(), String> {
+    let lib = r#"
+    declare enum Direction {
+        Up,
+        Down,
+        Left = 10,
+        Right,
+    }
+    declare const enum Color {
+        Red = "red",
+        Green = "green",
+    }
+    declare enum Flags {
+        None = -1,
+        Read = 1 << 1,
+    }
+    "#;
+    let (mut checker, mut ctx) = parse_dts(lib).unwrap();
+
+    let src = r#"
+    let up = Direction.Up
+    let right = Direction.Right
+    let color: Color = Color.Red
+    let read = Flags.Read
+    "#;
+
+    infer_script_with_checker(src, &mut checker, &mut ctx)?;
+
+    let scheme = ctx.schemes.get("Direction").unwrap();
+    assert_eq!(checker.print_type(&scheme.t), "0 | 1 | 10 | 11");
+    let scheme = ctx.schemes.get("Flags").unwrap();
+    assert_eq!(checker.print_type(&scheme.t), "-1 | number");
+    let binding = ctx.values.get("up").unwrap();
+    assert_eq!(checker.print_type(&binding.index), "0");
+    let binding = ctx.values.get("right").unwrap();
+    assert_eq!(checker.print_type(&binding.index), "11");
+    let binding = ctx.values.get("color").unwrap();
+    assert_eq!(checker.print_type(&binding.index), "Color");
+    let binding = ctx.values.get("read").unwrap();
+    assert_eq!(checker.print_type(&binding.index), "number");
+
+    let src = r#"
+    Direction.Up = 5
+    "#;
+
+    let error = infer_script_with_checker(src, &mut checker, &mut ctx);
+    assert!(error.is_err());
+
+    Ok(())
+}
+
+#[test]
+fn infer_default_exports() -> Result<(), String> {
+    let lib = r#"
+    declare function foo(x: number): string;
+    export = foo;
+    "#;
+    let (checker, ctx) = parse_dts(lib).unwrap();
+
+    let binding = ctx.values.get("default").unwrap();
+    assert_eq!(checker.print_type(&binding.index), "(x: number) -> string");
+
+    let lib = r#"
+    export default class Foo {
+        bar: number;
+    }
+    "#;
+    let (checker, ctx) = parse_dts(lib).unwrap();
+
+    let scheme = ctx.schemes.get("default").unwrap();
+    assert_eq!(checker.print_type(&scheme.t), "{bar: number}");
+    let binding = ctx.values.get("Foo").unwrap();
+    assert_eq!(checker.print_type(&binding.index), "{new fn() -> Foo}");
+
+    let lib = r#"
+    export default function (x: number): string;
+    "#;
+    let (checker, ctx) = parse_dts(lib).unwrap();
+
+    let binding = ctx.values.get("default").unwrap();
+    assert_eq!(checker.print_type(&binding.index), "(x: number) -> string");
+
+    Ok(())
+}
+
+static FIXTURE_PROJECT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/project/src");
+
+fn resolve_fixture(specifier: &str) -> Result<String, String> {
+    let resolver = Resolver::new(FIXTURE_PROJECT);
+    let path = resolver.resolve(specifier)?;
+    let fixtures = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/");
+    Ok(path.strip_prefix(fixtures).unwrap().display().to_string())
+}
+
+#[test]
+fn resolve_package_typings() {
+    assert_eq!(
+        resolve_fixture("typed"),
+        Ok("node_modules/typed/lib/index.d.ts".to_string())
+    );
+    assert_eq!(
+        resolve_fixture("legacy"),
+        Ok("node_modules/legacy/legacy.d.ts".to_string())
+    );
+    assert_eq!(
+        resolve_fixture("exported"),
+        Ok("node_modules/exported/dist/index.d.ts".to_string())
+    );
+    assert_eq!(
+        resolve_fixture("exported/utils"),
+        Ok("node_modules/exported/dist/utils/index.d.ts".to_string())
+    );
+    assert_eq!(
+        resolve_fixture("untyped"),
+        Ok("node_modules/@types/untyped/index.d.ts".to_string())
+    );
+    assert_eq!(
+        resolve_fixture("@scope/pkg"),
+        Ok("node_modules/@scope/pkg/index.d.ts".to_string())
+    );
+    assert_eq!(
+        resolve_fixture("@scope/other"),
+        Ok("node_modules/@types/scope__other/index.d.ts".to_string())
+    );
+    assert_eq!(
+        resolve_fixture("does-not-exist"),
+        Err("couldn't find types for \"does-not-exist\"".to_string())
+    );
+}
+
+#[test]
+fn load_follows_references_and_relative_imports() -> Result<(), String> {
+    let mut resolver = Resolver::new(FIXTURE_PROJECT);
+    let (mut checker, mut ctx) = resolver.load("typed")?;
+
+    let binding = ctx.values.get("add").unwrap();
+    assert_eq!(
+        checker.print_type(&binding.index),
+        "(a: Point, b: Point) -> Point"
+    );
+    assert!(ctx.schemes.contains_key("Point"));
+    assert!(ctx.schemes.contains_key("Origin"));
+
+    let src = r#"
+    let p = add({x: 1, y: 2}, {x: 5, y: 10})
+    let {x} = p
+    let {y} = origin
+    "#;
+    infer_script_with_checker(src, &mut checker, &mut ctx)?;
+
+    let binding = ctx.values.get("x").unwrap();
+    assert_eq!(checker.print_type(&binding.index), "number");
+    let binding = ctx.values.get("y").unwrap();
+    assert_eq!(checker.print_type(&binding.index), "0");
+
+    Ok(())
+}
+
+#[test]
+fn load_follows_reference_types_and_package_imports() -> Result<(), String> {
+    let mut resolver = Resolver::new(FIXTURE_PROJECT);
+
+    let (checker, ctx) = resolver.load("referencer")?;
+    let binding = ctx.values.get("version").unwrap();
+    assert_eq!(checker.print_type(&binding.index), "() -> string");
+
+    let (checker, ctx) = resolver.load("@scope/pkg")?;
+    let binding = ctx.values.get("scoped").unwrap();
+    assert_eq!(checker.print_type(&binding.index), "boolean");
+    let binding = ctx.values.get("other").unwrap();
+    assert_eq!(checker.print_type(&binding.index), "number");
+
+    assert_eq!(
+        resolver.load("does-not-exist").map(|_| ()),
+        Err("couldn't find types for \"does-not-exist\"".to_string())
+    );
+
+    Ok(())
+}
+
+#[test]
+fn load_infers_each_package_in_its_own_context() -> Result<(), String> {
+    let mut resolver = Resolver::new(FIXTURE_PROJECT);
+
+    // "alpha" and "beta" both export `Item` and `item`.
+    let (mut checker, mut ctx) = resolver.load("pair")?;
+    assert!(!ctx.schemes.contains_key("Item"));
+    assert!(!ctx.values.contains_key("item"));
+    assert!(ctx.schemes.contains_key("AlphaItem"));
+
+    let src = r#"
+    let a = first.alpha
+    let b = second.beta
+    let c = betaItem.beta
+    "#;
+    infer_script_with_checker(src, &mut checker, &mut ctx)?;
+
+    let binding = ctx.values.get("a").unwrap();
+    assert_eq!(checker.print_type(&binding.index), "number");
+    let binding = ctx.values.get("b").unwrap();
+    assert_eq!(checker.print_type(&binding.index), "string");
+    let binding = ctx.values.get("c").unwrap();
+    assert_eq!(checker.print_type(&binding.index), "string");
+
+    let (checker, ctx) = resolver.load("alpha")?;
+    let scheme = ctx.schemes.get("Item").unwrap();
+    assert_eq!(checker.print_scheme(scheme), "{alpha: number}");
+
+    Ok(())
+}
+
+#[test]
+fn lib_files_follow_lib_references() -> Result<(), String> {
+    let resolver = Resolver::new(FIXTURE_PROJECT);
+    let libs = vec!["ES2015".to_string(), "dom".to_string()];
+
+    let files = resolver
+        .lib_files(&libs)?
+        .iter()
+        .map(|path| path.file_name().unwrap().to_string_lossy().to_string())
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        files,
+        vec![
+            "lib.es5.d.ts",
+            "lib.es2015.collection.d.ts",
+            "lib.es2015.symbol.d.ts",
+            "lib.es2015.iterable.d.ts",
+            "lib.es2015.d.ts",
+            "lib.dom.d.ts",
+        ]
+    );
+
+    assert_eq!(
+        resolver.lib_files(&["es2099".to_string()]),
+        Err("couldn't find lib \"es2099\"".to_string())
+    );
+
+    Ok(())
+}
+
+#[test]
+fn load_libs_merges_interfaces_across_files() -> Result<(), String> {
+    let mut resolver = Resolver::new(FIXTURE_PROJECT);
+    let libs = vec!["es2015".to_string(), "dom".to_string()];
+    let (mut checker, mut ctx) = resolver.load_libs(&libs)?;
+
+    let scheme = ctx.schemes.get("Map").unwrap();
+    assert_eq!(checker.print_scheme(scheme), "<K, V>{readonly size: number, clear(mut self) -> undefined, get(self, key: K) -> V | undefined, keys(self) -> IterableIterator<K>, set(mut self, key: K, value: V) -> Map<K, V>}");
+
+    let scheme = ctx.schemes.get("Set").unwrap();
+    assert_eq!(
+        checker.print_scheme(scheme),
+        "<T>{add(mut self, value: T) -> Set<T>, has(self, value: T) -> boolean}"
+    );
+
+    let src = r#"
+    let {title} = document
+    "#;
+    infer_script_with_checker(src, &mut checker, &mut ctx)?;
+
+    let binding = ctx.values.get("title").unwrap();
+    assert_eq!(checker.print_type(&binding.index), "string");
+
+    Ok(())
+}
+
+#[test]
+fn lib_snapshots_round_trip() -> Result<(), String> {
+    let mut resolver = Resolver::new(FIXTURE_PROJECT);
+    let libs = vec!["es2015".to_string(), "dom".to_string()];
+    let (checker, ctx) = resolver.load_libs(&libs)?;
+
+    let bytes = save_snapshot(&checker, &ctx)?;
+    let (mut checker, mut ctx) = load_snapshot(&bytes)?;
+
+    let scheme = ctx.schemes.get("Map").unwrap();
+    assert_eq!(checker.print_scheme(scheme), "<K, V>{readonly size: number, clear(mut self) -> undefined, get(self, key: K) -> V | undefined, keys(self) -> IterableIterator<K>, set(mut self, key: K, value: V) -> Map<K, V>}");
+
+    let src = r#"
+    let {title} = document
+    "#;
+    infer_script_with_checker(src, &mut checker, &mut ctx)?;
+
+    let binding = ctx.values.get("title").unwrap();
+    assert_eq!(checker.print_type(&binding.index), "string");
+
+    assert_eq!(
+        load_snapshot(b"not a snapshot").map(|_| ()),
+        Err("not a snapshot".to_string())
+    );
+    let mut stale = bytes.to_owned();
+    stale[4] += 1;
+    assert_eq!(
+        load_snapshot(&stale).map(|_| ()),
+        Err("snapshot was created by a different version".to_string())
+    );
+
+    Ok(())
+}
+
+#[test]
+fn snapshots_include_ambient_modules() -> Result<(), String> {
+    let lib = r#"
+    declare module "a" {
+        export interface T {
+            a: number;
+        }
+        export const value: T;
+    }
+    "#;
+    let (checker, ctx) = parse_dts(lib).unwrap();
+
+    let bytes = save_snapshot(&checker, &ctx)?;
+    let (mut checker, mut ctx) = load_snapshot(&bytes)?;
+
+    // `import {value} from "a"`
+    let module = ctx.modules.get("a").unwrap();
+    let binding = module.values.get("value").unwrap().to_owned();
+    ctx.values.insert("value".to_string(), binding);
+
+    let src = r#"
+    let a = value.a
+    "#;
+    infer_script_with_checker(src, &mut checker, &mut ctx)?;
+
+    let binding = ctx.values.get("a").unwrap();
+    assert_eq!(checker.print_type(&binding.index), "number");
+
+    Ok(())
+}
+
+#[test]
+fn infer_mutating_methods() -> Result<(), String> {
+    let lib = r#"
+    interface Date {
+        getTime(): number;
+        setTime(time: number): number;
+    }
+    interface Counter {
+        /**
+         * Increments the count.
+         * @mutates
+         */
+        increment(): void;
+        /** Doesn't mention @mutating methods. */
+        value(): number;
+    }
+    declare class Stack<T> {
+        /** @mutates */
+        push(item: T): void;
+        peek(): T;
+    }
+    declare const counter: Counter;
+    "#;
+    let (mut checker, mut ctx) = parse_dts(lib).unwrap();
+
+    let scheme = ctx.schemes.get("Date").unwrap();
+    assert_eq!(
+        checker.print_scheme(scheme),
+        "{getTime(self) -> number, setTime(mut self, time: number) -> number}"
+    );
+    let scheme = ctx.schemes.get("Counter").unwrap();
+    assert_eq!(
+        checker.print_scheme(scheme),
+        "{increment(mut self) -> undefined, value(self) -> number}"
+    );
+    let scheme = ctx.schemes.get("Stack").unwrap();
+    assert_eq!(
+        checker.print_scheme(scheme),
+        "<T>{push(mut self, item: T) -> undefined, peek(self) -> T}"
+    );
+
+    let src = r#"
+    let count = counter.value()
+    "#;
+    infer_script_with_checker(src, &mut checker, &mut ctx)?;
+
+    let src = r#"
+    counter.increment()
+    "#;
+    let result = infer_script_with_checker(src, &mut checker, &mut ctx);
+    assert_eq!(
+        result,
+        Err("TypeError: Cannot call mutating method increment on a non-mutable object".to_string())
+    );
+
+    Ok(())
+}
+
+#[test]
+fn jsdoc_comments_are_preserved() -> Result<(), String> {
+    let lib = r#"
+    /**
+     * Converts a string to an integer.
+     * @param string A string to convert into a number.
+     * @param {number} [radix=10] - The base of the number in `string`.
+     * @returns The parsed integer.
+     */
+    declare function parseInt(string: string, radix?: number): number;
+    /** @deprecated Use `Date.now()` instead. */
+    export declare const timestamp: number;
+    /** A point in 2D space. */
+    interface Point {
+        /** The horizontal position. */
+        x: number;
+        y: number;
+    }
+    /** A last-in, first-out collection. */
+    declare class Stack<T> {
+        /**
+         * Removes the top item.
+         * @deprecated
+         */
+        pop(): T;
+    }
+    /** The primary colors. */
+    declare enum Color {
+        /** The color of blood. */
+        Red,
+        Green,
+    }
+    "#;
+    let (_, ctx) = parse_dts(lib).unwrap();
+
+    assert_eq!(
+        ctx.docs.get("parseInt"),
+        Some(&JsDoc {
+            text: "Converts a string to an integer.".to_string(),
+            deprecated: None,
+            params: vec![
+                (
+                    "string".to_string(),
+                    "A string to convert into a number.".to_string()
+                ),
+                (
+                    "radix".to_string(),
+                    "The base of the number in `string`.".to_string()
+                ),
+            ],
+            returns: Some("The parsed integer.".to_string()),
+        })
+    );
+    assert_eq!(
+        ctx.docs.get("timestamp"),
+        Some(&JsDoc {
+            deprecated: Some("Use `Date.now()` instead.".to_string()),
+            ..JsDoc::default()
+        })
+    );
+    assert_eq!(
+        ctx.docs.get("Point").map(|docs| docs.text.as_str()),
+        Some("A point in 2D space.")
+    );
+    assert_eq!(
+        ctx.docs.get("Point.x").map(|docs| docs.text.as_str()),
+        Some("The horizontal position.")
+    );
+    assert_eq!(ctx.docs.get("Point.y"), None);
+    assert_eq!(
+        ctx.docs.get("Stack").map(|docs| docs.text.as_str()),
+        Some("A last-in, first-out collection.")
+    );
+    assert_eq!(
+        ctx.docs.get("Stack.pop"),
+        Some(&JsDoc {
+            text: "Removes the top item.".to_string(),
+            deprecated: Some("".to_string()),
+            ..JsDoc::default()
+        })
+    );
+    assert_eq!(
+        ctx.docs.get("Color").map(|docs| docs.text.as_str()),
+        Some("The primary colors.")
+    );
+    assert_eq!(
+        ctx.docs.get("Color.Red").map(|docs| docs.text.as_str()),
+        Some("The color of blood.")
+    );
+
+    Ok(())
+}
+
+#[test]
+fn calling_deprecated_apis_reports_warnings() -> Result<(), String> {
+    let lib = r#"
+    /** @deprecated Use `parseInt` instead. */
+    declare function toInt(value: string): number;
+    interface Counter {
+        /** @deprecated */
+        reset(): void;
+        value(): number;
+    }
+    declare const counter: Counter;
+    "#;
+    let (mut checker, mut ctx) = parse_dts(lib).unwrap();
+
+    let src = r#"
+    let a = toInt("5")
+    let b = counter.value()
+    let c = counter.reset()
+    "#;
+    infer_script_with_checker(src, &mut checker, &mut ctx)?;
+
+    assert!(checker.current_report.diagnostics.is_empty());
+    let messages = checker
+        .current_report
+        .warnings
+        .iter()
+        .map(|warning| warning.message.to_owned())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        messages,
+        vec![
+            "toInt is deprecated: Use `parseInt` instead.".to_string(),
+            "Counter.reset is deprecated".to_string(),
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn convert_dts_to_escalier_declarations() {
+    let lib = r#"
+    /** A point in 2D space. */
+    export interface Point {
+        /** The horizontal position. */
+        x: number;
+        y: number;
+    }
+    type Shape = { kind: "circle"; radius: number } | { kind: "square"; size: number };
+    /**
+     * Converts a string to an integer.
+     * @param string A string to convert into a number.
+     */
+    declare function parseInt(string: string, radix?: number): number;
+    declare function area(shape: Shape): number;
+    declare function area(shape: Shape, scale: number): number;
+    declare const origin: Readonly<Point>;
+    declare class Stack<T> {
+        constructor(items: T[]);
+        /** @mutates */
+        push(item: T): void;
+        peek(): T | undefined;
+        readonly size: number;
+    }
+    interface Point {
+        z?: number;
+    }
+    "#;
+    let output = convert_dts(lib).unwrap();
+
+    insta::assert_snapshot!(output, @r###"
+    /** A point in 2D space. */
+    type Point = {
+        /** The horizontal position. */
+        x: number,
+        y: number,
+        z?: number,
+    }
+
+    type Shape = {kind: "circle", radius: number} | {kind: "square", size: number}
+
+    /**
+     * Converts a string to an integer.
+     * @param string_ A string to convert into a number.
+     */
+    declare let parseInt: fn (string_: string, radix?: number) -> number
+
+    declare let area: (fn (shape: Shape) -> number) & (fn (
+        shape: Shape,
+        scale: number,
+    ) -> number)
+
+    declare let origin: Readonly<Point>
+
+    type Stack<T> = {
+        fn push(mut self, item: T) -> undefined,
+        fn peek(self) -> T | undefined,
+        size: number,
+    }
+
+    declare let Stack: {new fn <T>(items: T[]) -> Stack<T>}
+    "###);
+
+    // The output can be parsed as Escalier.
+    parse(&output).unwrap();
+}
+
+#[test]
+fn convert_dts_notes_unsupported_types() {
+    let lib = r#"
+    declare function isString(value: string | number): value is string;
+    declare function assertIsString(value: string | number): asserts value is string;
+    type Frozen<T> = { readonly [K in keyof T]: T[K] };
+    "#;
+    let output = convert_dts(lib).unwrap();
+
+    insta::assert_snapshot!(output, @r###"
+    declare let isString: fn (value: string | number) -> boolean
+
+    declare let assertIsString: fn (value: string | number) -> undefined
+
+    type Frozen<T> = {
+        // TODO: `readonly` was dropped from this mapped type
+        [K]: T[K] for K in keyof T,
+    }
+    "###);
+
+    // The output can be parsed as Escalier.
+    parse(&output).unwrap();
+}
+
+#[test]
+fn mutating_methods_can_be_marked_by_merging_interfaces() -> Result<(), String> {
+    let lib = r#"
+    interface Cache {
+        get(key: string): string;
+        put(key: string, value: string): void;
+    }
+    // Augment a third-party type, e.g. from a package's typings.
+    interface Cache {
+        /** @mutates */
+        put(key: string, value: string): void;
+    }
+    "#;
+    let (checker, ctx) = parse_dts(lib).unwrap();
+
+    let scheme = ctx.schemes.get("Cache").unwrap();
+    assert_eq!(checker.print_scheme(scheme), "{get(self, key: string) -> string, put(mut self, key: string, value: string) -> undefined, put(mut self, key: string, value: string) -> undefined}");
+
+    Ok(())
+}
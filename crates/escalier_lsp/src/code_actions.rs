@@ -0,0 +1,275 @@
+use std::collections::HashMap;
+use swc_common::source_map::SourceFile;
+
+use lsp_types::Range;
+use lsp_types::*;
+
+use escalier_ast::*;
+use escalier_hm::checker::Checker;
+use escalier_hm::diagnostic::{Diagnostic, DiagnosticKind};
+use escalier_hm::types::TypeKind;
+
+use crate::util::{get_offset, get_range};
+
+/// Returns the quick fixes for the `diagnostics` that overlap `range` along
+/// with any refactors that apply to the nodes in `range`.  `script` must
+/// already have been inferred.
+pub fn get_code_actions(
+    checker: &mut Checker,
+    file: &SourceFile,
+    uri: &Url,
+    script: &Script,
+    diagnostics: &[Diagnostic],
+    range: &Range,
+) -> Vec<CodeAction> {
+    let start = get_offset(file, &range.start);
+    let end = get_offset(file, &range.end);
+
+    let mut builder = CodeActionBuilder {
+        file,
+        uri,
+        actions: vec![],
+    };
+
+    for diagnostic in diagnostics {
+        let span = match &diagnostic.span {
+            Some(span) if overlaps(span, start, end) => span,
+            _ => continue,
+        };
+
+        match &diagnostic.kind {
+            DiagnosticKind::MutabilityViolation { name } => {
+                if let Some(binding) = find_binding(script, name, span.start) {
+                    builder.add_action(
+                        &format!("Make `{name}` mutable"),
+                        CodeActionKind::QUICKFIX,
+                        binding.span.start,
+                        binding.span.start,
+                        String::from("mut "),
+                    );
+                }
+            }
+            DiagnosticKind::NonExhaustiveMatch { missing } => {
+                builder.add_missing_arms(span, missing)
+            }
+            DiagnosticKind::MissingThrows { throws } => builder.add_action(
+                &format!("Add `throws {throws}` to the signature"),
+                CodeActionKind::QUICKFIX,
+                span.end,
+                span.end,
+                format!(" throws {throws}"),
+            ),
+            DiagnosticKind::IncorrectArgs | DiagnosticKind::Deprecated { .. } => (),
+        }
+    }
+
+    let mut visitor = RefactorVisitor {
+        checker,
+        builder: &mut builder,
+        start,
+        end,
+    };
+    visitor.visit_program(script);
+
+    builder.actions
+}
+
+fn overlaps(span: &Span, start: usize, end: usize) -> bool {
+    span.start <= end && start <= span.end
+}
+
+struct CodeActionBuilder<'a> {
+    file: &'a SourceFile,
+    uri: &'a Url,
+    actions: Vec<CodeAction>,
+}
+
+impl<'a> CodeActionBuilder<'a> {
+    fn add_action(
+        &mut self,
+        title: &str,
+        kind: CodeActionKind,
+        start: usize,
+        end: usize,
+        new_text: String,
+    ) {
+        let edit = TextEdit {
+            range: get_range(self.file, &Span { start, end }),
+            new_text,
+        };
+        let is_quick_fix = kind == CodeActionKind::QUICKFIX;
+
+        self.actions.push(CodeAction {
+            title: title.to_owned(),
+            kind: Some(kind),
+            diagnostics: None,
+            edit: Some(WorkspaceEdit {
+                changes: Some(HashMap::from([(self.uri.to_owned(), vec![edit])])),
+                document_changes: None,
+                change_annotations: None,
+            }),
+            command: None,
+            is_preferred: Some(is_quick_fix),
+            disabled: None,
+            data: None,
+        });
+    }
+
+    fn add_missing_arms(&mut self, span: &Span, missing: &[String]) {
+        let src = &self.file.src;
+
+        // The new arms are added after the last arm, using its indentation.
+        let close = match src[..span.end].rfind('}') {
+            Some(close) if close > span.start => close,
+            _ => return,
+        };
+        let last = src[..close].trim_end();
+        let line_start = last.rfind('\n').map_or(0, |index| index + 1);
+        let indent: String = last[line_start..]
+            .chars()
+            .take_while(|c| c.is_whitespace())
+            .collect();
+
+        let mut new_text = String::new();
+        if !last.ends_with(',') {
+            new_text.push(',');
+        }
+        for (i, pattern) in missing.iter().enumerate() {
+            if i > 0 {
+                new_text.push(',');
+            }
+            new_text.push_str(&format!("\n{indent}{pattern} => throw \"TODO\""));
+        }
+
+        self.add_action(
+            "Add missing match arms",
+            CodeActionKind::QUICKFIX,
+            last.len(),
+            last.len(),
+            new_text,
+        );
+    }
+}
+
+// Finds the last binding named `name` that's declared before `offset`.
+fn find_binding(script: &Script, name: &str, offset: usize) -> Option<BindingIdent> {
+    let mut visitor = BindingVisitor { bindings: vec![] };
+    visitor.visit_program(script);
+
+    visitor
+        .bindings
+        .into_iter()
+        .filter(|binding| binding.name == name && binding.span.start < offset)
+        .max_by_key(|binding| binding.span.start)
+}
+
+struct BindingVisitor {
+    bindings: Vec<BindingIdent>,
+}
+
+impl Visitor for BindingVisitor {
+    fn visit_pattern(&mut self, pattern: &Pattern) {
+        match &pattern.kind {
+            PatternKind::Ident(binding) | PatternKind::Is(IsPat { ident: binding, .. }) => {
+                self.bindings.push(binding.to_owned())
+            }
+            PatternKind::Object(ObjectPat { props, .. }) => {
+                // `walk_pattern` doesn't visit shorthand props.
+                for prop in props {
+                    if let ObjectPatProp::Shorthand(ShorthandPatProp { ident, .. }) = prop {
+                        self.bindings.push(ident.to_owned());
+                    }
+                }
+            }
+            _ => (),
+        }
+
+        walk_pattern(self, pattern);
+    }
+}
+
+struct RefactorVisitor<'a, 'b> {
+    checker: &'a mut Checker,
+    builder: &'a mut CodeActionBuilder<'b>,
+    start: usize,
+    end: usize,
+}
+
+impl<'a, 'b> RefactorVisitor<'a, 'b> {
+    fn includes_undefined(&mut self, t: generational_arena::Index) -> bool {
+        let t = self.checker.prune(t);
+        match &self.checker.arena[t].kind {
+            TypeKind::Union(union) => union.types.clone().into_iter().any(|t| {
+                let t = self.checker.prune(t);
+                matches!(
+                    &self.checker.arena[t].kind,
+                    TypeKind::Literal(Literal::Undefined)
+                )
+            }),
+            _ => false,
+        }
+    }
+}
+
+impl<'a, 'b> Visitor for RefactorVisitor<'a, 'b> {
+    fn visit_decl(&mut self, decl: &Decl) {
+        if let DeclKind::VarDecl(VarDecl {
+            pattern,
+            expr: Some(expr),
+            type_ann: None,
+            ..
+        }) = &decl.kind
+        {
+            if let (PatternKind::Ident(binding), Some(t)) = (&pattern.kind, pattern.inferred_type) {
+                let is_func_or_class =
+                    matches!(&expr.kind, ExprKind::Function(_) | ExprKind::Class(_));
+                if !is_func_or_class && overlaps(&binding.span, self.start, self.end) {
+                    let t = self.checker.print_type(&t);
+                    self.builder.add_action(
+                        &format!("Add explicit type annotation `{t}`"),
+                        CodeActionKind::REFACTOR_REWRITE,
+                        binding.span.end,
+                        binding.span.end,
+                        format!(": {t}"),
+                    );
+                }
+            }
+        }
+
+        walk_decl(self, decl);
+    }
+
+    fn visit_expr(&mut self, expr: &Expr) {
+        if let ExprKind::Member(Member {
+            property: MemberProp::Computed(_),
+            ..
+        }) = &expr.kind
+        {
+            if let Some(t) = expr.inferred_type {
+                if overlaps(&expr.span, self.start, self.end) && self.includes_undefined(t) {
+                    let file = self.builder.file;
+                    let text = &file.src[expr.span.start..expr.span.end];
+                    let line_start = file.src[..expr.span.start]
+                        .rfind('\n')
+                        .map_or(0, |index| index + 1);
+                    let indent: String = file.src[line_start..]
+                        .chars()
+                        .take_while(|c| c.is_whitespace())
+                        .collect();
+
+                    self.builder.add_action(
+                        "Wrap in `match` to handle `undefined`",
+                        CodeActionKind::REFACTOR_REWRITE,
+                        expr.span.start,
+                        expr.span.end,
+                        format!(
+                            "match ({text}) {{\n{indent}    undefined => throw \"unexpected undefined\",\n{indent}    value => value\n{indent}}}"
+                        ),
+                    );
+                }
+            }
+        }
+
+        walk_expr(self, expr);
+    }
+}
@@ -0,0 +1,428 @@
+use generational_arena::Index;
+use std::collections::BTreeMap;
+
+use lsp_types::*;
+
+use escalier_ast::*;
+use escalier_hm::checker::Checker;
+use escalier_hm::context::Context;
+use escalier_hm::types::{self, TObjElem, TPropKey, TypeKind};
+use escalier_hm::util::filter_nullables;
+use escalier_parser::{parse, TokenKind, KEYWORDS};
+
+use crate::util::format_docs;
+
+/// Returns completion items for the cursor at `offset` in `src`.
+///
+/// If the cursor follows a `.` or `?.` we offer the members of the receiver's
+/// type, otherwise we offer bindings that are in scope, type names and
+/// keywords.  `checker` and `ctx` should already contain the lib schemes.
+pub fn get_completions(
+    checker: &mut Checker,
+    ctx: &mut Context,
+    src: &str,
+    offset: usize,
+) -> Vec<CompletionItem> {
+    let bytes = src.as_bytes();
+    let offset = offset.min(bytes.len());
+
+    let mut prefix_start = offset;
+    while prefix_start > 0 && is_ident_char(bytes[prefix_start - 1]) {
+        prefix_start -= 1;
+    }
+    let mut prefix_end = offset;
+    while prefix_end < bytes.len() && is_ident_char(bytes[prefix_end]) {
+        prefix_end += 1;
+    }
+    let prefix = &src[prefix_start..offset];
+
+    // `a.b` is a member access, but the dots in a spread like `...b` aren't.
+    let receiver_end = if prefix_start > 0
+        && bytes[prefix_start - 1] == b'.'
+        && (prefix_start < 2 || bytes[prefix_start - 2] != b'.')
+    {
+        match prefix_start > 1 && bytes[prefix_start - 2] == b'?' {
+            true => Some(prefix_start - 2),
+            false => Some(prefix_start - 1),
+        }
+    } else {
+        None
+    };
+
+    let mut items = match receiver_end {
+        Some(receiver_end) => {
+            // The source is unlikely to parse while the member is incomplete
+            // so we blank out everything from the `.` to the end of the
+            // identifier before parsing it.
+            let mut src = src.to_string();
+            src.replace_range(
+                receiver_end..prefix_end,
+                &" ".repeat(prefix_end - receiver_end),
+            );
+            match infer_src(checker, ctx, &src) {
+                Some(script) => match find_expr_ending_at(&script, receiver_end) {
+                    Some(t) => get_member_completions(checker, ctx, t),
+                    None => vec![],
+                },
+                None => vec![],
+            }
+        }
+        None => {
+            let mut items = vec![];
+            let mut values: BTreeMap<String, Option<Index>> = BTreeMap::new();
+
+            // Inference stops at the first error, but any bindings that were
+            // inferred before then will still be in `ctx`.
+            let script = infer_src(checker, ctx, src);
+
+            for (name, binding) in ctx.values.iter() {
+                values.insert(name.to_owned(), Some(binding.index));
+            }
+            if let Some(script) = &script {
+                let mut visitor = ScopeVisitor {
+                    offset,
+                    bindings: vec![],
+                };
+                visitor.visit_program(script);
+                for (name, t) in visitor.bindings {
+                    values.insert(name, t);
+                }
+            }
+
+            for (name, t) in values {
+                let (kind, detail) = match t {
+                    Some(t) => {
+                        let t = checker.prune(t);
+                        let kind = match &checker.arena[t].kind {
+                            TypeKind::Function(_) => CompletionItemKind::FUNCTION,
+                            _ => CompletionItemKind::VARIABLE,
+                        };
+                        (kind, Some(checker.print_type(&t)))
+                    }
+                    None => (CompletionItemKind::VARIABLE, None),
+                };
+                let documentation = get_documentation(ctx, &name);
+                items.push(CompletionItem {
+                    label: name,
+                    kind: Some(kind),
+                    detail,
+                    documentation,
+                    ..Default::default()
+                });
+            }
+
+            let mut schemes = ctx.schemes.iter().collect::<Vec<_>>();
+            schemes.sort_by(|a, b| a.0.cmp(b.0));
+            for (name, scheme) in schemes {
+                if scheme.is_type_param {
+                    continue;
+                }
+                items.push(CompletionItem {
+                    label: name.to_owned(),
+                    kind: Some(CompletionItemKind::INTERFACE),
+                    detail: Some(checker.print_scheme(scheme)),
+                    documentation: get_documentation(ctx, name),
+                    ..Default::default()
+                });
+            }
+
+            for (keyword, kind) in KEYWORDS.iter() {
+                if kind == &TokenKind::Underscore {
+                    continue;
+                }
+                items.push(CompletionItem {
+                    label: keyword.to_string(),
+                    kind: Some(CompletionItemKind::KEYWORD),
+                    ..Default::default()
+                });
+            }
+
+            items
+        }
+    };
+
+    items.retain(|item| item.label.starts_with(prefix));
+    items
+}
+
+fn is_ident_char(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || c == b'_'
+}
+
+pub fn infer_src(checker: &mut Checker, ctx: &mut Context, src: &str) -> Option<Script> {
+    let mut script = parse(src).ok()?;
+    // We ignore errors here since we want to provide completions even if
+    // there are errors elsewhere in the file.
+    let _ = checker.infer_script(&mut script, ctx);
+    Some(script)
+}
+
+/// Returns the completions for the members of the type `t`.
+pub fn get_member_completions(
+    checker: &mut Checker,
+    ctx: &Context,
+    t: Index,
+) -> Vec<CompletionItem> {
+    let members = match get_members(checker, ctx, t) {
+        Some(members) => members,
+        None => return vec![],
+    };
+    let type_name = checker.get_doc_type_name(t);
+
+    members
+        .into_iter()
+        .map(|(name, (kind, t))| CompletionItem {
+            documentation: type_name
+                .as_ref()
+                .and_then(|type_name| get_documentation(ctx, &format!("{type_name}.{name}"))),
+            label: name,
+            kind: Some(kind),
+            detail: t.map(|t| checker.print_type(&t)),
+            ..Default::default()
+        })
+        .collect()
+}
+
+fn get_documentation(ctx: &Context, key: &str) -> Option<Documentation> {
+    let docs = ctx.docs.get(key)?;
+    Some(Documentation::MarkupContent(MarkupContent {
+        kind: MarkupKind::Markdown,
+        value: format_docs(docs),
+    }))
+}
+
+type Members = BTreeMap<String, (CompletionItemKind, Option<Index>)>;
+
+fn get_members(checker: &mut Checker, ctx: &Context, t: Index) -> Option<Members> {
+    let t = checker.prune(t);
+
+    // It's okay to clone here because we aren't mutating the type
+    match &checker.arena[t].kind.clone() {
+        TypeKind::Object(object) => {
+            let mut members = Members::new();
+            for elem in &object.elems {
+                let (name, kind) = match elem {
+                    TObjElem::Method(method) => (&method.name, CompletionItemKind::METHOD),
+                    TObjElem::Getter(getter) => (&getter.name, CompletionItemKind::PROPERTY),
+                    TObjElem::Setter(setter) => (&setter.name, CompletionItemKind::PROPERTY),
+                    TObjElem::Prop(prop) => (&prop.name, CompletionItemKind::FIELD),
+                    TObjElem::Call(_) | TObjElem::Constructor(_) | TObjElem::Mapped(_) => continue,
+                };
+                let name = match name {
+                    TPropKey::StringKey(name) => name.to_owned(),
+                    TPropKey::NumberKey(name) => name.to_owned(),
+                };
+                let key = checker.new_lit_type(&Literal::String(name.to_owned()));
+                // Mutating methods are still completed, it's up to the checker
+                // to report an error if they're called on an immutable value.
+                let prop_t = checker.get_prop_value(ctx, t, key, true).ok();
+                members.insert(name, (kind, prop_t));
+            }
+            Some(members)
+        }
+        TypeKind::Union(union) => {
+            let types = filter_nullables(&checker.arena, &union.types);
+            let mut result: Option<Members> = None;
+            for t in types {
+                let members = get_members(checker, ctx, t)?;
+                result = Some(match result {
+                    // Only members that appear in every variant are safe to
+                    // access.
+                    Some(mut result) => {
+                        result.retain(|name, _| members.contains_key(name));
+                        for (name, (_, t)) in result.iter_mut() {
+                            if let (Some(t1), Some((_, Some(t2)))) = (*t, members.get(name)) {
+                                *t = Some(checker.new_union_type(&[t1, *t2]));
+                            }
+                        }
+                        result
+                    }
+                    None => members,
+                });
+            }
+            result
+        }
+        TypeKind::Intersection(intersection) => {
+            let mut result = Members::new();
+            for t in &intersection.types {
+                if let Some(members) = get_members(checker, ctx, *t) {
+                    result.extend(members);
+                }
+            }
+            Some(result)
+        }
+        _ => {
+            let t = expand_receiver(checker, ctx, t)?;
+            get_members(checker, ctx, t)
+        }
+    }
+}
+
+/// Expands `t` one step towards an object type whose elements are the members
+/// that can be accessed on a value of type `t`, e.g. `string` is expanded to
+/// the `String` interface from the lib.
+pub fn expand_receiver(checker: &mut Checker, ctx: &Context, t: Index) -> Option<Index> {
+    let t = checker.prune(t);
+
+    // It's okay to clone here because we aren't mutating the type
+    match &checker.arena[t].kind.clone() {
+        TypeKind::Array(types::Array { t }) => checker.expand_alias(ctx, "Array", &[*t]).ok(),
+        TypeKind::Tuple(types::Tuple { types, .. }) => {
+            let t = checker.new_union_type(types);
+            checker.expand_alias(ctx, "Array", &[t]).ok()
+        }
+        TypeKind::Primitive(primitive) => checker
+            .expand_alias(ctx, primitive.get_scheme_name(), &[])
+            .ok(),
+        TypeKind::Literal(lit) => checker.expand_alias(ctx, lit.get_scheme_name()?, &[]).ok(),
+        TypeKind::Function(_) => checker
+            .expand_alias(ctx, "Function", &[])
+            .ok()
+            .or_else(|| checker.expand_alias(ctx, "Object", &[]).ok()),
+        TypeKind::TypeVar(_) => None,
+        _ => {
+            let expanded = checker.expand_type(ctx, t).ok()?;
+            match expanded == t {
+                true => None,
+                false => Some(expanded),
+            }
+        }
+    }
+}
+
+fn find_expr_ending_at(script: &Script, end: usize) -> Option<Index> {
+    let mut visitor = ExprEndingAtVisitor { end, expr: None };
+    visitor.visit_program(script);
+    visitor.expr.map(|(_, t)| t)
+}
+
+struct ExprEndingAtVisitor {
+    end: usize,
+    // The outermost expression that ends at `end` along with its type.
+    expr: Option<(Span, Index)>,
+}
+
+impl Visitor for ExprEndingAtVisitor {
+    fn visit_expr(&mut self, expr: &Expr) {
+        if expr.span.end == self.end {
+            if let Some(t) = expr.inferred_type {
+                match &self.expr {
+                    Some((span, _)) if span.start <= expr.span.start => (),
+                    _ => self.expr = Some((expr.span, t)),
+                }
+            }
+        }
+
+        walk_expr(self, expr);
+    }
+}
+
+/// Collects bindings introduced by function params, match arms, `for` loops,
+/// `catch` clauses and local variable declarations that are in scope at
+/// `offset`.
+struct ScopeVisitor {
+    offset: usize,
+    bindings: Vec<(String, Option<Index>)>,
+}
+
+impl ScopeVisitor {
+    fn contains(&self, span: &Span) -> bool {
+        span.start <= self.offset && self.offset <= span.end
+    }
+}
+
+impl Visitor for ScopeVisitor {
+    fn visit_expr(&mut self, expr: &Expr) {
+        // Bindings introduced inside of an expression that doesn't contain
+        // the cursor aren't in scope.
+        if !self.contains(&expr.span) {
+            return;
+        }
+
+        match &expr.kind {
+            ExprKind::Function(Function { params, .. }) => {
+                for param in params {
+                    get_pattern_bindings(&param.pattern, &mut self.bindings);
+                }
+            }
+            ExprKind::Match(Match { arms, .. }) => {
+                for arm in arms {
+                    if self.contains(&arm.span) {
+                        get_pattern_bindings(&arm.pattern, &mut self.bindings);
+                    }
+                }
+            }
+            ExprKind::Try(Try {
+                catch: Some(catch), ..
+            }) => {
+                if let Some(param) = &catch.param {
+                    if self.contains(&catch.body.span) {
+                        get_pattern_bindings(param, &mut self.bindings);
+                    }
+                }
+            }
+            _ => (),
+        }
+
+        walk_expr(self, expr);
+    }
+
+    fn visit_stmt(&mut self, stmt: &Stmt) {
+        match &stmt.kind {
+            StmtKind::Decl(Decl {
+                kind: DeclKind::VarDecl(VarDecl { pattern, .. }),
+                ..
+            }) => {
+                if stmt.span.end <= self.offset {
+                    get_pattern_bindings(pattern, &mut self.bindings);
+                }
+            }
+            StmtKind::For(ForStmt { left, body, .. }) => {
+                if !self.contains(&stmt.span) {
+                    return;
+                }
+                if self.contains(&body.span) {
+                    get_pattern_bindings(left, &mut self.bindings);
+                }
+            }
+            _ => (),
+        }
+
+        walk_stmt(self, stmt);
+    }
+}
+
+fn get_pattern_bindings(pattern: &Pattern, bindings: &mut Vec<(String, Option<Index>)>) {
+    match &pattern.kind {
+        PatternKind::Ident(BindingIdent { name, .. }) => {
+            bindings.push((name.to_owned(), pattern.inferred_type))
+        }
+        PatternKind::Rest(RestPat { arg }) => get_pattern_bindings(arg, bindings),
+        PatternKind::Object(ObjectPat { props, .. })
+        | PatternKind::Struct(StructPat { props, .. }) => {
+            for prop in props {
+                match prop {
+                    ObjectPatProp::KeyValue(KeyValuePatProp { value, .. }) => {
+                        get_pattern_bindings(value, bindings)
+                    }
+                    ObjectPatProp::Shorthand(ShorthandPatProp { ident, .. }) => {
+                        bindings.push((ident.name.to_owned(), None))
+                    }
+                    ObjectPatProp::Rest(RestPat { arg }) => get_pattern_bindings(arg, bindings),
+                }
+            }
+        }
+        PatternKind::Tuple(TuplePat { elems, .. }) => {
+            for elem in elems.iter().flatten() {
+                get_pattern_bindings(&elem.pattern, bindings);
+            }
+        }
+        PatternKind::Is(IsPat { ident, .. }) => bindings.push((ident.name.to_owned(), None)),
+        PatternKind::Enum(EnumPat { args, .. }) => {
+            for arg in args {
+                get_pattern_bindings(arg, bindings);
+            }
+        }
+        PatternKind::Lit(_) | PatternKind::Wildcard => (),
+    }
+}
@@ -0,0 +1,111 @@
+use swc_common::source_map::SourceFile;
+
+use lsp_types::*;
+
+use escalier_ast::*;
+
+use crate::util::get_position;
+
+pub fn get_folding_ranges(file: &SourceFile, script: &Script) -> Vec<FoldingRange> {
+    let mut visitor = FoldingRangeVisitor {
+        file,
+        ranges: vec![],
+    };
+
+    visitor.visit_program(script);
+
+    visitor
+        .ranges
+        .sort_by_key(|range| (range.start_line, range.end_line));
+    visitor.ranges.dedup();
+    visitor.ranges
+}
+
+struct FoldingRangeVisitor<'a> {
+    file: &'a SourceFile,
+    ranges: Vec<FoldingRange>,
+}
+
+impl<'a> FoldingRangeVisitor<'a> {
+    fn add_range(&mut self, span: &Span) {
+        let start = get_position(self.file, span.start);
+        let end = get_position(self.file, span.end);
+
+        // Ranges that fit on a single line can't be folded.
+        if start.line < end.line {
+            self.ranges.push(FoldingRange {
+                start_line: start.line,
+                start_character: None,
+                end_line: end.line,
+                end_character: None,
+                kind: None,
+            });
+        }
+    }
+
+    fn visit_jsx_children(&mut self, children: &[JSXElementChild]) {
+        for child in children {
+            match child {
+                JSXElementChild::ExprContainer(JSXExprContainer { expr }) => self.visit_expr(expr),
+                JSXElementChild::Element(elem) => {
+                    self.add_range(&elem.span);
+                    self.visit_jsx_children(&elem.children);
+                }
+                JSXElementChild::Fragment(frag) => {
+                    self.add_range(&frag.span);
+                    self.visit_jsx_children(&frag.children);
+                }
+                JSXElementChild::Text(_) | JSXElementChild::SpreadChild(_) => (),
+            }
+        }
+    }
+}
+
+impl<'a> Visitor for FoldingRangeVisitor<'a> {
+    fn visit_expr(&mut self, expr: &Expr) {
+        match &expr.kind {
+            ExprKind::Object(_) | ExprKind::Match(_) => self.add_range(&expr.span),
+            ExprKind::JSXElement(JSXElement { children, .. })
+            | ExprKind::JSXFragment(JSXFragment { children, .. }) => {
+                self.add_range(&expr.span);
+                self.visit_jsx_children(children);
+            }
+            ExprKind::Class(Class { body, .. }) => {
+                self.add_range(&expr.span);
+
+                // `walk_expr` doesn't visit class members.
+                for member in body {
+                    match member {
+                        ClassMember::Method(Method { function, .. }) => {
+                            walk_block_or_expr(self, &function.body)
+                        }
+                        ClassMember::Getter(Getter { body, .. })
+                        | ClassMember::Setter(Setter { body, .. }) => self.visit_block(body),
+                        ClassMember::Field(Field { init, .. }) => {
+                            if let Some(init) = init {
+                                self.visit_expr(init);
+                            }
+                        }
+                    }
+                }
+            }
+            _ => (),
+        }
+
+        walk_expr(self, expr);
+    }
+
+    fn visit_type_ann(&mut self, type_ann: &TypeAnn) {
+        if let TypeAnnKind::Object(_) = &type_ann.kind {
+            self.add_range(&type_ann.span);
+        }
+
+        walk_type_ann(self, type_ann);
+    }
+
+    fn visit_block(&mut self, block: &Block) {
+        self.add_range(&block.span);
+
+        walk_block(self, block);
+    }
+}
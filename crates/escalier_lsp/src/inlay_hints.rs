@@ -0,0 +1,215 @@
+use generational_arena::Index;
+use swc_common::source_map::SourceFile;
+
+use lsp_types::*;
+
+use escalier_ast::*;
+use escalier_hm::checker::Checker;
+use escalier_hm::infer::generalize_func;
+use escalier_hm::types::{TObjElem, TPropKey, TypeKind};
+
+use crate::util::get_position;
+
+/// Returns hints for the types of bindings and functions in `script` that
+/// don't have explicit type annotations.  `script` must already have been
+/// inferred.
+pub fn get_inlay_hints(
+    checker: &mut Checker,
+    file: &SourceFile,
+    script: &Script,
+) -> Vec<InlayHint> {
+    let mut visitor = InlayHintVisitor {
+        checker,
+        file,
+        hints: vec![],
+    };
+
+    visitor.visit_program(script);
+
+    visitor
+        .hints
+        .sort_by_key(|hint| (hint.position.line, hint.position.character));
+    visitor.hints
+}
+
+struct InlayHintVisitor<'a> {
+    checker: &'a mut Checker,
+    file: &'a SourceFile,
+    hints: Vec<InlayHint>,
+}
+
+impl<'a> InlayHintVisitor<'a> {
+    fn add_hint(&mut self, offset: usize, label: String) {
+        self.hints.push(InlayHint {
+            position: get_position(self.file, offset),
+            label: InlayHintLabel::String(label),
+            kind: Some(InlayHintKind::TYPE),
+            text_edits: None,
+            tooltip: None,
+            padding_left: None,
+            padding_right: None,
+            data: None,
+        });
+    }
+
+    fn add_pattern_hints(&mut self, pattern: &Pattern) {
+        match &pattern.kind {
+            PatternKind::Ident(BindingIdent { span, .. }) => {
+                if let Some(t) = pattern.inferred_type {
+                    let label = format!(": {}", self.checker.print_type(&t));
+                    self.add_hint(span.end, label);
+                }
+            }
+            PatternKind::Rest(RestPat { arg }) => self.add_pattern_hints(arg),
+            PatternKind::Object(ObjectPat { props, .. })
+            | PatternKind::Struct(StructPat { props, .. }) => {
+                for prop in props {
+                    match prop {
+                        ObjectPatProp::KeyValue(KeyValuePatProp { value, .. }) => {
+                            self.add_pattern_hints(value)
+                        }
+                        ObjectPatProp::Shorthand(ShorthandPatProp { ident, .. }) => {
+                            // Shorthand props don't have their own type so we
+                            // look it up in the type of the object pattern.
+                            let t = pattern
+                                .inferred_type
+                                .and_then(|t| self.get_prop_type(t, &ident.name));
+                            if let Some(t) = t {
+                                let label = format!(": {}", self.checker.print_type(&t));
+                                self.add_hint(ident.span.end, label);
+                            }
+                        }
+                        ObjectPatProp::Rest(RestPat { arg }) => self.add_pattern_hints(arg),
+                    }
+                }
+            }
+            PatternKind::Tuple(TuplePat { elems, .. }) => {
+                for elem in elems.iter().flatten() {
+                    self.add_pattern_hints(&elem.pattern);
+                }
+            }
+            PatternKind::Enum(EnumPat { args, .. }) => {
+                for arg in args {
+                    self.add_pattern_hints(arg);
+                }
+            }
+            PatternKind::Lit(_) | PatternKind::Is(_) | PatternKind::Wildcard => (),
+        }
+    }
+
+    fn get_prop_type(&mut self, t: Index, name: &str) -> Option<Index> {
+        let t = self.checker.prune(t);
+
+        // It's okay to clone here because we aren't mutating the type
+        match &self.checker.arena[t].kind.clone() {
+            TypeKind::Object(object) => object.elems.iter().find_map(|elem| match elem {
+                TObjElem::Prop(prop) => match &prop.name {
+                    TPropKey::StringKey(key) if key == name => Some(prop.t),
+                    _ => None,
+                },
+                _ => None,
+            }),
+            // Object patterns with a rest element are inferred as `{...} & R`.
+            TypeKind::Intersection(intersection) => intersection
+                .types
+                .iter()
+                .find_map(|t| self.get_prop_type(*t, name)),
+            _ => None,
+        }
+    }
+
+    fn add_function_hints(&mut self, expr: &Expr, func: &Function) {
+        let func_t = match expr.inferred_type {
+            Some(t) => {
+                let t = self.checker.prune(t);
+                match &self.checker.arena[t].kind.clone() {
+                    // Functions are only generalized when they're bound to a
+                    // variable so we generalize here to avoid showing type
+                    // variables in the hints.
+                    TypeKind::Function(func_t) => generalize_func(self.checker, func_t),
+                    _ => return,
+                }
+            }
+            None => return,
+        };
+
+        // We use the types from the function's type instead of the types on
+        // the params' patterns since the former have been generalized.
+        for (param, param_t) in func.params.iter().zip(func_t.params.iter()) {
+            if param.type_ann.is_none() {
+                if let PatternKind::Ident(BindingIdent { span, .. }) = &param.pattern.kind {
+                    let label = format!(": {}", self.checker.print_type(&param_t.t));
+                    self.add_hint(span.end, label);
+                } else {
+                    self.add_pattern_hints(&param.pattern);
+                }
+            }
+        }
+
+        let throws = match (&func.throws, func_t.throws) {
+            (None, Some(throws)) => {
+                format!(" throws {}", self.checker.print_type(&throws))
+            }
+            _ => String::from(""),
+        };
+
+        match &func.type_ann {
+            Some(type_ann) => {
+                if !throws.is_empty() {
+                    self.add_hint(type_ann.span.end, throws);
+                }
+            }
+            None => {
+                // The closing paren of the params isn't part of the AST so we
+                // search for it in the source.
+                let start = match func.params.last() {
+                    Some(param) => match &param.type_ann {
+                        Some(type_ann) => type_ann.span.end,
+                        None => param.pattern.span.end,
+                    },
+                    None => expr.span.start,
+                };
+                if let Some(paren) = self.file.src[start..].find(')') {
+                    let label = format!(" -> {}{throws}", self.checker.print_type(&func_t.ret));
+                    self.add_hint(start + paren + 1, label);
+                }
+            }
+        }
+    }
+}
+
+impl<'a> Visitor for InlayHintVisitor<'a> {
+    fn visit_decl(&mut self, decl: &Decl) {
+        if let DeclKind::VarDecl(VarDecl {
+            pattern,
+            expr: Some(expr),
+            type_ann: None,
+            ..
+        }) = &decl.kind
+        {
+            match (&pattern.kind, &expr.kind) {
+                // Functions and classes get their own hints.
+                (PatternKind::Ident(_), ExprKind::Function(_) | ExprKind::Class(_)) => (),
+                _ => self.add_pattern_hints(pattern),
+            }
+        }
+
+        walk_decl(self, decl);
+    }
+
+    fn visit_stmt(&mut self, stmt: &Stmt) {
+        if let StmtKind::For(ForStmt { left, .. }) = &stmt.kind {
+            self.add_pattern_hints(left);
+        }
+
+        walk_stmt(self, stmt);
+    }
+
+    fn visit_expr(&mut self, expr: &Expr) {
+        if let ExprKind::Function(func) = &expr.kind {
+            self.add_function_hints(expr, func);
+        }
+
+        walk_expr(self, expr);
+    }
+}
@@ -1,13 +1,25 @@
 use std::collections::HashMap;
 use std::error::Error;
 use std::fs;
+use std::path::PathBuf;
 use swc_common::source_map::SourceFile;
 
-use lsp_server::Connection;
+use lsp_server::{Connection, Message, Notification};
+use lsp_types::notification::{Notification as _, ShowMessage};
 use lsp_types::*;
 
+use escalier_hm::snapshot::load_snapshot;
+use escalier_interop::parse::parse_dts_files;
+use escalier_interop::resolver::Resolver;
+
+mod code_actions;
+mod completion;
+mod folding_ranges;
+mod inlay_hints;
 mod semantic_tokens;
 mod server;
+mod signature_help;
+mod symbols;
 mod util;
 
 use server::LanguageServer;
@@ -25,6 +37,23 @@ fn main() -> Result<(), Box<dyn Error + Sync + Send>> {
     // Run the server and wait for the two threads to end (typically by trigger LSP Exit event).
     let server_capabilities = serde_json::to_value(ServerCapabilities {
         hover_provider: Some(HoverProviderCapability::Simple(true)),
+        completion_provider: Some(CompletionOptions {
+            trigger_characters: Some(vec![String::from(".")]),
+            ..Default::default()
+        }),
+        signature_help_provider: Some(SignatureHelpOptions {
+            trigger_characters: Some(vec![String::from("("), String::from(",")]),
+            retrigger_characters: None,
+            work_done_progress_options: WorkDoneProgressOptions {
+                work_done_progress: None,
+            },
+        }),
+        document_symbol_provider: Some(OneOf::Left(true)),
+        workspace_symbol_provider: Some(OneOf::Left(true)),
+        folding_range_provider: Some(FoldingRangeProviderCapability::Simple(true)),
+        inlay_hint_provider: Some(OneOf::Left(true)),
+        code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
+        document_formatting_provider: Some(OneOf::Left(true)),
         text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
         semantic_tokens_provider: Some(SemanticTokensServerCapabilities::SemanticTokensOptions(
             SemanticTokensOptions {
@@ -57,11 +86,61 @@ fn main() -> Result<(), Box<dyn Error + Sync + Send>> {
     .unwrap();
 
     let initialization_params = connection.initialize(server_capabilities)?;
-    let _params: InitializeParams = serde_json::from_value(initialization_params).unwrap();
+    let params: InitializeParams = serde_json::from_value(initialization_params).unwrap();
+    let workspace_root = params.root_uri.and_then(|uri| uri.to_file_path().ok());
+
+    // The libs can be configured with `{"lib": ["es2015", "dom"]}` in the
+    // initialization options.  A snapshot created with `escalier snapshot`
+    // can be used instead with `{"snapshot": "path/to/lib.snap"}`.
+    let options = params.initialization_options.as_ref();
+    let lib_option = options
+        .and_then(|options| options.get("lib"))
+        .and_then(|lib| serde_json::from_value::<Vec<String>>(lib.to_owned()).ok());
+    let libs = lib_option
+        .to_owned()
+        .unwrap_or_else(|| vec!["es5".to_string()]);
+    let snapshot = options
+        .and_then(|options| options.get("snapshot"))
+        .and_then(|snapshot| snapshot.as_str());
+
+    let lib = match snapshot {
+        Some(path) => load_snapshot(&fs::read(path)?)?,
+        None => {
+            // Prefer the version of TypeScript installed in the workspace.
+            let lib_paths = match &workspace_root {
+                Some(root) => Resolver::new(root).lib_files(&libs),
+                None => Err("there's no workspace root".to_string()),
+            };
+            let lib_paths = match lib_paths {
+                Ok(lib_paths) => lib_paths,
+                Err(err) => {
+                    // Let users know why they're only getting the types from
+                    // lib.es5.d.ts when they asked for other libs.
+                    if lib_option.is_some() {
+                        let message = format!(
+                            "Couldn't load the libs {:?}, using lib.es5.d.ts instead: {err}",
+                            libs
+                        );
+                        show_warning(&connection, message)?;
+                    }
+                    vec![PathBuf::from(LIB_ES5_D_TS)]
+                }
+            };
+            let mut files = vec![];
+            for path in lib_paths {
+                let src = fs::read_to_string(&path)?;
+                files.push((path, src));
+            }
+            parse_dts_files(&files).map_err(|err| err.kind().msg().to_string())?
+        }
+    };
 
-    let lib = fs::read_to_string(LIB_ES5_D_TS).unwrap();
     let file_cache: HashMap<Url, SourceFile> = HashMap::new();
-    let mut server = LanguageServer { lib, file_cache };
+    let mut server = LanguageServer {
+        lib,
+        file_cache,
+        workspace_root,
+    };
 
     server.main_loop(&connection)?;
 
@@ -71,3 +150,16 @@ fn main() -> Result<(), Box<dyn Error + Sync + Send>> {
     eprintln!("shutting down server");
     Ok(())
 }
+
+fn show_warning(
+    connection: &Connection,
+    message: String,
+) -> Result<(), Box<dyn Error + Sync + Send>> {
+    let params = ShowMessageParams {
+        typ: MessageType::WARNING,
+        message,
+    };
+    let note = Notification::new(ShowMessage::METHOD.to_string(), params);
+    connection.sender.send(Message::Notification(note))?;
+    Ok(())
+}
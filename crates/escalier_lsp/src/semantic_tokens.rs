@@ -94,18 +94,24 @@ impl<'a> Visitor for SemanticTokenVisitor<'a> {
             ExprKind::Num(_) => Some(11),
             ExprKind::Bool(_) => None,
             ExprKind::Str(_) => Some(10),
+            ExprKind::Regex(_) => Some(12),
             ExprKind::Null(_) => None,
             ExprKind::Undefined(_) => None,
             ExprKind::Binary(_) => None,
             ExprKind::Unary(_) => None,
             ExprKind::Object(_) => None,
+            ExprKind::Struct(_) => None,
             ExprKind::Tuple(_) => None,
             ExprKind::Member(_) => None,
+            ExprKind::Slice(_) => None,
+            ExprKind::Range(_) => None,
+            ExprKind::Pipeline(_) => None,
             ExprKind::TemplateLiteral(_) => None,
             ExprKind::TaggedTemplateLiteral(_) => None,
             ExprKind::Match(_) => None,
             ExprKind::Class(_) => None,
             ExprKind::Do(_) => None,
+            ExprKind::Loop(_) => None,
             ExprKind::Try(_) => None,
             ExprKind::Throw(_) => None,
             ExprKind::Yield(_) => None,
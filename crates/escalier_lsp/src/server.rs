@@ -1,6 +1,7 @@
 use generational_arena::Index;
 use std::collections::HashMap;
 use std::error::Error;
+use std::path::PathBuf;
 use std::time::{SystemTime, UNIX_EPOCH};
 use swc_common::source_map::{BytePos, FileName, SourceFile};
 
@@ -9,22 +10,36 @@ use lsp_server::{
     ResponseError,
 };
 use lsp_types::notification::{DidChangeTextDocument, DidOpenTextDocument};
-use lsp_types::request::{HoverRequest, SemanticTokensFullRequest};
+use lsp_types::request::{
+    CodeActionRequest, Completion, DocumentSymbolRequest, FoldingRangeRequest, Formatting,
+    HoverRequest, InlayHintRequest, SemanticTokensFullRequest, SignatureHelpRequest,
+    WorkspaceSymbol,
+};
 use lsp_types::*;
 
 use escalier_ast::{
-    walk_expr, walk_pattern, walk_stmt, walk_type_ann, Expr, Pattern, Script, Stmt, TypeAnn,
-    Visitor,
+    walk_expr, walk_pattern, walk_stmt, walk_type_ann, Expr, ExprKind, Pattern, Script, Span, Stmt,
+    TypeAnn, Visitor,
 };
-use escalier_interop::parse::parse_dts;
+use escalier_fmt::format_script;
+use escalier_hm::checker::Checker;
+use escalier_hm::context::Context;
 use escalier_parser::parse;
 
+use crate::code_actions::get_code_actions;
+use crate::completion::get_completions;
+use crate::folding_ranges::get_folding_ranges;
+use crate::inlay_hints::get_inlay_hints;
 use crate::semantic_tokens::get_semantic_tokens;
+use crate::signature_help::get_signature_help;
+use crate::symbols::{find_source_files, get_document_symbols, get_workspace_symbols};
 use crate::util;
 
 pub struct LanguageServer {
-    pub lib: String,
+    // The lib is parsed once on startup and cloned for each request.
+    pub lib: (Checker, Context),
     pub file_cache: HashMap<Url, SourceFile>,
+    pub workspace_root: Option<PathBuf>,
 }
 
 impl LanguageServer {
@@ -64,19 +79,7 @@ impl LanguageServer {
 
                 eprintln!("Handling HoverRequest");
 
-                // NOTE: This is slow so we'll want to do this once once
-                // on startup and re-use the results.
-                eprintln!("parsing .d.ts");
-                let (mut checker, mut ctx) = match parse_dts(&self.lib) {
-                    Ok(value) => {
-                        eprintln!("success");
-                        value
-                    }
-                    Err(_) => {
-                        eprintln!("error");
-                        panic!("parsing .d.ts file failed");
-                    }
-                };
+                let (mut checker, mut ctx) = self.lib.to_owned();
 
                 let start = SystemTime::now()
                     .duration_since(UNIX_EPOCH)
@@ -106,10 +109,18 @@ impl LanguageServer {
                 // TODO: create a From impl to convert from one Position to another.
                 let cursor_loc = params.text_document_position_params.position;
 
-                let message = match get_type_at_location(file, &program, &cursor_loc) {
+                let (t, expr) = get_type_at_location(file, &program, &cursor_loc);
+                let mut message = match t {
                     Some(t) => checker.print_type(&t),
                     None => String::from("no type info"),
                 };
+                let docs = expr
+                    .and_then(|expr| checker.get_doc_key(&expr))
+                    .and_then(|key| ctx.docs.get(&key));
+                if let Some(docs) = docs {
+                    message.push_str("\n\n");
+                    message.push_str(&util::format_docs(docs));
+                }
 
                 let result = Some(Hover {
                     contents: HoverContents::Scalar(MarkedString::String(message)),
@@ -128,6 +139,54 @@ impl LanguageServer {
 
                 connection.sender.send(Message::Response(resp))?;
             }
+            "textDocument/completion" => {
+                let (id, params) = cast_req::<Completion>(req)?;
+                let resp = self.handle_completion(id, params);
+
+                connection.sender.send(Message::Response(resp))?;
+            }
+            "textDocument/signatureHelp" => {
+                let (id, params) = cast_req::<SignatureHelpRequest>(req)?;
+                let resp = self.handle_signature_help(id, params);
+
+                connection.sender.send(Message::Response(resp))?;
+            }
+            "textDocument/documentSymbol" => {
+                let (id, params) = cast_req::<DocumentSymbolRequest>(req)?;
+                let resp = self.handle_document_symbols(id, params);
+
+                connection.sender.send(Message::Response(resp))?;
+            }
+            "workspace/symbol" => {
+                let (id, params) = cast_req::<WorkspaceSymbol>(req)?;
+                let resp = self.handle_workspace_symbols(id, params);
+
+                connection.sender.send(Message::Response(resp))?;
+            }
+            "textDocument/foldingRange" => {
+                let (id, params) = cast_req::<FoldingRangeRequest>(req)?;
+                let resp = self.handle_folding_ranges(id, params);
+
+                connection.sender.send(Message::Response(resp))?;
+            }
+            "textDocument/inlayHint" => {
+                let (id, params) = cast_req::<InlayHintRequest>(req)?;
+                let resp = self.handle_inlay_hints(id, params);
+
+                connection.sender.send(Message::Response(resp))?;
+            }
+            "textDocument/codeAction" => {
+                let (id, params) = cast_req::<CodeActionRequest>(req)?;
+                let resp = self.handle_code_action(id, params);
+
+                connection.sender.send(Message::Response(resp))?;
+            }
+            "textDocument/formatting" => {
+                let (id, params) = cast_req::<Formatting>(req)?;
+                let resp = self.handle_formatting(id, params);
+
+                connection.sender.send(Message::Response(resp))?;
+            }
             method => {
                 eprintln!("Unhandled request method: {method}");
             }
@@ -242,156 +301,626 @@ impl LanguageServer {
             error: None,
         }
     }
-}
 
-struct GetTypeVisitor<'a> {
-    cursor_pos: Position,
-    file: &'a SourceFile,
-    t: Option<Index>,
-}
+    fn handle_completion(&self, id: RequestId, params: CompletionParams) -> Response {
+        let TextDocumentPositionParams {
+            text_document,
+            position,
+        } = params.text_document_position;
 
-impl<'a> Visitor for GetTypeVisitor<'a> {
-    fn visit_expr(&mut self, expr: &Expr) {
-        let BytePos(offset) = util::get_byte_pos(self.file, &self.cursor_pos).unwrap();
-        let span = expr.span;
-        if offset >= span.start as u32 && offset < span.end as u32 {
-            if let Some(t) = &expr.inferred_type {
-                self.t = Some(t.to_owned())
+        let file = match self.file_cache.get(&text_document.uri) {
+            Some(file) => file,
+            None => {
+                return Response {
+                    id,
+                    result: None,
+                    error: Some(ResponseError {
+                        code: ErrorCode::InternalError as i32,
+                        message: String::from("Couldn't find file in cache"),
+                        data: None,
+                    }),
+                }
             }
-        }
-
-        walk_expr(self, expr);
-    }
+        };
 
-    fn visit_pattern(&mut self, pattern: &Pattern) {
-        eprintln!("pattern = {pattern:#?}");
-        eprintln!("cursor_pos = {:#?}", self.cursor_pos);
-        let BytePos(offset) = util::get_byte_pos(self.file, &self.cursor_pos).unwrap();
-        eprintln!("offset = {:#?}", offset);
-        let span = pattern.span;
-        if offset >= span.start as u32 && offset < span.end as u32 {
-            if let Some(t) = &pattern.inferred_type {
-                self.t = Some(t.to_owned())
-            }
-        }
+        let (mut checker, mut ctx) = self.lib.to_owned();
 
-        walk_pattern(self, pattern);
-    }
+        let offset = util::get_offset(file, &position);
 
-    fn visit_stmt(&mut self, stmt: &Stmt) {
-        // TODO - implemnet this method
-        walk_stmt(self, stmt);
-    }
+        let items = get_completions(&mut checker, &mut ctx, &file.src, offset);
+        let result = Some(CompletionResponse::Array(items));
 
-    fn visit_type_ann(&mut self, type_ann: &TypeAnn) {
-        let BytePos(offset) = util::get_byte_pos(self.file, &self.cursor_pos).unwrap();
-        let span = type_ann.span;
-        if offset >= span.start as u32 && offset < span.end as u32 {
-            if let Some(t) = &type_ann.inferred_type {
-                self.t = Some(t.to_owned())
+        let value = match serde_json::to_value(result) {
+            Ok(value) => value,
+            Err(_) => {
+                return Response {
+                    id,
+                    result: None,
+                    error: Some(ResponseError {
+                        code: ErrorCode::InternalError as i32,
+                        message: String::from("Failed to convert result to Value"),
+                        data: None,
+                    }),
+                }
             }
-        }
+        };
 
-        walk_type_ann(self, type_ann);
+        Response {
+            id,
+            result: Some(value),
+            error: None,
+        }
     }
-}
-
-fn get_type_at_location(
-    file: &SourceFile,
-    program: &Script,
-    cursor_pos: &Position,
-) -> Option<Index> {
-    let mut visitor = GetTypeVisitor {
-        file,
-        cursor_pos: *cursor_pos,
-        t: None,
-    };
 
-    // TODO: use visit_program() method
-    visitor.visit_program(program);
+    fn handle_signature_help(&self, id: RequestId, params: SignatureHelpParams) -> Response {
+        let TextDocumentPositionParams {
+            text_document,
+            position,
+        } = params.text_document_position_params;
 
-    visitor.t
-}
+        let file = match self.file_cache.get(&text_document.uri) {
+            Some(file) => file,
+            None => {
+                return Response {
+                    id,
+                    result: None,
+                    error: Some(ResponseError {
+                        code: ErrorCode::InternalError as i32,
+                        message: String::from("Couldn't find file in cache"),
+                        data: None,
+                    }),
+                }
+            }
+        };
 
-fn cast_req<R>(req: Request) -> Result<(RequestId, R::Params), ExtractError<Request>>
-where
-    R: lsp_types::request::Request,
-    R::Params: serde::de::DeserializeOwned,
-{
-    req.extract(R::METHOD)
-}
+        let (mut checker, mut ctx) = self.lib.to_owned();
 
-fn cast_note<R>(note: Notification) -> Result<R::Params, ExtractError<Notification>>
-where
-    R: lsp_types::notification::Notification,
-    R::Params: serde::de::DeserializeOwned,
-{
-    note.extract(R::METHOD)
-}
+        let offset = util::get_offset(file, &position);
 
-#[cfg(test)]
-mod tests {
-    use crossbeam::channel::unbounded;
-    use lsp_types::Position;
-    use serde_json::*;
-    use std::str::FromStr;
+        let result = get_signature_help(&mut checker, &mut ctx, &file.src, offset);
 
-    use super::*;
+        let value = match serde_json::to_value(result) {
+            Ok(value) => value,
+            Err(_) => {
+                return Response {
+                    id,
+                    result: None,
+                    error: Some(ResponseError {
+                        code: ErrorCode::InternalError as i32,
+                        message: String::from("Failed to convert result to Value"),
+                        data: None,
+                    }),
+                }
+            }
+        };
 
-    #[test]
-    fn test_handle_notification_did_open() {
-        let file_cache = HashMap::new();
+        Response {
+            id,
+            result: Some(value),
+            error: None,
+        }
+    }
 
-        let mut server = LanguageServer {
-            file_cache,
-            lib: String::from(""),
+    fn handle_document_symbols(&self, id: RequestId, params: DocumentSymbolParams) -> Response {
+        let file = match self.file_cache.get(&params.text_document.uri) {
+            Some(file) => file,
+            None => {
+                return Response {
+                    id,
+                    result: None,
+                    error: Some(ResponseError {
+                        code: ErrorCode::InternalError as i32,
+                        message: String::from("Couldn't find file in cache"),
+                        data: None,
+                    }),
+                }
+            }
         };
 
-        let uri = Url::from_str("file://path/to/file.esc").unwrap();
-        let params = DidOpenTextDocumentParams {
-            text_document: TextDocumentItem {
-                uri: uri.to_owned(),
-                language_id: String::from("escalier"),
-                version: 123,
-                text: String::from("let a = 5"),
-            },
+        let prog = match parse(&file.src) {
+            Ok(prog) => prog,
+            Err(_) => {
+                return Response {
+                    id,
+                    result: None,
+                    error: Some(ResponseError {
+                        code: ErrorCode::ParseError as i32,
+                        message: String::from("Failed to parse file"),
+                        data: None,
+                    }),
+                }
+            }
         };
 
-        let note = Notification {
-            method: String::from("textDocument/didOpen"),
-            params: to_value(params).unwrap(),
-        };
+        let result = Some(DocumentSymbolResponse::Nested(get_document_symbols(
+            file, &prog,
+        )));
 
-        server.handle_notification(note).unwrap();
+        let value = match serde_json::to_value(result) {
+            Ok(value) => value,
+            Err(_) => {
+                return Response {
+                    id,
+                    result: None,
+                    error: Some(ResponseError {
+                        code: ErrorCode::InternalError as i32,
+                        message: String::from("Failed to convert result to Value"),
+                        data: None,
+                    }),
+                }
+            }
+        };
 
-        assert!(server.file_cache.contains_key(&uri));
-        let file = server.file_cache.get(&uri).unwrap();
-        assert_eq!(file.src.to_string(), "let a = 5");
+        Response {
+            id,
+            result: Some(value),
+            error: None,
+        }
     }
 
-    #[test]
-    fn test_handle_notification_did_change() {
-        let uri = Url::from_str("file://path/to/file.esc").unwrap();
-        let mut file_cache = HashMap::new();
-        let file = SourceFile::new(
-            FileName::Anon,
-            false,
-            FileName::Anon,
-            String::from("let a = 5"),
-            BytePos(1),
-        );
-        file_cache.insert(uri.to_owned(), file);
-
-        let mut server = LanguageServer {
-            file_cache,
-            lib: String::from(""),
+    fn handle_workspace_symbols(&self, id: RequestId, params: WorkspaceSymbolParams) -> Response {
+        // Files that are open in the editor take precedence over what's on
+        // disk since they may contain unsaved changes.
+        let disk_files = match &self.workspace_root {
+            Some(root) => find_source_files(root),
+            None => vec![],
         };
+        let mut files: Vec<(Url, &SourceFile)> = self
+            .file_cache
+            .iter()
+            .map(|(uri, file)| (uri.to_owned(), file))
+            .collect();
+        for (uri, file) in &disk_files {
+            if !self.file_cache.contains_key(uri) {
+                files.push((uri.to_owned(), file));
+            }
+        }
+        files.sort_by(|a, b| a.0.cmp(&b.0));
 
-        let params = DidChangeTextDocumentParams {
-            text_document: VersionedTextDocumentIdentifier {
-                uri: uri.to_owned(),
-                version: 456,
-            },
+        let result = Some(get_workspace_symbols(&files, &params.query));
+
+        let value = match serde_json::to_value(result) {
+            Ok(value) => value,
+            Err(_) => {
+                return Response {
+                    id,
+                    result: None,
+                    error: Some(ResponseError {
+                        code: ErrorCode::InternalError as i32,
+                        message: String::from("Failed to convert result to Value"),
+                        data: None,
+                    }),
+                }
+            }
+        };
+
+        Response {
+            id,
+            result: Some(value),
+            error: None,
+        }
+    }
+
+    fn handle_folding_ranges(&self, id: RequestId, params: FoldingRangeParams) -> Response {
+        let file = match self.file_cache.get(&params.text_document.uri) {
+            Some(file) => file,
+            None => {
+                return Response {
+                    id,
+                    result: None,
+                    error: Some(ResponseError {
+                        code: ErrorCode::InternalError as i32,
+                        message: String::from("Couldn't find file in cache"),
+                        data: None,
+                    }),
+                }
+            }
+        };
+
+        let prog = match parse(&file.src) {
+            Ok(prog) => prog,
+            Err(_) => {
+                return Response {
+                    id,
+                    result: None,
+                    error: Some(ResponseError {
+                        code: ErrorCode::ParseError as i32,
+                        message: String::from("Failed to parse file"),
+                        data: None,
+                    }),
+                }
+            }
+        };
+
+        let result = Some(get_folding_ranges(file, &prog));
+
+        let value = match serde_json::to_value(result) {
+            Ok(value) => value,
+            Err(_) => {
+                return Response {
+                    id,
+                    result: None,
+                    error: Some(ResponseError {
+                        code: ErrorCode::InternalError as i32,
+                        message: String::from("Failed to convert result to Value"),
+                        data: None,
+                    }),
+                }
+            }
+        };
+
+        Response {
+            id,
+            result: Some(value),
+            error: None,
+        }
+    }
+
+    fn handle_inlay_hints(&self, id: RequestId, params: InlayHintParams) -> Response {
+        let file = match self.file_cache.get(&params.text_document.uri) {
+            Some(file) => file,
+            None => {
+                return Response {
+                    id,
+                    result: None,
+                    error: Some(ResponseError {
+                        code: ErrorCode::InternalError as i32,
+                        message: String::from("Couldn't find file in cache"),
+                        data: None,
+                    }),
+                }
+            }
+        };
+
+        let mut prog = match parse(&file.src) {
+            Ok(prog) => prog,
+            Err(_) => {
+                return Response {
+                    id,
+                    result: None,
+                    error: Some(ResponseError {
+                        code: ErrorCode::ParseError as i32,
+                        message: String::from("Failed to parse file"),
+                        data: None,
+                    }),
+                }
+            }
+        };
+
+        let (mut checker, mut ctx) = self.lib.to_owned();
+
+        // We ignore errors here so that we can still show hints for the
+        // parts of the file before the error.
+        let _ = checker.infer_script(&mut prog, &mut ctx);
+
+        let range = params.range;
+        let result: Option<Vec<InlayHint>> = Some(
+            get_inlay_hints(&mut checker, file, &prog)
+                .into_iter()
+                .filter(|hint| range.start <= hint.position && hint.position <= range.end)
+                .collect(),
+        );
+
+        let value = match serde_json::to_value(result) {
+            Ok(value) => value,
+            Err(_) => {
+                return Response {
+                    id,
+                    result: None,
+                    error: Some(ResponseError {
+                        code: ErrorCode::InternalError as i32,
+                        message: String::from("Failed to convert result to Value"),
+                        data: None,
+                    }),
+                }
+            }
+        };
+
+        Response {
+            id,
+            result: Some(value),
+            error: None,
+        }
+    }
+
+    fn handle_code_action(&self, id: RequestId, params: CodeActionParams) -> Response {
+        let uri = &params.text_document.uri;
+        let file = match self.file_cache.get(uri) {
+            Some(file) => file,
+            None => {
+                return Response {
+                    id,
+                    result: None,
+                    error: Some(ResponseError {
+                        code: ErrorCode::InternalError as i32,
+                        message: String::from("Couldn't find file in cache"),
+                        data: None,
+                    }),
+                }
+            }
+        };
+
+        let mut prog = match parse(&file.src) {
+            Ok(prog) => prog,
+            Err(_) => {
+                return Response {
+                    id,
+                    result: None,
+                    error: Some(ResponseError {
+                        code: ErrorCode::ParseError as i32,
+                        message: String::from("Failed to parse file"),
+                        data: None,
+                    }),
+                }
+            }
+        };
+
+        let (mut checker, mut ctx) = self.lib.to_owned();
+
+        // Errors are reported as diagnostics so we don't need to handle
+        // them here.
+        let _ = checker.infer_script(&mut prog, &mut ctx);
+        let report = &checker.current_report;
+        let mut diagnostics = [report.diagnostics.clone(), report.warnings.clone()].concat();
+        diagnostics.sort_by_key(|diagnostic| diagnostic.span.as_ref().map(|span| span.start));
+
+        let result: Option<CodeActionResponse> = Some(
+            get_code_actions(&mut checker, file, uri, &prog, &diagnostics, &params.range)
+                .into_iter()
+                .map(CodeActionOrCommand::CodeAction)
+                .collect(),
+        );
+
+        let value = match serde_json::to_value(result) {
+            Ok(value) => value,
+            Err(_) => {
+                return Response {
+                    id,
+                    result: None,
+                    error: Some(ResponseError {
+                        code: ErrorCode::InternalError as i32,
+                        message: String::from("Failed to convert result to Value"),
+                        data: None,
+                    }),
+                }
+            }
+        };
+
+        Response {
+            id,
+            result: Some(value),
+            error: None,
+        }
+    }
+
+    fn handle_formatting(&self, id: RequestId, params: DocumentFormattingParams) -> Response {
+        let file = match self.file_cache.get(&params.text_document.uri) {
+            Some(file) => file,
+            None => {
+                return Response {
+                    id,
+                    result: None,
+                    error: Some(ResponseError {
+                        code: ErrorCode::InternalError as i32,
+                        message: String::from("Couldn't find file in cache"),
+                        data: None,
+                    }),
+                }
+            }
+        };
+
+        let output = match format_script(&file.src) {
+            Ok(output) => output,
+            Err(_) => {
+                return Response {
+                    id,
+                    result: None,
+                    error: Some(ResponseError {
+                        code: ErrorCode::ParseError as i32,
+                        message: String::from("Failed to parse file"),
+                        data: None,
+                    }),
+                }
+            }
+        };
+
+        // We replace the whole document instead of computing a minimal diff.
+        let result = match output == file.src.as_str() {
+            true => Some(vec![]),
+            false => {
+                let span = Span {
+                    start: 0,
+                    end: file.src.len(),
+                };
+                Some(vec![TextEdit {
+                    range: util::get_range(file, &span),
+                    new_text: output,
+                }])
+            }
+        };
+
+        let value = match serde_json::to_value(result) {
+            Ok(value) => value,
+            Err(_) => {
+                return Response {
+                    id,
+                    result: None,
+                    error: Some(ResponseError {
+                        code: ErrorCode::InternalError as i32,
+                        message: String::from("Failed to convert result to Value"),
+                        data: None,
+                    }),
+                }
+            }
+        };
+
+        Response {
+            id,
+            result: Some(value),
+            error: None,
+        }
+    }
+}
+
+struct GetTypeVisitor<'a> {
+    cursor_pos: Position,
+    file: &'a SourceFile,
+    t: Option<Index>,
+    // The innermost identifier or member expression containing the cursor,
+    // which is used to look up docs.
+    expr: Option<Expr>,
+}
+
+impl<'a> Visitor for GetTypeVisitor<'a> {
+    fn visit_expr(&mut self, expr: &Expr) {
+        let BytePos(offset) = util::get_byte_pos(self.file, &self.cursor_pos).unwrap();
+        let span = expr.span;
+        if offset >= span.start as u32 && offset < span.end as u32 {
+            if let Some(t) = &expr.inferred_type {
+                self.t = Some(t.to_owned())
+            }
+            if let ExprKind::Ident(_) | ExprKind::Member(_) = &expr.kind {
+                self.expr = Some(expr.to_owned())
+            }
+        }
+
+        walk_expr(self, expr);
+    }
+
+    fn visit_pattern(&mut self, pattern: &Pattern) {
+        eprintln!("pattern = {pattern:#?}");
+        eprintln!("cursor_pos = {:#?}", self.cursor_pos);
+        let BytePos(offset) = util::get_byte_pos(self.file, &self.cursor_pos).unwrap();
+        eprintln!("offset = {:#?}", offset);
+        let span = pattern.span;
+        if offset >= span.start as u32 && offset < span.end as u32 {
+            if let Some(t) = &pattern.inferred_type {
+                self.t = Some(t.to_owned())
+            }
+        }
+
+        walk_pattern(self, pattern);
+    }
+
+    fn visit_stmt(&mut self, stmt: &Stmt) {
+        // TODO - implemnet this method
+        walk_stmt(self, stmt);
+    }
+
+    fn visit_type_ann(&mut self, type_ann: &TypeAnn) {
+        let BytePos(offset) = util::get_byte_pos(self.file, &self.cursor_pos).unwrap();
+        let span = type_ann.span;
+        if offset >= span.start as u32 && offset < span.end as u32 {
+            if let Some(t) = &type_ann.inferred_type {
+                self.t = Some(t.to_owned())
+            }
+        }
+
+        walk_type_ann(self, type_ann);
+    }
+}
+
+fn get_type_at_location(
+    file: &SourceFile,
+    program: &Script,
+    cursor_pos: &Position,
+) -> (Option<Index>, Option<Expr>) {
+    let mut visitor = GetTypeVisitor {
+        file,
+        cursor_pos: *cursor_pos,
+        t: None,
+        expr: None,
+    };
+
+    // TODO: use visit_program() method
+    visitor.visit_program(program);
+
+    (visitor.t, visitor.expr)
+}
+
+fn cast_req<R>(req: Request) -> Result<(RequestId, R::Params), ExtractError<Request>>
+where
+    R: lsp_types::request::Request,
+    R::Params: serde::de::DeserializeOwned,
+{
+    req.extract(R::METHOD)
+}
+
+fn cast_note<R>(note: Notification) -> Result<R::Params, ExtractError<Notification>>
+where
+    R: lsp_types::notification::Notification,
+    R::Params: serde::de::DeserializeOwned,
+{
+    note.extract(R::METHOD)
+}
+
+#[cfg(test)]
+mod tests {
+    use crossbeam::channel::unbounded;
+    use lsp_types::Position;
+    use serde_json::*;
+    use std::str::FromStr;
+
+    use escalier_interop::parse::parse_dts;
+
+    use super::*;
+
+    #[test]
+    fn test_handle_notification_did_open() {
+        let file_cache = HashMap::new();
+
+        let mut server = LanguageServer {
+            file_cache,
+            lib: Default::default(),
+            workspace_root: None,
+        };
+
+        let uri = Url::from_str("file://path/to/file.esc").unwrap();
+        let params = DidOpenTextDocumentParams {
+            text_document: TextDocumentItem {
+                uri: uri.to_owned(),
+                language_id: String::from("escalier"),
+                version: 123,
+                text: String::from("let a = 5"),
+            },
+        };
+
+        let note = Notification {
+            method: String::from("textDocument/didOpen"),
+            params: to_value(params).unwrap(),
+        };
+
+        server.handle_notification(note).unwrap();
+
+        assert!(server.file_cache.contains_key(&uri));
+        let file = server.file_cache.get(&uri).unwrap();
+        assert_eq!(file.src.to_string(), "let a = 5");
+    }
+
+    #[test]
+    fn test_handle_notification_did_change() {
+        let uri = Url::from_str("file://path/to/file.esc").unwrap();
+        let mut file_cache = HashMap::new();
+        let file = SourceFile::new(
+            FileName::Anon,
+            false,
+            FileName::Anon,
+            String::from("let a = 5"),
+            BytePos(1),
+        );
+        file_cache.insert(uri.to_owned(), file);
+
+        let mut server = LanguageServer {
+            file_cache,
+            lib: Default::default(),
+            workspace_root: None,
+        };
+
+        let params = DidChangeTextDocumentParams {
+            text_document: VersionedTextDocumentIdentifier {
+                uri: uri.to_owned(),
+                version: 456,
+            },
             content_changes: vec![TextDocumentContentChangeEvent {
                 range: None,
                 range_length: None, // deprecated
@@ -399,43 +928,382 @@ mod tests {
             }],
         };
 
-        let note = Notification {
-            method: String::from("textDocument/didChange"),
-            params: to_value(params).unwrap(),
-        };
+        let note = Notification {
+            method: String::from("textDocument/didChange"),
+            params: to_value(params).unwrap(),
+        };
+
+        server.handle_notification(note).unwrap();
+
+        assert!(server.file_cache.contains_key(&uri));
+        let file = server.file_cache.get(&uri).unwrap();
+        assert_eq!(file.src.to_string(), "let a = 10;");
+    }
+
+    #[test]
+    fn test_handle_hover_request() {
+        let uri = Url::from_str("file://path/to/file.esc").unwrap();
+        let mut file_cache = HashMap::new();
+        let file = SourceFile::new(
+            FileName::Anon,
+            false,
+            FileName::Anon,
+            String::from("let a = 5"),
+            BytePos(1),
+        );
+        file_cache.insert(uri.to_owned(), file);
+
+        let server = LanguageServer {
+            file_cache,
+            lib: Default::default(),
+            workspace_root: None,
+        };
+
+        let params = HoverParams {
+            text_document_position_params: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier { uri },
+                position: Position {
+                    line: 1, // deal with 1-indexing vs 0-indexing
+                    character: 4,
+                },
+            },
+            work_done_progress_params: WorkDoneProgressParams {
+                work_done_token: None,
+            },
+        };
+
+        let req = Request {
+            id: RequestId::from(3),
+            method: String::from("textDocument/hover"),
+            params: to_value(params).unwrap(),
+        };
+
+        let (writer_sender, writer_receiver) = unbounded();
+        let (_, reader_receiver) = unbounded();
+
+        let connection = Connection {
+            sender: writer_sender,
+            receiver: reader_receiver,
+        };
+
+        server.handle_request(&connection, req).unwrap();
+
+        let msg: Message = writer_receiver.recv().unwrap();
+
+        let mut map = Map::new();
+        map.insert(String::from("contents"), Value::String(String::from("5")));
+
+        insta::assert_snapshot!(format!("{msg:#?}"), @r###"
+        Response(
+            Response {
+                id: RequestId(
+                    I32(
+                        3,
+                    ),
+                ),
+                result: Some(
+                    Object {
+                        "contents": String("5"),
+                    },
+                ),
+                error: None,
+            },
+        )
+        "###);
+    }
+
+    #[test]
+    fn test_handle_hover_request_with_docs() {
+        let lib = r#"
+        /**
+         * Converts a string to an integer.
+         * @param string A string to convert into a number.
+         * @deprecated Use `Number.parseInt` instead.
+         */
+        declare function parseInt(string: string): number;
+        "#;
+        let uri = Url::from_str("file://path/to/file.esc").unwrap();
+        let mut file_cache = HashMap::new();
+        let file = SourceFile::new(
+            FileName::Anon,
+            false,
+            FileName::Anon,
+            String::from("let a = parseInt(\"5\")"),
+            BytePos(1),
+        );
+        file_cache.insert(uri.to_owned(), file);
+
+        let server = LanguageServer {
+            file_cache,
+            lib: parse_dts(lib).unwrap(),
+            workspace_root: None,
+        };
+
+        let params = HoverParams {
+            text_document_position_params: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier { uri },
+                position: Position {
+                    line: 1, // deal with 1-indexing vs 0-indexing
+                    character: 10,
+                },
+            },
+            work_done_progress_params: WorkDoneProgressParams {
+                work_done_token: None,
+            },
+        };
+
+        let req = Request {
+            id: RequestId::from(3),
+            method: String::from("textDocument/hover"),
+            params: to_value(params).unwrap(),
+        };
+
+        let (writer_sender, writer_receiver) = unbounded();
+        let (_, reader_receiver) = unbounded();
+
+        let connection = Connection {
+            sender: writer_sender,
+            receiver: reader_receiver,
+        };
+
+        server.handle_request(&connection, req).unwrap();
+
+        let msg: Message = writer_receiver.recv().unwrap();
+        let resp = match msg {
+            Message::Response(resp) => resp,
+            _ => panic!("expected a response"),
+        };
+        let hover: Hover = from_value(resp.result.unwrap()).unwrap();
+        let contents = match hover.contents {
+            HoverContents::Scalar(MarkedString::String(contents)) => contents,
+            _ => panic!("expected a string"),
+        };
+
+        insta::assert_snapshot!(contents, @r###"
+        (string: string) -> number
+
+        Converts a string to an integer.
+
+        *@deprecated* Use `Number.parseInt` instead.
+
+        *@param* `string` — A string to convert into a number.
+        "###);
+    }
+
+    fn get_completion_items(lib: &str, src: &str, position: Position) -> Vec<CompletionItem> {
+        let uri = Url::from_str("file://path/to/file.esc").unwrap();
+        let mut file_cache = HashMap::new();
+        let file = SourceFile::new(
+            FileName::Anon,
+            false,
+            FileName::Anon,
+            String::from(src),
+            BytePos(1),
+        );
+        file_cache.insert(uri.to_owned(), file);
+
+        let server = LanguageServer {
+            file_cache,
+            lib: parse_dts(lib).unwrap(),
+            workspace_root: None,
+        };
+
+        let params = CompletionParams {
+            text_document_position: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier { uri },
+                position,
+            },
+            work_done_progress_params: WorkDoneProgressParams {
+                work_done_token: None,
+            },
+            partial_result_params: PartialResultParams {
+                partial_result_token: None,
+            },
+            context: None,
+        };
+
+        let req = Request {
+            id: RequestId::from(4),
+            method: String::from("textDocument/completion"),
+            params: to_value(params).unwrap(),
+        };
+
+        let (writer_sender, writer_receiver) = unbounded();
+        let (_, reader_receiver) = unbounded();
+
+        let connection = Connection {
+            sender: writer_sender,
+            receiver: reader_receiver,
+        };
+
+        server.handle_request(&connection, req).unwrap();
+
+        let msg: Message = writer_receiver.recv().unwrap();
+        let resp = match msg {
+            Message::Response(resp) => resp,
+            _ => panic!("expected a response"),
+        };
+        from_value(resp.result.unwrap()).unwrap()
+    }
+
+    fn get_completion_labels(lib: &str, src: &str, position: Position) -> Vec<String> {
+        get_completion_items(lib, src, position)
+            .iter()
+            .map(|item| {
+                let kind = item.kind.unwrap();
+                match &item.detail {
+                    Some(detail) => format!("{} {kind:?} {detail}", item.label),
+                    None => format!("{} {kind:?}", item.label),
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_handle_completion_members() {
+        let lib = r#"
+        interface String {
+            charAt(pos: number): string;
+            readonly length: number;
+        }
+        "#;
+        let src = "let msg = \"hello\"\nlet len = msg.";
+
+        let labels = get_completion_labels(
+            lib,
+            src,
+            Position {
+                line: 1,
+                character: 14,
+            },
+        );
+
+        insta::assert_snapshot!(labels.join("\n"), @r###"
+        charAt Method (pos: number) -> string
+        length Field number
+        "###);
+    }
+
+    #[test]
+    fn test_handle_completion_members_with_docs() {
+        let lib = r#"
+        interface String {
+            /**
+             * Returns the character at the specified index.
+             * @param pos The zero-based index of the desired character.
+             */
+            charAt(pos: number): string;
+            readonly length: number;
+        }
+        "#;
+        let src = "let msg = \"hello\"\nlet len = msg.";
+
+        let items = get_completion_items(
+            lib,
+            src,
+            Position {
+                line: 1,
+                character: 14,
+            },
+        );
+
+        let docs = items
+            .iter()
+            .map(|item| match &item.documentation {
+                Some(Documentation::MarkupContent(content)) => {
+                    format!("{}: {}", item.label, content.value)
+                }
+                _ => format!("{}: no docs", item.label),
+            })
+            .collect::<Vec<_>>();
+
+        insta::assert_snapshot!(docs.join("\n"), @r###"
+        charAt: Returns the character at the specified index.
+
+        *@param* `pos` — The zero-based index of the desired character.
+        length: no docs
+        "###);
+    }
+
+    #[test]
+    fn test_handle_completion_members_with_prefix() {
+        let src = "let point = {x: 5, y: 10, z: 15}\nlet x = point.y";
+
+        let labels = get_completion_labels(
+            "",
+            src,
+            Position {
+                line: 1,
+                character: 15,
+            },
+        );
+
+        insta::assert_snapshot!(labels.join("\n"), @"y Field 10");
+    }
 
-        server.handle_notification(note).unwrap();
+    #[test]
+    fn test_handle_completion_scope() {
+        let src = "let foo = 5\nlet add = fn (fa, fb) => fa + fb\nlet bar = f";
+
+        let labels = get_completion_labels(
+            "",
+            src,
+            Position {
+                line: 2,
+                character: 11,
+            },
+        );
 
-        assert!(server.file_cache.contains_key(&uri));
-        let file = server.file_cache.get(&uri).unwrap();
-        assert_eq!(file.src.to_string(), "let a = 10;");
+        insta::assert_snapshot!(labels.join("\n"), @r###"
+        foo Variable 5
+        false Keyword
+        finally Keyword
+        fn Keyword
+        for Keyword
+        from Keyword
+        "###);
     }
 
     #[test]
-    fn test_handle_hover_request() {
+    fn test_handle_completion_start_of_file() {
+        let src = "f";
+
+        let labels = get_completion_labels(
+            "",
+            src,
+            Position {
+                line: 0,
+                character: 0,
+            },
+        );
+
+        // There's no prefix to filter by at the start of the file.
+        assert!(labels.contains(&String::from("let Keyword")));
+    }
+
+    fn get_signature_help_result(lib: &str, src: &str, position: Position) -> String {
         let uri = Url::from_str("file://path/to/file.esc").unwrap();
         let mut file_cache = HashMap::new();
         let file = SourceFile::new(
             FileName::Anon,
             false,
             FileName::Anon,
-            String::from("let a = 5"),
+            String::from(src),
             BytePos(1),
         );
         file_cache.insert(uri.to_owned(), file);
 
         let server = LanguageServer {
             file_cache,
-            lib: String::from(""),
+            lib: parse_dts(lib).unwrap(),
+            workspace_root: None,
         };
 
-        let params = HoverParams {
+        let params = SignatureHelpParams {
+            context: None,
             text_document_position_params: TextDocumentPositionParams {
                 text_document: TextDocumentIdentifier { uri },
-                position: Position {
-                    line: 1, // deal with 1-indexing vs 0-indexing
-                    character: 4,
-                },
+                position,
             },
             work_done_progress_params: WorkDoneProgressParams {
                 work_done_token: None,
@@ -443,8 +1311,8 @@ mod tests {
         };
 
         let req = Request {
-            id: RequestId::from(3),
-            method: String::from("textDocument/hover"),
+            id: RequestId::from(5),
+            method: String::from("textDocument/signatureHelp"),
             params: to_value(params).unwrap(),
         };
 
@@ -459,26 +1327,607 @@ mod tests {
         server.handle_request(&connection, req).unwrap();
 
         let msg: Message = writer_receiver.recv().unwrap();
+        let resp = match msg {
+            Message::Response(resp) => resp,
+            _ => panic!("expected a response"),
+        };
+        let help: Option<SignatureHelp> = from_value(resp.result.unwrap()).unwrap();
+        let help = match help {
+            Some(help) => help,
+            None => return String::from("None"),
+        };
 
-        let mut map = Map::new();
-        map.insert(String::from("contents"), Value::String(String::from("5")));
+        let mut lines = vec![];
+        for (i, sig) in help.signatures.iter().enumerate() {
+            let marker = match help.active_signature == Some(i as u32) {
+                true => "*",
+                false => " ",
+            };
+            let active_param = sig
+                .active_parameter
+                .and_then(|index| sig.parameters.as_ref()?.get(index as usize))
+                .map(|param| match param.label {
+                    ParameterLabel::LabelOffsets([start, end]) => {
+                        sig.label[start as usize..end as usize].to_string()
+                    }
+                    ParameterLabel::Simple(ref label) => label.to_owned(),
+                });
+            match active_param {
+                Some(param) => lines.push(format!("{marker} {} [{param}]", sig.label)),
+                None => lines.push(format!("{marker} {}", sig.label)),
+            }
+        }
+        lines.join("\n")
+    }
 
-        insta::assert_snapshot!(format!("{msg:#?}"), @r###"
-        Response(
-            Response {
-                id: RequestId(
-                    I32(
-                        3,
-                    ),
-                ),
-                result: Some(
-                    Object {
-                        "contents": String("5"),
-                    },
-                ),
-                error: None,
+    #[test]
+    fn test_handle_signature_help() {
+        let src = "let add = fn (a: number, b?: number) => a\nlet sum = add(5, ";
+
+        let result = get_signature_help_result(
+            "",
+            src,
+            Position {
+                line: 1,
+                character: 17,
+            },
+        );
+
+        insta::assert_snapshot!(result, @"* add(a: number, b?: number) -> number [b?: number]");
+    }
+
+    #[test]
+    fn test_handle_signature_help_nested_args() {
+        let src = "let foo = fn (a: number[], b: number) => a\nfoo([1, 2, 3], ";
+
+        let result = get_signature_help_result(
+            "",
+            src,
+            Position {
+                line: 1,
+                character: 15,
+            },
+        );
+
+        insta::assert_snapshot!(result, @"* foo(a: number[], b: number) -> number[] [b: number]");
+    }
+
+    #[test]
+    fn test_handle_signature_help_overloads() {
+        let lib = r#"
+        interface String {
+            replace(searchValue: string, replaceValue: string): string;
+            replace(searchValue: string, replacer: (substring: string) => string): string;
+            slice(start?: number, end?: number): string;
+        }
+        "#;
+        let src = "let msg = \"hello\"\nmsg.replace(\"h\", ";
+
+        let result = get_signature_help_result(
+            lib,
+            src,
+            Position {
+                line: 1,
+                character: 17,
+            },
+        );
+
+        insta::assert_snapshot!(result, @r###"
+        * replace(searchValue: string, replaceValue: string) -> string [replaceValue: string]
+          replace(searchValue: string, replacer: (substring: string) -> string) -> string [replacer: (substring: string) -> string]
+        "###);
+    }
+
+    #[test]
+    fn test_handle_signature_help_new() {
+        let lib = r#"
+        interface Point {
+            x: number;
+            y: number;
+        }
+        interface PointConstructor {
+            new (x: number, y: number): Point;
+        }
+        declare var Point: PointConstructor;
+        "#;
+        let src = "let p = new Point(";
+
+        let result = get_signature_help_result(
+            lib,
+            src,
+            Position {
+                line: 0,
+                character: 18,
+            },
+        );
+
+        insta::assert_snapshot!(result, @"* Point(x: number, y: number) -> Point [x: number]");
+    }
+
+    #[test]
+    fn test_handle_signature_help_outside_call() {
+        let src = "let add = fn (a: number, b: number) => a\nlet sum = add(5, 10)";
+
+        let result = get_signature_help_result(
+            "",
+            src,
+            Position {
+                line: 1,
+                character: 20,
+            },
+        );
+
+        insta::assert_snapshot!(result, @"None");
+    }
+
+    #[test]
+    fn test_handle_signature_help_start_of_file() {
+        let src = "add(5, 10)";
+
+        let result = get_signature_help_result(
+            "",
+            src,
+            Position {
+                line: 0,
+                character: 0,
             },
+        );
+
+        insta::assert_snapshot!(result, @"None");
+    }
+
+    fn send_request(server: &LanguageServer, req: Request) -> Value {
+        let (writer_sender, writer_receiver) = unbounded();
+        let (_, reader_receiver) = unbounded();
+
+        let connection = Connection {
+            sender: writer_sender,
+            receiver: reader_receiver,
+        };
+
+        server.handle_request(&connection, req).unwrap();
+
+        match writer_receiver.recv().unwrap() {
+            Message::Response(resp) => resp.result.unwrap(),
+            _ => panic!("expected a response"),
+        }
+    }
+
+    fn new_server(src: &str) -> (LanguageServer, Url) {
+        let uri = Url::from_str("file://path/to/file.esc").unwrap();
+        let mut file_cache = HashMap::new();
+        let file = SourceFile::new(
+            FileName::Anon,
+            false,
+            FileName::Anon,
+            String::from(src),
+            BytePos(1),
+        );
+        file_cache.insert(uri.to_owned(), file);
+
+        let server = LanguageServer {
+            file_cache,
+            lib: Default::default(),
+            workspace_root: None,
+        };
+
+        (server, uri)
+    }
+
+    fn print_range(range: &Range) -> String {
+        format!(
+            "{}:{}-{}:{}",
+            range.start.line, range.start.character, range.end.line, range.end.character
         )
+    }
+
+    fn print_symbols(symbols: &[DocumentSymbol], indent: usize, lines: &mut Vec<String>) {
+        for symbol in symbols {
+            lines.push(format!(
+                "{}{:?} {} {} {}",
+                " ".repeat(indent),
+                symbol.kind,
+                symbol.name,
+                print_range(&symbol.range),
+                print_range(&symbol.selection_range),
+            ));
+            if let Some(children) = &symbol.children {
+                print_symbols(children, indent + 2, lines);
+            }
+        }
+    }
+
+    #[test]
+    fn test_handle_document_symbols() {
+        let src = r#"type Point = {x: number, y: number}
+type Id = string
+let add = fn (a: number, b: number) => a + b
+let {x, y: z} = {x: 5, y: 10}
+let Counter = class {
+    count: number
+    fn constructor(mut self) {
+        self.count = 0
+    }
+    get value(self) {
+        return self.count
+    }
+}"#;
+        let (server, uri) = new_server(src);
+
+        let params = DocumentSymbolParams {
+            text_document: TextDocumentIdentifier { uri },
+            work_done_progress_params: WorkDoneProgressParams {
+                work_done_token: None,
+            },
+            partial_result_params: PartialResultParams {
+                partial_result_token: None,
+            },
+        };
+
+        let req = Request {
+            id: RequestId::from(6),
+            method: String::from("textDocument/documentSymbol"),
+            params: to_value(params).unwrap(),
+        };
+
+        let symbols = match from_value(send_request(&server, req)).unwrap() {
+            Some(DocumentSymbolResponse::Nested(symbols)) => symbols,
+            _ => panic!("expected nested symbols"),
+        };
+        let mut lines = vec![];
+        print_symbols(&symbols, 0, &mut lines);
+
+        insta::assert_snapshot!(lines.join("\n"), @r###"
+        Interface Point 0:0-0:35 0:0-0:35
+        TypeParameter Id 1:0-1:16 1:0-1:16
+        Function add 2:0-2:44 2:4-2:7
+        Variable x 3:0-3:29 3:5-3:6
+        Variable z 3:0-3:29 3:11-3:12
+        Class Counter 4:0-12:1 4:4-4:11
+          Field count 5:4-5:17 5:4-5:9
+          Constructor constructor 6:4-8:5 6:7-6:18
+          Property value 9:4-11:5 9:8-9:13
+        "###);
+    }
+
+    #[test]
+    fn test_handle_workspace_symbols() {
+        let (mut server, _) = new_server("let greeter = 5\nlet other = 10");
+        server.workspace_root = Some(PathBuf::from(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/fixtures/workspace"
+        )));
+
+        let params = WorkspaceSymbolParams {
+            query: String::from("GREET"),
+            work_done_progress_params: WorkDoneProgressParams {
+                work_done_token: None,
+            },
+            partial_result_params: PartialResultParams {
+                partial_result_token: None,
+            },
+        };
+
+        let req = Request {
+            id: RequestId::from(7),
+            method: String::from("workspace/symbol"),
+            params: to_value(params).unwrap(),
+        };
+
+        let symbols: Option<Vec<SymbolInformation>> =
+            from_value(send_request(&server, req)).unwrap();
+        let lines: Vec<String> = symbols
+            .unwrap()
+            .iter()
+            .map(|symbol| {
+                let path = symbol.location.uri.path();
+                let path = path
+                    .rsplit_once("/fixtures/")
+                    .map_or(path, |(_, path)| path);
+                format!(
+                    "{:?} {} {path} {}",
+                    symbol.kind,
+                    symbol.name,
+                    print_range(&symbol.location.range)
+                )
+            })
+            .collect();
+
+        insta::assert_snapshot!(lines.join("\n"), @r###"
+        Function greet workspace/greet.esc 0:4-0:9
+        Interface Greeting workspace/nested/greeting.esc 0:0-0:33
+        Variable greeting workspace/nested/greeting.esc 1:4-1:12
+        Variable greeter /to/file.esc 0:4-0:11
+        "###);
+    }
+
+    #[test]
+    fn test_handle_folding_ranges() {
+        let src = r#"let foo = fn (x: number) {
+    let point = {
+        x: x,
+        y: 0,
+    }
+    return match (x) {
+        0 => "zero",
+        _ => {
+            let y = x + 1
+            "other"
+        }
+    }
+}
+let bar = {a: 1, b: 2}"#;
+        let (server, uri) = new_server(src);
+
+        let params = FoldingRangeParams {
+            text_document: TextDocumentIdentifier { uri },
+            work_done_progress_params: WorkDoneProgressParams {
+                work_done_token: None,
+            },
+            partial_result_params: PartialResultParams {
+                partial_result_token: None,
+            },
+        };
+
+        let req = Request {
+            id: RequestId::from(8),
+            method: String::from("textDocument/foldingRange"),
+            params: to_value(params).unwrap(),
+        };
+
+        let ranges: Option<Vec<FoldingRange>> = from_value(send_request(&server, req)).unwrap();
+        let lines: Vec<String> = ranges
+            .unwrap()
+            .iter()
+            .map(|range| format!("{}-{}", range.start_line, range.end_line))
+            .collect();
+
+        insta::assert_snapshot!(lines.join("\n"), @r###"
+        0-12
+        1-4
+        5-11
+        7-10
+        "###);
+    }
+
+    #[test]
+    fn test_handle_inlay_hints() {
+        let src = r#"let msg = "hello"
+let point = {x: 5, y: 10}
+let {x, y: z} = point
+let [a, b] = [true, "b"]
+let add = fn (a, b: number) => a + b
+let id = fn (x) => x
+let check = fn (x: number) -> number {
+    if (x < 0) {
+        throw "negative"
+    }
+    return x
+}
+let annotated: number = 5"#;
+        let (server, uri) = new_server(src);
+
+        let params = InlayHintParams {
+            text_document: TextDocumentIdentifier { uri },
+            range: Range {
+                start: Position {
+                    line: 0,
+                    character: 0,
+                },
+                end: Position {
+                    line: 100,
+                    character: 0,
+                },
+            },
+            work_done_progress_params: WorkDoneProgressParams {
+                work_done_token: None,
+            },
+        };
+
+        let req = Request {
+            id: RequestId::from(9),
+            method: String::from("textDocument/inlayHint"),
+            params: to_value(params).unwrap(),
+        };
+
+        let hints: Option<Vec<InlayHint>> = from_value(send_request(&server, req)).unwrap();
+        let lines: Vec<String> = hints
+            .unwrap()
+            .iter()
+            .map(|hint| {
+                let label = match &hint.label {
+                    InlayHintLabel::String(label) => label.to_owned(),
+                    InlayHintLabel::LabelParts(_) => panic!("expected a string label"),
+                };
+                format!("{}:{} {label}", hint.position.line, hint.position.character)
+            })
+            .collect();
+
+        insta::assert_snapshot!(lines.join("\n"), @r###"
+        0:7 : "hello"
+        1:9 : {x: 5, y: 10}
+        2:6 : 5
+        2:12 : 10
+        3:6 : true
+        3:9 : "b"
+        4:15 : number
+        4:27  -> number
+        5:14 : A
+        5:15  -> A
+        6:36  throws "negative"
+        "###);
+    }
+
+    fn get_code_actions_result(src: &str, range: Range) -> String {
+        let (server, uri) = new_server(src);
+
+        let params = CodeActionParams {
+            text_document: TextDocumentIdentifier { uri },
+            range,
+            context: CodeActionContext {
+                diagnostics: vec![],
+                only: None,
+            },
+            work_done_progress_params: WorkDoneProgressParams {
+                work_done_token: None,
+            },
+            partial_result_params: PartialResultParams {
+                partial_result_token: None,
+            },
+        };
+
+        let req = Request {
+            id: RequestId::from(10),
+            method: String::from("textDocument/codeAction"),
+            params: to_value(params).unwrap(),
+        };
+
+        let actions: Option<CodeActionResponse> = from_value(send_request(&server, req)).unwrap();
+        let mut lines = vec![];
+        for action in actions.unwrap() {
+            let action = match action {
+                CodeActionOrCommand::CodeAction(action) => action,
+                CodeActionOrCommand::Command(_) => panic!("expected a code action"),
+            };
+            lines.push(format!(
+                "{} ({})",
+                action.title,
+                action.kind.unwrap().as_str()
+            ));
+            for edits in action.edit.unwrap().changes.unwrap().values() {
+                for edit in edits {
+                    lines.push(format!("{} {:?}", print_range(&edit.range), edit.new_text));
+                }
+            }
+        }
+
+        lines.join("\n")
+    }
+
+    #[test]
+    fn test_handle_code_action_quick_fixes() {
+        let src = r#"declare let dir: "up" | "down" | "left" | "right"
+let x: number = match (dir) {
+    "up" => 1,
+    "down" => 2
+}
+let div = fn (a: number, b: number) -> number {
+    if (b == 0) {
+        throw "division by zero"
+    }
+    return a / b
+}
+type Point = {x: number, y: number}
+let p: Point = {x: 5, y: 10}
+p.x = 0"#;
+
+        let range = Range {
+            start: Position {
+                line: 0,
+                character: 0,
+            },
+            end: Position {
+                line: 100,
+                character: 0,
+            },
+        };
+
+        insta::assert_snapshot!(get_code_actions_result(src, range), @r###"
+        Add missing match arms (quickfix)
+        3:15-3:15 ",\n    \"left\" => throw \"TODO\",\n    \"right\" => throw \"TODO\""
+        Add `throws "division by zero"` to the signature (quickfix)
+        5:45-5:45 " throws \"division by zero\""
+        Make `p` mutable (quickfix)
+        12:4-12:4 "mut "
+        "###);
+    }
+
+    #[test]
+    fn test_handle_code_action_refactors() {
+        let src = r#"declare let nums: number[]
+let first = nums[0]"#;
+
+        // The cursor is on `first`.
+        let range = Range {
+            start: Position {
+                line: 1,
+                character: 6,
+            },
+            end: Position {
+                line: 1,
+                character: 6,
+            },
+        };
+        insta::assert_snapshot!(get_code_actions_result(src, range), @r###"
+        Add explicit type annotation `number | undefined` (refactor.rewrite)
+        1:9-1:9 ": number | undefined"
+        "###);
+
+        // The cursor is on `nums[0]`.
+        let range = Range {
+            start: Position {
+                line: 1,
+                character: 14,
+            },
+            end: Position {
+                line: 1,
+                character: 14,
+            },
+        };
+        insta::assert_snapshot!(get_code_actions_result(src, range), @r###"
+        Wrap in `match` to handle `undefined` (refactor.rewrite)
+        1:12-1:19 "match (nums[0]) {\n    undefined => throw \"unexpected undefined\",\n    value => value\n}"
+        "###);
+    }
+
+    fn get_formatting_result(src: &str) -> String {
+        let (server, uri) = new_server(src);
+
+        let params = DocumentFormattingParams {
+            text_document: TextDocumentIdentifier { uri },
+            options: FormattingOptions {
+                tab_size: 4,
+                insert_spaces: true,
+                ..Default::default()
+            },
+            work_done_progress_params: WorkDoneProgressParams {
+                work_done_token: None,
+            },
+        };
+
+        let req = Request {
+            id: RequestId::from(11),
+            method: String::from("textDocument/formatting"),
+            params: to_value(params).unwrap(),
+        };
+
+        let edits: Option<Vec<TextEdit>> = from_value(send_request(&server, req)).unwrap();
+        let mut lines = vec![];
+        for edit in edits.unwrap() {
+            lines.push(print_range(&edit.range));
+            lines.push(edit.new_text);
+        }
+
+        lines.join("\n")
+    }
+
+    #[test]
+    fn test_handle_formatting() {
+        let src = r#"let add=fn(a:number,b:number)->number{return a+b}
+// comment
+let sum = add(1,   2)"#;
+
+        insta::assert_snapshot!(get_formatting_result(src), @r###"
+        0:0-2:21
+        let add = fn (a: number, b: number) -> number {
+            return a + b
+        }
+        // comment
+        let sum = add(1, 2)
         "###);
+
+        // Formatting an already formatted file produces no edits.
+        let src = "let x = 5\n";
+        assert_eq!(get_formatting_result(src), "");
     }
 }
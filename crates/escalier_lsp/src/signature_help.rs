@@ -0,0 +1,322 @@
+use generational_arena::Index;
+use lsp_types::*;
+
+use escalier_ast::*;
+use escalier_hm::checker::Checker;
+use escalier_hm::context::Context;
+use escalier_hm::types::{self, TObjElem, TPropKey, TypeKind};
+use escalier_hm::util::filter_nullables;
+
+use crate::completion::{expand_receiver, infer_src};
+
+pub fn get_signature_help(
+    checker: &mut Checker,
+    ctx: &mut Context,
+    src: &str,
+    offset: usize,
+) -> Option<SignatureHelp> {
+    let offset = offset.min(src.len());
+    let (paren, active_param) = find_open_paren(&src[..offset])?;
+
+    // The arguments are likely incomplete so we replace them with `()` and
+    // close any brackets that are still open before the call so that we can
+    // infer the type of the callee.
+    let mut fixed_src = String::from(&src[..paren]);
+    fixed_src.push_str("()");
+    for opener in get_unclosed_brackets(&src[..paren]).iter().rev() {
+        fixed_src.push(match opener {
+            b'(' => ')',
+            b'[' => ']',
+            _ => '}',
+        });
+    }
+
+    let script = infer_src(checker, ctx, &fixed_src)?;
+
+    let mut visitor = CallEndingAtVisitor {
+        end: paren + 2,
+        call: None,
+    };
+    visitor.visit_program(&script);
+    let (callee, newable) = visitor.call?;
+
+    let mut funcs = vec![];
+    if let ExprKind::Member(Member {
+        object,
+        property: MemberProp::Ident(Ident { name, .. }),
+        ..
+    }) = &callee.kind
+    {
+        // Methods can be overloaded, e.g. when the interfaces from multiple
+        // lib files are merged, but member access only returns the first one.
+        if let Some(t) = object.inferred_type {
+            funcs = get_method_overloads(checker, ctx, t, name);
+        }
+    }
+    if funcs.is_empty() {
+        funcs = get_signatures(checker, ctx, callee.inferred_type?, newable);
+    }
+    if funcs.is_empty() {
+        return None;
+    }
+
+    let name = match &callee.kind {
+        ExprKind::Ident(Ident { name, .. }) => name.to_owned(),
+        ExprKind::Member(Member {
+            property: MemberProp::Ident(Ident { name, .. }),
+            ..
+        }) => name.to_owned(),
+        _ => String::from(""),
+    };
+
+    let signatures: Vec<SignatureInformation> = funcs
+        .iter()
+        .map(|func| get_signature_info(checker, &name, func, active_param))
+        .collect();
+
+    // Pick the first overload that can accept the active parameter.
+    let active_signature = funcs
+        .iter()
+        .position(|func| {
+            let params = get_params(func);
+            params.len() > active_param as usize
+                || matches!(params.last(), Some(param) if is_rest(param))
+        })
+        .unwrap_or(0);
+
+    Some(SignatureHelp {
+        active_parameter: signatures[active_signature].active_parameter,
+        active_signature: Some(active_signature as u32),
+        signatures,
+    })
+}
+
+fn get_params(func: &types::Function) -> Vec<&types::FuncParam> {
+    func.params
+        .iter()
+        .filter(|param| !param.is_self())
+        .collect()
+}
+
+fn is_rest(param: &types::FuncParam) -> bool {
+    matches!(param.pattern, types::TPat::Rest(_))
+}
+
+fn get_signature_info(
+    checker: &Checker,
+    name: &str,
+    func: &types::Function,
+    active_param: u32,
+) -> SignatureInformation {
+    let mut label = String::from(name);
+
+    if let Some(type_params) = &func.type_params {
+        if !type_params.is_empty() {
+            let type_params = type_params
+                .iter()
+                .map(|tp| match &tp.constraint {
+                    Some(constraint) => {
+                        format!("{}:{}", tp.name, checker.print_type(constraint))
+                    }
+                    None => tp.name.to_owned(),
+                })
+                .collect::<Vec<_>>();
+            label.push_str(&format!("<{}>", type_params.join(", ")));
+        }
+    }
+
+    label.push('(');
+    let params = get_params(func);
+    let mut parameters = vec![];
+    for (i, param) in params.iter().enumerate() {
+        if i > 0 {
+            label.push_str(", ");
+        }
+        let start = label.encode_utf16().count() as u32;
+        label.push_str(&checker.print_param(param));
+        let end = label.encode_utf16().count() as u32;
+        parameters.push(ParameterInformation {
+            label: ParameterLabel::LabelOffsets([start, end]),
+            documentation: None,
+        });
+    }
+    label.push_str(&format!(") -> {}", checker.print_type(&func.ret)));
+    if let Some(throws) = func.throws {
+        label.push_str(&format!(" throws {}", checker.print_type(&throws)));
+    }
+
+    // Any number of args can be passed to a rest param.
+    let active_parameter = match params.last() {
+        Some(param) if is_rest(param) => active_param.min(params.len() as u32 - 1),
+        _ => active_param,
+    };
+
+    SignatureInformation {
+        label,
+        documentation: None,
+        parameters: Some(parameters),
+        active_parameter: Some(active_parameter),
+    }
+}
+
+fn get_signatures(
+    checker: &mut Checker,
+    ctx: &Context,
+    t: Index,
+    newable: bool,
+) -> Vec<types::Function> {
+    let t = checker.prune(t);
+
+    // It's okay to clone here because we aren't mutating the type
+    match &checker.arena[t].kind.clone() {
+        TypeKind::Function(func) => vec![func.to_owned()],
+        TypeKind::Intersection(intersection) => intersection
+            .types
+            .iter()
+            .flat_map(|t| get_signatures(checker, ctx, *t, newable))
+            .collect(),
+        TypeKind::Union(union) => match filter_nullables(&checker.arena, &union.types)[..] {
+            [t] => get_signatures(checker, ctx, t, newable),
+            _ => vec![],
+        },
+        TypeKind::Object(object) => object
+            .elems
+            .iter()
+            .filter_map(|elem| match elem {
+                TObjElem::Call(func) if !newable => Some(func.to_owned()),
+                TObjElem::Constructor(func) if newable => Some(func.to_owned()),
+                _ => None,
+            })
+            .collect(),
+        TypeKind::TypeVar(_) => vec![],
+        _ => match checker.expand_type(ctx, t) {
+            Ok(expanded) if expanded != t => get_signatures(checker, ctx, expanded, newable),
+            _ => vec![],
+        },
+    }
+}
+
+fn get_method_overloads(
+    checker: &mut Checker,
+    ctx: &Context,
+    t: Index,
+    name: &str,
+) -> Vec<types::Function> {
+    let t = checker.prune(t);
+
+    // It's okay to clone here because we aren't mutating the type
+    match &checker.arena[t].kind.clone() {
+        TypeKind::Object(object) => {
+            let mut funcs = vec![];
+            for elem in &object.elems {
+                match elem {
+                    TObjElem::Method(method) if prop_key_eq(&method.name, name) => {
+                        funcs.push(method.function.to_owned());
+                    }
+                    TObjElem::Prop(prop) if prop_key_eq(&prop.name, name) => {
+                        funcs.extend(get_signatures(checker, ctx, prop.t, false));
+                    }
+                    _ => (),
+                }
+            }
+            funcs
+        }
+        TypeKind::Intersection(intersection) => intersection
+            .types
+            .iter()
+            .flat_map(|t| get_method_overloads(checker, ctx, *t, name))
+            .collect(),
+        TypeKind::Union(union) => match filter_nullables(&checker.arena, &union.types)[..] {
+            [t] => get_method_overloads(checker, ctx, t, name),
+            _ => vec![],
+        },
+        _ => match expand_receiver(checker, ctx, t) {
+            Some(t) => get_method_overloads(checker, ctx, t, name),
+            None => vec![],
+        },
+    }
+}
+
+fn prop_key_eq(key: &TPropKey, name: &str) -> bool {
+    match key {
+        TPropKey::StringKey(key) => key == name,
+        TPropKey::NumberKey(key) => key == name,
+    }
+}
+
+/// Returns the position of the `(` for the call containing the end of `src`
+/// along with the index of the argument being typed.
+fn find_open_paren(src: &str) -> Option<(usize, u32)> {
+    let mut depth = 0;
+    let mut active_param = 0;
+    let mut in_str = false;
+
+    for (i, c) in src.bytes().enumerate().rev() {
+        if c == b'"' {
+            in_str = !in_str;
+        }
+        if in_str {
+            continue;
+        }
+        match c {
+            b')' | b']' | b'}' => depth += 1,
+            b'(' if depth == 0 => return Some((i, active_param)),
+            b'[' | b'{' if depth == 0 => {
+                // We're inside of an array or object that's being passed as
+                // an arg so any commas we've seen so far don't count.
+                active_param = 0;
+            }
+            b'(' | b'[' | b'{' => depth -= 1,
+            b',' if depth == 0 => active_param += 1,
+            _ => (),
+        }
+    }
+
+    None
+}
+
+fn get_unclosed_brackets(src: &str) -> Vec<u8> {
+    let mut stack = vec![];
+    let mut in_str = false;
+
+    for c in src.bytes() {
+        if c == b'"' {
+            in_str = !in_str;
+        }
+        if in_str {
+            continue;
+        }
+        match c {
+            b'(' | b'[' | b'{' => stack.push(c),
+            b')' | b']' | b'}' => {
+                stack.pop();
+            }
+            _ => (),
+        }
+    }
+
+    stack
+}
+
+struct CallEndingAtVisitor {
+    end: usize,
+    call: Option<(Expr, bool)>,
+}
+
+impl Visitor for CallEndingAtVisitor {
+    fn visit_expr(&mut self, expr: &Expr) {
+        if expr.span.end == self.end {
+            match &expr.kind {
+                ExprKind::Call(Call { callee, .. }) => {
+                    self.call = Some((callee.as_ref().to_owned(), false));
+                }
+                ExprKind::New(New { callee, .. }) => {
+                    self.call = Some((callee.as_ref().to_owned(), true));
+                }
+                _ => (),
+            }
+        }
+
+        walk_expr(self, expr);
+    }
+}
@@ -0,0 +1,333 @@
+use std::fs;
+use std::path::Path;
+use swc_common::source_map::{BytePos, FileName, SourceFile};
+
+use lsp_types::Range;
+use lsp_types::*;
+
+use escalier_ast::*;
+use escalier_parser::parse;
+
+use crate::util::get_range;
+
+/// Returns a symbol for each top-level declaration in `script`.  Classes
+/// include their members as children.
+pub fn get_document_symbols(file: &SourceFile, script: &Script) -> Vec<DocumentSymbol> {
+    let mut symbols = vec![];
+
+    for stmt in &script.stmts {
+        let decl = match &stmt.kind {
+            StmtKind::Decl(decl) => decl,
+            _ => continue,
+        };
+
+        match &decl.kind {
+            DeclKind::VarDecl(VarDecl { pattern, expr, .. }) => {
+                let mut bindings = vec![];
+                get_binding_idents(pattern, &mut bindings);
+
+                for binding in bindings {
+                    let (kind, children) = match (&pattern.kind, expr) {
+                        (PatternKind::Ident(_), Some(expr)) => match &expr.kind {
+                            ExprKind::Class(class) => {
+                                (SymbolKind::CLASS, Some(get_class_symbols(file, class)))
+                            }
+                            ExprKind::Function(_) => (SymbolKind::FUNCTION, None),
+                            _ => (SymbolKind::VARIABLE, None),
+                        },
+                        _ => (SymbolKind::VARIABLE, None),
+                    };
+
+                    symbols.push(new_symbol(
+                        &binding.name,
+                        kind,
+                        get_range(file, &decl.span),
+                        get_range(file, &binding.span),
+                        children,
+                    ));
+                }
+            }
+            DeclKind::TypeDecl(TypeDecl { name, type_ann, .. }) => {
+                // There's no `SymbolKind` for type aliases so we use the same
+                // kind that rust-analyzer uses for them.
+                let kind = match &type_ann.kind {
+                    TypeAnnKind::Object(_) => SymbolKind::INTERFACE,
+                    _ => SymbolKind::TYPE_PARAMETER,
+                };
+                let range = get_range(file, &decl.span);
+
+                symbols.push(new_symbol(name, kind, range, range, None));
+            }
+            DeclKind::EnumDecl(EnumDecl { name, variants, .. }) => {
+                let children = variants
+                    .iter()
+                    .map(|variant| {
+                        let range = get_range(file, &variant.span);
+                        new_symbol(&variant.name, SymbolKind::ENUM_MEMBER, range, range, None)
+                    })
+                    .collect();
+                let range = get_range(file, &decl.span);
+
+                symbols.push(new_symbol(
+                    name,
+                    SymbolKind::ENUM,
+                    range,
+                    range,
+                    Some(children),
+                ));
+            }
+            DeclKind::StructDecl(StructDecl { name, fields, .. }) => {
+                let children = fields
+                    .iter()
+                    .map(|field| {
+                        let range = get_range(file, &field.span);
+                        new_symbol(&field.name, SymbolKind::FIELD, range, range, None)
+                    })
+                    .collect();
+                let range = get_range(file, &decl.span);
+
+                symbols.push(new_symbol(
+                    name,
+                    SymbolKind::STRUCT,
+                    range,
+                    range,
+                    Some(children),
+                ));
+            }
+            DeclKind::TraitDecl(TraitDecl { name, methods }) => {
+                let children = methods
+                    .iter()
+                    .map(|method| {
+                        new_symbol(
+                            &method.name.name,
+                            SymbolKind::METHOD,
+                            get_range(file, &method.span),
+                            get_range(file, &method.name.span),
+                            None,
+                        )
+                    })
+                    .collect();
+                let range = get_range(file, &decl.span);
+
+                symbols.push(new_symbol(
+                    name,
+                    SymbolKind::INTERFACE,
+                    range,
+                    range,
+                    Some(children),
+                ));
+            }
+            DeclKind::ImplDecl(ImplDecl {
+                trait_name,
+                type_name,
+                methods,
+            }) => {
+                let children = methods
+                    .iter()
+                    .map(|method| {
+                        let (name, name_span) = get_prop_name(file, &method.name);
+                        new_symbol(
+                            &name,
+                            SymbolKind::METHOD,
+                            get_range(file, &method.span),
+                            get_range(file, &name_span),
+                            None,
+                        )
+                    })
+                    .collect();
+                let range = get_range(file, &decl.span);
+
+                symbols.push(new_symbol(
+                    &format!("impl {} for {}", trait_name.name, type_name.name),
+                    SymbolKind::OBJECT,
+                    range,
+                    range,
+                    Some(children),
+                ));
+            }
+        }
+    }
+
+    symbols
+}
+
+fn get_class_symbols(file: &SourceFile, class: &Class) -> Vec<DocumentSymbol> {
+    let mut symbols = vec![];
+
+    for member in &class.body {
+        let (name, kind, span, name_span) = match member {
+            ClassMember::Method(Method { name, span, .. }) => {
+                let (name, name_span) = get_prop_name(file, name);
+                let kind = match name.as_str() {
+                    "constructor" => SymbolKind::CONSTRUCTOR,
+                    _ => SymbolKind::METHOD,
+                };
+                (name, kind, span, name_span)
+            }
+            ClassMember::Getter(Getter { name, span, .. })
+            | ClassMember::Setter(Setter { name, span, .. }) => {
+                let (name, name_span) = get_prop_name(file, name);
+                (name, SymbolKind::PROPERTY, span, name_span)
+            }
+            ClassMember::Field(Field { name, span, .. }) => {
+                (name.name.to_owned(), SymbolKind::FIELD, span, name.span)
+            }
+        };
+
+        let mut symbol = new_symbol(
+            &name,
+            kind,
+            get_range(file, span),
+            get_range(file, &name_span),
+            None,
+        );
+        symbol.detail = match member {
+            ClassMember::Getter(_) => Some(String::from("get")),
+            ClassMember::Setter(_) => Some(String::from("set")),
+            _ => None,
+        };
+        symbols.push(symbol);
+    }
+
+    symbols
+}
+
+fn get_prop_name(file: &SourceFile, name: &PropName) -> (String, Span) {
+    match name {
+        PropName::Ident(Ident { name, span }) => (name.to_owned(), *span),
+        PropName::Computed(expr) => {
+            let text = &file.src[expr.span.start..expr.span.end];
+            (format!("[{text}]"), expr.span)
+        }
+    }
+}
+
+fn get_binding_idents<'a>(pattern: &'a Pattern, bindings: &mut Vec<&'a BindingIdent>) {
+    match &pattern.kind {
+        PatternKind::Ident(binding) => bindings.push(binding),
+        PatternKind::Rest(RestPat { arg }) => get_binding_idents(arg, bindings),
+        PatternKind::Object(ObjectPat { props, .. })
+        | PatternKind::Struct(StructPat { props, .. }) => {
+            for prop in props {
+                match prop {
+                    ObjectPatProp::KeyValue(KeyValuePatProp { value, .. }) => {
+                        get_binding_idents(value, bindings)
+                    }
+                    ObjectPatProp::Shorthand(ShorthandPatProp { ident, .. }) => {
+                        bindings.push(ident)
+                    }
+                    ObjectPatProp::Rest(RestPat { arg }) => get_binding_idents(arg, bindings),
+                }
+            }
+        }
+        PatternKind::Tuple(TuplePat { elems, .. }) => {
+            for elem in elems.iter().flatten() {
+                get_binding_idents(&elem.pattern, bindings);
+            }
+        }
+        PatternKind::Is(IsPat { ident, .. }) => bindings.push(ident),
+        PatternKind::Enum(EnumPat { args, .. }) => {
+            for arg in args {
+                get_binding_idents(arg, bindings);
+            }
+        }
+        PatternKind::Lit(_) | PatternKind::Wildcard => (),
+    }
+}
+
+#[allow(deprecated)] // `deprecated` has been replaced by `tags`
+fn new_symbol(
+    name: &str,
+    kind: SymbolKind,
+    range: Range,
+    selection_range: Range,
+    children: Option<Vec<DocumentSymbol>>,
+) -> DocumentSymbol {
+    DocumentSymbol {
+        name: name.to_owned(),
+        detail: None,
+        kind,
+        tags: None,
+        deprecated: None,
+        range,
+        selection_range,
+        children,
+    }
+}
+
+/// Returns the symbols from all `files` whose names contain `query`, ignoring
+/// case.
+pub fn get_workspace_symbols(files: &[(Url, &SourceFile)], query: &str) -> Vec<SymbolInformation> {
+    let query = query.to_lowercase();
+    let mut result = vec![];
+
+    for (uri, file) in files {
+        let script = match parse(&file.src) {
+            Ok(script) => script,
+            Err(_) => continue,
+        };
+
+        let symbols = get_document_symbols(file, &script);
+        flatten_symbols(uri, &symbols, None, &query, &mut result);
+    }
+
+    result
+}
+
+#[allow(deprecated)] // `deprecated` has been replaced by `tags`
+fn flatten_symbols(
+    uri: &Url,
+    symbols: &[DocumentSymbol],
+    container_name: Option<&str>,
+    query: &str,
+    result: &mut Vec<SymbolInformation>,
+) {
+    for symbol in symbols {
+        if symbol.name.to_lowercase().contains(query) {
+            result.push(SymbolInformation {
+                name: symbol.name.to_owned(),
+                kind: symbol.kind,
+                tags: None,
+                deprecated: None,
+                location: Location {
+                    uri: uri.to_owned(),
+                    range: symbol.selection_range,
+                },
+                container_name: container_name.map(|name| name.to_owned()),
+            });
+        }
+        if let Some(children) = &symbol.children {
+            flatten_symbols(uri, children, Some(&symbol.name), query, result);
+        }
+    }
+}
+
+/// Recursively finds all of the .esc files in `dir`, skipping hidden
+/// directories and `node_modules`.
+pub fn find_source_files(dir: &Path) -> Vec<(Url, SourceFile)> {
+    let mut files = vec![];
+
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return files,
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        if path.is_dir() {
+            if !name.starts_with('.') && name != "node_modules" && name != "target" {
+                files.extend(find_source_files(&path));
+            }
+        } else if path.extension().map_or(false, |ext| ext == "esc") {
+            let (uri, src) = match (Url::from_file_path(&path), fs::read_to_string(&path)) {
+                (Ok(uri), Ok(src)) => (uri, src),
+                _ => continue,
+            };
+            let file = SourceFile::new(FileName::Anon, false, FileName::Anon, src, BytePos(1));
+            files.push((uri, file));
+        }
+    }
+
+    files
+}
@@ -1,6 +1,9 @@
 use swc_common::source_map::{BytePos, SourceFile};
 
-use lsp_types::Position;
+use lsp_types::{Position, Range};
+
+use escalier_ast::Span;
+use escalier_hm::docs::JsDoc;
 
 #[derive(Clone)]
 pub struct SourceLocation {
@@ -26,3 +29,54 @@ pub fn get_byte_pos(file: &SourceFile, pos: &Position) -> Option<BytePos> {
 
     Some(byte_pos)
 }
+
+/// Converts an offset from the start of the source into a zero-based LSP
+/// `Position`.
+pub fn get_position(file: &SourceFile, offset: usize) -> Position {
+    let byte_pos = file.start_pos + BytePos(offset as u32);
+    let line = file.lookup_line(byte_pos).unwrap_or(0);
+    let column = byte_pos - file.line_begin_pos(byte_pos);
+
+    Position {
+        line: line as u32,
+        character: column.0,
+    }
+}
+
+pub fn get_range(file: &SourceFile, span: &Span) -> Range {
+    Range {
+        start: get_position(file, span.start),
+        end: get_position(file, span.end),
+    }
+}
+
+/// Converts a zero-based LSP `Position` into an offset from the start of the
+/// source.
+pub fn get_offset(file: &SourceFile, pos: &Position) -> usize {
+    let line = (pos.line as usize).min(file.count_lines().saturating_sub(1));
+    let (start, end) = file.line_bounds(line);
+    let offset = (start - file.start_pos).0 + pos.character;
+
+    (offset as usize).min((end - file.start_pos).0 as usize)
+}
+
+/// Formats `docs` as markdown for hovers and completions.
+pub fn format_docs(docs: &JsDoc) -> String {
+    let mut sections = vec![];
+    if !docs.text.is_empty() {
+        sections.push(docs.text.to_owned());
+    }
+    if let Some(reason) = &docs.deprecated {
+        match reason.is_empty() {
+            true => sections.push("*@deprecated*".to_string()),
+            false => sections.push(format!("*@deprecated* {reason}")),
+        }
+    }
+    for (name, description) in &docs.params {
+        sections.push(format!("*@param* `{name}` \u{2014} {description}"));
+    }
+    if let Some(returns) = &docs.returns {
+        sections.push(format!("*@returns* {returns}"));
+    }
+    sections.join("\n\n")
+}
@@ -34,7 +34,23 @@ impl<'a> Parser<'a> {
         let mut body = vec![];
 
         while self.peek().unwrap_or(&EOF).kind != TokenKind::RightBrace {
-            let member = self.parse_class_member()?;
+            let leading = self.parse_leading_comments(IdentMode::Default);
+            if self.peek().unwrap_or(&EOF).kind == TokenKind::RightBrace {
+                self.comments.extend(leading);
+                break;
+            }
+
+            let mut member = self.parse_class_member()?;
+            let trailing = self.parse_trailing_comments(IdentMode::Default);
+
+            let comments = match &mut member {
+                ClassMember::Method(Method { comments, .. })
+                | ClassMember::Getter(Getter { comments, .. })
+                | ClassMember::Setter(Setter { comments, .. })
+                | ClassMember::Field(Field { comments, .. }) => comments,
+            };
+            *comments = Comments { leading, trailing };
+
             body.push(member);
         }
 
@@ -120,7 +136,7 @@ impl<'a> Parser<'a> {
             TokenKind::Colon => {
                 self.next(); // consumes ':'
                 let type_ann = self.parse_type_ann()?;
-                let end = self.scanner.cursor();
+                let end = type_ann.span.end;
 
                 let span = Span { start, end };
 
@@ -131,12 +147,13 @@ impl<'a> Parser<'a> {
                     is_static,
                     init: None,
                     type_ann: Some(type_ann),
+                    comments: Comments::default(),
                 })
             }
             TokenKind::Assign => {
                 self.next(); // consumes '='
                 let init = self.parse_expr()?;
-                let end = self.scanner.cursor();
+                let end = init.span.end;
 
                 let span = Span { start, end };
 
@@ -147,6 +164,7 @@ impl<'a> Parser<'a> {
                     is_static,
                     init: Some(Box::new(init)),
                     type_ann: None,
+                    comments: Comments::default(),
                 })
             }
             _ => panic!("expected ':' or '='"),
@@ -175,6 +193,7 @@ impl<'a> Parser<'a> {
             type_ann: None,
             params,
             body,
+            comments: Comments::default(),
         });
 
         Ok(getter)
@@ -200,12 +219,13 @@ impl<'a> Parser<'a> {
             type_ann: None,
             params,
             body,
+            comments: Comments::default(),
         });
 
         Ok(setter)
     }
 
-    fn parse_method(
+    pub(crate) fn parse_method(
         &mut self,
         is_public: bool,
         is_static: bool,
@@ -269,6 +289,7 @@ impl<'a> Parser<'a> {
                 type_ann,
                 throws,
             },
+            comments: Comments::default(),
         });
 
         Ok(method)
@@ -295,7 +316,7 @@ impl<'a> Parser<'a> {
                     self.next().unwrap_or(EOF.clone()).kind,
                     TokenKind::RightBracket
                 );
-                PropName::Computed(expr)
+                PropName::Computed(Box::new(expr))
             }
             _ => panic!("expected identifier or computed property name"),
         };
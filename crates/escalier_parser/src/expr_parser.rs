@@ -32,6 +32,9 @@ fn get_infix_op_info(op: &Token) -> Option<OpInfo> {
         TokenKind::Plus => PRECEDENCE_TABLE.get(&Operator::Addition).cloned(),
         TokenKind::Minus => PRECEDENCE_TABLE.get(&Operator::Subtraction).cloned(),
 
+        // range
+        TokenKind::DotDot => PRECEDENCE_TABLE.get(&Operator::Range).cloned(),
+
         // equality
         TokenKind::Equals => PRECEDENCE_TABLE.get(&Operator::Equals).cloned(),
         TokenKind::NotEquals => PRECEDENCE_TABLE.get(&Operator::NotEquals).cloned(),
@@ -46,6 +49,9 @@ fn get_infix_op_info(op: &Token) -> Option<OpInfo> {
         TokenKind::And => PRECEDENCE_TABLE.get(&Operator::LogicalAnd).cloned(),
         TokenKind::Or => PRECEDENCE_TABLE.get(&Operator::LogicalOr).cloned(),
 
+        // pipeline
+        TokenKind::PipeArrow => PRECEDENCE_TABLE.get(&Operator::Pipeline).cloned(),
+
         // assignment
         TokenKind::Assign => PRECEDENCE_TABLE.get(&Operator::Assignment).cloned(),
         TokenKind::PlusAssign => PRECEDENCE_TABLE.get(&Operator::Assignment).cloned(),
@@ -73,20 +79,102 @@ fn get_postfix_op_info(op: &Token) -> Option<OpInfo> {
     }
 }
 
+struct TopicVisitor {
+    found: bool,
+}
+
+impl Visitor for TopicVisitor {
+    fn visit_expr(&mut self, expr: &Expr) {
+        match &expr.kind {
+            ExprKind::Ident(Ident { name, .. }) if name == "_" => self.found = true,
+            // The right side of a nested pipeline has its own `_`.
+            ExprKind::Pipeline(Pipeline { left, .. }) => self.visit_expr(left),
+            _ => walk_expr(self, expr),
+        }
+    }
+}
+
+fn uses_topic(expr: &Expr) -> bool {
+    let mut visitor = TopicVisitor { found: false };
+    visitor.visit_expr(expr);
+    visitor.found
+}
+
 impl<'a> Parser<'a> {
     // consumes leading '{' and trailing '}' tokens
+    fn parse_prop_or_spread(&mut self) -> Result<PropOrSpread, ParseError> {
+        let next = self
+            .next_with_mode(IdentMode::PropName)
+            .unwrap_or(EOF.clone());
+
+        match &next.kind {
+            TokenKind::DotDotDot => {
+                let expr = self.parse_expr()?;
+                Ok(PropOrSpread::Spread(Box::new(expr)))
+            }
+            TokenKind::Identifier(id)
+                if matches!(
+                    self.peek().unwrap_or(&EOF).kind,
+                    TokenKind::Comma
+                        | TokenKind::RightBrace
+                        | TokenKind::Comment(_)
+                        | TokenKind::BlockComment(_)
+                ) =>
+            {
+                Ok(PropOrSpread::Prop(expr::Prop::Shorthand {
+                    ident: Ident {
+                        span: next.span,
+                        name: id.to_owned(),
+                    },
+                    comments: Comments::default(),
+                }))
+            }
+            _ => {
+                let key = match &next.kind {
+                    TokenKind::Identifier(id) => ObjectKey::Ident(Ident {
+                        span: next.span,
+                        name: id.to_owned(),
+                    }),
+                    TokenKind::StrLit(s) => ObjectKey::String(s.to_owned()),
+                    TokenKind::NumLit(n) => ObjectKey::Number(n.to_owned()),
+                    TokenKind::LeftBracket => {
+                        let expr = self.parse_expr()?;
+                        assert_eq!(
+                            self.next().unwrap_or(EOF.clone()).kind,
+                            TokenKind::RightBracket
+                        );
+                        ObjectKey::Computed(Box::new(expr))
+                    }
+                    _ => {
+                        panic!("Expected identifier or string literal, got {:?}", next)
+                    }
+                };
+
+                assert_eq!(self.next().unwrap_or(EOF.clone()).kind, TokenKind::Colon);
+
+                let value = self.parse_expr()?;
+
+                Ok(PropOrSpread::Prop(expr::Prop::Property {
+                    key,
+                    value: Box::new(value),
+                    comments: Comments::default(),
+                }))
+            }
+        }
+    }
+
     pub fn parse_block(&mut self) -> Result<Block, ParseError> {
         let open = self.next().unwrap_or(EOF.clone());
         assert_eq!(open.kind, TokenKind::LeftBrace);
         let mut stmts = Vec::new();
         while self.peek().unwrap_or(&EOF).kind != TokenKind::RightBrace {
-            // TODO: attach comments to AST nodes
-            if let TokenKind::Comment(_) = &self.peek().unwrap_or(&EOF).kind {
-                self.next(); // consumes the comment
-                continue;
+            let leading = self.parse_leading_comments(IdentMode::Default);
+            if self.peek().unwrap_or(&EOF).kind == TokenKind::RightBrace {
+                self.comments.extend(leading);
+                break;
             }
 
-            stmts.push(self.parse_stmt()?);
+            stmts.push(self.parse_stmt_with_comments(leading)?);
 
             // The last statement in a block is allowed to omit the trailing
             // semicolon.
@@ -115,17 +203,47 @@ impl<'a> Parser<'a> {
                     inferred_type: None,
                 }
             }
-            TokenKind::Identifier(id) => {
-                self.next(); // consume identifier
+            // The topic reference within the right side of a pipeline.
+            TokenKind::Underscore => {
+                self.next(); // consume '_'
                 Expr {
                     kind: ExprKind::Ident(Ident {
-                        name: id.to_owned(),
+                        name: "_".to_string(),
                         span: token.span,
                     }),
                     span: token.span,
                     inferred_type: None,
                 }
             }
+            TokenKind::Identifier(id) => {
+                self.next(); // consume identifier
+                let ident = Ident {
+                    name: id.to_owned(),
+                    span: token.span,
+                };
+                match self.peek().unwrap_or(&EOF).kind {
+                    // User {name: "John", ssn: 123456789}
+                    TokenKind::LeftBrace => {
+                        let obj = self.parse_atom()?;
+                        match obj.kind {
+                            ExprKind::Object(Object { properties }) => Expr {
+                                kind: ExprKind::Struct(Struct {
+                                    name: ident,
+                                    properties,
+                                }),
+                                span: merge_spans(&token.span, &obj.span),
+                                inferred_type: None,
+                            },
+                            _ => unreachable!("expected object literal"),
+                        }
+                    }
+                    _ => Expr {
+                        kind: ExprKind::Ident(ident),
+                        span: token.span,
+                        inferred_type: None,
+                    },
+                }
+            }
             TokenKind::BoolLit(b) => {
                 self.next(); // consume boolean
                 Expr {
@@ -167,6 +285,25 @@ impl<'a> Parser<'a> {
                     inferred_type: None,
                 }
             }
+            TokenKind::Divide | TokenKind::DivideAssign => {
+                self.next(); // consume '/' or '/='
+                let pattern = match token.kind {
+                    TokenKind::DivideAssign => "=".to_string(),
+                    _ => String::new(),
+                };
+                let regex = self.lex_regex(token.span.start, pattern)?;
+                match regex.kind {
+                    TokenKind::RegexLit { pattern, flags } => Expr {
+                        kind: ExprKind::Regex(Regex {
+                            pattern,
+                            flags: if flags.is_empty() { None } else { Some(flags) },
+                        }),
+                        span: regex.span,
+                        inferred_type: None,
+                    },
+                    _ => unreachable!(),
+                }
+            }
             TokenKind::Null => {
                 self.next(); // consume 'null'
                 Expr {
@@ -219,60 +356,63 @@ impl<'a> Parser<'a> {
                 self.next(); // consumes '{'
                 let start = token;
 
-                let properties = self.parse_many(
-                    |p| {
-                        // TODO: we need `parse_many` to use the same `mode`
-                        let next = p.next_with_mode(IdentMode::PropName).unwrap_or(EOF.clone());
-
-                        match &next.kind {
-                            TokenKind::DotDotDot => {
-                                let expr = p.parse_expr()?;
-                                Ok(PropOrSpread::Spread(expr))
-                            }
-                            TokenKind::Identifier(id)
-                                if p.peek().unwrap_or(&EOF).kind == TokenKind::Comma
-                                    || p.peek().unwrap_or(&EOF).kind == TokenKind::RightBrace =>
-                            {
-                                Ok(PropOrSpread::Prop(expr::Prop::Shorthand(Ident {
-                                    span: next.span,
-                                    name: id.to_owned(),
-                                })))
-                            }
-                            _ => {
-                                let key = match &next.kind {
-                                    TokenKind::Identifier(id) => ObjectKey::Ident(Ident {
-                                        span: next.span,
-                                        name: id.to_owned(),
-                                    }),
-                                    TokenKind::StrLit(s) => ObjectKey::String(s.to_owned()),
-                                    TokenKind::NumLit(n) => ObjectKey::Number(n.to_owned()),
-                                    TokenKind::LeftBracket => {
-                                        let expr = p.parse_expr()?;
-                                        assert_eq!(
-                                            p.next().unwrap_or(EOF.clone()).kind,
-                                            TokenKind::RightBracket
-                                        );
-                                        ObjectKey::Computed(Box::new(expr))
-                                    }
-                                    _ => {
-                                        panic!(
-                                            "Expected identifier or string literal, got {:?}",
-                                            next
-                                        )
-                                    }
-                                };
+                let mut properties = vec![];
+                loop {
+                    let mut leading = self.parse_leading_comments(IdentMode::PropName);
 
-                                assert_eq!(p.next().unwrap_or(EOF.clone()).kind, TokenKind::Colon);
+                    let next = self.peek_with_mode(IdentMode::PropName).unwrap_or(&EOF);
+                    match next.kind {
+                        TokenKind::RightBrace => {
+                            self.comments.append(&mut leading);
+                            break;
+                        }
+                        // Comments aren't attached to spreads.
+                        TokenKind::DotDotDot => self.comments.append(&mut leading),
+                        _ => (),
+                    }
 
-                                let value = p.parse_expr()?;
+                    let mut prop = self.parse_prop_or_spread()?;
 
-                                Ok(PropOrSpread::Prop(expr::Prop::Property { key, value }))
+                    // Trailing comments can appear before or after the comma.
+                    let mut trailing = self.parse_trailing_comments(IdentMode::PropName);
+                    let next = self.peek_with_mode(IdentMode::PropName).unwrap_or(&EOF);
+                    let done = match next.kind {
+                        TokenKind::Comma => {
+                            self.next_with_mode(IdentMode::PropName);
+                            trailing.extend(self.parse_trailing_comments(IdentMode::PropName));
+                            false
+                        }
+                        _ => {
+                            // Comments after the last prop without a trailing comma.
+                            let mut unattached = self.parse_leading_comments(IdentMode::PropName);
+                            self.comments.append(&mut unattached);
+                            let next = self.peek_with_mode(IdentMode::PropName).unwrap_or(&EOF);
+                            if next.kind != TokenKind::RightBrace {
+                                return Err(ParseError {
+                                    message: format!(
+                                        "Expected Comma or RightBrace, got {:?}",
+                                        next
+                                    ),
+                                });
                             }
+                            true
                         }
-                    },
-                    TokenKind::Comma,
-                    TokenKind::RightBrace,
-                )?;
+                    };
+
+                    match &mut prop {
+                        PropOrSpread::Prop(
+                            expr::Prop::Shorthand { comments, .. }
+                            | expr::Prop::Property { comments, .. },
+                        ) => *comments = Comments { leading, trailing },
+                        PropOrSpread::Spread(_) => self.comments.extend(trailing),
+                    }
+
+                    properties.push(prop);
+
+                    if done {
+                        break;
+                    }
+                }
 
                 let end = self.next().unwrap_or(EOF.clone());
 
@@ -411,6 +551,7 @@ impl<'a> Parser<'a> {
                     }
                 }
             }
+            TokenKind::Loop => self.parse_loop(None)?,
             TokenKind::Do => {
                 self.next(); // consumes 'do'
                 let body = self.parse_block()?;
@@ -698,7 +839,7 @@ impl<'a> Parser<'a> {
 
             if let Some(next_op_info) = get_postfix_op_info(&next) {
                 if precedence < next_op_info.normalized_prec() {
-                    if let Some(result) = self.parse_postfix(lhs.clone(), next_op_info, false)? {
+                    if let Some(result) = self.parse_postfix(lhs.clone(), false)? {
                         lhs = result;
                         continue;
                     }
@@ -757,6 +898,39 @@ impl<'a> Parser<'a> {
             });
         }
 
+        if token.kind == TokenKind::DotDot {
+            let rhs = self.parse_expr_with_precedence(precedence)?;
+            let span = merge_spans(&lhs.get_span(), &rhs.get_span());
+
+            return Ok(Expr {
+                kind: ExprKind::Range(Range {
+                    start: Box::new(lhs),
+                    end: Box::new(rhs),
+                }),
+                span,
+                inferred_type: None,
+            });
+        }
+
+        if token.kind == TokenKind::PipeArrow {
+            let rhs = self.parse_expr_with_precedence(precedence)?;
+            if !uses_topic(&rhs) {
+                return Err(ParseError {
+                    message: "expected `_` in the right side of `|>`".to_string(),
+                });
+            }
+            let span = merge_spans(&lhs.get_span(), &rhs.get_span());
+
+            return Ok(Expr {
+                kind: ExprKind::Pipeline(Pipeline {
+                    left: Box::new(lhs),
+                    right: Box::new(rhs),
+                }),
+                span,
+                inferred_type: None,
+            });
+        }
+
         let op: BinaryOp = match &token.kind {
             TokenKind::Plus => BinaryOp::Plus,
             TokenKind::Minus => BinaryOp::Minus,
@@ -791,25 +965,48 @@ impl<'a> Parser<'a> {
     // If we attempt to parse explicit type args for a function call and fail,
     // we return None and restore the parser state to what it was before the
     // attempt.
-    fn parse_postfix(
-        &mut self,
-        lhs: Expr,
-        next_op_info: OpInfo,
-        opt_chain: bool,
-    ) -> Result<Option<Expr>, ParseError> {
-        let precedence = next_op_info.infix_postfix_prec();
-
+    fn parse_postfix(&mut self, lhs: Expr, opt_chain: bool) -> Result<Option<Expr>, ParseError> {
         let token = self.peek().unwrap_or(&EOF).clone();
 
         let expr = match &token.kind {
             TokenKind::LeftBracket => {
                 self.next(); // consumes '['
-                let rhs = self.parse_expr()?;
-                let span = merge_spans(&lhs.get_span(), &rhs.get_span());
-                assert_eq!(
-                    self.next().unwrap_or(EOF.clone()).kind,
-                    TokenKind::RightBracket
-                );
+
+                // Slices, e.g. `xs[1:3]`, `xs[:3]`, `xs[1:]`, and `xs[:]`.
+                let start = match self.peek().unwrap_or(&EOF).kind {
+                    TokenKind::Colon => None,
+                    _ => Some(self.parse_expr()?),
+                };
+                if self.peek().unwrap_or(&EOF).kind == TokenKind::Colon {
+                    self.next(); // consumes ':'
+                    let end = match self.peek().unwrap_or(&EOF).kind {
+                        TokenKind::RightBracket => None,
+                        _ => Some(Box::new(self.parse_expr()?)),
+                    };
+                    let close = self.next().unwrap_or(EOF.clone());
+                    if close.kind != TokenKind::RightBracket {
+                        return Err(ParseError {
+                            message: "expected ']' after slice".to_string(),
+                        });
+                    }
+                    let span = merge_spans(&lhs.get_span(), &close.span);
+                    return Ok(Some(Expr {
+                        kind: ExprKind::Slice(Slice {
+                            object: Box::new(lhs),
+                            start: start.map(Box::new),
+                            end,
+                            opt_chain,
+                        }),
+                        span,
+                        inferred_type: None,
+                    }));
+                }
+
+                // `start` is only `None` when it's followed by a ':'.
+                let rhs = start.unwrap();
+                let close = self.next().unwrap_or(EOF.clone());
+                assert_eq!(close.kind, TokenKind::RightBracket);
+                let span = merge_spans(&lhs.get_span(), &close.span);
                 Expr {
                     kind: ExprKind::Member(Member {
                         object: Box::new(lhs),
@@ -899,14 +1096,20 @@ impl<'a> Parser<'a> {
             }
             TokenKind::Dot => {
                 self.next(); // consumes '.'
-                let rhs = self.parse_expr_with_precedence(precedence)?;
-                match &rhs.kind {
-                    ExprKind::Ident(ident) => {
-                        let span = merge_spans(&lhs.get_span(), &rhs.get_span());
+                             // Keywords are valid property names, e.g. `str.match(regex)`
+                let next = self
+                    .next_with_mode(IdentMode::PropName)
+                    .unwrap_or(EOF.clone());
+                match next.kind {
+                    TokenKind::Identifier(name) => {
+                        let span = merge_spans(&lhs.get_span(), &next.span);
                         Expr {
                             kind: ExprKind::Member(Member {
                                 object: Box::new(lhs),
-                                property: MemberProp::Ident(ident.to_owned()),
+                                property: MemberProp::Ident(Ident {
+                                    name,
+                                    span: next.span,
+                                }),
                                 opt_chain: false,
                             }),
                             span,
@@ -923,19 +1126,28 @@ impl<'a> Parser<'a> {
             TokenKind::QuestionDot => {
                 self.next(); // consumes '?.'
 
-                let result = match self.peek().unwrap_or(&EOF).kind {
+                let result = match self
+                    .peek_with_mode(IdentMode::PropName)
+                    .unwrap_or(&EOF)
+                    .kind
+                {
                     TokenKind::LeftParen | TokenKind::LeftBracket => {
-                        self.parse_postfix(lhs, next_op_info, true)?
+                        self.parse_postfix(lhs, true)?
                     }
                     _ => {
-                        let rhs = self.parse_expr_with_precedence(precedence)?;
-                        match &rhs.kind {
-                            ExprKind::Ident(ident) => {
-                                let span = merge_spans(&lhs.get_span(), &rhs.get_span());
+                        let next = self
+                            .next_with_mode(IdentMode::PropName)
+                            .unwrap_or(EOF.clone());
+                        match next.kind {
+                            TokenKind::Identifier(name) => {
+                                let span = merge_spans(&lhs.get_span(), &next.span);
                                 let expr = Expr {
                                     kind: ExprKind::Member(Member {
                                         object: Box::new(lhs),
-                                        property: MemberProp::Ident(ident.to_owned()),
+                                        property: MemberProp::Ident(Ident {
+                                            name,
+                                            span: next.span,
+                                        }),
                                         opt_chain: true,
                                     }),
                                     span,
@@ -1097,6 +1309,12 @@ mod tests {
         insta::assert_debug_snapshot!(parse("{ a, b }"));
     }
 
+    #[test]
+    fn parse_struct_literals() {
+        insta::assert_debug_snapshot!(parse(r#"User { name: "John", ssn: 123456789 }"#));
+        insta::assert_debug_snapshot!(parse("Point { x, y }"));
+    }
+
     #[test]
     #[should_panic]
     fn parse_object_literals_missing_colon() {
@@ -1156,6 +1374,52 @@ mod tests {
         insta::assert_debug_snapshot!(parse("a[1][c]"));
     }
 
+    #[test]
+    fn parse_ranges() {
+        insta::assert_debug_snapshot!(parse("0..10"));
+        insta::assert_debug_snapshot!(parse("a..b - 1"));
+    }
+
+    #[test]
+    fn parse_regexes() {
+        insta::assert_debug_snapshot!(parse(r"/(?<foo>foo)|bar/g"));
+        insta::assert_debug_snapshot!(parse(r"/=[/\]]+\//"));
+        insta::assert_debug_snapshot!(parse(r"a / b / c"));
+        insta::assert_debug_snapshot!(parse(r"f(/\d+/, 2)"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn parse_regex_with_invalid_flags() {
+        parse(r"/foo/x");
+    }
+
+    #[test]
+    #[should_panic]
+    fn parse_unterminated_regex() {
+        parse(r"/foo");
+    }
+
+    #[test]
+    fn parse_pipelines() {
+        insta::assert_debug_snapshot!(parse("x |> f(_) |> g(_, 1)"));
+        insta::assert_debug_snapshot!(parse("a || b |> _ || c"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn parse_pipeline_without_topic() {
+        parse("x |> f");
+    }
+
+    #[test]
+    fn parse_slices() {
+        insta::assert_debug_snapshot!(parse("a[1:3]"));
+        insta::assert_debug_snapshot!(parse("a[:-1]"));
+        insta::assert_debug_snapshot!(parse("a[1:]"));
+        insta::assert_debug_snapshot!(parse("a[:]"));
+    }
+
     #[test]
     fn parse_function() {
         insta::assert_debug_snapshot!(parse("fn () { let x = 5 let y = 10 return x + y }"));
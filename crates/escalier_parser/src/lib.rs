@@ -16,3 +16,4 @@ mod type_ann_parser;
 pub use parse_error::ParseError;
 pub use parser::Parser;
 pub use stmt_parser::parse;
+pub use token::{TokenKind, KEYWORDS};
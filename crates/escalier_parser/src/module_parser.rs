@@ -28,7 +28,7 @@ impl<'a> Parser<'a> {
                 let expr = match self.peek().unwrap_or(&EOF).kind {
                     TokenKind::Assign => {
                         self.next().unwrap_or(EOF.clone());
-                        Some(self.parse_expr()?)
+                        Some(Box::new(self.parse_expr()?))
                     }
                     _ => None,
                 };
@@ -54,6 +54,7 @@ impl<'a> Parser<'a> {
                         type_ann,
                     }),
                     span,
+                    comments: Comments::default(),
                 }
             }
             TokenKind::Type => {
@@ -81,8 +82,13 @@ impl<'a> Parser<'a> {
                         type_params,
                     }),
                     span,
+                    comments: Comments::default(),
                 }
             }
+            TokenKind::Enum => self.parse_enum_decl()?,
+            TokenKind::Struct => self.parse_struct_decl()?,
+            TokenKind::Trait => self.parse_trait_decl()?,
+            TokenKind::Impl => self.parse_impl_decl()?,
             _ => {
                 return Err(ParseError {
                     message: "expected module item".to_string(),
@@ -187,12 +193,26 @@ impl<'a> Parser<'a> {
     pub fn parse_module(&mut self) -> Result<Module, ParseError> {
         let mut items = Vec::new();
         while self.peek().unwrap_or(&EOF).kind != TokenKind::Eof {
-            // TODO: attach comments to AST nodes
-            if let TokenKind::Comment(_) = &self.peek().unwrap_or(&EOF).kind {
-                self.next(); // consumes the comment
-                continue;
+            let mut leading = self.parse_leading_comments(IdentMode::Default);
+            if self.peek().unwrap_or(&EOF).kind == TokenKind::Import {
+                self.comments.append(&mut leading);
             }
-            items.push(self.parse_module_item()?);
+            if self.peek().unwrap_or(&EOF).kind == TokenKind::Eof {
+                self.comments.extend(leading);
+                break;
+            }
+
+            let mut item = self.parse_module_item()?;
+            let trailing = self.parse_trailing_comments(IdentMode::Default);
+
+            match &mut item.kind {
+                ModuleItemKind::Export(Export { decl }) | ModuleItemKind::Decl(decl) => {
+                    decl.comments = Comments { leading, trailing };
+                }
+                ModuleItemKind::Import(_) => self.comments.extend(trailing),
+            }
+
+            items.push(item);
         }
         Ok(Module { items })
     }
@@ -12,6 +12,12 @@ pub struct Parser<'a> {
     pub scanner: Scanner<'a>,
     pub brace_counts: Vec<usize>,
     pub peeked: Option<Token>,
+    // Comments that aren't attached to an AST node are kept here so that
+    // tools like the formatter can preserve them.
+    pub comments: Vec<Comment>,
+    // The labels of the loops we're currently parsing, this is used to tell
+    // `break label` apart from `break value`.
+    pub labels: Vec<String>,
 }
 
 impl<'a> Iterator for Parser<'a> {
@@ -40,6 +46,8 @@ impl<'a> Parser<'a> {
             scanner: Scanner::new(input),
             brace_counts: vec![0], // we need separate brace counts for each mode
             peeked: None,
+            comments: vec![],
+            labels: vec![],
         }
     }
 
@@ -47,6 +55,48 @@ impl<'a> Parser<'a> {
         self.scanner = backup.scanner;
         self.brace_counts = backup.brace_counts;
         self.peeked = backup.peeked;
+        self.comments = backup.comments;
+        self.labels = backup.labels;
+    }
+
+    // Consumes the next token if it's a comment.
+    fn parse_comment(&mut self, mode: IdentMode) -> Option<Comment> {
+        let token = self.peek_with_mode(mode).unwrap_or(&EOF);
+        let (kind, text) = match &token.kind {
+            TokenKind::Comment(text) => (CommentKind::Line, text.to_owned()),
+            TokenKind::BlockComment(text) => (CommentKind::Block, text.to_owned()),
+            _ => return None,
+        };
+        let span = token.span;
+        self.next_with_mode(mode);
+
+        Some(Comment { kind, text, span })
+    }
+
+    /// Consumes all of the comments before the next token.
+    pub fn parse_leading_comments(&mut self, mode: IdentMode) -> Vec<Comment> {
+        let mut comments = vec![];
+        while let Some(comment) = self.parse_comment(mode) {
+            comments.push(comment);
+        }
+        comments
+    }
+
+    /// Consumes the comments that appear on the same line as the previous
+    /// token.
+    pub fn parse_trailing_comments(&mut self, mode: IdentMode) -> Vec<Comment> {
+        let mut comments = vec![];
+        loop {
+            let start = self.peek_with_mode(mode).unwrap_or(&EOF).span.start;
+            if self.scanner.is_start_of_line(start) {
+                break;
+            }
+            match self.parse_comment(mode) {
+                Some(comment) => comments.push(comment),
+                None => break,
+            }
+        }
+        comments
     }
 
     pub fn peek(&mut self) -> Option<&Token> {
@@ -157,6 +207,27 @@ impl<'a> Parser<'a> {
                         self.scanner.pop();
                         TokenKind::DivideAssign
                     }
+                    Some('*') => {
+                        self.scanner.pop();
+                        self.scanner.pop();
+                        let mut comment = String::new();
+                        while let Some(c) = self.scanner.peek(0) {
+                            if c == '*' && self.scanner.peek(1) == Some('/') {
+                                break;
+                            }
+                            self.scanner.pop();
+                            comment.push(c);
+                        }
+                        self.scanner.pop(); // consumes '*'
+                        self.scanner.pop(); // consumes '/'
+                        let end = self.scanner.cursor();
+                        let kind = TokenKind::BlockComment(comment);
+
+                        return Some(Token {
+                            kind,
+                            span: Span { start, end },
+                        });
+                    }
                     Some('/') => {
                         self.scanner.pop();
                         self.scanner.pop();
@@ -262,6 +333,10 @@ impl<'a> Parser<'a> {
                         self.scanner.pop();
                         TokenKind::Or
                     }
+                    Some('>') => {
+                        self.scanner.pop();
+                        TokenKind::PipeArrow
+                    }
                     _ => TokenKind::Pipe,
                 },
                 _ => panic!("Unexpected character: '{}'", character),
@@ -324,57 +399,9 @@ impl<'a> Parser<'a> {
             };
         }
 
-        let kind = match ident.as_ref() {
-            "import" => TokenKind::Import,
-            "export" => TokenKind::Export,
-            "from" => TokenKind::From,
-            "as" => TokenKind::As,
-            "fn" => TokenKind::Fn,
-            "get" => TokenKind::Get,
-            "set" => TokenKind::Set,
-            "pub" => TokenKind::Pub,
-            "private" => TokenKind::Private,
-            "static" => TokenKind::Static,
-            "async" => TokenKind::Async,
-            "await" => TokenKind::Await,
-            "gen" => TokenKind::Gen,
-            "yield" => TokenKind::Yield,
-            "declare" => TokenKind::Declare,
-            "let" => TokenKind::Let,
-            "var" => TokenKind::Var,
-            "mut" => TokenKind::Mut,
-            "match" => TokenKind::Match,
-            "is" => TokenKind::Is,
-            "try" => TokenKind::Try,
-            "catch" => TokenKind::Catch,
-            "finally" => TokenKind::Finally,
-            "throw" => TokenKind::Throw,
-            "do" => TokenKind::Do,
-            "for" => TokenKind::For,
-            "in" => TokenKind::In,
-            "class" => TokenKind::Class,
-            "extends" => TokenKind::Extends,
-            "infer" => TokenKind::Infer,
-            "return" => TokenKind::Return,
-            "throws" => TokenKind::Throws,
-            "if" => TokenKind::If,
-            "else" => TokenKind::Else,
-            "true" => TokenKind::BoolLit(true),
-            "false" => TokenKind::BoolLit(false),
-            "null" => TokenKind::Null,
-            "undefined" => TokenKind::Undefined,
-            "number" => TokenKind::Number,
-            "string" => TokenKind::String,
-            "boolean" => TokenKind::Boolean,
-            "symbol" => TokenKind::Symbol,
-            "unknown" => TokenKind::Unknown,
-            "never" => TokenKind::Never,
-            "type" => TokenKind::Type,
-            "typeof" => TokenKind::TypeOf,
-            "keyof" => TokenKind::KeyOf,
-            "new" => TokenKind::New,
-            "_" => TokenKind::Underscore,
-            _ => TokenKind::Identifier(ident),
+        let kind = match KEYWORDS.get(ident.as_str()) {
+            Some(kind) => kind.to_owned(),
+            None => TokenKind::Identifier(ident),
         };
         Token {
             kind,
@@ -398,6 +425,10 @@ impl<'a> Parser<'a> {
                     number.push(character);
                     self.scanner.pop();
                 }
+                // `..` after a number is a range, e.g. `0..10`.
+                '.' if self.scanner.peek(1) == Some('.') => {
+                    break;
+                }
                 '.' => {
                     if decimal {
                         panic!("Unexpected character: '{}'", character);
@@ -472,6 +503,70 @@ impl<'a> Parser<'a> {
         }
     }
 
+    // `/` is lexed as a division operator by default.  The parser calls this
+    // when it finds a `/` (or `/=`) where an expression is expected.  `start`
+    // is the position of the leading `/` and `pattern` contains anything
+    // after it that was already consumed.
+    pub fn lex_regex(&mut self, start: usize, mut pattern: String) -> Result<Token, ParseError> {
+        let mut in_class = false;
+        loop {
+            match self.scanner.peek(0) {
+                None | Some('\n') => {
+                    return Err(ParseError {
+                        message: "unterminated regex literal".to_string(),
+                    })
+                }
+                Some('/') if !in_class => {
+                    self.scanner.pop();
+                    break;
+                }
+                Some('\\') => {
+                    pattern.push('\\');
+                    self.scanner.pop();
+                    match self.scanner.pop() {
+                        Some(c) if c != '\n' => pattern.push(c),
+                        _ => {
+                            return Err(ParseError {
+                                message: "unterminated regex literal".to_string(),
+                            })
+                        }
+                    }
+                }
+                Some(c) => {
+                    match c {
+                        '[' => in_class = true,
+                        ']' => in_class = false,
+                        _ => (),
+                    }
+                    pattern.push(c);
+                    self.scanner.pop();
+                }
+            }
+        }
+
+        let mut flags = String::new();
+        while let Some(c) = self.scanner.peek(0) {
+            if !c.is_ascii_alphanumeric() {
+                break;
+            }
+            if !"dgimsuy".contains(c) || flags.contains(c) {
+                return Err(ParseError {
+                    message: format!("invalid regex flag '{c}'"),
+                });
+            }
+            flags.push(c);
+            self.scanner.pop();
+        }
+
+        Ok(Token {
+            kind: TokenKind::RegexLit { pattern, flags },
+            span: Span {
+                start,
+                end: self.scanner.cursor(),
+            },
+        })
+    }
+
     pub fn lex_template_string(&mut self, start: usize) -> Result<Token, ParseError> {
         let mut string = String::new();
         let mut parts: Vec<Token> = vec![];
@@ -598,6 +693,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn lex_number_followed_by_range() {
+        let parser = Parser::new("0..10");
+
+        let tokens = parser.collect::<Vec<_>>();
+
+        assert_eq!(
+            tokens[0].kind,
+            crate::token::TokenKind::NumLit("0".to_string())
+        );
+        assert_eq!(tokens[1].kind, crate::token::TokenKind::DotDot);
+        assert_eq!(
+            tokens[2].kind,
+            crate::token::TokenKind::NumLit("10".to_string())
+        );
+    }
+
     #[test]
     #[should_panic = "Unexpected character: '.'"]
     fn lex_number_multiple_decimals_error() {
@@ -45,6 +45,54 @@ impl<'a> Parser<'a> {
                             is_id,
                         })
                     }
+                    TokenKind::Dot => {
+                        self.next(); // consumes '.'
+                        let enum_name = Ident { name, span };
+                        let next = self.next().unwrap_or(EOF.clone());
+                        let variant = match &next.kind {
+                            TokenKind::Identifier(name) => Ident {
+                                name: name.to_owned(),
+                                span: next.span,
+                            },
+                            _ => panic!("expected variant name after '.'"),
+                        };
+                        span = merge_spans(&span, &next.span);
+
+                        let mut args: Vec<Pattern> = vec![];
+                        if self.peek().unwrap_or(&EOF).kind == TokenKind::LeftParen {
+                            self.next(); // consumes '('
+                            while self.peek().unwrap_or(&EOF).kind != TokenKind::RightParen {
+                                args.push(self.parse_pattern()?);
+                                if self.peek().unwrap_or(&EOF).kind == TokenKind::Comma {
+                                    self.next();
+                                } else {
+                                    break;
+                                }
+                            }
+                            span = merge_spans(&span, &self.peek().unwrap_or(&EOF).span);
+                            assert_eq!(
+                                self.next().unwrap_or(EOF.clone()).kind,
+                                TokenKind::RightParen
+                            );
+                        }
+
+                        PatternKind::Enum(EnumPat {
+                            enum_name,
+                            variant,
+                            args,
+                        })
+                    }
+                    TokenKind::LeftBrace => {
+                        let name = Ident { name, span };
+                        let obj = self.parse_pattern()?;
+                        span = merge_spans(&span, &obj.span);
+                        match obj.kind {
+                            PatternKind::Object(ObjectPat { props, .. }) => {
+                                PatternKind::Struct(StructPat { name, props })
+                            }
+                            _ => unreachable!("expected object pattern"),
+                        }
+                    }
                     _ => PatternKind::Ident(BindingIdent {
                         name,
                         span,
@@ -290,4 +338,17 @@ mod tests {
     fn parse_mixed_patterns() {
         insta::assert_debug_snapshot!(parse(r#"{kind: "foo", bar: _, values: [head, ...tail]}"#));
     }
+
+    #[test]
+    fn parse_enum_patterns() {
+        insta::assert_debug_snapshot!(parse("IpAddr.V4(addr)"));
+        insta::assert_debug_snapshot!(parse("Shape.Rect(w, {h})"));
+        insta::assert_debug_snapshot!(parse("Option.None"));
+    }
+
+    #[test]
+    fn parse_struct_patterns() {
+        insta::assert_debug_snapshot!(parse("User {name, age: a}"));
+        insta::assert_debug_snapshot!(parse("Point {x: 0, ...rest}"));
+    }
 }
@@ -56,6 +56,7 @@ pub enum Operator {
     Subtraction,
 
     // 10
+    Range,
     // BitwiseLeftShift,
     // BitwiseRightShift,
     // BitwiseUnsignedRightShift,
@@ -89,6 +90,7 @@ pub enum Operator {
     // 3
     LogicalOr,
     NullishCoalescing,
+    Pipeline,
 
     // 2
     Assignment,
@@ -193,6 +195,10 @@ lazy_static! {
             OpInfo::new_infix(11, Associativity::Left),
         );
 
+        // NOTE: JavaScript doesn't have a range operator.  It's given a lower
+        // precedence than arithmetic so that `0..n - 1` works as expected.
+        table.insert(Operator::Range, OpInfo::new_infix(10, Associativity::Left));
+
         table.insert(
             Operator::LessThan,
             OpInfo::new_infix(9, Associativity::Left),
@@ -234,6 +240,14 @@ lazy_static! {
             Operator::NullishCoalescing,
             OpInfo::new_infix(3, Associativity::Left),
         );
+        // NOTE: JavaScript doesn't have a pipeline operator yet.  Making it
+        // right associative allows `||` and `??` to appear in the right side
+        // without parens.  `x |> f(_) |> g(_)` behaves the same regardless of
+        // associativity since each stage binds its own `_`.
+        table.insert(
+            Operator::Pipeline,
+            OpInfo::new_infix(3, Associativity::Right),
+        );
 
         table.insert(
             Operator::Assignment,
@@ -31,6 +31,15 @@ impl<'a> Scanner<'a> {
             .map(|sub_str| sub_str.chars().next().unwrap())
     }
 
+    /// Returns true if there's only whitespace between the start of the line
+    /// and `offset`.
+    pub fn is_start_of_line(&self, offset: usize) -> bool {
+        let line_start = self.input[..offset]
+            .rfind('\n')
+            .map_or(0, |index| index + 1);
+        self.input[line_start..offset].trim().is_empty()
+    }
+
     /// Returns true if further progress is not possible.
     pub fn is_done(&self) -> bool {
         self.cursor == self.input.len()
@@ -8,12 +8,12 @@ impl<'a> Parser<'a> {
     pub fn parse_script(&mut self) -> Result<Script, ParseError> {
         let mut stmts = Vec::new();
         while self.peek().unwrap_or(&EOF).kind != TokenKind::Eof {
-            // TODO: attach comments to AST nodes
-            if let TokenKind::Comment(_) = &self.peek().unwrap_or(&EOF).kind {
-                self.next(); // consumes the comment
-                continue;
+            let leading = self.parse_leading_comments(IdentMode::Default);
+            if self.peek().unwrap_or(&EOF).kind == TokenKind::Eof {
+                self.comments.extend(leading);
+                break;
             }
-            stmts.push(self.parse_stmt()?);
+            stmts.push(self.parse_stmt_with_comments(leading)?);
         }
         Ok(Script { stmts })
     }
@@ -5,6 +5,39 @@ use crate::parser::*;
 use crate::token::*;
 
 impl<'a> Parser<'a> {
+    /// Parses a statement and attaches `leading` along with any trailing
+    /// comments to it if it's a declaration.  Other comments are added to
+    /// `self.comments`.
+    pub fn parse_stmt_with_comments(
+        &mut self,
+        mut leading: Vec<Comment>,
+    ) -> Result<Stmt, ParseError> {
+        let is_decl = matches!(
+            self.peek().unwrap_or(&EOF).kind,
+            TokenKind::Let
+                | TokenKind::Var
+                | TokenKind::Type
+                | TokenKind::Enum
+                | TokenKind::Struct
+                | TokenKind::Trait
+                | TokenKind::Impl
+                | TokenKind::Declare
+        );
+        if !is_decl {
+            self.comments.append(&mut leading);
+        }
+
+        let mut stmt = self.parse_stmt()?;
+        let trailing = self.parse_trailing_comments(IdentMode::Default);
+
+        match &mut stmt.kind {
+            StmtKind::Decl(decl) => decl.comments = Comments { leading, trailing },
+            _ => self.comments.extend(trailing),
+        }
+
+        Ok(stmt)
+    }
+
     pub fn parse_stmt(&mut self) -> Result<Stmt, ParseError> {
         let mut token = self.peek().unwrap_or(&EOF).clone();
         let start = token.span.start;
@@ -19,6 +52,19 @@ impl<'a> Parser<'a> {
             _ => false,
         };
 
+        // Labels can only appear in front of loops, e.g. `outer: for (...) {}`.
+        if let TokenKind::Identifier(name) = &token.kind {
+            let backup = self.clone();
+            self.next(); // consumes label
+            if self.peek().unwrap_or(&EOF).kind == TokenKind::Colon {
+                self.next(); // consumes ':'
+                let mut stmt = self.parse_loop_stmt(Some(name.to_owned()))?;
+                stmt.span.start = start;
+                return Ok(stmt);
+            }
+            self.restore(backup);
+        }
+
         let stmt = match &token.kind {
             TokenKind::Let | TokenKind::Var => {
                 let token = self.next().unwrap_or(EOF.clone()); // consumes 'let' or 'var'
@@ -38,7 +84,7 @@ impl<'a> Parser<'a> {
                 let expr = match self.peek().unwrap_or(&EOF).kind {
                     TokenKind::Assign => {
                         self.next().unwrap_or(EOF.clone());
-                        Some(self.parse_expr()?)
+                        Some(Box::new(self.parse_expr()?))
                     }
                     _ => None,
                 };
@@ -63,6 +109,7 @@ impl<'a> Parser<'a> {
                         type_ann,
                     }),
                     span,
+                    comments: Comments::default(),
                 };
 
                 // TODO: check invariants in semantic analysis pass
@@ -72,32 +119,53 @@ impl<'a> Parser<'a> {
                     inferred_type: None,
                 }
             }
-            TokenKind::For => {
-                self.next(); // consumes 'for'
+            TokenKind::For | TokenKind::While => self.parse_loop_stmt(None)?,
+            TokenKind::Break => {
+                self.next(); // consumes 'break'
 
-                assert_eq!(
-                    self.next().unwrap_or(EOF.clone()).kind,
-                    TokenKind::LeftParen
-                );
-                let left = self.parse_pattern()?;
-                assert_eq!(self.next().unwrap_or(EOF.clone()).kind, TokenKind::In);
-                let right = self.parse_expr()?;
-                assert_eq!(
-                    self.next().unwrap_or(EOF.clone()).kind,
-                    TokenKind::RightParen
-                );
-                assert_eq!(self.peek().unwrap_or(&EOF).kind, TokenKind::LeftBrace);
-                let body = self.parse_block()?;
+                let mut label: Option<String> = None;
+                let mut end = token.span.end;
+                if let Some(next) = self.peek_same_line() {
+                    if let TokenKind::Identifier(name) = &next.kind {
+                        if self.labels.contains(name) {
+                            label = Some(name.to_owned());
+                            end = next.span.end;
+                            self.next(); // consumes label
+                        }
+                    }
+                }
 
-                let span = merge_spans(&left.span, &body.span);
+                let arg = match self.peek_same_line() {
+                    Some(_) => {
+                        let arg = self.parse_expr()?;
+                        end = arg.get_span().end;
+                        Some(arg)
+                    }
+                    None => None,
+                };
 
                 Stmt {
-                    kind: StmtKind::For(ForStmt {
-                        left: Box::new(left),
-                        right: Box::new(right),
-                        body,
-                    }),
-                    span,
+                    kind: StmtKind::Break(BreakStmt { label, arg }),
+                    span: Span { start, end },
+                    inferred_type: None,
+                }
+            }
+            TokenKind::Continue => {
+                self.next(); // consumes 'continue'
+
+                let mut label: Option<String> = None;
+                let mut end = token.span.end;
+                if let Some(next) = self.peek_same_line() {
+                    if let TokenKind::Identifier(name) = &next.kind {
+                        label = Some(name.to_owned());
+                        end = next.span.end;
+                        self.next(); // consumes label
+                    }
+                }
+
+                Stmt {
+                    kind: StmtKind::Continue(ContinueStmt { label }),
+                    span: Span { start, end },
                     inferred_type: None,
                 }
             }
@@ -147,6 +215,7 @@ impl<'a> Parser<'a> {
                         type_params,
                     }),
                     span,
+                    comments: Comments::default(),
                 };
 
                 Stmt {
@@ -155,6 +224,21 @@ impl<'a> Parser<'a> {
                     inferred_type: None,
                 }
             }
+            TokenKind::Enum | TokenKind::Struct | TokenKind::Trait | TokenKind::Impl => {
+                let decl = match token.kind {
+                    TokenKind::Enum => self.parse_enum_decl()?,
+                    TokenKind::Struct => self.parse_struct_decl()?,
+                    TokenKind::Trait => self.parse_trait_decl()?,
+                    _ => self.parse_impl_decl()?,
+                };
+                let span = decl.span;
+
+                Stmt {
+                    kind: StmtKind::Decl(decl),
+                    span,
+                    inferred_type: None,
+                }
+            }
             _ => {
                 let expr = self.parse_expr()?;
                 let span = expr.get_span();
@@ -168,6 +252,442 @@ impl<'a> Parser<'a> {
 
         Ok(stmt)
     }
+
+    /// Returns the next token if it's on the same line as the previous token
+    /// and could start an expression.
+    fn peek_same_line(&mut self) -> Option<Token> {
+        let next = self.peek().unwrap_or(&EOF).clone();
+        match next.kind {
+            TokenKind::Eof | TokenKind::RightBrace => None,
+            _ if self.scanner.is_start_of_line(next.span.start) => None,
+            _ => Some(next),
+        }
+    }
+
+    /// Parses a `for`, `while`, or `loop` optionally preceded by a label.
+    fn parse_loop_stmt(&mut self, label: Option<String>) -> Result<Stmt, ParseError> {
+        let token = self.peek().unwrap_or(&EOF).clone();
+
+        match token.kind {
+            TokenKind::For => {
+                self.next(); // consumes 'for'
+
+                assert_eq!(
+                    self.next().unwrap_or(EOF.clone()).kind,
+                    TokenKind::LeftParen
+                );
+                let left = self.parse_pattern()?;
+                assert_eq!(self.next().unwrap_or(EOF.clone()).kind, TokenKind::In);
+                let right = self.parse_expr()?;
+                assert_eq!(
+                    self.next().unwrap_or(EOF.clone()).kind,
+                    TokenKind::RightParen
+                );
+                assert_eq!(self.peek().unwrap_or(&EOF).kind, TokenKind::LeftBrace);
+                let body = self.parse_loop_body(&label)?;
+
+                let span = merge_spans(&token.span, &body.span);
+
+                Ok(Stmt {
+                    kind: StmtKind::For(ForStmt {
+                        label,
+                        left: Box::new(left),
+                        right: Box::new(right),
+                        body,
+                    }),
+                    span,
+                    inferred_type: None,
+                })
+            }
+            TokenKind::While => {
+                self.next(); // consumes 'while'
+
+                assert_eq!(
+                    self.next().unwrap_or(EOF.clone()).kind,
+                    TokenKind::LeftParen
+                );
+                let test = self.parse_expr()?;
+                assert_eq!(
+                    self.next().unwrap_or(EOF.clone()).kind,
+                    TokenKind::RightParen
+                );
+                assert_eq!(self.peek().unwrap_or(&EOF).kind, TokenKind::LeftBrace);
+                let body = self.parse_loop_body(&label)?;
+
+                let span = merge_spans(&token.span, &body.span);
+
+                Ok(Stmt {
+                    kind: StmtKind::While(WhileStmt {
+                        label,
+                        test: Box::new(test),
+                        body,
+                    }),
+                    span,
+                    inferred_type: None,
+                })
+            }
+            TokenKind::Loop => {
+                let expr = self.parse_loop(label)?;
+                let span = expr.get_span();
+
+                Ok(Stmt {
+                    kind: StmtKind::Expr(ExprStmt { expr }),
+                    span,
+                    inferred_type: None,
+                })
+            }
+            _ => Err(ParseError {
+                message: "labels can only be used with loops".to_string(),
+            }),
+        }
+    }
+
+    /// Parses `loop { ... }`.
+    pub(crate) fn parse_loop(&mut self, label: Option<String>) -> Result<Expr, ParseError> {
+        let token = self.next().unwrap_or(EOF.clone()); // consumes 'loop'
+
+        assert_eq!(self.peek().unwrap_or(&EOF).kind, TokenKind::LeftBrace);
+        let body = self.parse_loop_body(&label)?;
+        let span = merge_spans(&token.span, &body.span);
+
+        Ok(Expr {
+            kind: ExprKind::Loop(Loop { label, body }),
+            span,
+            inferred_type: None,
+        })
+    }
+
+    fn parse_loop_body(&mut self, label: &Option<String>) -> Result<Block, ParseError> {
+        if let Some(label) = label {
+            self.labels.push(label.to_owned());
+        }
+        let body = self.parse_block();
+        if label.is_some() {
+            self.labels.pop();
+        }
+        body
+    }
+
+    /// Parses `enum Name<T> { A, B(T), C(number, string) }`.
+    pub(crate) fn parse_enum_decl(&mut self) -> Result<Decl, ParseError> {
+        let token = self.next().unwrap_or(EOF.clone()); // consumes 'enum'
+
+        let name = match self.next().unwrap_or(EOF.clone()).kind {
+            TokenKind::Identifier(name) => name,
+            _ => {
+                return Err(ParseError {
+                    message: "expected identifier".to_string(),
+                })
+            }
+        };
+
+        let type_params = self.maybe_parse_type_params()?;
+
+        assert_eq!(
+            self.next().unwrap_or(EOF.clone()).kind,
+            TokenKind::LeftBrace
+        );
+
+        let mut variants: Vec<EnumVariant> = vec![];
+        while self.peek().unwrap_or(&EOF).kind != TokenKind::RightBrace {
+            let next = self.next().unwrap_or(EOF.clone());
+            let variant_name = match next.kind {
+                TokenKind::Identifier(name) => name,
+                _ => {
+                    return Err(ParseError {
+                        message: "expected variant name".to_string(),
+                    })
+                }
+            };
+            let mut span = next.span;
+
+            let mut fields: Vec<TypeAnn> = vec![];
+            if self.peek().unwrap_or(&EOF).kind == TokenKind::LeftParen {
+                self.next(); // consumes '('
+                while self.peek().unwrap_or(&EOF).kind != TokenKind::RightParen {
+                    fields.push(self.parse_type_ann()?);
+                    if self.peek().unwrap_or(&EOF).kind == TokenKind::Comma {
+                        self.next();
+                    } else {
+                        break;
+                    }
+                }
+                span = merge_spans(&span, &self.peek().unwrap_or(&EOF).span);
+                assert_eq!(
+                    self.next().unwrap_or(EOF.clone()).kind,
+                    TokenKind::RightParen
+                );
+            }
+
+            variants.push(EnumVariant {
+                name: variant_name,
+                fields,
+                span,
+            });
+
+            if self.peek().unwrap_or(&EOF).kind == TokenKind::Comma {
+                self.next();
+            } else {
+                break;
+            }
+        }
+
+        let end = self.next().unwrap_or(EOF.clone());
+        if end.kind != TokenKind::RightBrace {
+            return Err(ParseError {
+                message: "expected '}' after enum variants".to_string(),
+            });
+        }
+
+        Ok(Decl {
+            kind: DeclKind::EnumDecl(EnumDecl {
+                name,
+                type_params,
+                variants,
+            }),
+            span: merge_spans(&token.span, &end.span),
+            comments: Comments::default(),
+        })
+    }
+
+    /// Parses `struct Name<T> { pub a: T, b: string }`.
+    pub(crate) fn parse_struct_decl(&mut self) -> Result<Decl, ParseError> {
+        let token = self.next().unwrap_or(EOF.clone()); // consumes 'struct'
+
+        let name = match self.next().unwrap_or(EOF.clone()).kind {
+            TokenKind::Identifier(name) => name,
+            _ => {
+                return Err(ParseError {
+                    message: "expected identifier".to_string(),
+                })
+            }
+        };
+
+        let type_params = self.maybe_parse_type_params()?;
+
+        assert_eq!(
+            self.next().unwrap_or(EOF.clone()).kind,
+            TokenKind::LeftBrace
+        );
+
+        let mut fields: Vec<StructField> = vec![];
+        while self.peek().unwrap_or(&EOF).kind != TokenKind::RightBrace {
+            let start = self.peek().unwrap_or(&EOF).span;
+            let is_public = self.peek().unwrap_or(&EOF).kind == TokenKind::Pub;
+            if is_public {
+                self.next(); // consumes 'pub'
+            }
+
+            let field_name = match self.next().unwrap_or(EOF.clone()).kind {
+                TokenKind::Identifier(name) => name,
+                _ => {
+                    return Err(ParseError {
+                        message: "expected field name".to_string(),
+                    })
+                }
+            };
+
+            assert_eq!(self.next().unwrap_or(EOF.clone()).kind, TokenKind::Colon);
+            let type_ann = self.parse_type_ann()?;
+
+            fields.push(StructField {
+                name: field_name,
+                span: merge_spans(&start, &type_ann.span),
+                type_ann,
+                is_public,
+            });
+
+            if self.peek().unwrap_or(&EOF).kind == TokenKind::Comma {
+                self.next();
+            } else {
+                break;
+            }
+        }
+
+        let end = self.next().unwrap_or(EOF.clone());
+        if end.kind != TokenKind::RightBrace {
+            return Err(ParseError {
+                message: "expected '}' after struct fields".to_string(),
+            });
+        }
+
+        Ok(Decl {
+            kind: DeclKind::StructDecl(StructDecl {
+                name,
+                type_params,
+                fields,
+            }),
+            span: merge_spans(&token.span, &end.span),
+            comments: Comments::default(),
+        })
+    }
+
+    /// Parses `trait Name { fn required(self) -> T  fn default(self) -> T {...} }`.
+    pub(crate) fn parse_trait_decl(&mut self) -> Result<Decl, ParseError> {
+        let token = self.next().unwrap_or(EOF.clone()); // consumes 'trait'
+
+        let name = match self.next().unwrap_or(EOF.clone()).kind {
+            TokenKind::Identifier(name) => name,
+            _ => {
+                return Err(ParseError {
+                    message: "expected identifier".to_string(),
+                })
+            }
+        };
+
+        assert_eq!(
+            self.next().unwrap_or(EOF.clone()).kind,
+            TokenKind::LeftBrace
+        );
+
+        let mut methods: Vec<TraitMethod> = vec![];
+        while self.peek().unwrap_or(&EOF).kind != TokenKind::RightBrace {
+            let leading = self.parse_leading_comments(IdentMode::Default);
+            if self.peek().unwrap_or(&EOF).kind == TokenKind::RightBrace {
+                self.comments.extend(leading);
+                break;
+            }
+
+            let mut method = self.parse_trait_method()?;
+            let trailing = self.parse_trailing_comments(IdentMode::Default);
+            method.comments = Comments { leading, trailing };
+
+            methods.push(method);
+        }
+
+        let end = self.next().unwrap_or(EOF.clone());
+        if end.kind != TokenKind::RightBrace {
+            return Err(ParseError {
+                message: "expected '}' after trait methods".to_string(),
+            });
+        }
+
+        Ok(Decl {
+            kind: DeclKind::TraitDecl(TraitDecl { name, methods }),
+            span: merge_spans(&token.span, &end.span),
+            comments: Comments::default(),
+        })
+    }
+
+    fn parse_trait_method(&mut self) -> Result<TraitMethod, ParseError> {
+        let start = self.next().unwrap_or(EOF.clone()); // consumes 'fn'
+        if start.kind != TokenKind::Fn {
+            return Err(ParseError {
+                message: format!("expected 'fn', found {:?}", start.kind),
+            });
+        }
+
+        let next = self.next().unwrap_or(EOF.clone());
+        let name = match next.kind {
+            TokenKind::Identifier(name) => Ident {
+                name,
+                span: next.span,
+            },
+            _ => {
+                return Err(ParseError {
+                    message: "expected method name".to_string(),
+                })
+            }
+        };
+
+        let type_params = self.maybe_parse_type_params()?;
+        let (params, is_mutating) = self.parse_method_params()?;
+
+        if self.next().unwrap_or(EOF.clone()).kind != TokenKind::SingleArrow {
+            return Err(ParseError {
+                message: format!("expected return type for trait method {}", name.name),
+            });
+        }
+        let type_ann = self.parse_type_ann()?;
+
+        let body = match self.peek().unwrap_or(&EOF).kind {
+            TokenKind::LeftBrace => Some(BlockOrExpr::Block(self.parse_block()?)),
+            _ => None,
+        };
+
+        let span = Span {
+            start: start.span.start,
+            end: self.scanner.cursor(),
+        };
+
+        Ok(TraitMethod {
+            span,
+            name,
+            is_mutating,
+            type_params,
+            params,
+            type_ann,
+            body,
+            comments: Comments::default(),
+        })
+    }
+
+    /// Parses `impl Trait for Type { fn method(self) {...} }`.
+    pub(crate) fn parse_impl_decl(&mut self) -> Result<Decl, ParseError> {
+        let token = self.next().unwrap_or(EOF.clone()); // consumes 'impl'
+
+        let trait_name = self.parse_ident()?;
+        if self.next().unwrap_or(EOF.clone()).kind != TokenKind::For {
+            return Err(ParseError {
+                message: "expected 'for' after trait name".to_string(),
+            });
+        }
+        let type_name = self.parse_ident()?;
+
+        assert_eq!(
+            self.next().unwrap_or(EOF.clone()).kind,
+            TokenKind::LeftBrace
+        );
+
+        let mut methods: Vec<Method> = vec![];
+        while self.peek().unwrap_or(&EOF).kind != TokenKind::RightBrace {
+            let leading = self.parse_leading_comments(IdentMode::Default);
+            if self.peek().unwrap_or(&EOF).kind == TokenKind::RightBrace {
+                self.comments.extend(leading);
+                break;
+            }
+
+            // Trait methods are always public.
+            let mut method = match self.parse_method(true, false)? {
+                ClassMember::Method(method) => method,
+                _ => unreachable!("parse_method only returns methods"),
+            };
+            let trailing = self.parse_trailing_comments(IdentMode::Default);
+            method.comments = Comments { leading, trailing };
+
+            methods.push(method);
+        }
+
+        let end = self.next().unwrap_or(EOF.clone());
+        if end.kind != TokenKind::RightBrace {
+            return Err(ParseError {
+                message: "expected '}' after impl methods".to_string(),
+            });
+        }
+
+        Ok(Decl {
+            kind: DeclKind::ImplDecl(ImplDecl {
+                trait_name,
+                type_name,
+                methods,
+            }),
+            span: merge_spans(&token.span, &end.span),
+            comments: Comments::default(),
+        })
+    }
+
+    fn parse_ident(&mut self) -> Result<Ident, ParseError> {
+        let token = self.next().unwrap_or(EOF.clone());
+        match token.kind {
+            TokenKind::Identifier(name) => Ok(Ident {
+                name,
+                span: token.span,
+            }),
+            _ => Err(ParseError {
+                message: format!("expected identifier, found {:?}", token.kind),
+            }),
+        }
+    }
 }
 
 // TODO: remove this function
@@ -326,6 +846,46 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn parse_while_loop() {
+        insta::assert_debug_snapshot!(parse(
+            r#"
+            while (i < 10) {
+                i = i + 1
+            }"#
+        ));
+    }
+
+    #[test]
+    fn parse_loop_with_break_value() {
+        insta::assert_debug_snapshot!(parse(
+            r#"
+            let result = loop {
+                break result
+            }"#
+        ));
+    }
+
+    #[test]
+    fn parse_labeled_loops() {
+        insta::assert_debug_snapshot!(parse(
+            r#"
+            outer: for (row in rows) {
+                inner: loop {
+                    continue outer
+                    break inner row
+                }
+                break
+            }"#
+        ));
+    }
+
+    #[test]
+    #[should_panic = "labels can only be used with loops"]
+    fn parse_label_without_loop() {
+        parse("outer: let x = 5");
+    }
+
     #[test]
     fn parse_comments() {
         insta::assert_debug_snapshot!(parse(
@@ -345,6 +905,91 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn parse_doc_comments() {
+        let stmts = parse(
+            r#"
+            // not a doc comment
+            /// Adds two numbers.
+            ///
+            /// Returns their sum.
+            let add = fn (a: number, b: number) => a + b
+
+            /**
+             * A point in 2D space.
+             */
+            type Point = {x: number, y: number}
+
+            //// not a doc comment
+            let x = 5
+            "#,
+        );
+
+        let docs: Vec<_> = stmts
+            .iter()
+            .map(|stmt| match &stmt.kind {
+                StmtKind::Decl(decl) => decl.doc(),
+                _ => panic!("expected decl"),
+            })
+            .collect();
+
+        assert_eq!(
+            docs,
+            vec![
+                Some("Adds two numbers.\n\nReturns their sum.".to_string()),
+                Some("A point in 2D space.".to_string()),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_member_comments() {
+        insta::assert_debug_snapshot!(parse(
+            r#"
+            let Foo = class {
+                /// The x-coord
+                x: number // trailing
+                // unattached
+            }
+            let obj = {
+                // leading
+                a: 1, // trailing
+                b,
+            }
+            type Obj = {
+                /** The y-coord */
+                y: number,
+                fn (a: number) -> string, // call signature
+            }
+            "#
+        ));
+    }
+
+    #[test]
+    fn serialize_and_deserialize() {
+        let input = r#"
+        /// A point
+        type Point<T: number> = {x: T, y?: T, fn scale(self, k: T) -> Self}
+        let Foo = class extends Bar {
+            x: number // x-coord
+            get value(self) { return self.x }
+        }
+        let {x, y: [a, ...rest]} = obj
+        let f = async fn (a: number) throws string => await g(a, `hello ${a}`)
+        let b = <div id={x}>{y}</div>
+        let m = match (x) { n is number => n + 1, _ => 0 }
+        for (p in points) { p.x = -1 }
+        "#;
+        let mut parser = Parser::new(input);
+        let script = parser.parse_script().unwrap();
+
+        let json = script_to_json(&script, std::collections::HashMap::new());
+        let result: Script = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(result, script);
+    }
+
     #[test]
     fn parse_typeof() {
         insta::assert_debug_snapshot!(parse("type RetType = GetReturnType<typeof foo>"));
@@ -361,4 +1006,54 @@ mod tests {
             r#"let button = <Button count={5} foo="bar"></Button>"#
         ));
     }
+
+    #[test]
+    fn parse_enum_decl() {
+        insta::assert_debug_snapshot!(parse(
+            r#"enum Option<T> { Some(T), None }
+            enum Shape { Circle(number), Rect(number, number), }"#
+        ));
+    }
+
+    #[test]
+    fn parse_struct_decl() {
+        insta::assert_debug_snapshot!(parse(
+            r#"struct User { pub name: string, ssn: number }
+            struct Point<T> { pub x: T, pub y: T, }"#
+        ));
+    }
+
+    #[test]
+    fn parse_trait_decl() {
+        insta::assert_debug_snapshot!(parse(
+            r#"trait Shape {
+                fn area(self) -> number
+                fn describe(self) -> string {
+                    return `area = ${self.area()}`
+                }
+            }"#
+        ));
+    }
+
+    #[test]
+    fn parse_impl_decl() {
+        insta::assert_debug_snapshot!(parse(
+            r#"impl Shape for Circle {
+                fn area(self) -> number {
+                    return 3.14 * self.radius * self.radius
+                }
+            }"#
+        ));
+    }
+
+    #[test]
+    fn parse_trait_method_without_return_type() {
+        let mut parser = Parser::new("trait Shape { fn area(self) }");
+        assert_eq!(
+            parser.parse_script(),
+            Err(ParseError {
+                message: "expected return type for trait method area".to_string()
+            })
+        );
+    }
 }
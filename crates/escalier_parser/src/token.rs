@@ -1,9 +1,13 @@
+use lazy_static::lazy_static;
+use std::collections::BTreeMap;
+
 use escalier_ast::*;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum TokenKind {
     Identifier(String), // [a-zA-Z_][a-zA-Z0-9_]*
     Comment(String),
+    BlockComment(String),
 
     // Literals
     BoolLit(bool),
@@ -13,6 +17,10 @@ pub enum TokenKind {
         parts: Vec<Token>, // This should only contain StrLit tokens
         exprs: Vec<Expr>,
     },
+    RegexLit {
+        pattern: String,
+        flags: String,
+    },
     Null,
     Undefined,
 
@@ -56,8 +64,16 @@ pub enum TokenKind {
     Do,
     For,
     In,
+    While,
+    Loop,
+    Break,
+    Continue,
     Class,
     Extends,
+    Enum,
+    Struct,
+    Trait,
+    Impl,
     Type,
     TypeOf,
     KeyOf,
@@ -111,6 +127,7 @@ pub enum TokenKind {
     DotDot,    // used for ranges
     DotDotDot, // used for rest/spread
     Pipe,
+    PipeArrow, // used for pipelines
     Ampersand,
 
     Eof,
@@ -126,3 +143,71 @@ pub const EOF: Token = Token {
     kind: TokenKind::Eof,
     span: DUMMY_SPAN,
 };
+
+lazy_static! {
+    /// Reserved words and the tokens they lex to.  Anything not in this table
+    /// is lexed as an `Identifier`.
+    pub static ref KEYWORDS: BTreeMap<&'static str, TokenKind> = {
+        let mut table: BTreeMap<&'static str, TokenKind> = BTreeMap::new();
+
+        table.insert("import", TokenKind::Import);
+        table.insert("export", TokenKind::Export);
+        table.insert("from", TokenKind::From);
+        table.insert("as", TokenKind::As);
+        table.insert("fn", TokenKind::Fn);
+        table.insert("get", TokenKind::Get);
+        table.insert("set", TokenKind::Set);
+        table.insert("pub", TokenKind::Pub);
+        table.insert("private", TokenKind::Private);
+        table.insert("static", TokenKind::Static);
+        table.insert("async", TokenKind::Async);
+        table.insert("await", TokenKind::Await);
+        table.insert("gen", TokenKind::Gen);
+        table.insert("yield", TokenKind::Yield);
+        table.insert("declare", TokenKind::Declare);
+        table.insert("let", TokenKind::Let);
+        table.insert("var", TokenKind::Var);
+        table.insert("mut", TokenKind::Mut);
+        table.insert("match", TokenKind::Match);
+        table.insert("is", TokenKind::Is);
+        table.insert("try", TokenKind::Try);
+        table.insert("catch", TokenKind::Catch);
+        table.insert("finally", TokenKind::Finally);
+        table.insert("throw", TokenKind::Throw);
+        table.insert("do", TokenKind::Do);
+        table.insert("for", TokenKind::For);
+        table.insert("in", TokenKind::In);
+        table.insert("while", TokenKind::While);
+        table.insert("loop", TokenKind::Loop);
+        table.insert("break", TokenKind::Break);
+        table.insert("continue", TokenKind::Continue);
+        table.insert("class", TokenKind::Class);
+        table.insert("extends", TokenKind::Extends);
+        table.insert("enum", TokenKind::Enum);
+        table.insert("struct", TokenKind::Struct);
+        table.insert("trait", TokenKind::Trait);
+        table.insert("impl", TokenKind::Impl);
+        table.insert("infer", TokenKind::Infer);
+        table.insert("return", TokenKind::Return);
+        table.insert("throws", TokenKind::Throws);
+        table.insert("if", TokenKind::If);
+        table.insert("else", TokenKind::Else);
+        table.insert("true", TokenKind::BoolLit(true));
+        table.insert("false", TokenKind::BoolLit(false));
+        table.insert("null", TokenKind::Null);
+        table.insert("undefined", TokenKind::Undefined);
+        table.insert("number", TokenKind::Number);
+        table.insert("string", TokenKind::String);
+        table.insert("boolean", TokenKind::Boolean);
+        table.insert("symbol", TokenKind::Symbol);
+        table.insert("unknown", TokenKind::Unknown);
+        table.insert("never", TokenKind::Never);
+        table.insert("type", TokenKind::Type);
+        table.insert("typeof", TokenKind::TypeOf);
+        table.insert("keyof", TokenKind::KeyOf);
+        table.insert("new", TokenKind::New);
+        table.insert("_", TokenKind::Underscore);
+
+        table
+    };
+}
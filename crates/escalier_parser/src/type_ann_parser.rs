@@ -92,11 +92,57 @@ impl<'a> Parser<'a> {
                     .kind
                     != TokenKind::RightBrace
                 {
-                    match self
-                        .next_with_mode(IdentMode::PropName)
-                        .unwrap_or(EOF.clone())
+                    let leading = self.parse_leading_comments(IdentMode::PropName);
+                    if self
+                        .peek_with_mode(IdentMode::PropName)
+                        .unwrap_or(&EOF)
                         .kind
+                        == TokenKind::RightBrace
                     {
+                        self.comments.extend(leading);
+                        break;
+                    }
+
+                    let token = self
+                        .next_with_mode(IdentMode::PropName)
+                        .unwrap_or(EOF.clone());
+                    match token.kind {
+                        // Constructor, e.g. `new fn (x: number) -> Foo`
+                        TokenKind::Identifier(name)
+                            if name == "new"
+                                && self.peek().unwrap_or(&EOF).kind == TokenKind::Fn =>
+                        {
+                            self.next(); // consume `fn`
+
+                            let type_params = self.maybe_parse_type_params()?;
+                            let params = self.parse_type_ann_func_params()?;
+                            assert_eq!(
+                                self.next().unwrap_or(EOF.clone()).kind,
+                                TokenKind::SingleArrow
+                            );
+                            let ret = self.parse_type_ann()?;
+                            let throws = match self.peek().unwrap_or(&EOF).kind {
+                                TokenKind::Throws => {
+                                    self.next(); // consume `throws`
+                                    let type_ann = self.parse_type_ann()?;
+                                    Some(Box::new(type_ann))
+                                }
+                                _ => None,
+                            };
+
+                            let end_span = match &throws {
+                                Some(throws) => throws.span,
+                                None => ret.span,
+                            };
+
+                            props.push(ObjectProp::Constructor(FunctionType {
+                                span: merge_spans(&token.span, &end_span),
+                                type_params,
+                                params,
+                                ret: Box::new(ret),
+                                throws,
+                            }));
+                        }
                         TokenKind::Identifier(name) => {
                             let optional =
                                 if self.peek().unwrap_or(&EOF).kind == TokenKind::Question {
@@ -141,6 +187,7 @@ impl<'a> Parser<'a> {
                                         type_ann: Box::new(type_ann),
                                         // TODO(#642): compute correct spans for type annotations
                                         span: Span { start: 0, end: 0 },
+                                        comments: Comments::default(),
                                     })
                                 }
                                 TokenKind::Set => {
@@ -175,6 +222,7 @@ impl<'a> Parser<'a> {
                                         type_ann: Box::new(type_ann),
                                         // TODO(#642): compute correct spans for type annotations
                                         span: Span { start: 0, end: 0 },
+                                        comments: Comments::default(),
                                     })
                                 }
                                 _ => {
@@ -189,6 +237,7 @@ impl<'a> Parser<'a> {
                                         type_ann: Box::new(type_ann),
                                         // TODO(#642): compute correct spans for type annotations
                                         span: Span { start: 0, end: 0 },
+                                        comments: Comments::default(),
                                     })
                                 }
                             };
@@ -256,6 +305,7 @@ impl<'a> Parser<'a> {
                                 // TODO: handle 'if' clause
                                 check: None,
                                 extends: None,
+                                comments: Comments::default(),
                             }))
                         }
                         TokenKind::Fn => {
@@ -287,13 +337,14 @@ impl<'a> Parser<'a> {
                                     };
 
                                     props.push(ObjectProp::Method(type_ann::MethodType {
-                                        span: merge_spans(&span, &end_span),
+                                        span: merge_spans(&token.span, &end_span),
                                         name,
                                         type_params,
                                         params,
                                         ret: Box::new(ret),
                                         throws,
                                         mutates,
+                                        comments: Comments::default(),
                                     }));
                                 }
                                 // Callable
@@ -320,7 +371,7 @@ impl<'a> Parser<'a> {
                                     };
 
                                     props.push(ObjectProp::Call(FunctionType {
-                                        span: merge_spans(&span, &end_span),
+                                        span: merge_spans(&token.span, &end_span),
                                         type_params,
                                         params,
                                         ret: Box::new(ret),
@@ -370,6 +421,7 @@ impl<'a> Parser<'a> {
                                 span,
                                 name,
                                 ret: Box::new(ret),
+                                comments: Comments::default(),
                             }));
                         }
                         TokenKind::Set => {
@@ -424,6 +476,7 @@ impl<'a> Parser<'a> {
                                 span,
                                 name,
                                 param: Box::new(param),
+                                comments: Comments::default(),
                             }));
                         }
                         token => {
@@ -434,19 +487,47 @@ impl<'a> Parser<'a> {
                         }
                     }
 
-                    match self.peek().unwrap_or(&EOF).kind {
+                    // Trailing comments can appear before or after the comma.
+                    let mut trailing = self.parse_trailing_comments(IdentMode::PropName);
+                    let done = match self.peek().unwrap_or(&EOF).kind {
                         TokenKind::Comma => {
                             self.next();
+                            trailing.extend(self.parse_trailing_comments(IdentMode::PropName));
+                            false
                         }
-                        TokenKind::RightBrace => {
-                            break;
+                        _ => {
+                            // Comments after the last prop without a trailing comma.
+                            let mut unattached = self.parse_leading_comments(IdentMode::PropName);
+                            self.comments.append(&mut unattached);
+                            if self.peek().unwrap_or(&EOF).kind != TokenKind::RightBrace {
+                                return Err(ParseError {
+                                    message: "expected ',' or '}'".to_string(),
+                                });
+                            }
+                            true
                         }
+                    };
+
+                    let comments = Comments { leading, trailing };
+                    match props.last_mut() {
+                        Some(
+                            ObjectProp::Method(MethodType { comments: c, .. })
+                            | ObjectProp::Getter(GetterType { comments: c, .. })
+                            | ObjectProp::Setter(SetterType { comments: c, .. })
+                            | ObjectProp::Mapped(Mapped { comments: c, .. })
+                            | ObjectProp::Prop(type_ann::Prop { comments: c, .. }),
+                        ) => *c = comments,
+                        // Call and constructor signatures don't have comments
+                        // attached to them.
                         _ => {
-                            return Err(ParseError {
-                                message: "expected ',' or '}'".to_string(),
-                            })
+                            self.comments.extend(comments.leading);
+                            self.comments.extend(comments.trailing);
                         }
                     }
+
+                    if done {
+                        break;
+                    }
                 }
 
                 span = merge_spans(&span, &self.peek().unwrap_or(&EOF).span);
@@ -1066,6 +1147,21 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn parse_constructors_in_object_types() -> Result<(), ParseError> {
+        let input = r#"
+            {
+                new fn <T>(value: T) -> Box<T>,
+                new: number,
+            }
+        "#;
+        let mut parser = Parser::new(input);
+        let result = parser.parse_type_ann()?;
+        insta::assert_debug_snapshot!(result);
+
+        Ok(())
+    }
+
     #[test]
     #[should_panic]
     fn parse_object_type_missing_comma() {